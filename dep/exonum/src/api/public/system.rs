@@ -135,7 +135,7 @@ impl Api for SystemApi {
         let healthcheck = move |_: &mut Request| {
             let net_height = *self_.shared_api_state.net_height.read().unwrap();
             let last_block = self_.blockchain.last_block().clone();
-            let (status, code) = if net_height > last_block.height() {
+            let (status, code) = if self_.shared_api_state.is_behind(last_block.height()) {
                 (true, status::ServiceUnavailable)
             } else {
                 (false, status::Ok)
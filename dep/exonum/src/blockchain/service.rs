@@ -329,6 +329,8 @@ pub struct SharedNodeState {
     pub net_height: Arc<RwLock<Height>>,
     /// Timeout to update api state.
     pub state_update_timeout: Milliseconds,
+    /// Number of blocks the node may lag behind the network.
+    pub max_blocks_behind: Option<u64>,
 }
 
 impl SharedNodeState {
@@ -338,9 +340,27 @@ impl SharedNodeState {
             state: Arc::new(RwLock::new(ApiNodeState::new())),
             net_height: Arc::new(RwLock::new(Height(0))),
             state_update_timeout,
+            max_blocks_behind: None,
         }
     }
 
+    /// Sets the number of blocks the node may lag behind the network.
+    pub fn with_max_blocks_behind(self, max_blocks_behind: Option<u64>) -> SharedNodeState {
+        SharedNodeState {
+            max_blocks_behind,
+            ..self
+        }
+    }
+
+    /// Checks whether the node at `height` lags behind the highest height
+    /// seen from its peers by more than `max_blocks_behind` blocks.
+    /// Without the limit any lag counts.
+    pub fn is_behind(&self, height: Height) -> bool {
+        let net_height = *self.net_height.read().unwrap();
+        let lag = net_height.0.saturating_sub(height.0);
+        lag > self.max_blocks_behind.unwrap_or(0)
+    }
+
     pub fn update_net_height(&self, h: Height) {
         if *self.net_height.read().unwrap() < h {
             *self.net_height.write().unwrap() = h;
@@ -539,3 +559,31 @@ impl<'a, T: Transaction> From<T> for Box<Transaction + 'a> {
         Box::new(tx) as Box<Transaction>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use helpers::Height;
+
+    use super::*;
+
+    #[test]
+    fn shared_node_state_behind_without_limit() {
+        let state = SharedNodeState::new(10_000);
+        assert!(!state.is_behind(Height(0)));
+
+        state.update_net_height(Height(1));
+        assert!(state.is_behind(Height(0)));
+        assert!(!state.is_behind(Height(1)));
+    }
+
+    #[test]
+    fn shared_node_state_behind_with_limit() {
+        let state = SharedNodeState::new(10_000).with_max_blocks_behind(Some(5));
+        state.update_net_height(Height(100));
+
+        assert!(!state.is_behind(Height(100)));
+        assert!(!state.is_behind(Height(95)));
+        assert!(state.is_behind(Height(94)));
+        assert!(state.is_behind(Height(0)));
+    }
+}
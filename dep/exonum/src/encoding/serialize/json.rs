@@ -93,6 +93,15 @@ macro_rules! impl_deserialize_int {
                 -> Result<(), Box<Error>>
             {
                 let number = value.as_i64().ok_or("Can't cast json as integer")?;
+                if number < <$typename>::min_value() as i64
+                    || number > <$typename>::max_value() as i64
+                {
+                    return Err(format!(
+                        "Integer {} is out of range for {}",
+                        number,
+                        stringify!($typename)
+                    ).into());
+                }
                 buffer.write(from, to, number as $typename);
                 Ok(())
             }
@@ -663,6 +663,10 @@ impl NodeHandler {
         if self.state.locked_propose().is_some() {
             return;
         }
+        if self.api_state.max_blocks_behind.is_some() && self.api_state.is_behind(height) {
+            warn!("Node is behind the network, skipping propose at height {}", height);
+            return;
+        }
         if let Some(validator_id) = self.state.validator_id() {
             if self.state.have_prevote(round) {
                 return;
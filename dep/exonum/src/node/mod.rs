@@ -31,7 +31,7 @@ use mount::Mount;
 use iron::{Chain, Iron};
 use iron_cors::CorsMiddleware;
 use serde::{ser, de};
-use futures::{Future, Sink};
+use futures::{future, Future, Sink};
 use futures::sync::mpsc;
 use tokio_core::reactor::Core;
 
@@ -150,6 +150,10 @@ pub struct NodeApiConfig {
     ///
     /// [cors]: https://developer.mozilla.org/en-US/docs/Web/HTTP/CORS
     pub allow_origin: Option<AllowOrigin>,
+    /// Number of blocks the node may lag behind its peers before it stops
+    /// proposing blocks and reports itself as degraded in the healthcheck.
+    #[serde(default)]
+    pub max_blocks_behind: Option<u64>,
 }
 
 impl Default for NodeApiConfig {
@@ -160,6 +164,7 @@ impl Default for NodeApiConfig {
             public_api_address: None,
             private_api_address: None,
             allow_origin: None,
+            max_blocks_behind: None,
         }
     }
 }
@@ -786,7 +791,8 @@ impl Node {
             warn!("Could not find 'external_address' in the config, using 'listen_address'");
             node_cfg.listen_address
         };
-        let api_state = SharedNodeState::new(node_cfg.api.state_update_timeout as u64);
+        let api_state = SharedNodeState::new(node_cfg.api.state_update_timeout as u64)
+            .with_max_blocks_behind(node_cfg.api.max_blocks_behind);
         let system_state = Box::new(DefaultSystemState(node_cfg.listen_address));
         let network_config = config.network;
         let handler = NodeHandler::new(
@@ -808,9 +814,21 @@ impl Node {
 
     /// Launches only consensus messages handler.
     /// This may be used if you want to customize api with the `ApiContext`.
-    pub fn run_handler(mut self) -> io::Result<()> {
+    pub fn run_handler(self) -> io::Result<()> {
+        self.run_handler_until(future::empty())
+    }
+
+    /// Launches only consensus messages handler, until `stop` resolves.
+    ///
+    /// The handler finishes the event it is processing, so a block being
+    /// committed is written out before the network thread is shut down.
+    pub fn run_handler_until<F>(mut self, stop: F) -> io::Result<()>
+    where
+        F: Future<Item = (), Error = ()> + 'static,
+    {
         self.handler.initialize();
 
+        let network_requests = self.channel.network_requests.0.clone();
         let (handler_part, network_part, timeouts_part) = self.into_reactor();
 
         let network_thread = thread::spawn(move || {
@@ -826,9 +844,14 @@ impl Node {
         });
 
         let mut core = Core::new()?;
-        core.run(handler_part.run()).map_err(|_| {
+        core.run(handler_part.run().select(stop)).map_err(|_| {
             other_error("An error in the `Handler` thread occurred")
         })?;
+        info!("Shutting down the network");
+        network_requests
+            .send(NetworkRequest::Shutdown)
+            .wait()
+            .map_err(into_other)?;
         network_thread.join().unwrap()
     }
 
@@ -838,6 +861,16 @@ impl Node {
     /// Public api prefix is `/api/services/{service_name}`
     /// Private api prefix is `/api/services/{service_name}`
     pub fn run(self) -> io::Result<()> {
+        self.run_until(future::empty())
+    }
+
+    /// Same as `run`, but returns once `stop` resolves and the consensus
+    /// handler and network have shut down. The api threads are left
+    /// running and end with the process.
+    pub fn run_until<F>(self, stop: F) -> io::Result<()>
+    where
+        F: Future<Item = (), Error = ()> + 'static,
+    {
         let blockchain = self.handler().blockchain.clone();
         let api_sender = self.channel();
 
@@ -873,14 +906,11 @@ impl Node {
             None => None,
         };
 
-        self.run_handler()?;
+        self.run_handler_until(stop)?;
 
-        if let Some(private_config_api_thread) = private_config_api_thread {
-            private_config_api_thread.join().unwrap();
-        }
-        if let Some(public_config_api_thread) = public_config_api_thread {
-            public_config_api_thread.join().unwrap();
-        }
+        // Iron servers can't be stopped, so the threads are detached.
+        drop(private_config_api_thread);
+        drop(public_config_api_thread);
 
         Ok(())
     }
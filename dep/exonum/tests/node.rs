@@ -17,6 +17,7 @@ extern crate exonum;
 extern crate futures;
 extern crate tokio_timer;
 
+use std::sync::mpsc;
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 use std::sync::Mutex;
@@ -85,3 +86,24 @@ fn test_node_run() {
         rx.wait().unwrap();
     }
 }
+
+#[test]
+fn test_node_run_until() {
+    let node_cfg = helpers::generate_testnet_config(1, 16_400).pop().unwrap();
+    let (commit_tx, commit_rx) = oneshot::channel();
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let (result_tx, result_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let service = Box::new(CommitWatcherService(Mutex::new(Some(commit_tx))));
+        let node = Node::new(Box::new(MemoryDB::new()), vec![service], node_cfg);
+        result_tx.send(node.run_until(stop_rx.map_err(drop))).unwrap();
+    });
+
+    let timer = Timer::default();
+    let duration = Duration::from_secs(60);
+    timer.timeout(commit_rx.map_err(drop), duration).wait().unwrap();
+
+    stop_tx.send(()).unwrap();
+    let result = result_rx.recv_timeout(duration).expect("Node did not stop");
+    assert!(result.is_ok());
+}
@@ -52,6 +52,7 @@ pub struct ServiceDiscovery {
     handle: Handle,
     timer: Timer,
     nodes: Arc<RwLock<HashMap<PKeys, ValidatorInfo>>>,
+    auditors: Arc<RwLock<HashMap<PKeys, ValidatorInfo>>>,
 }
 
 impl ServiceDiscovery {
@@ -61,11 +62,20 @@ impl ServiceDiscovery {
             handle,
             timer: Timer::default(),
             nodes: Arc::new(RwLock::new(nodes)),
+            auditors: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
     fn get_nodes(&self) -> <Self as Service>::Future {
-        match serde_json::to_string_pretty(&*self.nodes.read().unwrap()) {
+        ServiceDiscovery::list(&self.nodes)
+    }
+
+    fn get_auditors(&self) -> <Self as Service>::Future {
+        ServiceDiscovery::list(&self.auditors)
+    }
+
+    fn list(pool: &RwLock<HashMap<PKeys, ValidatorInfo>>) -> <Self as Service>::Future {
+        match serde_json::to_string_pretty(&*pool.read().unwrap()) {
             Ok(nodes) => Box::new(future::ok(Response::new().with_body(nodes))),
             Err(e) => {
                 eprintln!("Error when parsing GET: {}", e);
@@ -80,6 +90,7 @@ impl ServiceDiscovery {
 
     fn post_node(&self, body: Body) -> <Self as Service>::Future {
         let nodes = Arc::clone(&self.nodes);
+        let auditors = Arc::clone(&self.auditors);
         let handle = self.handle.clone();
         let timer = self.timer.clone();
         let post = body.concat2().and_then(move |v| {
@@ -87,6 +98,7 @@ impl ServiceDiscovery {
                 Ok(info) => {
                     let mut nodes = nodes.write().unwrap();
                     eprintln!("Received value: {:?}", &info);
+                    auditors.write().unwrap().remove(&info.keys());
                     nodes.insert(info.keys(), info);
                     ServiceDiscovery::publish_peer(&handle, timer, nodes.clone(), info);
                     if let Err(e) = ServiceDiscovery::save_peers(&nodes) {
@@ -100,6 +112,23 @@ impl ServiceDiscovery {
         Box::new(post)
     }
 
+    /// Auditors only sync from the validators, so they are kept apart from
+    /// `nodes` and never proposed as validator keys.
+    fn post_auditor(&self, body: Body) -> <Self as Service>::Future {
+        let auditors = Arc::clone(&self.auditors);
+        let post = body.concat2().and_then(move |v| {
+            match serde_json::from_slice::<ValidatorInfo>(&v) {
+                Ok(info) => {
+                    eprintln!("Received auditor: {:?}", &info);
+                    auditors.write().unwrap().insert(info.keys(), info);
+                    future::ok(Response::new().with_status(StatusCode::Ok))
+                }
+                Err(e) => future::err(io::Error::from(e).into()),
+            }
+        });
+        Box::new(post)
+    }
+
     fn publish_peer(
         handle: &Handle,
         timer: Timer,
@@ -287,6 +316,8 @@ impl Service for ServiceDiscovery {
         match (req.method(), req.path()) {
             (&Method::Get, "/nodes") => self.get_nodes(),
             (&Method::Post, "/nodes") => self.post_node(req.body()),
+            (&Method::Get, "/auditors") => self.get_auditors(),
+            (&Method::Post, "/auditors") => self.post_auditor(req.body()),
             _ => Box::new(future::ok(
                 Response::new().with_status(StatusCode::NotFound),
             )),
@@ -0,0 +1,122 @@
+//! Summary of the node setup, logged once on startup.
+
+use dmbc::keys;
+
+use net_config::ValidatorInfo;
+
+/// Storage backend of the node.
+pub const DB_BACKEND: &str = "RocksDB";
+
+/// What the node is about to run with. Holds public material only, secret
+/// keys never make it into the banner.
+pub struct Banner<'a> {
+    pub version: &'a str,
+    pub is_validator: bool,
+    pub info: &'a ValidatorInfo,
+    pub db_path: &'a str,
+    pub peers: usize,
+    pub validators: usize,
+}
+
+impl<'a> Banner<'a> {
+    /// The banner as `field: value` lines.
+    pub fn lines(&self) -> Vec<String> {
+        let role = if self.is_validator {
+            "validator"
+        } else {
+            "auditor"
+        };
+        vec![
+            format!("version: v{}", self.version),
+            format!("role: {}", role),
+            format!("public API: {}", self.info.public),
+            format!("private API: {}", self.info.private),
+            format!("peer address: {}", self.info.peer),
+            format!("consensus key: {}", keys::log(&self.info.consensus)),
+            format!("service key: {}", keys::log(&self.info.service)),
+            format!("db: {} at {}", DB_BACKEND, self.db_path),
+            format!("peers: {}", self.peers),
+            format!("genesis validators: {}", self.validators),
+        ]
+    }
+
+    /// Write the banner to the log.
+    pub fn log(&self) {
+        for line in self.lines() {
+            info!("{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use exonum::crypto;
+
+    use super::*;
+
+    #[test]
+    fn banner_lists_setup_without_secrets() {
+        let (consensus, consensus_secret) = crypto::gen_keypair();
+        let (service, service_secret) = crypto::gen_keypair();
+        let info = ValidatorInfo {
+            public: "127.0.0.1:8000".parse().unwrap(),
+            private: "127.0.0.1:8001".parse().unwrap(),
+            peer: "127.0.0.1:2000".parse().unwrap(),
+            consensus,
+            service,
+        };
+        let banner = Banner {
+            version: "1.2.3",
+            is_validator: true,
+            info: &info,
+            db_path: "/var/lib/dmbc/db",
+            peers: 3,
+            validators: 1,
+        };
+
+        let lines = banner.lines();
+        let expected = vec![
+            "version: v1.2.3".to_string(),
+            "role: validator".to_string(),
+            "public API: 127.0.0.1:8000".to_string(),
+            "private API: 127.0.0.1:8001".to_string(),
+            "peer address: 127.0.0.1:2000".to_string(),
+            format!("consensus key: {}", keys::log(&consensus)),
+            format!("service key: {}", keys::log(&service)),
+            "db: RocksDB at /var/lib/dmbc/db".to_string(),
+            "peers: 3".to_string(),
+            "genesis validators: 1".to_string(),
+        ];
+        assert_eq!(lines, expected);
+
+        let text = lines.join("\n");
+        for secret in &[consensus_secret, service_secret] {
+            assert!(!text.contains(&secret.to_hex()));
+            assert!(!text.contains(&secret.to_hex()[..16]));
+        }
+    }
+
+    #[test]
+    fn banner_role_auditor() {
+        let (consensus, _) = crypto::gen_keypair();
+        let (service, _) = crypto::gen_keypair();
+        let address = "127.0.0.1:8000".parse().unwrap();
+        let info = ValidatorInfo {
+            public: address,
+            private: address,
+            peer: address,
+            consensus,
+            service,
+        };
+        let banner = Banner {
+            version: "1.2.3",
+            is_validator: false,
+            info: &info,
+            db_path: "db",
+            peers: 0,
+            validators: 1,
+        };
+
+        assert_eq!(banner.lines()[1], "role: auditor");
+    }
+}
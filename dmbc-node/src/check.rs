@@ -0,0 +1,108 @@
+//! Dry run of the node setup, see `--check`.
+
+use exonum::blockchain::{self, Blockchain};
+use exonum::crypto;
+use exonum::node::{ApiSender, NodeConfig};
+use exonum::storage::MemoryDB;
+use exonum_configuration::ConfigurationService;
+use futures::sync::mpsc;
+
+use dmbc::currency::configuration::Configuration;
+use dmbc::currency::genesis::GenesisAllocation;
+use dmbc::currency::Service;
+
+use error::StartupError;
+use keyfile;
+
+/// Assemble the node configuration like a start would, and create its
+/// genesis block in memory. Neither connects to peers nor opens the
+/// database, and missing key files are not written.
+pub fn run() -> Result<Vec<String>, StartupError> {
+    let setup = ::setup(|name| {
+        let pair = keyfile::read(name)?;
+        if pair.is_none() {
+            eprintln!("No {} key pair, one will be generated on start", name);
+        }
+        Ok(pair.unwrap_or_else(crypto::gen_keypair))
+    })?;
+    genesis_report(&setup.node_cfg, setup.allocation)
+}
+
+/// Create the genesis block of `node_cfg` in a `MemoryDB` and describe the
+/// validators and fees the chain starts with.
+pub fn genesis_report(
+    node_cfg: &NodeConfig,
+    allocation: GenesisAllocation,
+) -> Result<Vec<String>, StartupError> {
+    let services: Vec<Box<blockchain::Service>> = vec![
+        Box::new(ConfigurationService::new()),
+        Box::new(Service::with_allocation(allocation)),
+    ];
+    let (api_sender, _api_requests) = mpsc::channel(1);
+    let mut blockchain = Blockchain::new(
+        Box::new(MemoryDB::new()),
+        services,
+        node_cfg.service_public_key,
+        node_cfg.service_secret_key.clone(),
+        ApiSender::new(api_sender),
+    );
+    blockchain
+        .create_genesis_block(node_cfg.genesis.clone())
+        .map_err(|e| StartupError::Genesis(e.to_string()))?;
+
+    let snapshot = blockchain.snapshot();
+    let stored = blockchain::Schema::new(&snapshot).actual_configuration();
+    let configuration = Configuration::extract(&*snapshot);
+    let fees = configuration.fees();
+
+    let mut lines = vec![format!("genesis validators: {}", stored.validator_keys.len())];
+    for (i, validator) in stored.validator_keys.iter().enumerate() {
+        lines.push(format!(
+            "validator {}: consensus key {}, service key {}",
+            i,
+            validator.consensus_key.to_hex(),
+            validator.service_key.to_hex()
+        ));
+    }
+    lines.push(format!("fee recipient: {}", fees.recipient().to_hex()));
+    lines.push(format!(
+        "fees: add_assets {} (+{} per entry), delete_assets {}, exchange {}, trade {}, transfer {}",
+        fees.add_assets(),
+        fees.add_assets_per_entry(),
+        fees.delete_assets(),
+        fees.exchange(),
+        fees.trade(),
+        fees.transfer()
+    ));
+    lines.push(format!("fee rate: {} bps", configuration.fee_rate_bps()));
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::File;
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn check_valid_and_invalid_config() {
+        let lines = run().unwrap();
+        assert_eq!(lines[0], "genesis validators: 1");
+        assert!(lines.iter().any(|line| line.starts_with("fee recipient: ")));
+
+        let path = env::temp_dir().join("dmbc_check_no_validators.json");
+        File::create(&path)
+            .and_then(|mut file| write!(file, "[]"))
+            .unwrap();
+        env::set_var("GENESIS_VALIDATORS", &path);
+        let result = run();
+        env::remove_var("GENESIS_VALIDATORS");
+
+        match result {
+            Err(StartupError::GenesisValidators { .. }) => (),
+            other => panic!("expected invalid genesis validators, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::net::AddrParseError;
+
+/// Errors which prevent the node from starting.
+#[derive(Debug)]
+pub enum StartupError {
+    /// Logger could not be initialized.
+    Logger(String),
+
+    /// Key file could not be read or created.
+    KeyFile { path: String, error: io::Error },
+
+    /// Key file does not contain a valid hex encoded key.
+    Key { path: String, error: String },
+
+    /// Configured address is malformed.
+    Address {
+        name: &'static str,
+        value: String,
+        error: AddrParseError,
+    },
+
+    /// Configured genesis key is malformed.
+    GenesisKey { name: &'static str, error: String },
+
+    /// Genesis allocation file could not be read or is invalid.
+    GenesisAllocation { path: String, error: String },
+
+    /// Genesis validators file could not be read or is invalid.
+    GenesisValidators { path: String, error: String },
+
+    /// Consensus configuration is invalid.
+    Consensus(String),
+
+    /// Genesis block could not be created.
+    Genesis(String),
+
+    /// Database tuning is invalid.
+    DbTuning(String),
+
+    /// Memory pool configuration is invalid.
+    Mempool(String),
+
+    /// Shutdown signals are invalid or could not be handled.
+    Shutdown(String),
+
+    /// Database could not be opened.
+    Database { path: String, error: String },
+
+    /// Node stopped with an error.
+    Node(io::Error),
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StartupError::Logger(ref error) => write!(f, "unable to initialize logger: {}", error),
+            StartupError::KeyFile { ref path, ref error } => {
+                write!(f, "unable to access key file {}: {}", path, error)
+            }
+            StartupError::Key { ref path, ref error } => {
+                write!(f, "invalid key in {}: {}", path, error)
+            }
+            StartupError::Address {
+                name,
+                ref value,
+                ref error,
+            } => write!(f, "invalid {} address {:?}: {}", name, value, error),
+            StartupError::GenesisKey { name, ref error } => {
+                write!(f, "invalid genesis {} key: {}", name, error)
+            }
+            StartupError::GenesisAllocation { ref path, ref error } => {
+                write!(f, "invalid genesis allocation in {}: {}", path, error)
+            }
+            StartupError::GenesisValidators { ref path, ref error } => {
+                write!(f, "invalid genesis validators in {}: {}", path, error)
+            }
+            StartupError::Consensus(ref error) => {
+                write!(f, "invalid consensus configuration: {}", error)
+            }
+            StartupError::Genesis(ref error) => {
+                write!(f, "unable to create genesis block: {}", error)
+            }
+            StartupError::DbTuning(ref error) => write!(f, "invalid database tuning: {}", error),
+            StartupError::Mempool(ref error) => {
+                write!(f, "invalid mempool configuration: {}", error)
+            }
+            StartupError::Shutdown(ref error) => write!(f, "invalid shutdown signals: {}", error),
+            StartupError::Database { ref path, ref error } => {
+                write!(f, "unable to open database at {}: {}", path, error)
+            }
+            StartupError::Node(ref error) => write!(f, "node failed: {}", error),
+        }
+    }
+}
+
+impl error::Error for StartupError {
+    fn description(&self) -> &str {
+        match *self {
+            StartupError::Logger(_) => "unable to initialize logger",
+            StartupError::KeyFile { .. } => "unable to access key file",
+            StartupError::Key { .. } => "invalid key",
+            StartupError::Address { .. } => "invalid address",
+            StartupError::GenesisKey { .. } => "invalid genesis key",
+            StartupError::GenesisAllocation { .. } => "invalid genesis allocation",
+            StartupError::GenesisValidators { .. } => "invalid genesis validators",
+            StartupError::Consensus(_) => "invalid consensus configuration",
+            StartupError::Genesis(_) => "unable to create genesis block",
+            StartupError::DbTuning(_) => "invalid database tuning",
+            StartupError::Mempool(_) => "invalid mempool configuration",
+            StartupError::Shutdown(_) => "invalid shutdown signals",
+            StartupError::Database { .. } => "unable to open database",
+            StartupError::Node(_) => "node failed",
+        }
+    }
+}
@@ -3,7 +3,10 @@ extern crate clap;
 use clap::{App, Arg};
 use super::VERSION;
 
-pub struct Flags {}
+pub struct Flags {
+    /// Validate the configuration and exit, see `check`.
+    pub check: bool,
+}
 
 pub fn parse() -> Option<Flags> {
     let matches = App::new("DMarket blockchain")
@@ -13,6 +16,11 @@ pub fn parse() -> Option<Flags> {
             .long("version")
             .multiple(false)
         )
+        .arg(Arg::with_name("check")
+            .help("validate the configuration and genesis, then exit")
+            .long("check")
+            .multiple(false)
+        )
         .get_matches();
 
     match matches.occurrences_of("version") {
@@ -20,6 +28,8 @@ pub fn parse() -> Option<Flags> {
             println!("DMarket Blockchain v{}", VERSION);
             None
         }
-        _ => return Some(Flags {}),
+        _ => return Some(Flags {
+            check: matches.is_present("check"),
+        }),
     }
 }
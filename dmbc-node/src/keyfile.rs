@@ -9,29 +9,71 @@ use exonum::encoding::serialize::FromHex;
 
 use dmbc::config;
 
-pub fn pair(name: &str) -> io::Result<(PublicKey, SecretKey)> {
+use error::StartupError;
+
+pub fn pair(name: &str) -> Result<(PublicKey, SecretKey), StartupError> {
+    match read(name)? {
+        Some(pair) => Ok(pair),
+        None => {
+            let (public_path, secret_path) = paths(name);
+            generate(&public_path, &secret_path)
+        }
+    }
+}
+
+/// Read the key pair `name` without generating it, `None` if either of
+/// its files is missing.
+pub fn read(name: &str) -> Result<Option<(PublicKey, SecretKey)>, StartupError> {
+    let (public_path, secret_path) = paths(name);
+
+    let public_string = match slurp(&public_path) {
+        Ok(key_string) => key_string,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(key_file_error(&public_path, e)),
+    };
+    let public_key = PublicKey::from_hex(public_string.trim()).map_err(|e| StartupError::Key {
+        path: public_path.clone(),
+        error: e.to_string(),
+    })?;
+
+    let secret_string = match slurp(&secret_path) {
+        Ok(key_string) => key_string,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(key_file_error(&secret_path, e)),
+    };
+    let secret_key = SecretKey::from_hex(secret_string.trim()).map_err(|e| StartupError::Key {
+        path: secret_path.clone(),
+        error: e.to_string(),
+    })?;
+
+    Ok(Some((public_key, secret_key)))
+}
+
+fn paths(name: &str) -> (String, String) {
     let keys_path = config::config().api().keys_path();
     let public_path = keys_path.clone() + "/" + name + ".pub";
-    let secret_path = keys_path.clone() + "/" + name;
-
-    slurp(&public_path)
-        .map(|key_string| PublicKey::from_hex(key_string).unwrap())
-        .and_then(|public_key| {
-            let key_string = slurp(&secret_path)?;
-            let secret_key = SecretKey::from_hex(key_string).unwrap();
-            Ok((public_key, secret_key))
-        })
-        .or_else(|e| {
-            if e.kind() != io::ErrorKind::NotFound {
-                return Err(e);
-            }
-            let (public_key, secret_key) = crypto::gen_keypair();
-            let mut public_file = File::create(public_path)?;
-            let mut secret_file = File::create(secret_path)?;
-            write!(public_file, "{}", public_key.to_hex())?;
-            write!(secret_file, "{}", secret_key.to_hex())?;
-            Ok((public_key, secret_key))
-        })
+    let secret_path = keys_path + "/" + name;
+    (public_path, secret_path)
+}
+
+fn generate(public_path: &str, secret_path: &str) -> Result<(PublicKey, SecretKey), StartupError> {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    write_key(public_path, &public_key.to_hex())?;
+    write_key(secret_path, &secret_key.to_hex())?;
+    Ok((public_key, secret_key))
+}
+
+fn write_key(path: &str, key: &str) -> Result<(), StartupError> {
+    File::create(path)
+        .and_then(|mut file| write!(file, "{}", key))
+        .map_err(|e| key_file_error(path, e))
+}
+
+fn key_file_error(path: &str, error: io::Error) -> StartupError {
+    StartupError::KeyFile {
+        path: path.to_string(),
+        error,
+    }
 }
 
 fn slurp<P: AsRef<Path>>(filename: P) -> io::Result<String> {
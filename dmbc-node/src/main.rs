@@ -1,4 +1,5 @@
 extern crate curl;
+extern crate futures;
 extern crate exonum;
 extern crate exonum_configuration;
 extern crate exonum_rocksdb;
@@ -7,23 +8,39 @@ extern crate serde;
 extern crate serde_derive;
 extern crate serde_json;
 extern crate clap;
+extern crate signal_hook;
 extern crate jemallocator;
+#[macro_use]
+extern crate log;
 
 extern crate dmbc;
 
+mod banner;
+mod check;
+mod error;
 mod keyfile;
 mod net_config;
 mod flag;
+mod shutdown;
+mod validators;
+
+use std::net::SocketAddr;
 
 use dmbc::config;
+use dmbc::currency::genesis::GenesisAllocation;
 use dmbc::currency::Service;
 use exonum::blockchain;
-use exonum::blockchain::{ConsensusConfig, GenesisConfig, TimeoutAdjusterConfig, ValidatorKeys};
-use exonum::crypto::PublicKey;
+use exonum::blockchain::{ConsensusConfig, ValidatorKeys};
+use exonum::crypto::{PublicKey, SecretKey};
 use exonum::encoding::serialize::FromHex;
 use exonum::node::{Node, NodeApiConfig, NodeConfig};
-use exonum::storage::{RocksDB, RocksDBOptions};
+use exonum::storage::{Database, RocksDB, RocksDBOptions};
 use exonum_configuration::ConfigurationService;
+use futures::Future;
+
+use banner::Banner;
+use error::StartupError;
+use validators::GenesisValidators;
 
 #[cfg(not(target_env = "msvc"))]
 use jemallocator::Jemalloc;
@@ -40,25 +57,143 @@ const GENESIS_SERVICE_PUBLIC: &str =
     "68e774a4339cccfae644dcf3e44360839c84a6475c7d2943ed59b81d7eb6e9f0";
 
 fn main() {
-    let _f = match flag::parse() {
+    let f = match flag::parse() {
         Some(f) => f,
         None => ::std::process::exit(0)
     };
 
-    exonum::helpers::init_logger().unwrap();
+    if f.check {
+        match check::run() {
+            Ok(lines) => {
+                for line in lines {
+                    println!("{}", line);
+                }
+                ::std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("Invalid configuration: {}", e);
+                ::std::process::exit(1);
+            }
+        }
+    }
+
+    if let Err(e) = run() {
+        eprintln!("Unable to start the node: {}", e);
+        ::std::process::exit(1);
+    }
+}
+
+fn parse_address(name: &'static str, value: String) -> Result<SocketAddr, StartupError> {
+    value.parse().map_err(|error| StartupError::Address { name, value, error })
+}
+
+fn genesis_key(name: &'static str, hex: &str) -> Result<PublicKey, StartupError> {
+    PublicKey::from_hex(hex).map_err(|e| StartupError::GenesisKey {
+        name,
+        error: e.to_string(),
+    })
+}
+
+/// Read the wallets to fund at genesis. The allocation is only used to
+/// create the genesis block, but it's validated on every start.
+fn genesis_allocation() -> Result<GenesisAllocation, StartupError> {
+    match config::config().genesis().allocation() {
+        Some(path) => GenesisAllocation::from_file(&path)
+            .map_err(|error| StartupError::GenesisAllocation { path, error }),
+        None => Ok(GenesisAllocation::default()),
+    }
+}
+
+/// Read the validators of the genesis block, or fall back to the built-in
+/// one. Like the allocation, they are validated on every start.
+fn genesis_validators() -> Result<GenesisValidators, StartupError> {
+    match config::config().genesis().validators() {
+        Some(path) => GenesisValidators::from_file(&path)
+            .map_err(|error| StartupError::GenesisValidators { path, error }),
+        None => {
+            let validator = ValidatorKeys {
+                consensus_key: genesis_key("validator", GENESIS_VALIDATOR_PUBLIC)?,
+                service_key: genesis_key("service", GENESIS_SERVICE_PUBLIC)?,
+            };
+            Ok(GenesisValidators::new(vec![validator]).expect("Built-in validator is invalid"))
+        }
+    }
+}
+
+/// Compare the configured consensus parameters against the ones the chain is
+/// running with. They are only used to create the genesis block, so any
+/// difference on an existing chain is ignored by the node.
+fn check_genesis_consensus(db: &RocksDB, configured: &ConsensusConfig) -> Result<(), StartupError> {
+    let snapshot = db.snapshot();
+    let schema = blockchain::Schema::new(&snapshot);
+    if schema.block_hashes_by_height().len() == 0 {
+        return Ok(());
+    }
+
+    let actual = schema.actual_configuration().consensus;
+    let mismatches = config::consensus_mismatches(configured, &actual);
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    if config::config().consensus().strict_genesis() {
+        return Err(StartupError::Consensus(mismatches.join("; ")));
+    }
+    for mismatch in mismatches {
+        eprintln!("Consensus config mismatch, using the chain's value: {}", mismatch);
+    }
+    Ok(())
+}
+
+/// Run `Service::migrate` on the database of an existing chain. A new
+/// chain is migrated when its genesis block is created.
+fn migrate(db: &RocksDB, path: &str) -> Result<(), StartupError> {
+    let snapshot = db.snapshot();
+    if blockchain::Schema::new(&snapshot).block_hashes_by_height().len() == 0 {
+        return Ok(());
+    }
+
+    let mut fork = db.fork();
+    Service::migrate(&mut fork);
+    db.merge(fork.into_patch()).map_err(|e| StartupError::Database {
+        path: path.to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// Node configuration assembled from the config and genesis files. Peers
+/// are only known once connected, so `node_cfg` has none.
+struct Setup {
+    info: net_config::ValidatorInfo,
+    consensus_config: ConsensusConfig,
+    allocation: GenesisAllocation,
+    node_cfg: NodeConfig,
+}
 
+/// Assemble the node configuration, reading the key pairs with `key_pair`.
+fn setup<K>(key_pair: K) -> Result<Setup, StartupError>
+where
+    K: Fn(&str) -> Result<(PublicKey, SecretKey), StartupError>,
+{
     /** Create Keys */
-    println!(
-        "Initializing node version: v{}",
-        VERSION
-    );
+    let (consensus_public_key, consensus_secret_key) = key_pair("consensus")?;
+    let (service_public_key, service_secret_key) = key_pair("service")?;
+
+    let public_api = parse_address("public API", config::config().api().address())?;
+    let private_api = parse_address("private API", config::config().api().private_address())?;
+    let peer_address = parse_address("peer", config::config().api().peer_address())?;
 
-    let (consensus_public_key, consensus_secret_key) = keyfile::pair("consensus").unwrap();
-    let (service_public_key, service_secret_key) = keyfile::pair("service").unwrap();
+    let consensus_config = config::config()
+        .consensus()
+        .consensus_config()
+        .map_err(StartupError::Consensus)?;
+    let mempool = config::config()
+        .mempool()
+        .mempool_config(consensus_config.txs_block_limit)
+        .map_err(StartupError::Mempool)?;
 
-    let public_api = config::config().api().address().parse().unwrap();
-    let private_api = config::config().api().private_address().parse().unwrap();
-    let peer_address = config::config().api().peer_address().parse().unwrap();
+    let allocation = genesis_allocation()?;
+    let genesis_validators = genesis_validators()?;
 
     let info = net_config::ValidatorInfo {
         public: public_api,
@@ -67,58 +202,20 @@ fn main() {
         consensus: consensus_public_key,
         service: service_public_key,
     };
-    eprintln!("Node info: {:?}", &info);
-
-    let is_validator = config::config().api().is_validator();
-    eprintln!(
-        "Connecting {}",
-        if is_validator {
-            "as validator"
-        } else {
-            "as auditor"
-        }
-    );
-
-    let peers = match net_config::connect(&info, is_validator) {
-        Ok(peers) => {
-            eprintln!("Connected as validator, peers: {:?}", &peers);
-            peers
-        }
-        Err(e) => {
-            eprintln!("Unable to connect as validator: {}", &e);
-            eprintln!("Running in loner-mode.");
-            Default::default()
-        }
-    };
-
-    let consensus_config = ConsensusConfig {
-        round_timeout: 3500,
-        status_timeout: 5000,
-        peers_timeout: 10_000,
-        txs_block_limit: 3000,
-        max_message_len: ConsensusConfig::DEFAULT_MESSAGE_MAX_LEN,
-        timeout_adjuster: TimeoutAdjusterConfig::Constant { timeout: 2500},
-    };
 
     // Configure Node
-    let validators = Some(ValidatorKeys {
-        consensus_key: PublicKey::from_hex(GENESIS_VALIDATOR_PUBLIC).unwrap(),
-        service_key: PublicKey::from_hex(GENESIS_SERVICE_PUBLIC).unwrap(),
-    });
-
-    let genesis = GenesisConfig::new_with_consensus(consensus_config, validators.into_iter());
+    let genesis = genesis_validators.genesis_config(consensus_config.clone());
     let api_cfg = NodeApiConfig {
         public_api_address: Some(public_api),
         private_api_address: Some(private_api),
+        max_blocks_behind: config::config().api().max_blocks_behind(),
         ..Default::default()
     };
 
-    let peer_addrs = peers.iter().map(|(_, p)| p.peer).collect();
-
     // Complete node configuration
     let node_cfg = NodeConfig {
-        listen_address: config::config().api().peer_address().parse().unwrap(),
-        peers: peer_addrs,
+        listen_address: peer_address,
+        peers: Vec::new(),
         service_public_key,
         service_secret_key,
         consensus_public_key,
@@ -128,11 +225,72 @@ fn main() {
         network: Default::default(),
         whitelist: Default::default(),
         api: api_cfg,
-        mempool: Default::default(),
+        mempool,
         services_configs: Default::default(),
     };
 
+    Ok(Setup {
+        info,
+        consensus_config,
+        allocation,
+        node_cfg,
+    })
+}
+
+fn run() -> Result<(), StartupError> {
+    exonum::helpers::init_logger().map_err(|e| StartupError::Logger(e.to_string()))?;
+
+    let Setup {
+        info,
+        consensus_config,
+        allocation,
+        mut node_cfg,
+    } = setup(keyfile::pair)?;
+    let signals = shutdown::signals(&config::config().shutdown().signals())
+        .map_err(StartupError::Shutdown)?;
+
+    let is_validator = config::config().api().is_validator();
+    eprintln!(
+        "Connecting {}",
+        if is_validator {
+            "as validator"
+        } else {
+            "as auditor"
+        }
+    );
+
+    let peers = match net_config::connect(&info, is_validator) {
+        Ok(connection) => {
+            let peers = connection.peers().clone();
+            eprintln!(
+                "Connected as {}, {} peers:",
+                if connection.is_validator() {
+                    "validator"
+                } else {
+                    "auditor"
+                },
+                peers.len()
+            );
+            for peer in peers.values() {
+                eprintln!("    {}", peer);
+            }
+            peers
+        }
+        Err(e) => {
+            eprintln!("Unable to connect after retries: {}", &e);
+            eprintln!("Running in loner-mode.");
+            Default::default()
+        }
+    };
+
+    let validator_count = node_cfg.genesis.validator_keys.len();
+    node_cfg.peers = peers.iter().map(|(_, p)| p.peer).collect();
+
     // Initialize database
+    let tuning = config::config()
+        .db()
+        .tuning()
+        .map_err(StartupError::DbTuning)?;
     let mut options = RocksDBOptions::default();
     options.create_if_missing(true);
     options.enable_statistics();
@@ -140,17 +298,59 @@ fn main() {
         use exonum_rocksdb::DBCompressionType;
         options.set_compression_type(DBCompressionType::Zlib);
     }
+    if let Some(size) = tuning.write_buffer_size {
+        options.set_write_buffer_size(size);
+    }
+    if let Some(number) = tuning.max_write_buffer_number {
+        options.set_max_write_buffer_number(number);
+    }
+    if let Some(trigger) = tuning.level_zero_file_num_compaction_trigger {
+        options.set_level_zero_file_num_compaction_trigger(trigger);
+    }
+    if let Some(files) = tuning.max_open_files {
+        options.set_max_open_files(files);
+    }
+    if let Some(style) = tuning.compaction_style {
+        use exonum_rocksdb::DBCompactionStyle;
+        options.set_compaction_style(match style {
+            config::CompactionStyle::Level => DBCompactionStyle::Level,
+            config::CompactionStyle::Universal => DBCompactionStyle::Universal,
+            config::CompactionStyle::Fifo => DBCompactionStyle::Fifo,
+        });
+    }
     let path = config::config().db().path();
-    let db = Box::new(RocksDB::open(path, &options).unwrap());
+    let db = RocksDB::open(&path, &options).map_err(|e| StartupError::Database {
+        path: path.clone(),
+        error: e.to_string(),
+    })?;
+    check_genesis_consensus(&db, &consensus_config)?;
+    migrate(&db, &path)?;
+    let db_path = ::std::fs::canonicalize(&path)
+        .map(|path| path.display().to_string())
+        .unwrap_or(path);
+    let db = Box::new(db);
 
     // Initialize services
     let services: Vec<Box<blockchain::Service>> = vec![
         Box::new(ConfigurationService::new()),
-        Box::new(Service::new()),
+        Box::new(Service::with_allocation(allocation)),
     ];
 
-    eprintln!("Launching node. What can possibly go wrong?");
+    // Dump metrics in the background, if enabled.
+    let _metrics_dump = dmbc::metrics::start();
+
+    Banner {
+        version: VERSION,
+        is_validator,
+        info: &info,
+        db_path: &db_path,
+        peers: peers.len(),
+        validators: validator_count,
+    }.log();
 
+    let stop = shutdown::listen(&signals).map_err(|e| StartupError::Shutdown(e.to_string()))?;
     let node = Node::new(db, services, node_cfg);
-    node.run().unwrap();
+    node.run_until(stop.map_err(drop)).map_err(StartupError::Node)?;
+    info!("Node stopped");
+    Ok(())
 }
@@ -1,13 +1,16 @@
 use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
 use std::net::SocketAddr;
 use std::thread;
+use std::time::Duration;
 
 use curl::easy::Easy;
 use exonum::crypto::PublicKey;
 use serde_json;
 
 use dmbc::config;
+use dmbc::keys;
 
 type PKeys = String;
 
@@ -28,27 +31,148 @@ impl ValidatorInfo {
     }
 }
 
-pub fn connect(
-    info: &ValidatorInfo,
-    is_validator: bool,
-) -> Result<HashMap<PKeys, ValidatorInfo>, Box<Error>> {
+impl fmt::Display for ValidatorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "public {}, private {}, peer {}, consensus key {}, service key {}",
+            self.public,
+            self.private,
+            self.peer,
+            keys::log(&self.consensus),
+            keys::log(&self.service)
+        )
+    }
+}
+
+/// Role a node registered with at the service discovery, along with the
+/// validators it should peer with.
+#[derive(Debug, PartialEq)]
+pub enum Connection {
+    /// Registered in the validator pool and proposed for consensus.
+    Validator(HashMap<PKeys, ValidatorInfo>),
+    /// Registered in the auditor pool. Auditors don't count toward the
+    /// validator quorum and only sync from the validators.
+    Auditor(HashMap<PKeys, ValidatorInfo>),
+}
+
+impl Connection {
+    fn new(
+        info: &ValidatorInfo,
+        is_validator: bool,
+        mut nodes: HashMap<PKeys, ValidatorInfo>,
+    ) -> Self {
+        if is_validator {
+            Connection::Validator(nodes)
+        } else {
+            // An auditor is never one of its own validator peers, even if
+            // it was once registered in the validator pool.
+            nodes.remove(&info.keys());
+            Connection::Auditor(nodes)
+        }
+    }
+
+    pub fn is_validator(&self) -> bool {
+        match *self {
+            Connection::Validator(_) => true,
+            Connection::Auditor(_) => false,
+        }
+    }
+
+    pub fn peers(&self) -> &HashMap<PKeys, ValidatorInfo> {
+        match *self {
+            Connection::Validator(ref peers) | Connection::Auditor(ref peers) => peers,
+        }
+    }
+}
+
+/// Limits on a single request to the service discovery, so that a
+/// coordinator which accepts connections but never answers fails the
+/// attempt instead of blocking startup.
+#[derive(Debug, Clone, Copy)]
+struct Timeouts {
+    connect: Duration,
+    total: Duration,
+}
+
+impl Timeouts {
+    fn from_config() -> Self {
+        Timeouts {
+            connect: Duration::from_millis(config::config().service_discovery().connect_timeout()),
+            total: Duration::from_millis(config::config().service_discovery().timeout()),
+        }
+    }
+
+    fn handle(&self, url: &str) -> Result<Easy, Box<Error>> {
+        let mut handle = Easy::new();
+        handle.url(url).map_err(Box::new)?;
+        handle.connect_timeout(self.connect).map_err(Box::new)?;
+        handle.timeout(self.total).map_err(Box::new)?;
+        Ok(handle)
+    }
+}
+
+/// Register with the service discovery and receive the known validators.
+/// Validators join the `/nodes` pool, auditors the `/auditors` pool next to it.
+/// Failed attempts are retried with exponential backoff, as configured in
+/// the `service_discovery` section, before the last error is returned.
+/// Every request is bounded by the configured connect and total timeouts.
+pub fn connect(info: &ValidatorInfo, is_validator: bool) -> Result<Connection, Box<Error>> {
+    let retries = config::config().service_discovery().retries();
+    let mut delay = config::config().service_discovery().retry_delay();
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match try_connect(info, is_validator) {
+            Ok(connection) => return Ok(connection),
+            Err(ref e) if attempt <= retries => {
+                eprintln!(
+                    "Attempt {} of {} to reach service discovery failed: {}. Retrying in {} ms.",
+                    attempt,
+                    retries + 1,
+                    e,
+                    delay
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        thread::sleep(Duration::from_millis(delay));
+        delay = delay.saturating_mul(2);
+    }
+}
+
+fn try_connect(info: &ValidatorInfo, is_validator: bool) -> Result<Connection, Box<Error>> {
     let discovery = config::config().service_discovery().address();
+    let timeouts = Timeouts::from_config();
 
-    let nodes = receive_nodes(&discovery)?;
-    if nodes.contains_key(&info.keys()) || !is_validator {
-        return Ok(nodes);
+    let nodes = receive_nodes(&discovery, timeouts)?;
+    if !is_validator {
+        send_node(&auditors_address(&discovery), info, timeouts)?;
+    } else if !nodes.contains_key(&info.keys()) {
+        send_node(&discovery, info, timeouts)?;
     }
 
-    send_node(&discovery, info)?;
+    Ok(Connection::new(info, is_validator, nodes))
+}
 
-    Ok(nodes)
+/// The auditor pool lives next to the configured validator pool,
+/// e.g. `127.0.0.1:3000/nodes` -> `127.0.0.1:3000/auditors`.
+fn auditors_address(discovery: &str) -> String {
+    let base = match discovery.rfind('/') {
+        Some(i) if !discovery[..i].ends_with('/') => &discovery[..i],
+        _ => discovery,
+    };
+    format!("{}/auditors", base)
 }
 
-fn receive_nodes(discovery: &str) -> Result<HashMap<PKeys, ValidatorInfo>, Box<Error>> {
+fn receive_nodes(
+    discovery: &str,
+    timeouts: Timeouts,
+) -> Result<HashMap<PKeys, ValidatorInfo>, Box<Error>> {
     let mut nodes_get = Vec::new();
 
-    let mut handle = Easy::new();
-    handle.url(discovery).map_err(Box::new)?;
+    let mut handle = timeouts.handle(discovery)?;
     {
         let mut transfer = handle.transfer();
         transfer
@@ -64,11 +188,10 @@ fn receive_nodes(discovery: &str) -> Result<HashMap<PKeys, ValidatorInfo>, Box<E
     nodes.map_err(|e| e.into())
 }
 
-fn send_node(discovery: &str, info: &ValidatorInfo) -> Result<(), Box<Error>> {
+fn send_node(discovery: &str, info: &ValidatorInfo, timeouts: Timeouts) -> Result<(), Box<Error>> {
     let node_post = serde_json::to_string(info).map_err(Box::new)?;
 
-    let mut handle = Easy::new();
-    handle.url(discovery).map_err(Box::new)?;
+    let mut handle = timeouts.handle(discovery)?;
     handle.post(true).map_err(Box::new)?;
     handle
         .post_fields_copy(node_post.as_bytes())
@@ -80,3 +203,84 @@ fn send_node(discovery: &str, info: &ValidatorInfo) -> Result<(), Box<Error>> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    use exonum::crypto;
+
+    use super::*;
+
+    fn node(port: u16) -> ValidatorInfo {
+        let (consensus, _) = crypto::gen_keypair();
+        let (service, _) = crypto::gen_keypair();
+        let address = format!("127.0.0.1:{}", port).parse().unwrap();
+        ValidatorInfo {
+            public: address,
+            private: address,
+            peer: address,
+            consensus,
+            service,
+        }
+    }
+
+    fn pool(nodes: &[ValidatorInfo]) -> HashMap<PKeys, ValidatorInfo> {
+        nodes.iter().map(|n| (n.keys(), *n)).collect()
+    }
+
+    #[test]
+    fn auditors_register_next_to_nodes() {
+        assert_eq!(
+            auditors_address("127.0.0.1:3000/nodes"),
+            "127.0.0.1:3000/auditors"
+        );
+        assert_eq!(
+            auditors_address("http://sd.local:3000/nodes"),
+            "http://sd.local:3000/auditors"
+        );
+        assert_eq!(
+            auditors_address("127.0.0.1:3000"),
+            "127.0.0.1:3000/auditors"
+        );
+    }
+
+    #[test]
+    fn auditor_receives_validators_but_is_not_one() {
+        let validators = [node(2000), node(2001)];
+        let auditor = node(2002);
+
+        let mut response = pool(&validators);
+        response.insert(auditor.keys(), auditor);
+
+        let connection = Connection::new(&auditor, false, response);
+        assert!(!connection.is_validator());
+        assert_eq!(connection.peers(), &pool(&validators));
+        assert!(!connection.peers().contains_key(&auditor.keys()));
+    }
+
+    #[test]
+    fn validator_keeps_the_validator_set() {
+        let validators = [node(2000), node(2001)];
+
+        let connection = Connection::new(&validators[0], true, pool(&validators));
+        assert!(connection.is_validator());
+        assert_eq!(connection, Connection::Validator(pool(&validators)));
+    }
+
+    #[test]
+    fn unresponsive_discovery_times_out() {
+        // The listener completes the handshake but never answers.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let discovery = format!("{}/nodes", listener.local_addr().unwrap());
+        let timeouts = Timeouts {
+            connect: Duration::from_millis(100),
+            total: Duration::from_millis(300),
+        };
+
+        let start = Instant::now();
+        assert!(receive_nodes(&discovery, timeouts).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,69 @@
+//! Graceful shutdown on signals.
+
+use std::io;
+use std::os::raw::c_int;
+use std::thread;
+
+use futures::sync::oneshot;
+use signal_hook;
+use signal_hook::iterator::Signals;
+
+/// Number of the signal called `name`, with or without the `SIG` prefix.
+pub fn signal_number(name: &str) -> Option<c_int> {
+    let name = name.trim().to_uppercase();
+    let name = if name.starts_with("SIG") { &name[3..] } else { &name[..] };
+    match name {
+        "HUP" => Some(signal_hook::SIGHUP),
+        "INT" => Some(signal_hook::SIGINT),
+        "QUIT" => Some(signal_hook::SIGQUIT),
+        "TERM" => Some(signal_hook::SIGTERM),
+        "USR1" => Some(signal_hook::SIGUSR1),
+        "USR2" => Some(signal_hook::SIGUSR2),
+        _ => None,
+    }
+}
+
+/// Numbers of the signals named in the config.
+pub fn signals(names: &[String]) -> Result<Vec<c_int>, String> {
+    if names.is_empty() {
+        return Err("no signals configured".to_string());
+    }
+    names
+        .iter()
+        .map(|name| signal_number(name).ok_or_else(|| format!("unknown signal {:?}", name)))
+        .collect()
+}
+
+/// Wait for any of `signals` in the background. The returned receiver
+/// resolves on the first one; the node then finishes the block it is
+/// committing and stops.
+pub fn listen(signals: &[c_int]) -> io::Result<oneshot::Receiver<()>> {
+    let signals = Signals::new(signals)?;
+    let (stop_tx, stop_rx) = oneshot::channel();
+    thread::Builder::new()
+        .name("Shutdown".to_string())
+        .spawn(move || {
+            if let Some(signal) = signals.forever().next() {
+                info!("Received signal {}, shutting down", signal);
+                let _ = stop_tx.send(());
+            }
+        })?;
+    Ok(stop_rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signal_names() {
+        assert_eq!(Some(signal_hook::SIGTERM), signal_number("SIGTERM"));
+        assert_eq!(Some(signal_hook::SIGINT), signal_number("int"));
+        assert_eq!(None, signal_number("SIGKILL"));
+
+        let names = vec!["SIGINT".to_string(), "SIGTERM".to_string()];
+        assert_eq!(Ok(vec![signal_hook::SIGINT, signal_hook::SIGTERM]), signals(&names));
+        assert!(signals(&["SIGFOO".to_string()]).is_err());
+        assert!(signals(&[]).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! Validators of the genesis block.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use exonum::blockchain::{ConsensusConfig, GenesisConfig, ValidatorKeys};
+use serde_json;
+
+/// Keys of the validators the chain starts with.
+///
+/// The validators file is a JSON list of `ValidatorKeys`:
+///
+/// ```json
+/// [
+///     { "consensus_key": "...", "service_key": "..." },
+///     { "consensus_key": "...", "service_key": "..." }
+/// ]
+/// ```
+#[derive(Clone, Debug)]
+pub struct GenesisValidators(Vec<ValidatorKeys>);
+
+impl GenesisValidators {
+    /// Create the validator set from a list of keys.
+    ///
+    /// # Errors
+    /// Fails if the list is empty or a key is used more than once.
+    pub fn new(validators: Vec<ValidatorKeys>) -> Result<Self, String> {
+        if validators.is_empty() {
+            return Err("at least one validator is required".to_string());
+        }
+
+        let mut keys = HashSet::new();
+        for validator in &validators {
+            for key in &[validator.consensus_key, validator.service_key] {
+                if !keys.insert(*key) {
+                    return Err(format!("duplicate key {}", key.to_hex()));
+                }
+            }
+        }
+
+        Ok(GenesisValidators(validators))
+    }
+
+    /// Parse the validator set from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let validators: Vec<ValidatorKeys> =
+            serde_json::from_str(json).map_err(|e| e.to_string())?;
+        GenesisValidators::new(validators)
+    }
+
+    /// Read the validator set from a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let mut json = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut json))
+            .map_err(|e| e.to_string())?;
+        GenesisValidators::from_json(&json)
+    }
+
+    /// Genesis configuration with these validators.
+    pub fn genesis_config(&self, consensus: ConsensusConfig) -> GenesisConfig {
+        GenesisConfig::new_with_consensus(consensus, self.0.iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use exonum::crypto;
+
+    use super::*;
+
+    fn validator() -> ValidatorKeys {
+        ValidatorKeys {
+            consensus_key: crypto::gen_keypair().0,
+            service_key: crypto::gen_keypair().0,
+        }
+    }
+
+    #[test]
+    fn three_validators() {
+        let keys = vec![validator(), validator(), validator()];
+        let json = serde_json::to_string(&keys).unwrap();
+
+        let validators = GenesisValidators::from_json(&json).unwrap();
+        let genesis = validators.genesis_config(ConsensusConfig::default());
+
+        assert_eq!(keys, genesis.validator_keys);
+    }
+
+    #[test]
+    fn no_validators() {
+        assert!(GenesisValidators::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn duplicate_validators() {
+        let first = validator();
+        let second = ValidatorKeys {
+            service_key: first.consensus_key,
+            ..validator()
+        };
+
+        assert!(GenesisValidators::new(vec![first, first]).is_err());
+        assert!(GenesisValidators::new(vec![first, second]).is_err());
+    }
+}
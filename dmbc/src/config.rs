@@ -8,6 +8,9 @@ use std::io::prelude::*;
 use std::net::{SocketAddr, ToSocketAddrs};
 use std::path::Path;
 
+use exonum::blockchain::{ConsensusConfig, TimeoutAdjusterConfig};
+use exonum::node::{EventsPoolCapacity, MemoryPoolConfig};
+
 /// Representation of configuration file contents.
 #[derive(Deserialize, Clone)]
 #[warn(unused_must_use)]
@@ -16,6 +19,12 @@ pub struct Config {
     db: Db,
     nats: Nats,
     service_discovery: ServiceDiscovery,
+    metrics: Option<Metrics>,
+    consensus: Option<Consensus>,
+    genesis: Option<Genesis>,
+    log: Option<Log>,
+    mempool: Option<Mempool>,
+    shutdown: Option<Shutdown>,
 }
 
 /// Node communications configuration.
@@ -28,12 +37,39 @@ pub struct Api {
     peer_address: Option<String>,
     peers: Option<Vec<String>>,
     is_validator: Option<bool>,
+    max_blocks_behind: Option<u64>,
+    max_wallet_assets: Option<usize>,
+    max_batch_wallets: Option<usize>,
+    dedupe_submissions: Option<bool>,
 }
 
 /// Database configuration.
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, Default)]
 pub struct Db {
     path: Option<String>,
+    write_buffer_size: Option<usize>,
+    max_write_buffer_number: Option<i32>,
+    level_zero_file_num_compaction_trigger: Option<i32>,
+    max_open_files: Option<i32>,
+    compaction_style: Option<String>,
+}
+
+/// RocksDB compaction style, see `Db::compaction_style`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactionStyle {
+    Level,
+    Universal,
+    Fifo,
+}
+
+/// Validated RocksDB tuning. `None` keeps the RocksDB default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbTuning {
+    pub write_buffer_size: Option<usize>,
+    pub max_write_buffer_number: Option<i32>,
+    pub level_zero_file_num_compaction_trigger: Option<i32>,
+    pub max_open_files: Option<i32>,
+    pub compaction_style: Option<CompactionStyle>,
 }
 
 /// NATS reporting configuration.
@@ -48,6 +84,63 @@ pub struct Nats {
 #[derive(Deserialize, Clone)]
 pub struct ServiceDiscovery {
     address: Option<String>,
+    retries: Option<u32>,
+    retry_delay: Option<u64>,
+    connect_timeout: Option<u64>,
+    timeout: Option<u64>,
+}
+
+/// Periodic metrics dump configuration.
+#[derive(Deserialize, Clone, Default)]
+pub struct Metrics {
+    dump_interval: Option<u64>,
+    dump_path: Option<String>,
+    dump_max_size: Option<u64>,
+}
+
+/// Consensus parameters of the genesis block. Missing values fall back to
+/// the defaults the network was launched with.
+#[derive(Deserialize, Clone, Default)]
+pub struct Consensus {
+    round_timeout: Option<u64>,
+    status_timeout: Option<u64>,
+    peers_timeout: Option<u64>,
+    txs_block_limit: Option<u32>,
+    propose_timeout: Option<u64>,
+    propose_timeout_min: Option<u64>,
+    propose_timeout_max: Option<u64>,
+    propose_timeout_threshold: Option<u32>,
+    strict_genesis: Option<bool>,
+}
+
+/// State of a fresh network at block zero.
+#[derive(Deserialize, Clone, Default)]
+pub struct Genesis {
+    allocation: Option<String>,
+    validators: Option<String>,
+}
+
+/// Logging configuration.
+#[derive(Deserialize, Clone, Default)]
+pub struct Log {
+    key_fingerprint: Option<bool>,
+}
+
+/// Capacities of the pool of uncommitted transactions and of the event
+/// queues feeding it. Missing values fall back to the Exonum defaults.
+#[derive(Deserialize, Clone, Default)]
+pub struct Mempool {
+    capacity: Option<usize>,
+    network_requests_capacity: Option<usize>,
+    network_events_capacity: Option<usize>,
+    internal_events_capacity: Option<usize>,
+    api_requests_capacity: Option<usize>,
+}
+
+/// Graceful shutdown of the node.
+#[derive(Deserialize, Clone, Default)]
+pub struct Shutdown {
+    signals: Option<Vec<String>>,
 }
 
 impl Config {
@@ -70,6 +163,42 @@ impl Config {
     pub fn service_discovery(self) -> ServiceDiscovery {
         self.service_discovery
     }
+
+    /// Get `Metrics` configuration from the config file.
+    /// The section is optional.
+    pub fn metrics(self) -> Metrics {
+        self.metrics.unwrap_or_default()
+    }
+
+    /// Get `Consensus` configuration from the config file.
+    /// The section is optional.
+    pub fn consensus(self) -> Consensus {
+        self.consensus.unwrap_or_default()
+    }
+
+    /// Get `Genesis` configuration from the config file.
+    /// The section is optional.
+    pub fn genesis(self) -> Genesis {
+        self.genesis.unwrap_or_default()
+    }
+
+    /// Get `Log` configuration from the config file.
+    /// The section is optional.
+    pub fn log(self) -> Log {
+        self.log.unwrap_or_default()
+    }
+
+    /// Get `Mempool` configuration from the config file.
+    /// The section is optional.
+    pub fn mempool(self) -> Mempool {
+        self.mempool.unwrap_or_default()
+    }
+
+    /// Get `Shutdown` configuration from the config file.
+    /// The section is optional.
+    pub fn shutdown(self) -> Shutdown {
+        self.shutdown.unwrap_or_default()
+    }
 }
 
 impl Api {
@@ -140,6 +269,40 @@ impl Api {
             Err(_) => self.is_validator.unwrap_or(true),
         }
     }
+
+    /// Number of blocks the node may lag behind its peers before it stops
+    /// proposing and reports itself as degraded.
+    pub fn max_blocks_behind(self) -> Option<u64> {
+        match env::var("API_MAX_BLOCKS_BEHIND") {
+            Ok(value) => Some(value.parse::<u64>().unwrap()),
+            Err(_) => self.max_blocks_behind,
+        }
+    }
+
+    /// Maximum number of assets listed in a wallet summary. The complete
+    /// list is available from the wallet assets endpoint.
+    pub fn max_wallet_assets(self) -> usize {
+        match env::var("API_MAX_WALLET_ASSETS") {
+            Ok(value) => value.parse::<usize>().unwrap(),
+            Err(_) => self.max_wallet_assets.unwrap_or(100),
+        }
+    }
+
+    /// Maximum number of wallets a single balances request may ask for.
+    /// Larger requests are rejected.
+    pub fn max_batch_wallets(self) -> usize {
+        match env::var("API_MAX_BATCH_WALLETS") {
+            Ok(value) => value.parse::<usize>().unwrap(),
+            Err(_) => self.max_batch_wallets.unwrap_or(100),
+        }
+    }
+
+    /// Answer a submitted transaction which already has a status with that
+    /// status, or which is still pending, instead of passing it to the node
+    /// again.
+    pub fn dedupe_submissions(self) -> bool {
+        env_or("API_DEDUPE_SUBMISSIONS", self.dedupe_submissions).unwrap_or(true)
+    }
 }
 
 impl Db {
@@ -150,6 +313,103 @@ impl Db {
             Err(_) => self.path.unwrap(),
         }
     }
+
+    /// Size of a memtable in bytes; it is flushed to disk once full.
+    /// `None` keeps the RocksDB default.
+    pub fn write_buffer_size(self) -> Option<usize> {
+        match env::var("DB_WRITE_BUFFER_SIZE") {
+            Ok(value) => Some(value.parse::<usize>().unwrap()),
+            Err(_) => self.write_buffer_size,
+        }
+    }
+
+    /// Maximum number of memtables held in memory before writes stall
+    /// waiting for a flush. `None` keeps the RocksDB default.
+    pub fn max_write_buffer_number(self) -> Option<i32> {
+        match env::var("DB_MAX_WRITE_BUFFER_NUMBER") {
+            Ok(value) => Some(value.parse::<i32>().unwrap()),
+            Err(_) => self.max_write_buffer_number,
+        }
+    }
+
+    /// Number of level-0 files that triggers a compaction.
+    /// `None` keeps the RocksDB default.
+    pub fn level_zero_file_num_compaction_trigger(self) -> Option<i32> {
+        match env::var("DB_LEVEL_ZERO_FILE_NUM_COMPACTION_TRIGGER") {
+            Ok(value) => Some(value.parse::<i32>().unwrap()),
+            Err(_) => self.level_zero_file_num_compaction_trigger,
+        }
+    }
+
+    /// Number of files RocksDB keeps open, `-1` for no limit.
+    /// `None` keeps the RocksDB default.
+    pub fn max_open_files(self) -> Option<i32> {
+        env_or("DB_MAX_OPEN_FILES", self.max_open_files)
+    }
+
+    /// Compaction style: `level`, `universal` or `fifo`.
+    /// `None` keeps the RocksDB default.
+    pub fn compaction_style(self) -> Option<String> {
+        env::var("DB_COMPACTION_STYLE").ok().or(self.compaction_style)
+    }
+
+    /// RocksDB tuning for opening the database.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if a value is out of the range
+    /// RocksDB works with or the compaction style is unknown.
+    pub fn tuning(self) -> Result<DbTuning, String> {
+        let write_buffer_size = self.clone().write_buffer_size();
+        if let Some(size) = write_buffer_size {
+            if size < 64 * 1024 {
+                return Err(format!("write_buffer_size ({}) is below 64 KiB", size));
+            }
+        }
+
+        let max_write_buffer_number = self.clone().max_write_buffer_number();
+        if let Some(number) = max_write_buffer_number {
+            if number < 2 {
+                return Err(format!("max_write_buffer_number ({}) is below 2", number));
+            }
+        }
+
+        let level_zero_file_num_compaction_trigger =
+            self.clone().level_zero_file_num_compaction_trigger();
+        if let Some(trigger) = level_zero_file_num_compaction_trigger {
+            if trigger < 1 {
+                return Err(format!(
+                    "level_zero_file_num_compaction_trigger ({}) is below 1",
+                    trigger
+                ));
+            }
+        }
+
+        let max_open_files = self.clone().max_open_files();
+        if let Some(files) = max_open_files {
+            if files != -1 && files < 20 {
+                return Err(format!(
+                    "max_open_files ({}) must be -1 or at least 20",
+                    files
+                ));
+            }
+        }
+
+        let compaction_style = match self.compaction_style() {
+            None => None,
+            Some(ref style) if style == "level" => Some(CompactionStyle::Level),
+            Some(ref style) if style == "universal" => Some(CompactionStyle::Universal),
+            Some(ref style) if style == "fifo" => Some(CompactionStyle::Fifo),
+            Some(style) => return Err(format!("unknown compaction_style {:?}", style)),
+        };
+
+        Ok(DbTuning {
+            write_buffer_size,
+            max_write_buffer_number,
+            level_zero_file_num_compaction_trigger,
+            max_open_files,
+            compaction_style,
+        })
+    }
 }
 
 impl Nats {
@@ -195,6 +455,291 @@ impl ServiceDiscovery {
             Err(_) => self.address.unwrap(),
         }
     }
+
+    /// Number of times a failed request to the service discovery is retried.
+    pub fn retries(self) -> u32 {
+        match env::var("SD_RETRIES") {
+            Ok(value) => value.parse::<u32>().unwrap(),
+            Err(_) => self.retries.unwrap_or(5),
+        }
+    }
+
+    /// Delay before the first retry, in milliseconds. Doubles with every
+    /// following attempt.
+    pub fn retry_delay(self) -> u64 {
+        match env::var("SD_RETRY_DELAY") {
+            Ok(value) => value.parse::<u64>().unwrap(),
+            Err(_) => self.retry_delay.unwrap_or(500),
+        }
+    }
+
+    /// Time allowed to establish a connection to the service discovery,
+    /// in milliseconds.
+    pub fn connect_timeout(self) -> u64 {
+        match env::var("SD_CONNECT_TIMEOUT") {
+            Ok(value) => value.parse::<u64>().unwrap(),
+            Err(_) => self.connect_timeout.unwrap_or(5_000),
+        }
+    }
+
+    /// Time allowed for a whole request to the service discovery, in
+    /// milliseconds. A request that takes longer fails and is retried.
+    pub fn timeout(self) -> u64 {
+        match env::var("SD_TIMEOUT") {
+            Ok(value) => value.parse::<u64>().unwrap(),
+            Err(_) => self.timeout.unwrap_or(15_000),
+        }
+    }
+}
+
+impl Metrics {
+    /// Seconds between metrics dumps. `None` disables dumping.
+    pub fn dump_interval(self) -> Option<u64> {
+        match env::var("METRICS_DUMP_INTERVAL") {
+            Ok(value) => Some(value.parse::<u64>().unwrap()),
+            Err(_) => self.dump_interval,
+        }
+    }
+
+    /// File the metrics are appended to. `None` writes them to the log.
+    pub fn dump_path(self) -> Option<String> {
+        match env::var("METRICS_DUMP_PATH") {
+            Ok(value) => Some(value),
+            Err(_) => self.dump_path,
+        }
+    }
+
+    /// Size in bytes after which the dump file is rotated.
+    pub fn dump_max_size(self) -> u64 {
+        match env::var("METRICS_DUMP_MAX_SIZE") {
+            Ok(value) => value.parse::<u64>().unwrap(),
+            Err(_) => self.dump_max_size.unwrap_or(10 * 1024 * 1024),
+        }
+    }
+}
+
+fn env_or<T: ::std::str::FromStr>(name: &str, value: Option<T>) -> Option<T> {
+    match env::var(name) {
+        Ok(value) => Some(
+            value
+                .parse::<T>()
+                .unwrap_or_else(|_| panic!("{} is not a valid number: {:?}", name, value)),
+        ),
+        Err(_) => value,
+    }
+}
+
+impl Consensus {
+    /// Interval between rounds, in milliseconds.
+    pub fn round_timeout(self) -> u64 {
+        env_or("CONSENSUS_ROUND_TIMEOUT", self.round_timeout).unwrap_or(3500)
+    }
+
+    /// Interval between status broadcasts, in milliseconds.
+    pub fn status_timeout(self) -> u64 {
+        env_or("CONSENSUS_STATUS_TIMEOUT", self.status_timeout).unwrap_or(5000)
+    }
+
+    /// Interval between peers exchanges, in milliseconds.
+    pub fn peers_timeout(self) -> u64 {
+        env_or("CONSENSUS_PEERS_TIMEOUT", self.peers_timeout).unwrap_or(10_000)
+    }
+
+    /// Maximum number of transactions in a block.
+    pub fn txs_block_limit(self) -> u32 {
+        env_or("CONSENSUS_TXS_BLOCK_LIMIT", self.txs_block_limit).unwrap_or(3000)
+    }
+
+    /// Propose timeout adjuster.
+    ///
+    /// Constant `propose_timeout` unless both `propose_timeout_min` and
+    /// `propose_timeout_max` are set, in which case the timeout adapts to the
+    /// number of pending transactions, reaching the minimum at
+    /// `propose_timeout_threshold` of them.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if only one of the bounds is set
+    /// or the minimum is greater than the maximum.
+    pub fn timeout_adjuster(self) -> Result<TimeoutAdjusterConfig, String> {
+        let min = env_or("CONSENSUS_PROPOSE_TIMEOUT_MIN", self.propose_timeout_min);
+        let max = env_or("CONSENSUS_PROPOSE_TIMEOUT_MAX", self.propose_timeout_max);
+        match (min, max) {
+            (None, None) => Ok(TimeoutAdjusterConfig::Constant {
+                timeout: env_or("CONSENSUS_PROPOSE_TIMEOUT", self.propose_timeout)
+                    .unwrap_or(2500),
+            }),
+            (Some(min), Some(max)) if min <= max => {
+                let threshold = env_or(
+                    "CONSENSUS_PROPOSE_TIMEOUT_THRESHOLD",
+                    self.propose_timeout_threshold,
+                );
+                Ok(TimeoutAdjusterConfig::Dynamic {
+                    min,
+                    max,
+                    threshold: threshold.unwrap_or_else(|| self.txs_block_limit()),
+                })
+            }
+            (Some(min), Some(max)) => Err(format!(
+                "propose_timeout_min ({}) is greater than propose_timeout_max ({})",
+                min, max
+            )),
+            _ => Err(
+                "propose_timeout_min and propose_timeout_max must be set together".to_string(),
+            ),
+        }
+    }
+
+    /// Refuse to start when the configured parameters differ from the ones
+    /// the chain is running with, instead of only warning about it.
+    pub fn strict_genesis(self) -> bool {
+        env_or("CONSENSUS_STRICT_GENESIS", self.strict_genesis).unwrap_or(false)
+    }
+
+    /// Consensus configuration for the genesis block.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the timeout adjuster
+    /// settings are invalid.
+    pub fn consensus_config(self) -> Result<ConsensusConfig, String> {
+        Ok(ConsensusConfig {
+            round_timeout: self.clone().round_timeout(),
+            status_timeout: self.clone().status_timeout(),
+            peers_timeout: self.clone().peers_timeout(),
+            txs_block_limit: self.clone().txs_block_limit(),
+            max_message_len: ConsensusConfig::DEFAULT_MESSAGE_MAX_LEN,
+            timeout_adjuster: self.timeout_adjuster()?,
+        })
+    }
+}
+
+impl Genesis {
+    /// JSON file with the wallets funded at genesis, see
+    /// `currency::genesis::GenesisAllocation`. `None` funds no wallets.
+    pub fn allocation(self) -> Option<String> {
+        match env::var("GENESIS_ALLOCATION") {
+            Ok(value) => Some(value),
+            Err(_) => self.allocation,
+        }
+    }
+
+    /// JSON file with the keys of the genesis validators. `None` uses the
+    /// single built-in validator.
+    pub fn validators(self) -> Option<String> {
+        match env::var("GENESIS_VALIDATORS") {
+            Ok(value) => Some(value),
+            Err(_) => self.validators,
+        }
+    }
+}
+
+impl Log {
+    /// Shorten public keys in logs to their fingerprint, see `keys::log`.
+    pub fn key_fingerprint(self) -> bool {
+        env_or("LOG_KEY_FINGERPRINT", self.key_fingerprint).unwrap_or(false)
+    }
+}
+
+impl Mempool {
+    /// Maximum number of uncommitted transactions the node keeps. Further
+    /// transactions are dropped until blocks make room for them.
+    pub fn capacity(self) -> usize {
+        env_or("MEMPOOL_CAPACITY", self.capacity).unwrap_or(100_000)
+    }
+
+    /// Event queue capacities of the node.
+    pub fn events_pool_capacity(self) -> EventsPoolCapacity {
+        let default = EventsPoolCapacity::default();
+        EventsPoolCapacity {
+            network_requests_capacity: env_or(
+                "MEMPOOL_NETWORK_REQUESTS_CAPACITY",
+                self.network_requests_capacity,
+            ).unwrap_or(default.network_requests_capacity),
+            network_events_capacity: env_or(
+                "MEMPOOL_NETWORK_EVENTS_CAPACITY",
+                self.network_events_capacity,
+            ).unwrap_or(default.network_events_capacity),
+            internal_events_capacity: env_or(
+                "MEMPOOL_INTERNAL_EVENTS_CAPACITY",
+                self.internal_events_capacity,
+            ).unwrap_or(default.internal_events_capacity),
+            api_requests_capacity: env_or(
+                "MEMPOOL_API_REQUESTS_CAPACITY",
+                self.api_requests_capacity,
+            ).unwrap_or(default.api_requests_capacity),
+        }
+    }
+
+    /// Memory pool configuration of the node.
+    ///
+    /// A block takes at most `txs_block_limit` transactions from the pool,
+    /// so a pool holding less could never fill a block.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the capacity is below
+    /// `txs_block_limit` or an event queue has no room at all.
+    pub fn mempool_config(self, txs_block_limit: u32) -> Result<MemoryPoolConfig, String> {
+        let tx_pool_capacity = self.clone().capacity();
+        if tx_pool_capacity < txs_block_limit as usize {
+            return Err(format!(
+                "capacity ({}) is below txs_block_limit ({})",
+                tx_pool_capacity, txs_block_limit
+            ));
+        }
+
+        let events_pool_capacity = self.events_pool_capacity();
+        let queues = [
+            ("network_requests_capacity", events_pool_capacity.network_requests_capacity),
+            ("network_events_capacity", events_pool_capacity.network_events_capacity),
+            ("internal_events_capacity", events_pool_capacity.internal_events_capacity),
+            ("api_requests_capacity", events_pool_capacity.api_requests_capacity),
+        ];
+        for &(name, capacity) in queues.iter() {
+            if capacity == 0 {
+                return Err(format!("{} must be at least 1", name));
+            }
+        }
+
+        Ok(MemoryPoolConfig {
+            tx_pool_capacity,
+            events_pool_capacity,
+        })
+    }
+}
+
+impl Shutdown {
+    /// Names of the signals on which the node stops gracefully.
+    pub fn signals(self) -> Vec<String> {
+        match env::var("SHUTDOWN_SIGNALS") {
+            Ok(signals) => signals.split(',').map(|s| s.trim().to_string()).collect(),
+            Err(_) => self.signals
+                .unwrap_or_else(|| vec!["SIGINT".to_string(), "SIGTERM".to_string()]),
+        }
+    }
+}
+
+/// Describe every consensus parameter of `configured` which differs from
+/// the chain's `actual` one.
+pub fn consensus_mismatches(configured: &ConsensusConfig, actual: &ConsensusConfig) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    macro_rules! compare {
+        ($field:ident) => {
+            if configured.$field != actual.$field {
+                mismatches.push(format!(
+                    "{} is {:?} in the config but {:?} on the chain",
+                    stringify!($field),
+                    configured.$field,
+                    actual.$field
+                ));
+            }
+        };
+    }
+    compare!(round_timeout);
+    compare!(status_timeout);
+    compare!(peers_timeout);
+    compare!(txs_block_limit);
+    compare!(max_message_len);
+    compare!(timeout_adjuster);
+    mismatches
 }
 
 lazy_static! {
@@ -223,3 +768,205 @@ fn env_positive() {
     env::set_var("API_ADDRESS", address);
     assert_eq!(address, config().api().address().as_str())
 }
+
+#[test]
+fn max_wallet_assets_default() {
+    assert_eq!(100, config().api().max_wallet_assets());
+}
+
+#[test]
+fn dedupe_submissions_default() {
+    assert_eq!(true, config().api().dedupe_submissions());
+}
+
+#[test]
+fn db_tuning_defaults() {
+    assert_eq!(None, config().db().max_write_buffer_number());
+    assert_eq!(None, config().db().level_zero_file_num_compaction_trigger());
+    assert_eq!(None, config().db().max_open_files());
+    assert_eq!(None, config().db().compaction_style());
+    assert!(config().db().tuning().is_ok());
+}
+
+#[test]
+fn db_tuning_env_positive() {
+    env::set_var("DB_WRITE_BUFFER_SIZE", "67108864");
+    assert_eq!(Some(67108864), config().db().write_buffer_size())
+}
+
+#[test]
+fn db_tuning_options() {
+    let db = Db {
+        max_write_buffer_number: Some(4),
+        max_open_files: Some(-1),
+        compaction_style: Some("universal".to_string()),
+        ..Default::default()
+    };
+    let tuning = db.tuning().unwrap();
+    assert_eq!(Some(4), tuning.max_write_buffer_number);
+    assert_eq!(None, tuning.level_zero_file_num_compaction_trigger);
+    assert_eq!(Some(-1), tuning.max_open_files);
+    assert_eq!(Some(CompactionStyle::Universal), tuning.compaction_style);
+}
+
+#[test]
+fn db_tuning_invalid() {
+    let db = Db {
+        max_write_buffer_number: Some(0),
+        ..Default::default()
+    };
+    assert!(db.tuning().is_err());
+
+    let db = Db {
+        max_open_files: Some(5),
+        ..Default::default()
+    };
+    assert!(db.tuning().is_err());
+
+    let db = Db {
+        level_zero_file_num_compaction_trigger: Some(-3),
+        ..Default::default()
+    };
+    assert!(db.tuning().is_err());
+
+    let db = Db {
+        compaction_style: Some("sideways".to_string()),
+        ..Default::default()
+    };
+    assert!(db.tuning().is_err());
+}
+
+#[test]
+fn service_discovery_retry_defaults() {
+    assert_eq!(5, config().service_discovery().retries());
+    assert_eq!(500, config().service_discovery().retry_delay());
+}
+
+#[test]
+fn service_discovery_timeout_defaults() {
+    assert_eq!(5_000, config().service_discovery().connect_timeout());
+    assert_eq!(15_000, config().service_discovery().timeout());
+}
+
+#[test]
+fn metrics_defaults() {
+    assert_eq!(None, config().metrics().dump_interval());
+    assert_eq!(10 * 1024 * 1024, config().metrics().dump_max_size());
+}
+
+#[test]
+fn genesis_allocation_default() {
+    assert_eq!(None, config().genesis().allocation());
+}
+
+#[test]
+fn genesis_validators_default() {
+    assert_eq!(None, config().genesis().validators());
+}
+
+#[test]
+fn log_defaults() {
+    assert_eq!(false, config().log().key_fingerprint());
+}
+
+#[test]
+fn consensus_defaults() {
+    let config = Consensus::default().consensus_config().unwrap();
+    assert_eq!(3500, config.round_timeout);
+    assert_eq!(5000, config.status_timeout);
+    assert_eq!(10_000, config.peers_timeout);
+    assert_eq!(3000, config.txs_block_limit);
+    assert_eq!(TimeoutAdjusterConfig::Constant { timeout: 2500 }, config.timeout_adjuster);
+}
+
+#[test]
+fn consensus_dynamic_timeout() {
+    let consensus = Consensus {
+        propose_timeout_min: Some(200),
+        propose_timeout_max: Some(1000),
+        ..Default::default()
+    };
+    assert_eq!(
+        Ok(TimeoutAdjusterConfig::Dynamic { min: 200, max: 1000, threshold: 3000 }),
+        consensus.timeout_adjuster()
+    );
+}
+
+#[test]
+fn consensus_invalid_timeout_bounds() {
+    let consensus = Consensus {
+        propose_timeout_min: Some(1000),
+        propose_timeout_max: Some(200),
+        ..Default::default()
+    };
+    assert!(consensus.consensus_config().is_err());
+
+    let consensus = Consensus {
+        propose_timeout_min: Some(200),
+        ..Default::default()
+    };
+    assert!(consensus.timeout_adjuster().is_err());
+}
+
+#[test]
+fn mempool_defaults() {
+    let mempool = Mempool::default().mempool_config(3000).unwrap();
+    assert_eq!(100_000, mempool.tx_pool_capacity);
+    assert_eq!(512, mempool.events_pool_capacity.network_requests_capacity);
+    assert_eq!(1024, mempool.events_pool_capacity.api_requests_capacity);
+    assert!(config().mempool().mempool_config(3000).is_ok());
+}
+
+#[test]
+fn mempool_parse() {
+    let mempool: Mempool = toml::from_str(
+        r#"
+        capacity = 5000
+        api_requests_capacity = 64
+        "#,
+    ).unwrap();
+    let mempool = mempool.mempool_config(3000).unwrap();
+    assert_eq!(5000, mempool.tx_pool_capacity);
+    assert_eq!(64, mempool.events_pool_capacity.api_requests_capacity);
+    assert_eq!(128, mempool.events_pool_capacity.internal_events_capacity);
+}
+
+#[test]
+fn mempool_invalid() {
+    let mempool = Mempool {
+        capacity: Some(999),
+        ..Default::default()
+    };
+    assert!(mempool.clone().mempool_config(999).is_ok());
+    assert!(mempool.mempool_config(1000).is_err());
+
+    let mempool = Mempool {
+        network_events_capacity: Some(0),
+        ..Default::default()
+    };
+    assert!(mempool.mempool_config(1000).is_err());
+}
+
+#[test]
+fn shutdown_signals() {
+    assert_eq!(vec!["SIGINT", "SIGTERM"], Shutdown::default().signals());
+
+    let shutdown: Shutdown = toml::from_str(r#"signals = ["SIGTERM", "SIGHUP"]"#).unwrap();
+    assert_eq!(vec!["SIGTERM", "SIGHUP"], shutdown.signals());
+}
+
+#[test]
+fn consensus_round_timeout_mismatch() {
+    let actual = Consensus::default().consensus_config().unwrap();
+    let configured = Consensus {
+        round_timeout: Some(actual.round_timeout + 500),
+        ..Default::default()
+    }.consensus_config()
+        .unwrap();
+
+    assert!(consensus_mismatches(&actual, &actual).is_empty());
+
+    let mismatches = consensus_mismatches(&configured, &actual);
+    assert_eq!(1, mismatches.len());
+    assert!(mismatches[0].starts_with("round_timeout"));
+}
@@ -0,0 +1,133 @@
+extern crate serde_json;
+
+use exonum::api::Api;
+use exonum::blockchain::Blockchain;
+use hyper::header::ContentType;
+use iron::prelude::*;
+use iron::status;
+use prometheus::IntCounter;
+use router::Router;
+
+use config;
+use currency::api::error::ApiError;
+use currency::configuration::Configuration;
+
+/// Placeholder for configuration values which point at secrets.
+pub const REDACTED: &str = "<redacted>";
+
+#[derive(Clone)]
+pub struct AdminApi {
+    pub blockchain: Blockchain,
+}
+
+/// Configuration the node is running with.
+///
+/// `node` holds the startup configuration after environment overrides and
+/// defaults are applied, `service` the currency configuration of the chain.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct AdminConfigResponseBody {
+    pub node: serde_json::Value,
+    pub service: Configuration,
+}
+
+pub type AdminConfigResponse = Result<AdminConfigResponseBody, ApiError>;
+
+impl AdminApi {
+    /// Effective startup configuration. The keys path is redacted and the
+    /// keys themselves are never read.
+    fn node_config() -> serde_json::Value {
+        let api = config::config().api();
+        let db = config::config().db();
+        let nats = config::config().nats();
+        let service_discovery = config::config().service_discovery();
+        let metrics = config::config().metrics();
+        let consensus = match config::config().consensus().consensus_config() {
+            Ok(consensus) => json!(consensus),
+            Err(error) => json!({ "error": error }),
+        };
+
+        json!({
+            "api": {
+                "current_node": api.clone().current_node(),
+                "address": api.clone().address(),
+                "private_address": api.clone().private_address(),
+                "peer_address": api.clone().peer_address(),
+                "peers": api.clone().peers(),
+                "keys_path": REDACTED,
+                "is_validator": api.clone().is_validator(),
+                "max_blocks_behind": api.clone().max_blocks_behind(),
+                "max_wallet_assets": api.clone().max_wallet_assets(),
+                "max_batch_wallets": api.max_batch_wallets(),
+            },
+            "db": {
+                "path": db.clone().path(),
+                "write_buffer_size": db.clone().write_buffer_size(),
+                "max_write_buffer_number": db.clone().max_write_buffer_number(),
+                "level_zero_file_num_compaction_trigger":
+                    db.level_zero_file_num_compaction_trigger(),
+            },
+            "nats": {
+                "enabled": nats.clone().enabled(),
+                "addresses": nats.clone().addresses(),
+                "queuename": nats.queuename(),
+            },
+            "service_discovery": {
+                "address": service_discovery.clone().address(),
+                "retries": service_discovery.clone().retries(),
+                "retry_delay": service_discovery.retry_delay(),
+            },
+            "metrics": {
+                "dump_interval": metrics.clone().dump_interval(),
+                "dump_path": metrics.clone().dump_path(),
+                "dump_max_size": metrics.dump_max_size(),
+            },
+            "consensus": consensus,
+            "genesis": {
+                "allocation": config::config().genesis().allocation(),
+            },
+            "log": {
+                "key_fingerprint": config::config().log().key_fingerprint(),
+            },
+        })
+    }
+
+    fn service_config(&self) -> Configuration {
+        Configuration::extract(&self.blockchain.snapshot())
+    }
+}
+
+lazy_static! {
+    static ref CONFIG_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_admin_api_config_requests_total",
+        "Effective configuration requests."
+    ).unwrap();
+    static ref CONFIG_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_admin_api_config_responses_total",
+        "Effective configuration responses."
+    ).unwrap();
+}
+
+impl Api for AdminApi {
+    fn wire(&self, router: &mut Router) {
+        // Gets the configuration the node is running with.
+        let self_ = self.clone();
+        let admin_config = move |_: &mut Request| -> IronResult<Response> {
+            CONFIG_REQUESTS.inc();
+
+            let result: AdminConfigResponse = Ok(AdminConfigResponseBody {
+                node: AdminApi::node_config(),
+                service: self_.service_config(),
+            });
+
+            let mut res =
+                Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+
+            CONFIG_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        router.get("/v1/admin/config", admin_config, "admin_config");
+    }
+}
@@ -7,6 +7,8 @@ extern crate serde_json;
 
 use exonum::api::Api;
 use exonum::blockchain::Blockchain;
+use exonum::crypto::PublicKey;
+use exonum::encoding::serialize::FromHex;
 use hyper::header::ContentType;
 use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
@@ -15,8 +17,13 @@ use prometheus::IntCounter;
 use router::Router;
 
 use currency::api::error::ApiError;
+use currency::api::ServiceApi;
 use currency::assets;
 use currency::assets::{AssetId, AssetInfo};
+use currency::wallet;
+
+/// Query parameter requesting the holders of an asset.
+pub const PARAMETER_HOLDERS_KEY: &str = "holders";
 
 #[derive(Clone)]
 pub struct AssetApi {
@@ -29,15 +36,125 @@ impl AssetApi {
         let view = self.blockchain.fork();
         assets::Schema(view).fetch(asset_id)
     }
+
+    /// Assets created by `creator` with the amount of each still in
+    /// circulation, in asset id order. Only the ones in
+    /// `offset..offset + limit` are returned.
+    fn created_assets(&self, creator: &PublicKey, offset: u64, limit: u64) -> CreatedAssetsBody {
+        let view = &mut self.blockchain.fork();
+        let created = assets::Schema(&*view).created(creator);
+
+        let mut total = 0;
+        let mut assets = Vec::new();
+        for id in created.iter() {
+            if total >= offset && total < offset.saturating_add(limit) {
+                let amount = assets::Schema(&*view).fetch(&id).map_or(0, |info| info.amount());
+                assets.push(CreatedAsset { id, amount });
+            }
+            total += 1;
+        }
+
+        CreatedAssetsBody {
+            total,
+            count: assets.len() as u64,
+            assets,
+        }
+    }
+
+    /// Wallets holding `asset_id`, in wallet key order. Every wallet is
+    /// scanned, only the ones in `offset..offset + limit` are returned.
+    fn pagination_holders(
+        &self,
+        asset_id: &AssetId,
+        offset: u64,
+        limit: u64,
+    ) -> AssetHoldersBody {
+        let view = &mut self.blockchain.fork();
+        let index = wallet::Schema(view).index();
+        let all = index.iter().filter_map(|(pub_key, wallet)| {
+            wallet
+                .assets()
+                .into_iter()
+                .find(|a| a.id() == *asset_id)
+                .map(|a| AssetHolder {
+                    pub_key,
+                    amount: a.amount(),
+                })
+        });
+
+        let mut total = 0;
+        let mut holders = Vec::new();
+        for holder in all {
+            if total >= offset && total < offset + limit {
+                holders.push(holder);
+            }
+            total += 1;
+        }
+
+        AssetHoldersBody {
+            total,
+            count: holders.len() as u64,
+            holders,
+        }
+    }
+}
+
+/// Amount of an asset held by a wallet.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct AssetHolder {
+    pub pub_key: PublicKey,
+    pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct AssetHoldersBody {
+    pub total: u64,
+    pub count: u64,
+    pub holders: Vec<AssetHolder>,
+}
+
+/// Creator of an asset and, if requested with `holders=true`, the wallets
+/// holding it.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct AssetOwnersResponseBody {
+    pub creator: PublicKey,
+    pub holders: Option<AssetHoldersBody>,
+}
+
+/// Asset created by a wallet and the amount of it outstanding.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct CreatedAsset {
+    pub id: AssetId,
+    pub amount: u64,
 }
 
 pub type AssetResponse = Result<Option<AssetInfo>, ApiError>;
 
+pub type AssetOwnersResponse = Result<AssetOwnersResponseBody, ApiError>;
+
+/// Page of the assets created by a wallet, out of `total`.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct CreatedAssetsBody {
+    pub total: u64,
+    pub count: u64,
+    pub assets: Vec<CreatedAsset>,
+}
+
+pub type CreatedAssetsResponse = Result<CreatedAssetsBody, ApiError>;
+
 lazy_static! {
     static ref INFO_REQUESTS: IntCounter =
         register_int_counter!("dmbc_asset_api_info_requests_total", "AssetInfo requests.").unwrap();
     static ref INFO_RESPONSES: IntCounter =
         register_int_counter!("dmbc_asset_api_info_responses_total", "AssetInfo response.").unwrap();
+    static ref OWNERS_REQUESTS: IntCounter =
+        register_int_counter!("dmbc_asset_api_owners_requests_total", "Asset owners requests.").unwrap();
+    static ref OWNERS_RESPONSES: IntCounter =
+        register_int_counter!("dmbc_asset_api_owners_responses_total", "Asset owners responses.").unwrap();
+    static ref CREATED_REQUESTS: IntCounter =
+        register_int_counter!("dmbc_asset_api_created_requests_total", "Created assets requests.").unwrap();
+    static ref CREATED_RESPONSES: IntCounter =
+        register_int_counter!("dmbc_asset_api_created_responses_total", "Created assets responses.").unwrap();
 }
 
 impl Api for AssetApi {
@@ -65,10 +182,92 @@ impl Api for AssetApi {
             Ok(res)
         };
 
+        // Gets the creator and, optionally, the holders of an asset.
+        let self_ = self.clone();
+        let get_asset_owners = move |req: &mut Request| -> IronResult<Response> {
+            OWNERS_REQUESTS.inc();
+
+            let asset_id_str = req.extensions
+                .get::<Router>()
+                .unwrap()
+                .find("asset_id")
+                .unwrap_or("")
+                .to_string();
+            let with_holders = ServiceApi::read_parameter(req, PARAMETER_HOLDERS_KEY, false);
+            let (offset, limit) = ServiceApi::pagination_params(req);
+
+            let result: AssetOwnersResponse = AssetId::from_hex(&asset_id_str)
+                .map_err(|_| ApiError::AssetIdInvalid)
+                .and_then(|asset_id| {
+                    let info = self_
+                        .get_asset_info(&asset_id)
+                        .ok_or(ApiError::AssetIdNotFound)?;
+                    let holders = if with_holders {
+                        Some(self_.pagination_holders(&asset_id, offset, limit))
+                    } else {
+                        None
+                    };
+                    Ok(AssetOwnersResponseBody {
+                        creator: *info.creator(),
+                        holders,
+                    })
+                });
+
+            let mut res = Response::with((
+                result.clone().err().map(|e| e.to_status()).unwrap_or(status::Ok),
+                serde_json::to_string_pretty(&result).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            OWNERS_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        // Gets the assets created by a wallet.
+        let self_ = self.clone();
+        let get_created_assets = move |req: &mut Request| -> IronResult<Response> {
+            CREATED_REQUESTS.inc();
+
+            let creator_str = req.extensions
+                .get::<Router>()
+                .unwrap()
+                .find("pub_key")
+                .unwrap_or("")
+                .to_string();
+            let (offset, limit) = ServiceApi::pagination_params(req);
+
+            let result: CreatedAssetsResponse = PublicKey::from_hex(&creator_str)
+                .map_err(|_| ApiError::WalletHexInvalid)
+                .map(|creator| self_.created_assets(&creator, offset, limit));
+
+            let mut res = Response::with((
+                result.clone().err().map(|e| e.to_status()).unwrap_or(status::Ok),
+                serde_json::to_string_pretty(&result).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            CREATED_RESPONSES.inc();
+
+            Ok(res)
+        };
+
         router.get(
             "/v1/assets/:asset_id",
             get_owner_for_asset_id,
             "get_owner_for_asset_id",
         );
+        router.get(
+            "/v1/assets/:asset_id/owners",
+            get_asset_owners,
+            "get_asset_owners",
+        );
+        router.get(
+            "/v1/creators/:pub_key/assets",
+            get_created_assets,
+            "get_created_assets",
+        );
     }
 }
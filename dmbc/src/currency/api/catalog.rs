@@ -0,0 +1,256 @@
+extern crate serde_json;
+
+use exonum::api::Api;
+use hyper::header::ContentType;
+use iron::headers::AccessControlAllowOrigin;
+use iron::prelude::*;
+use iron::status;
+use router::Router;
+
+use currency::api::error::ApiError;
+
+#[derive(Clone)]
+pub struct CatalogApi {}
+
+/// Description of a single service endpoint.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct RouteInfo {
+    pub method: String,
+    pub path: String,
+    pub description: String,
+    pub params: Vec<String>,
+    pub response: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct CatalogResponseBody {
+    pub routes: Vec<RouteInfo>,
+}
+
+pub type CatalogResponse = Result<CatalogResponseBody, ApiError>;
+
+fn route(
+    method: &str,
+    path: &str,
+    description: &str,
+    params: &[&str],
+    response: &str,
+) -> RouteInfo {
+    RouteInfo {
+        method: method.to_string(),
+        path: path.to_string(),
+        description: description.to_string(),
+        params: params.iter().map(|p| p.to_string()).collect(),
+        response: response.to_string(),
+    }
+}
+
+/// Endpoints served by the currency service, relative to the service root.
+///
+/// Keep in sync with the `wire` implementations of the service APIs.
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        route(
+            "GET",
+            "/v1",
+            "List of available endpoints.",
+            &[],
+            "CatalogResponse",
+        ),
+        route(
+            "POST",
+            "/v1/transactions",
+            "Submit a signed transaction.",
+            &[],
+            "TxPostResponse",
+        ),
+        route(
+            "GET",
+            "/v1/transactions",
+            "Hashes of the transactions with a tag.",
+            &["tag", "offset", "limit"],
+            "TaggedTransactionsResponse",
+        ),
+        route(
+            "GET",
+            "/v1/transactions/:hash",
+            "Status of a transaction.",
+            &["hash"],
+            "StatusResponse",
+        ),
+        route(
+            "GET",
+            "/v1/transactions/:hash/status",
+            "Status of a transaction.",
+            &["hash"],
+            "StatusResponse",
+        ),
+        route(
+            "GET",
+            "/v1/transactions/:hash/warnings",
+            "Price deviations flagged for an executed trade.",
+            &["hash"],
+            "PriceWarningsResponse",
+        ),
+        route(
+            "POST",
+            "/v1/fees/transactions",
+            "Fees a transaction would be charged.",
+            &[],
+            "FeesResponse",
+        ),
+        route(
+            "POST",
+            "/v1/fees/offers",
+            "Fees an unsigned trade or exchange offer would be charged.",
+            &[],
+            "OfferFeesResponse",
+        ),
+        route(
+            "GET",
+            "/v1/fees/revenue",
+            "Total of the blockchain fees collected by the platform.",
+            &[],
+            "RevenueResponse",
+        ),
+        route(
+            "POST",
+            "/v1/hex/transactions",
+            "Hex encoding of a transaction.",
+            &[],
+            "HexApiResponse",
+        ),
+        route(
+            "POST",
+            "/v1/hex/transactions/offer",
+            "Hex encoding of the offer bytes its parties sign.",
+            &[],
+            "HexApiResponse",
+        ),
+        route(
+            "GET",
+            "/v1/assets/:asset_id",
+            "Information about an asset.",
+            &["asset_id"],
+            "AssetResponse",
+        ),
+        route(
+            "GET",
+            "/v1/assets/:asset_id/owners",
+            "Creator of an asset and, with `holders`, the wallets holding it.",
+            &["asset_id", "holders", "offset", "limit"],
+            "AssetOwnersResponse",
+        ),
+        route(
+            "GET",
+            "/v1/creators/:pub_key/assets",
+            "Assets created by a wallet and the amount of each outstanding.",
+            &["pub_key", "offset", "limit"],
+            "CreatedAssetsResponse",
+        ),
+        route(
+            "GET",
+            "/v1/intern/assets/:pub_key/:meta_data",
+            "Id of an asset.",
+            &["pub_key", "meta_data"],
+            "AssetIdResponse",
+        ),
+        route(
+            "GET",
+            "/v1/intern/assets/:pub_key",
+            "Ids of assets of a creator.",
+            &["pub_key"],
+            "AssetIdBatchResponse",
+        ),
+        route(
+            "POST",
+            "/v1/intern/assets",
+            "Ids of a batch of assets.",
+            &[],
+            "AssetIdBatchResponse",
+        ),
+        route(
+            "GET",
+            "/v1/wallets",
+            "Balances of all wallets.",
+            &["offset", "limit"],
+            "WalletsResponse",
+        ),
+        route(
+            "GET",
+            "/v1/wallets/:pub_key",
+            "Balance of a wallet.",
+            &["pub_key"],
+            "WalletResponse",
+        ),
+        route(
+            "POST",
+            "/v1/wallets/balances",
+            "Balances and assets of up to `max_batch_wallets` wallets, given as an array of keys.",
+            &[],
+            "WalletBalancesResponse",
+        ),
+        route(
+            "GET",
+            "/v1/wallets/:pub_key/state",
+            "Balance and up to `max_wallet_assets` assets of a wallet.",
+            &["pub_key"],
+            "WalletStateResponse",
+        ),
+        route(
+            "GET",
+            "/v1/wallets/:pub_key/history",
+            "Transactions touching a wallet, oldest first, with their statuses and memos.",
+            &["pub_key", "offset", "limit"],
+            "WalletHistoryResponse",
+        ),
+        route(
+            "GET",
+            "/v1/wallets/:pub_key/assets",
+            "Assets of a wallet.",
+            &["pub_key", "offset", "limit", "meta_data"],
+            "WalletAssetsResponse",
+        ),
+        route(
+            "GET",
+            "/v1/wallets/:pub_key/assets/:asset_id",
+            "Single asset of a wallet.",
+            &["pub_key", "asset_id", "meta_data"],
+            "WalletAssetResponse",
+        ),
+        route(
+            "GET",
+            "/v1/blocks",
+            "Latest blocks.",
+            &["count", "latest", "skip_empty_blocks"],
+            "BlocksResponse",
+        ),
+        route(
+            "GET",
+            "/v1/blocks/:height",
+            "Block at the height.",
+            &["height"],
+            "BlockResponse",
+        ),
+        route("GET", "/metrics", "Prometheus metrics.", &[], "text"),
+        route("GET", "/db_stats", "Database statistics.", &[], "text"),
+    ]
+}
+
+impl Api for CatalogApi {
+    fn wire(&self, router: &mut Router) {
+        let catalog = move |_: &mut Request| -> IronResult<Response> {
+            let result: CatalogResponse = Ok(CatalogResponseBody { routes: routes() });
+
+            let mut res =
+                Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            Ok(res)
+        };
+
+        router.get("/v1", catalog.clone(), "catalog");
+        router.get("/v1/", catalog, "catalog_slash");
+    }
+}
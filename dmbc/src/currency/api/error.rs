@@ -1,6 +1,8 @@
 use hyper::status::StatusCode;
 
-#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+use currency::api::validation::FieldError;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum ApiError {
     TransactionNotFound,
     TransactionHashInvalid,
@@ -11,6 +13,9 @@ pub enum ApiError {
     WalletHexInvalid,
     HeightIsMissing,
     BlockNotFound,
+    SnapshotNotFound,
+    TooManyWallets,
+    InvalidField(FieldError),
 }
 
 impl ApiError {
@@ -25,6 +30,9 @@ impl ApiError {
             ApiError::WalletHexInvalid => StatusCode::BadRequest,
             ApiError::HeightIsMissing => StatusCode::BadRequest,
             ApiError::BlockNotFound => StatusCode::NotFound,
+            ApiError::SnapshotNotFound => StatusCode::NotFound,
+            ApiError::TooManyWallets => StatusCode::BadRequest,
+            ApiError::InvalidField(_) => StatusCode::BadRequest,
         }
     }
 }
@@ -0,0 +1,115 @@
+extern crate serde_json;
+
+use std::cmp;
+use std::time::Duration;
+
+use exonum::api::Api;
+use exonum::blockchain::Blockchain;
+use exonum::crypto::PublicKey;
+use exonum::encoding::serialize::FromHex;
+use hyper::header::ContentType;
+use iron::headers::AccessControlAllowOrigin;
+use iron::prelude::*;
+use iron::status;
+use prometheus::IntCounter;
+use router::Router;
+
+use currency::api::error::ApiError;
+use currency::api::ServiceApi;
+use currency::events;
+use currency::events::{Event, Events};
+
+/// Longest a subscriber may wait for the next block, in milliseconds.
+const MAX_TIMEOUT_MS: u64 = 30_000;
+
+#[derive(Clone)]
+pub struct EventsApi {
+    pub blockchain: Blockchain,
+    pub events: Events,
+}
+
+/// Transactions applied in the blocks above the requested height. The
+/// next request should continue from `height`.
+///
+/// `gap` is set when the subscriber is more than `events::MAX_BLOCKS`
+/// blocks behind and the events of the oldest blocks are missing, their
+/// transactions have to be looked up from the wallet history instead.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventsResponseBody {
+    pub height: u64,
+    #[serde(default)]
+    pub gap: bool,
+    pub events: Vec<Event>,
+}
+
+pub type EventsResponse = Result<EventsResponseBody, ApiError>;
+
+impl EventsApi {
+    fn get_events(&self, after: u64, key: Option<PublicKey>, timeout: u64) -> EventsResponseBody {
+        let timeout = Duration::from_millis(cmp::min(timeout, MAX_TIMEOUT_MS));
+        let (height, gap, events) = self.events.after(after, timeout);
+        let events = match key {
+            Some(key) => {
+                let view = self.blockchain.snapshot();
+                events::involving(&*view, &key, after, events)
+            }
+            None => events,
+        };
+        EventsResponseBody {
+            height,
+            gap,
+            events,
+        }
+    }
+}
+
+lazy_static! {
+    static ref EVENTS_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_events_api_requests_total",
+        "Event subscription requests."
+    ).unwrap();
+    static ref EVENTS_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_events_api_responses_total",
+        "Event subscription responses."
+    ).unwrap();
+}
+
+impl Api for EventsApi {
+    fn wire(&self, router: &mut Router) {
+        // Long-polls for the transactions applied in the blocks above `after`,
+        // optionally only those involving the wallet `key`.
+        let self_ = self.clone();
+        let get_events = move |req: &mut Request| -> IronResult<Response> {
+            EVENTS_REQUESTS.inc();
+
+            let after: u64 = ServiceApi::read_parameter(req, "after", 0);
+            let timeout: u64 = ServiceApi::read_parameter(req, "timeout", 0);
+            let key: String = ServiceApi::read_parameter(req, "key", String::new());
+
+            let result: EventsResponse = if key.is_empty() {
+                Ok(self_.get_events(after, None, timeout))
+            } else {
+                match PublicKey::from_hex(&key) {
+                    Ok(key) => Ok(self_.get_events(after, Some(key), timeout)),
+                    Err(_) => Err(ApiError::WalletHexInvalid),
+                }
+            };
+
+            let mut res = Response::with((
+                result
+                    .clone()
+                    .err()
+                    .map(|e| e.to_status())
+                    .unwrap_or(status::Ok),
+                serde_json::to_string_pretty(&result).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            EVENTS_RESPONSES.inc();
+            Ok(res)
+        };
+
+        router.get("/v1/events", get_events, "get_events");
+    }
+}
@@ -10,6 +10,7 @@ use std::collections::HashMap;
 use exonum::api::Api;
 use exonum::blockchain::Blockchain;
 use exonum::crypto::PublicKey;
+use exonum::storage::Snapshot;
 // use exonum::encoding::serialize::FromHex;
 use hyper::header::ContentType;
 use iron::headers::AccessControlAllowOrigin;
@@ -18,11 +19,22 @@ use iron::status;
 use router::Router;
 
 use currency::api::error::ApiError;
+use currency::configuration::Configuration;
 use currency::error::Error;
-use currency::transactions::components::FeesCalculator;
-use currency::transactions::{AddAssets, DeleteAssets, Exchange, ExchangeIntermediary, Trade,
-                             TradeIntermediary, Transfer};
+use currency::revenue;
+use currency::transactions::components::{percentage_fee, FeesCalculator, ThirdPartyFees};
+use currency::transactions::{AddAssets, DeleteAssets, EscrowCreate, EscrowRefund,
+                             EscrowRelease, Exchange, ExchangeIntermediary, ExchangeOffer,
+                             FreezeAsset, MatchOffers, MergeAsset, MultiSigTransfer,
+                             SetAssetBlacklist, SetIntermediary, SetMultiSig,
+                             SetReferencePrice, SplitAsset, SponsoredTransfer, Trade,
+                             TradeIntermediary, TradeOffer, Transfer};
 
+/// Fee estimation for transactions.
+///
+/// Fees are calculated against a fork of the current state which is dropped
+/// afterwards, so nothing is submitted or changed. Transactions referring to
+/// unknown assets are answered with `Error::AssetNotFound`.
 #[derive(Clone)]
 pub struct FeesApi {
     pub blockchain: Blockchain,
@@ -38,6 +50,19 @@ pub enum FeesRequest {
     TradeIntermediary(TradeIntermediary),
     Exchange(Exchange),
     ExchangeIntermediary(ExchangeIntermediary),
+    SetAssetBlacklist(SetAssetBlacklist),
+    FreezeAsset(FreezeAsset),
+    SetIntermediary(SetIntermediary),
+    EscrowCreate(EscrowCreate),
+    EscrowRelease(EscrowRelease),
+    EscrowRefund(EscrowRefund),
+    SponsoredTransfer(SponsoredTransfer),
+    SetMultiSig(SetMultiSig),
+    MultiSigTransfer(MultiSigTransfer),
+    MatchOffers(MatchOffers),
+    SetReferencePrice(SetReferencePrice),
+    SplitAsset(SplitAsset),
+    MergeAsset(MergeAsset),
 }
 
 impl Into<Box<FeesCalculator>> for FeesRequest {
@@ -50,6 +75,19 @@ impl Into<Box<FeesCalculator>> for FeesRequest {
             FeesRequest::TradeIntermediary(trans) => Box::new(trans),
             FeesRequest::Exchange(trans) => Box::new(trans),
             FeesRequest::ExchangeIntermediary(trans) => Box::new(trans),
+            FeesRequest::SetAssetBlacklist(trans) => Box::new(trans),
+            FeesRequest::FreezeAsset(trans) => Box::new(trans),
+            FeesRequest::SetIntermediary(trans) => Box::new(trans),
+            FeesRequest::EscrowCreate(trans) => Box::new(trans),
+            FeesRequest::EscrowRelease(trans) => Box::new(trans),
+            FeesRequest::EscrowRefund(trans) => Box::new(trans),
+            FeesRequest::SponsoredTransfer(trans) => Box::new(trans),
+            FeesRequest::SetMultiSig(trans) => Box::new(trans),
+            FeesRequest::MultiSigTransfer(trans) => Box::new(trans),
+            FeesRequest::MatchOffers(trans) => Box::new(trans),
+            FeesRequest::SetReferencePrice(trans) => Box::new(trans),
+            FeesRequest::SplitAsset(trans) => Box::new(trans),
+            FeesRequest::MergeAsset(trans) => Box::new(trans),
         }
     }
 }
@@ -61,6 +99,75 @@ pub struct FeesResponseBody {
 
 pub type FeesResponse = Result<Result<FeesResponseBody, Error>, ApiError>;
 
+/// Unsigned offer to estimate the fees of, before its parties sign it.
+#[serde(rename_all = "snake_case")]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub enum OfferFeesRequest {
+    Trade(TradeOffer),
+    Exchange(ExchangeOffer),
+}
+
+impl OfferFeesRequest {
+    /// Fees of the offer against `view`, before any fee discount.
+    ///
+    /// # Errors
+    /// Returns `AssetNotFound` if an offered asset doesn't exist.
+    pub fn fees<S>(&self, view: S) -> Result<OfferFeesResponseBody, Error>
+    where
+        S: AsRef<Snapshot>,
+    {
+        let view = view.as_ref();
+        let configuration = Configuration::extract(view);
+        let genesis_fees = configuration.fees();
+        let fee_rate_bps = configuration.fee_rate_bps();
+        let (transaction_fee, assets_fees) = match *self {
+            OfferFeesRequest::Trade(ref offer) => {
+                let assets = offer.assets();
+                let mut total = 0u64;
+                for asset in &assets {
+                    total = asset
+                        .total_price()
+                        .and_then(|price| total.checked_add(price))
+                        .ok_or(Error::Overflow)?;
+                }
+                let fee = percentage_fee(genesis_fees.trade(), total, fee_rate_bps)?;
+                (fee, ThirdPartyFees::new_trade(view, offer.seller(), &assets)?)
+            }
+            OfferFeesRequest::Exchange(ref offer) => {
+                let fee =
+                    percentage_fee(genesis_fees.exchange(), offer.sender_value(), fee_rate_bps)?;
+                let assets = offer
+                    .sender_assets()
+                    .into_iter()
+                    .chain(offer.recipient_assets().into_iter());
+                (fee, ThirdPartyFees::new_exchange(view, assets)?)
+            }
+        };
+
+        Ok(OfferFeesResponseBody {
+            transaction_fee,
+            assets_fees: assets_fees.0.into_iter().collect(),
+        })
+    }
+}
+
+/// Fees of an offer: the blockchain fee of the transaction and the fees
+/// owed to the creator of each offered asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OfferFeesResponseBody {
+    pub transaction_fee: u64,
+    pub assets_fees: HashMap<PublicKey, u64>,
+}
+
+pub type OfferFeesResponse = Result<Result<OfferFeesResponseBody, Error>, ApiError>;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RevenueResponseBody {
+    pub total: u64,
+}
+
+pub type RevenueResponse = Result<RevenueResponseBody, ApiError>;
+
 impl Api for FeesApi {
     fn wire(&self, router: &mut Router) {
         let self_ = self.clone();
@@ -96,6 +203,47 @@ impl Api for FeesApi {
             Ok(res)
         };
 
+        // Fees of an unsigned offer, read from a snapshot.
+        let self_ = self.clone();
+        let offer_fees = move |req: &mut Request| -> IronResult<Response> {
+            let result: OfferFeesResponse =
+                match req.get::<bodyparser::Struct<OfferFeesRequest>>() {
+                    Ok(Some(request)) => Ok(request.fees(self_.blockchain.snapshot())),
+                    Ok(None) => Err(ApiError::EmptyRequestBody),
+                    Err(_) => Err(ApiError::IncorrectRequest),
+                };
+
+            let status_code = match result {
+                Ok(Ok(_)) => status::Ok,
+                _ => status::BadRequest,
+            };
+
+            let mut res =
+                Response::with((status_code, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            Ok(res)
+        };
+
+        // Total of the blockchain fees collected, see `revenue::Schema`.
+        let self_ = self.clone();
+        let revenue = move |_: &mut Request| -> IronResult<Response> {
+            let view = self_.blockchain.snapshot();
+            let result: RevenueResponse = Ok(RevenueResponseBody {
+                total: revenue::Schema(&view).total(),
+            });
+
+            let mut res =
+                Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            Ok(res)
+        };
+
         router.post("/v1/fees/transactions", fees, "transaction_fee");
+        router.post("/v1/fees/offers", offer_fees, "offer_fees");
+        router.get("/v1/fees/revenue", revenue, "fees_revenue");
     }
 }
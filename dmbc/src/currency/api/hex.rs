@@ -15,8 +15,12 @@ use iron::status as istatus;
 use router::Router;
 
 use currency::api::error::ApiError;
-use currency::transactions::{AddAssets, DeleteAssets, Exchange, ExchangeIntermediary, Trade,
-                             TradeIntermediary, Transfer, EXCHANGE_ID, EXCHANGE_INTERMEDIARY_ID,
+use currency::transactions::{AddAssets, DeleteAssets, EscrowCreate, EscrowRefund,
+                             EscrowRelease, Exchange, ExchangeIntermediary, FreezeAsset,
+                             MatchOffers, MergeAsset, MultiSigTransfer, SetAssetBlacklist,
+                             SetIntermediary, SetMultiSig, SetReferencePrice, SplitAsset,
+                             SponsoredTransfer, Trade, TradeIntermediary, Transfer, EXCHANGE_ID,
+                             EXCHANGE_INTERMEDIARY_ID, MATCH_OFFERS_ID, MULTISIG_TRANSFER_ID,
                              TRADE_ID, TRADE_INTERMEDIARY_ID};
 
 #[derive(Clone)]
@@ -32,6 +36,19 @@ enum TransactionRequest {
     TradeIntermediary(TradeIntermediary),
     Exchange(Exchange),
     ExchangeIntermediary(ExchangeIntermediary),
+    SetAssetBlacklist(SetAssetBlacklist),
+    FreezeAsset(FreezeAsset),
+    SetIntermediary(SetIntermediary),
+    EscrowCreate(EscrowCreate),
+    EscrowRelease(EscrowRelease),
+    EscrowRefund(EscrowRefund),
+    SponsoredTransfer(SponsoredTransfer),
+    SetMultiSig(SetMultiSig),
+    MultiSigTransfer(MultiSigTransfer),
+    MatchOffers(MatchOffers),
+    SetReferencePrice(SetReferencePrice),
+    SplitAsset(SplitAsset),
+    MergeAsset(MergeAsset),
 }
 
 impl Into<Box<Transaction>> for TransactionRequest {
@@ -44,6 +61,19 @@ impl Into<Box<Transaction>> for TransactionRequest {
             TransactionRequest::TradeIntermediary(trans) => Box::new(trans),
             TransactionRequest::Exchange(trans) => Box::new(trans),
             TransactionRequest::ExchangeIntermediary(trans) => Box::new(trans),
+            TransactionRequest::SetAssetBlacklist(trans) => Box::new(trans),
+            TransactionRequest::FreezeAsset(trans) => Box::new(trans),
+            TransactionRequest::SetIntermediary(trans) => Box::new(trans),
+            TransactionRequest::EscrowCreate(trans) => Box::new(trans),
+            TransactionRequest::EscrowRelease(trans) => Box::new(trans),
+            TransactionRequest::EscrowRefund(trans) => Box::new(trans),
+            TransactionRequest::SponsoredTransfer(trans) => Box::new(trans),
+            TransactionRequest::SetMultiSig(trans) => Box::new(trans),
+            TransactionRequest::MultiSigTransfer(trans) => Box::new(trans),
+            TransactionRequest::MatchOffers(trans) => Box::new(trans),
+            TransactionRequest::SetReferencePrice(trans) => Box::new(trans),
+            TransactionRequest::SplitAsset(trans) => Box::new(trans),
+            TransactionRequest::MergeAsset(trans) => Box::new(trans),
         }
     }
 }
@@ -98,19 +128,27 @@ impl Api for HexApi {
 
                     let vec_hash: Option<Vec<u8>> = match transaction.raw().message_type() {
                         EXCHANGE_ID => match Exchange::from_raw(raw_) {
-                            Ok(exchange) => Some(exchange.offer_raw()),
+                            Ok(exchange) => Some(exchange.offer_signing_bytes()),
                             Err(_) => None,
                         },
                         EXCHANGE_INTERMEDIARY_ID => match ExchangeIntermediary::from_raw(raw_) {
-                            Ok(exchange) => Some(exchange.offer_raw()),
+                            Ok(exchange) => Some(exchange.offer_signing_bytes()),
                             Err(_) => None,
                         },
                         TRADE_ID => match Trade::from_raw(raw_) {
-                            Ok(trade) => Some(trade.offer_raw()),
+                            Ok(trade) => Some(trade.offer_signing_bytes()),
                             Err(_) => None,
                         },
                         TRADE_INTERMEDIARY_ID => match TradeIntermediary::from_raw(raw_) {
-                            Ok(trade) => Some(trade.offer_raw()),
+                            Ok(trade) => Some(trade.offer_signing_bytes()),
+                            Err(_) => None,
+                        },
+                        MULTISIG_TRANSFER_ID => match MultiSigTransfer::from_raw(raw_) {
+                            Ok(transfer) => Some(transfer.offer_signing_bytes()),
+                            Err(_) => None,
+                        },
+                        MATCH_OFFERS_ID => match MatchOffers::from_raw(raw_) {
+                            Ok(match_offers) => Some(match_offers.offer_signing_bytes()),
                             Err(_) => None,
                         },
                         _ => None,
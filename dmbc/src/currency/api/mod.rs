@@ -1,14 +1,18 @@
 // TODO: currency service API documentation.
 #![allow(missing_docs)]
 
+pub mod admin;
 pub mod asset;
 pub mod assets_intern;
+pub mod catalog;
 pub mod error;
+pub mod events;
 pub mod fees;
 pub mod hex;
 pub mod metrics;
 pub mod db_stats;
 pub mod transaction;
+pub mod validation;
 pub mod wallet;
 pub mod blocks;
 
@@ -26,8 +30,14 @@ use router::Router;
 use std::cmp;
 use unicase::UniCase;
 
+use config;
+use currency::events::Events;
+use currency::pending::Pending;
+
 use self::asset::AssetApi;
 use self::assets_intern::AssetInternApi;
+use self::catalog::CatalogApi;
+use self::events::EventsApi;
 use self::fees::FeesApi;
 use self::hex::HexApi;
 use self::metrics::MetricsApi;
@@ -44,6 +54,29 @@ const PARAMETER_LIMIT_KEY: &str = "limit";
 pub struct ServiceApi {
     pub channel: ApiSender,
     pub blockchain: Blockchain,
+    pub events: Events,
+    pub pending: Pending,
+    pub limits: ApiLimits,
+}
+
+/// Request size limits of the public API.
+#[derive(Clone, Copy, Debug)]
+pub struct ApiLimits {
+    /// Maximum number of wallets a single balances request may ask for.
+    pub max_batch_wallets: usize,
+    /// Maximum number of assets listed in a wallet summary.
+    pub max_wallet_assets: usize,
+}
+
+impl ApiLimits {
+    /// Limits from the `[api]` section of the node config.
+    pub fn from_config() -> Self {
+        let api = config::config().api();
+        ApiLimits {
+            max_batch_wallets: api.max_batch_wallets(),
+            max_wallet_assets: api.max_wallet_assets(),
+        }
+    }
 }
 
 impl ServiceApi {
@@ -120,6 +153,7 @@ impl Api for ServiceApi {
         let api = TransactionApi {
             channel: self_.channel,
             blockchain: self_.blockchain,
+            pending: self_.pending,
         };
         api.wire(router);
 
@@ -130,6 +164,7 @@ impl Api for ServiceApi {
 
         let api = WalletApi {
             blockchain: self.clone().blockchain,
+            limits: self.limits,
         };
         api.wire(router);
 
@@ -155,6 +190,15 @@ impl Api for ServiceApi {
         };
         api.wire(router);
 
+        let api = CatalogApi {};
+        api.wire(router);
+
+        let api = EventsApi {
+            blockchain: self.clone().blockchain,
+            events: self.clone().events,
+        };
+        api.wire(router);
+
         let send_option = move |_request: &mut Request| -> IronResult<Response> {
             let mut resp = Response::with(StatusCode::Ok);
             ServiceApi::add_option_headers(&mut resp.headers);
@@ -5,23 +5,39 @@ extern crate router;
 extern crate serde;
 extern crate serde_json;
 
+use std::collections::BTreeMap;
+
 use exonum::api::Api;
 use exonum::blockchain::{Blockchain, Transaction};
-use exonum::crypto::Hash;
+use exonum::crypto::{Hash, PublicKey};
 use exonum::encoding::serialize::FromHex;
 use exonum::messages::Message;
 use exonum::node::{ApiSender, TransactionSend};
-use hyper::header::ContentType;
+use hyper::header::{ContentType, Location};
 use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status as istatus;
 use prometheus::IntCounter;
 use router::Router;
 
+use config;
 use currency::api::error::ApiError;
+use currency::api::ServiceApi;
+use currency::api::validation;
+use currency::pending::Pending;
+use currency::assets::PriceDeviation;
+use currency::price_warnings;
 use currency::status;
-use currency::transactions::{AddAssets, DeleteAssets, Exchange, ExchangeIntermediary, Trade,
-                             TradeIntermediary, Transfer};
+use currency::tags;
+use currency::wallet;
+use currency::wallet::Wallet;
+use currency::SERVICE_NAME;
+use currency::transactions::components::Apply;
+use currency::transactions::{AddAssets, DeleteAssets, EscrowCreate, EscrowRefund,
+                             EscrowRelease, Exchange, ExchangeIntermediary, FreezeAsset,
+                             MatchOffers, MergeAsset, MultiSigTransfer, SetAssetBlacklist,
+                             SetIntermediary, SetMultiSig, SetReferencePrice, SplitAsset,
+                             SponsoredTransfer, Trade, TradeIntermediary, Transfer};
 
 use currency::error::Error;
 
@@ -31,6 +47,7 @@ const MAX_TRANSACTION_LENGTH: usize = 17408;
 pub struct TransactionApi {
     pub channel: ApiSender,
     pub blockchain: Blockchain,
+    pub pending: Pending,
 }
 
 #[serde(untagged)]
@@ -43,6 +60,19 @@ pub enum TransactionRequest {
     TradeIntermediary(TradeIntermediary),
     Exchange(Exchange),
     ExchangeIntermediary(ExchangeIntermediary),
+    SetAssetBlacklist(SetAssetBlacklist),
+    FreezeAsset(FreezeAsset),
+    SetIntermediary(SetIntermediary),
+    EscrowCreate(EscrowCreate),
+    EscrowRelease(EscrowRelease),
+    EscrowRefund(EscrowRefund),
+    SponsoredTransfer(SponsoredTransfer),
+    SetMultiSig(SetMultiSig),
+    MultiSigTransfer(MultiSigTransfer),
+    MatchOffers(MatchOffers),
+    SetReferencePrice(SetReferencePrice),
+    SplitAsset(SplitAsset),
+    MergeAsset(MergeAsset),
 }
 
 impl TransactionRequest {
@@ -55,6 +85,19 @@ impl TransactionRequest {
             &TransactionRequest::TradeIntermediary(ref trans) => trans.raw().len(),
             &TransactionRequest::Exchange(ref trans) => trans.raw().len(),
             &TransactionRequest::ExchangeIntermediary(ref trans) => trans.raw().len(),
+            &TransactionRequest::SetAssetBlacklist(ref trans) => trans.raw().len(),
+            &TransactionRequest::FreezeAsset(ref trans) => trans.raw().len(),
+            &TransactionRequest::SetIntermediary(ref trans) => trans.raw().len(),
+            &TransactionRequest::EscrowCreate(ref trans) => trans.raw().len(),
+            &TransactionRequest::EscrowRelease(ref trans) => trans.raw().len(),
+            &TransactionRequest::EscrowRefund(ref trans) => trans.raw().len(),
+            &TransactionRequest::SponsoredTransfer(ref trans) => trans.raw().len(),
+            &TransactionRequest::SetMultiSig(ref trans) => trans.raw().len(),
+            &TransactionRequest::MultiSigTransfer(ref trans) => trans.raw().len(),
+            &TransactionRequest::MatchOffers(ref trans) => trans.raw().len(),
+            &TransactionRequest::SetReferencePrice(ref trans) => trans.raw().len(),
+            &TransactionRequest::SplitAsset(ref trans) => trans.raw().len(),
+            &TransactionRequest::MergeAsset(ref trans) => trans.raw().len(),
         }
     }
 }
@@ -69,13 +112,60 @@ impl Into<Box<Transaction>> for TransactionRequest {
             TransactionRequest::TradeIntermediary(trans) => Box::new(trans),
             TransactionRequest::Exchange(trans) => Box::new(trans),
             TransactionRequest::ExchangeIntermediary(trans) => Box::new(trans),
+            TransactionRequest::SetAssetBlacklist(trans) => Box::new(trans),
+            TransactionRequest::FreezeAsset(trans) => Box::new(trans),
+            TransactionRequest::SetIntermediary(trans) => Box::new(trans),
+            TransactionRequest::EscrowCreate(trans) => Box::new(trans),
+            TransactionRequest::EscrowRelease(trans) => Box::new(trans),
+            TransactionRequest::EscrowRefund(trans) => Box::new(trans),
+            TransactionRequest::SponsoredTransfer(trans) => Box::new(trans),
+            TransactionRequest::SetMultiSig(trans) => Box::new(trans),
+            TransactionRequest::MultiSigTransfer(trans) => Box::new(trans),
+            TransactionRequest::MatchOffers(trans) => Box::new(trans),
+            TransactionRequest::SetReferencePrice(trans) => Box::new(trans),
+            TransactionRequest::SplitAsset(trans) => Box::new(trans),
+            TransactionRequest::MergeAsset(trans) => Box::new(trans),
         }
     }
 }
 
+impl Into<Box<Apply>> for TransactionRequest {
+    fn into(self) -> Box<Apply> {
+        match self {
+            TransactionRequest::Transfer(trans) => Box::new(trans),
+            TransactionRequest::AddAssets(trans) => Box::new(trans),
+            TransactionRequest::DeleteAssets(trans) => Box::new(trans),
+            TransactionRequest::Trade(trans) => Box::new(trans),
+            TransactionRequest::TradeIntermediary(trans) => Box::new(trans),
+            TransactionRequest::Exchange(trans) => Box::new(trans),
+            TransactionRequest::ExchangeIntermediary(trans) => Box::new(trans),
+            TransactionRequest::SetAssetBlacklist(trans) => Box::new(trans),
+            TransactionRequest::FreezeAsset(trans) => Box::new(trans),
+            TransactionRequest::SetIntermediary(trans) => Box::new(trans),
+            TransactionRequest::EscrowCreate(trans) => Box::new(trans),
+            TransactionRequest::EscrowRelease(trans) => Box::new(trans),
+            TransactionRequest::EscrowRefund(trans) => Box::new(trans),
+            TransactionRequest::SponsoredTransfer(trans) => Box::new(trans),
+            TransactionRequest::SetMultiSig(trans) => Box::new(trans),
+            TransactionRequest::MultiSigTransfer(trans) => Box::new(trans),
+            TransactionRequest::MatchOffers(trans) => Box::new(trans),
+            TransactionRequest::SetReferencePrice(trans) => Box::new(trans),
+            TransactionRequest::SplitAsset(trans) => Box::new(trans),
+            TransactionRequest::MergeAsset(trans) => Box::new(trans),
+        }
+    }
+}
+
+/// Response to a submitted transaction.
+///
+/// `status` is only set for a transaction which was already executed, see
+/// `config::Api::dedupe_submissions`. A transaction which is still pending is
+/// answered with `200 OK` and no status, and isn't passed to the node again.
 #[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
 pub struct TransactionResponse {
     pub tx_hash: Hash,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Result<(), Error>>,
 }
 
 pub type TxPostResponse = Result<Result<TransactionResponse, Error>, ApiError>;
@@ -83,11 +173,118 @@ pub type TxPostResponse = Result<Result<TransactionResponse, Error>, ApiError>;
 //#[derive(Serialize, Deserialize, Debug)]
 pub type StatusResponse = Result<Result<(), Error>, ApiError>;
 
+pub type TaggedTransactionsResponse = Result<Vec<Hash>, ApiError>;
+
+pub type PriceWarningsResponse = Result<Vec<PriceDeviation>, ApiError>;
+
+/// Predicted outcome of a transaction, see `/v1/transactions/dry_run`.
+///
+/// `wallets` holds the state every changed wallet would end up in, fee
+/// recipients included.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct DryRunResponseBody {
+    pub tx_hash: Hash,
+    pub status: Result<(), Error>,
+    pub wallets: BTreeMap<PublicKey, Wallet>,
+}
+
+pub type DryRunResponse = Result<Result<DryRunResponseBody, Error>, ApiError>;
+
 impl TransactionApi {
+    /// Canonical URL for polling the status of the transaction.
+    pub fn status_url(tx_hash: &Hash) -> String {
+        format!(
+            "/api/services/{}/v1/transactions/{}/status",
+            SERVICE_NAME,
+            tx_hash.to_hex()
+        )
+    }
+
+    /// Error for a body which isn't any of the transactions, naming the
+    /// malformed field if there is one.
+    fn incorrect_request(req: &mut Request) -> ApiError {
+        match req.get::<bodyparser::Json>() {
+            Ok(Some(ref json)) => match validation::validate(json) {
+                Err(e) => ApiError::InvalidField(e),
+                Ok(()) => ApiError::IncorrectRequest,
+            },
+            _ => ApiError::IncorrectRequest,
+        }
+    }
+
     fn get_status(&self, tx_hash: &Hash) -> Option<Result<(), Error>> {
         let view = &mut self.blockchain.fork();
         status::Schema(view).fetch(tx_hash)
     }
+
+    /// Status of a resubmitted transaction, if it is already executed and
+    /// duplicates are answered from the statuses.
+    fn known_status(&self, tx_hash: &Hash) -> Option<Result<(), Error>> {
+        if !config::config().api().dedupe_submissions() {
+            return None;
+        }
+        self.get_status(tx_hash)
+    }
+
+    /// Remember a submitted transaction until it is committed, if duplicates
+    /// are answered from the pending submissions.
+    ///
+    /// Returns `false` for a transaction which is already pending, and must
+    /// not be passed to the node again.
+    fn add_pending(&self, tx_hash: &Hash) -> bool {
+        if !config::config().api().dedupe_submissions() {
+            return true;
+        }
+        let height = self.blockchain.last_block().height().0;
+        self.pending.insert(*tx_hash, height)
+    }
+
+    /// Price deviations flagged for an executed transaction, `None` if the
+    /// transaction isn't executed.
+    fn get_price_warnings(&self, tx_hash: &Hash) -> Option<Vec<PriceDeviation>> {
+        let view = &mut self.blockchain.fork();
+        if !status::Schema(&*view).contains(tx_hash) {
+            return None;
+        }
+        Some(price_warnings::Schema(&*view).fetch(tx_hash))
+    }
+
+    fn get_tagged(&self, tag: &str) -> Vec<Hash> {
+        let view = &mut self.blockchain.fork();
+        tags::Schema(view).fetch(tag)
+    }
+
+    /// Execute the transaction against a fork of the current state, which
+    /// is dropped afterwards.
+    ///
+    /// The transaction goes through the same checks and state changes as
+    /// on submission, but leaves the node's metrics and logs alone.
+    /// A transaction that was already executed reports its recorded status
+    /// and changes nothing.
+    fn dry_run(&self, tx: Box<Apply>) -> Result<DryRunResponseBody, Error> {
+        if !tx.check() {
+            return Err(Error::UnableToVerifyTransaction);
+        }
+
+        let tx_hash = tx.hash();
+        let view = &mut self.blockchain.fork();
+        let recorded = status::Schema(&*view).fetch(&tx_hash);
+        let status = match recorded {
+            Some(status) => status,
+            None => tx.apply(view),
+        };
+        let changed = wallet::Schema(&mut *view).changed();
+        let wallets = changed
+            .into_iter()
+            .map(|key| (key, wallet::Schema(&*view).fetch(&key)))
+            .collect();
+
+        Ok(DryRunResponseBody {
+            tx_hash,
+            status,
+            wallets,
+        })
+    }
 }
 
 lazy_static! {
@@ -107,6 +304,30 @@ lazy_static! {
         "dmbc_transaction_api_get_status_responses_total",
         "Transaction status responses."
     ).unwrap();
+    static ref GET_TAGGED_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_get_tagged_requests_total",
+        "Tagged transactions requests."
+    ).unwrap();
+    static ref GET_TAGGED_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_get_tagged_responses_total",
+        "Tagged transactions responses."
+    ).unwrap();
+    static ref DRY_RUN_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_dry_run_requests_total",
+        "Transaction dry run requests."
+    ).unwrap();
+    static ref DRY_RUN_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_dry_run_responses_total",
+        "Transaction dry run responses."
+    ).unwrap();
+    static ref GET_WARNINGS_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_get_warnings_requests_total",
+        "Transaction price warnings requests."
+    ).unwrap();
+    static ref GET_WARNINGS_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_get_warnings_responses_total",
+        "Transaction price warnings responses."
+    ).unwrap();
 }
 
 impl Api for TransactionApi {
@@ -115,6 +336,7 @@ impl Api for TransactionApi {
         let transaction = move |req: &mut Request| -> IronResult<Response> {
             POST_REQUESTS.inc();
 
+            let mut known = false;
             let s: TxPostResponse = match req.get::<bodyparser::Struct<TransactionRequest>>() {
                 Ok(Some(ref transaction)) if transaction.len() > MAX_TRANSACTION_LENGTH => {
                     Ok(Err(Error::InvalidTransaction))
@@ -122,26 +344,40 @@ impl Api for TransactionApi {
                 Ok(Some(transaction)) => {
                     let tx: Box<Transaction> = transaction.into();
                     let tx_hash = tx.hash();
-                    match self_.channel.send(tx) {
-                        Ok(_) => Ok(Ok(TransactionResponse { tx_hash })),
-                        Err(_) => Ok(Err(Error::UnableToVerifyTransaction)),
+                    match self_.known_status(&tx_hash) {
+                        Some(status) => Ok(Ok(TransactionResponse {
+                            tx_hash,
+                            status: Some(status),
+                        })),
+                        None if !self_.add_pending(&tx_hash) => {
+                            known = true;
+                            Ok(Ok(TransactionResponse { tx_hash, status: None }))
+                        }
+                        None => match self_.channel.send(tx) {
+                            Ok(_) => Ok(Ok(TransactionResponse { tx_hash, status: None })),
+                            Err(_) => {
+                                self_.pending.remove(&tx_hash);
+                                Ok(Err(Error::UnableToVerifyTransaction))
+                            }
+                        },
                     }
                 }
                 Ok(None) => Err(ApiError::EmptyRequestBody),
-                Err(_) => Err(ApiError::IncorrectRequest),
+                Err(_) => Err(Self::incorrect_request(req)),
+            };
+            let ss = match s {
+                Ok(Ok(TransactionResponse { status: Some(_), .. })) => istatus::Ok,
+                Ok(Ok(_)) if known => istatus::Ok,
+                Ok(Ok(_)) => istatus::Accepted,
+                _ => istatus::BadRequest,
             };
-            let ss = s.clone()
-                .ok()
-                .map(|r| {
-                    r.err()
-                        .map(|_| istatus::BadRequest)
-                        .unwrap_or(istatus::Created)
-                })
-                .unwrap_or(istatus::BadRequest);
 
             let mut res = Response::with((ss, serde_json::to_string_pretty(&s).unwrap()));
             res.headers.set(ContentType::json());
             res.headers.set(AccessControlAllowOrigin::Any);
+            if let Ok(Ok(ref response)) = s {
+                res.headers.set(Location(Self::status_url(&response.tx_hash)));
+            }
 
             POST_RESPONSES.inc();
 
@@ -153,8 +389,13 @@ impl Api for TransactionApi {
         let get_status = move |request: &mut Request| -> IronResult<Response> {
             GET_STATUS_REQUESTS.inc();
 
-            let path = request.url.path();
-            let tx_hash_str = path.last().unwrap();
+            let tx_hash_str = request
+                .extensions
+                .get::<Router>()
+                .unwrap()
+                .find("hash")
+                .unwrap_or("")
+                .to_string();
             let s: StatusResponse = Hash::from_hex(tx_hash_str)
                 .map_err(|_| ApiError::TransactionHashInvalid)
                 .and_then(|tx_hash| {
@@ -178,11 +419,112 @@ impl Api for TransactionApi {
             Ok(res)
         };
 
+        // Gets the price deviations flagged for a trade.
+        let self_ = self.clone();
+        let get_warnings = move |request: &mut Request| -> IronResult<Response> {
+            GET_WARNINGS_REQUESTS.inc();
+
+            let tx_hash_str = request
+                .extensions
+                .get::<Router>()
+                .unwrap()
+                .find("hash")
+                .unwrap_or("")
+                .to_string();
+            let s: PriceWarningsResponse = Hash::from_hex(tx_hash_str)
+                .map_err(|_| ApiError::TransactionHashInvalid)
+                .and_then(|tx_hash| {
+                    self_
+                        .get_price_warnings(&tx_hash)
+                        .ok_or(ApiError::TransactionNotFound)
+                });
+
+            let mut res = Response::with((
+                s.clone()
+                    .err()
+                    .map(|e| e.to_status())
+                    .unwrap_or(istatus::Ok),
+                serde_json::to_string_pretty(&s).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            GET_WARNINGS_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        // Predicts the outcome of a signed transaction without submitting it.
+        let self_ = self.clone();
+        let dry_run = move |req: &mut Request| -> IronResult<Response> {
+            DRY_RUN_REQUESTS.inc();
+
+            let s: DryRunResponse = match req.get::<bodyparser::Struct<TransactionRequest>>() {
+                Ok(Some(ref transaction)) if transaction.len() > MAX_TRANSACTION_LENGTH => {
+                    Ok(Err(Error::InvalidTransaction))
+                }
+                Ok(Some(transaction)) => Ok(self_.dry_run(transaction.into())),
+                Ok(None) => Err(ApiError::EmptyRequestBody),
+                Err(_) => Err(Self::incorrect_request(req)),
+            };
+            let ss = match s {
+                Ok(Ok(_)) => istatus::Ok,
+                _ => istatus::BadRequest,
+            };
+
+            let mut res = Response::with((ss, serde_json::to_string_pretty(&s).unwrap()));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            DRY_RUN_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        let self_ = self.clone();
+        let get_tagged = move |request: &mut Request| -> IronResult<Response> {
+            GET_TAGGED_REQUESTS.inc();
+
+            let tag: String = ServiceApi::read_parameter(request, "tag", String::new());
+            let s: TaggedTransactionsResponse = if tag.is_empty() {
+                Err(ApiError::IncorrectRequest)
+            } else {
+                let hashes = self_.get_tagged(&tag);
+                Ok(ServiceApi::apply_pagination(request, &hashes).to_vec())
+            };
+
+            let mut res = Response::with((
+                s.clone()
+                    .err()
+                    .map(|e| e.to_status())
+                    .unwrap_or(istatus::Ok),
+                serde_json::to_string_pretty(&s).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            GET_TAGGED_RESPONSES.inc();
+
+            Ok(res)
+        };
+
         router.post("/v1/transactions", transaction, "transaction");
+        router.post("/v1/transactions/dry_run", dry_run, "transaction_dry_run");
+        router.get("/v1/transactions", get_tagged, "get_tagged_transactions");
         router.get(
             "/v1/transactions/:hash",
-            get_status,
+            get_status.clone(),
             "get_transaction_status",
         );
+        router.get(
+            "/v1/transactions/:hash/status",
+            get_status,
+            "get_transaction_status_url",
+        );
+        router.get(
+            "/v1/transactions/:hash/warnings",
+            get_warnings,
+            "get_transaction_warnings",
+        );
     }
 }
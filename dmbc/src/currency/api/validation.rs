@@ -0,0 +1,175 @@
+//! Field-level validation of submitted transactions.
+//!
+//! Transactions are parsed straight into their types, which on malformed
+//! input only tells that none of them matched. The JSON is then checked
+//! field by field, to point the client at what is actually wrong.
+
+use exonum::crypto::{HASH_SIZE, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+use serde_json::Value;
+
+use currency::SERVICE_ID;
+use currency::transactions::{ADD_ASSETS_ID, DELETE_ASSETS_ID, ESCROW_CREATE_ID,
+                             ESCROW_REFUND_ID, ESCROW_RELEASE_ID, EXCHANGE_ID,
+                             EXCHANGE_INTERMEDIARY_ID, FREEZE_ASSET_ID, MATCH_OFFERS_ID,
+                             MERGE_ASSET_ID, MULTISIG_TRANSFER_ID, SET_ASSET_BLACKLIST_ID,
+                             SET_INTERMEDIARY_ID, SET_MULTISIG_ID, SET_REFERENCE_PRICE_ID,
+                             SPLIT_ASSET_ID, SPONSORED_TRANSFER_ID, TRADE_ID,
+                             TRADE_INTERMEDIARY_ID, TRANSFER_ID, RETIRED_IDS};
+
+/// Length of a hex encoded `AssetId`.
+const ASSET_ID_HEX_LENGTH: usize = 32;
+
+const TRANSACTION_IDS: &[u16] = &[
+    TRANSFER_ID,
+    SPONSORED_TRANSFER_ID,
+    MULTISIG_TRANSFER_ID,
+    ADD_ASSETS_ID,
+    DELETE_ASSETS_ID,
+    SPLIT_ASSET_ID,
+    MERGE_ASSET_ID,
+    TRADE_ID,
+    TRADE_INTERMEDIARY_ID,
+    EXCHANGE_ID,
+    EXCHANGE_INTERMEDIARY_ID,
+    MATCH_OFFERS_ID,
+    SET_ASSET_BLACKLIST_ID,
+    FREEZE_ASSET_ID,
+    SET_INTERMEDIARY_ID,
+    SET_REFERENCE_PRICE_ID,
+    ESCROW_CREATE_ID,
+    ESCROW_RELEASE_ID,
+    ESCROW_REFUND_ID,
+    SET_MULTISIG_ID,
+];
+
+// Body fields are recognized by name, the same name has the same type in
+// every transaction.
+const PUBLIC_KEY_FIELDS: &[&str] = &[
+    "pub_key",
+    "from",
+    "to",
+    "sender",
+    "recipient",
+    "seller",
+    "buyer",
+    "creator",
+    "owner",
+    "receiver",
+    "wallet",
+    "intermediary",
+    "payer_key",
+    "payer_key_1",
+    "payer_key_2",
+];
+const HASH_FIELDS: &[&str] = &["escrow", "origin"];
+const ASSET_ID_FIELDS: &[&str] = &["id", "asset_id", "into"];
+
+/// A field of a submitted transaction which can't be parsed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FieldError {
+    /// Path to the field, e.g. `body.offer.sender`.
+    pub field: String,
+    /// What is wrong with the field.
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: &str, message: &str) -> Self {
+        FieldError {
+            field: field.to_string(),
+            message: format!("{} {}", field, message),
+        }
+    }
+}
+
+/// Find the first malformed field of a transaction.
+///
+/// Passing validation doesn't make the transaction valid, only the fields
+/// it knows about are checked.
+pub fn validate(tx: &Value) -> Result<(), FieldError> {
+    let tx = tx.as_object()
+        .ok_or_else(|| FieldError::new("transaction", "must be a JSON object"))?;
+
+    for field in &["protocol_version", "network_id"] {
+        match tx.get(*field).and_then(Value::as_u64) {
+            Some(value) if value <= u64::from(u8::max_value()) => (),
+            Some(_) => return Err(FieldError::new(field, "must be at most 255")),
+            None => return Err(FieldError::new(field, "must be a number")),
+        }
+    }
+
+    match tx.get("service_id").and_then(Value::as_u64) {
+        Some(id) if id == u64::from(SERVICE_ID) => (),
+        _ => {
+            let message = format!("must be {}", SERVICE_ID);
+            return Err(FieldError::new("service_id", &message));
+        }
+    }
+
+    match tx.get("message_id").and_then(Value::as_u64) {
+        Some(id) if TRANSACTION_IDS.iter().any(|known| u64::from(*known) == id) => (),
+        Some(id) => match RETIRED_IDS.iter().find(|&&(retired, _)| u64::from(retired) == id) {
+            Some(&(_, current)) => {
+                let message = format!("is retired, use {}", current);
+                return Err(FieldError::new("message_id", &message));
+            }
+            None => return Err(FieldError::new("message_id", "must be a known transaction type")),
+        },
+        None => return Err(FieldError::new("message_id", "must be a known transaction type")),
+    }
+
+    match tx.get("signature") {
+        Some(signature) => hex_field("signature", signature, SIGNATURE_LENGTH * 2)?,
+        None => return Err(FieldError::new("signature", "is missing")),
+    }
+
+    match tx.get("body") {
+        Some(body) if body.is_object() => validate_value("body", body),
+        _ => Err(FieldError::new("body", "must be a JSON object")),
+    }
+}
+
+fn validate_value(path: &str, value: &Value) -> Result<(), FieldError> {
+    match *value {
+        Value::Object(ref fields) => {
+            for (name, value) in fields {
+                let path = format!("{}.{}", path, name);
+                if value.is_string() {
+                    validate_string(&path, name, value)?;
+                } else {
+                    validate_value(&path, value)?;
+                }
+            }
+            Ok(())
+        }
+        Value::Array(ref values) => {
+            for (i, value) in values.iter().enumerate() {
+                validate_value(&format!("{}[{}]", path, i), value)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_string(path: &str, name: &str, value: &Value) -> Result<(), FieldError> {
+    if PUBLIC_KEY_FIELDS.contains(&name) {
+        hex_field(path, value, PUBLIC_KEY_LENGTH * 2)
+    } else if HASH_FIELDS.contains(&name) {
+        hex_field(path, value, HASH_SIZE * 2)
+    } else if ASSET_ID_FIELDS.contains(&name) {
+        hex_field(path, value, ASSET_ID_HEX_LENGTH)
+    } else if name == "signature" || name.ends_with("_signature") {
+        hex_field(path, value, SIGNATURE_LENGTH * 2)
+    } else {
+        Ok(())
+    }
+}
+
+fn hex_field(path: &str, value: &Value, length: usize) -> Result<(), FieldError> {
+    let message = format!("must be {} hex chars", length);
+    match value.as_str() {
+        Some(hex) if hex.len() == length && hex.chars().all(|c| c.is_digit(16)) => Ok(()),
+        _ => Err(FieldError::new(path, &message)),
+    }
+}
@@ -1,10 +1,12 @@
+extern crate bodyparser;
 extern crate serde_json;
 
 use std::collections::HashMap;
+use std::u64;
 
 use exonum::api::Api;
 use exonum::blockchain::Blockchain;
-use exonum::crypto::PublicKey;
+use exonum::crypto::{Hash, PublicKey};
 use exonum::encoding::serialize::FromHex;
 use hyper::header::ContentType;
 use iron::headers::AccessControlAllowOrigin;
@@ -14,17 +16,22 @@ use prometheus::IntCounter;
 use router::Router;
 
 use currency::api::error::ApiError;
-use currency::api::ServiceApi;
+use currency::api::{ApiLimits, ServiceApi};
 use currency::assets;
 use currency::assets::{AssetBundle, AssetId, AssetInfo};
+use currency::error::Error;
+use currency::history;
+use currency::memos;
+use currency::status as tx_status;
 use currency::wallet;
-use currency::wallet::Wallet;
+use currency::wallet::{BalanceSnapshot, Wallet};
 
 pub const PARAMETER_META_DATA_KEY: &str = "meta_data";
 
 #[derive(Clone)]
 pub struct WalletApi {
     pub blockchain: Blockchain,
+    pub limits: ApiLimits,
 }
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct WalletInfo {
@@ -76,14 +83,72 @@ pub struct WalletAssetsResponseBody {
     pub assets: Vec<ExtendedAsset>,
 }
 
+/// Balance and assets of a single wallet.
+///
+/// At most `max_assets` assets are listed, `has_more` tells whether the
+/// wallet holds others. See `/v1/wallets/:pub_key/assets` for all of them.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct WalletStateResponseBody {
+    pub balance: u64,
+    pub assets: Vec<AssetBundle>,
+    pub has_more: bool,
+}
+
+impl WalletStateResponseBody {
+    pub fn from(wallet: Wallet, max_assets: usize) -> Self {
+        let mut assets = wallet.assets();
+        let has_more = assets.len() > max_assets;
+        assets.truncate(max_assets);
+        WalletStateResponseBody {
+            balance: wallet.balance(),
+            assets,
+            has_more,
+        }
+    }
+}
+
+/// Balance and assets of one of the wallets asked for, see
+/// `/v1/wallets/balances`. Unknown wallets are empty.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct WalletBalanceEntry {
+    pub pub_key: PublicKey,
+    pub balance: u64,
+    pub assets: Vec<AssetBundle>,
+}
+
+/// A transaction touching a wallet, see `/v1/wallets/:pub_key/history`.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct WalletHistoryEntry {
+    pub tx_hash: Hash,
+    pub status: Result<(), Error>,
+    /// Memo of an intermediary trade or exchange, see `memos::Schema`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_info: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct WalletHistoryResponseBody {
+    pub total: u64,
+    pub count: u64,
+    pub transactions: Vec<WalletHistoryEntry>,
+}
+
 pub type WalletResponse = Result<WalletInfo, ApiError>;
 
+pub type WalletStateResponse = Result<WalletStateResponseBody, ApiError>;
+
 pub type WalletsResponse = Result<WalletsResponseBody, ApiError>;
 
 pub type WalletAssetsResponse = Result<WalletAssetsResponseBody, ApiError>;
 
 pub type WalletAssetResponse = Result<ExtendedAsset, ApiError>;
 
+pub type WalletHistoryResponse = Result<WalletHistoryResponseBody, ApiError>;
+
+pub type WalletSnapshotResponse = Result<BalanceSnapshot, ApiError>;
+
+pub type WalletBalancesResponse = Result<Vec<WalletBalanceEntry>, ApiError>;
+
 impl WalletApi {
     fn wallet(&self, pub_key: &PublicKey) -> Wallet {
         let view = &mut self.blockchain.fork();
@@ -141,6 +206,56 @@ impl WalletApi {
         let view = self.blockchain.fork();
         assets::Schema(view).fetch(asset_id)
     }
+
+    fn pagination_history(
+        &self,
+        pub_key: &PublicKey,
+        offset: u64,
+        limit: u64,
+    ) -> (Vec<WalletHistoryEntry>, u64) {
+        let view = &mut self.blockchain.fork();
+        let index = history::Schema(&*view).index(pub_key);
+        let transactions = index
+            .iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|tx_hash| {
+                // Statuses are stored in the same fork as the history.
+                let status = tx_status::Schema(&*view)
+                    .fetch(&tx_hash)
+                    .expect("Transaction in the history has no status");
+                let data_info = memos::Schema(&*view).fetch(&tx_hash);
+                WalletHistoryEntry {
+                    tx_hash,
+                    status,
+                    data_info,
+                }
+            })
+            .collect();
+
+        (transactions, index.len())
+    }
+
+    fn snapshot_at(&self, pub_key: &PublicKey, height: u64) -> Option<BalanceSnapshot> {
+        let view = self.blockchain.snapshot();
+        wallet::Schema(&*view).snapshot_at(pub_key, height)
+    }
+
+    fn balances(&self, pub_keys: &[PublicKey]) -> Vec<WalletBalanceEntry> {
+        // One snapshot, so that all the wallets are read as of the same block.
+        let view = self.blockchain.snapshot();
+        pub_keys
+            .iter()
+            .map(|pub_key| {
+                let wallet = wallet::Schema(&*view).fetch(pub_key);
+                WalletBalanceEntry {
+                    pub_key: *pub_key,
+                    balance: wallet.balance(),
+                    assets: wallet.assets(),
+                }
+            })
+            .collect()
+    }
 }
 
 lazy_static! {
@@ -176,6 +291,38 @@ lazy_static! {
         "dmbc_wallet_api_asset_responses_total",
         "Wallet asset counter responses."
     ).unwrap();
+    static ref STATE_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_state_requests_total",
+        "Wallet state requests."
+    ).unwrap();
+    static ref STATE_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_state_responses_total",
+        "Wallet state responses."
+    ).unwrap();
+    static ref HISTORY_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_history_requests_total",
+        "Wallet transaction history requests."
+    ).unwrap();
+    static ref HISTORY_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_history_responses_total",
+        "Wallet transaction history responses."
+    ).unwrap();
+    static ref SNAPSHOT_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_snapshot_requests_total",
+        "Wallet balance snapshot requests."
+    ).unwrap();
+    static ref SNAPSHOT_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_snapshot_responses_total",
+        "Wallet balance snapshot responses."
+    ).unwrap();
+    static ref BALANCES_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_balances_requests_total",
+        "Batch balance requests."
+    ).unwrap();
+    static ref BALANCES_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_wallet_api_balances_responses_total",
+        "Batch balance responses."
+    ).unwrap();
 }
 
 impl Api for WalletApi {
@@ -211,6 +358,162 @@ impl Api for WalletApi {
             Ok(res)
         };
 
+        // Gets balance and assets of the wallet. Unknown wallets are empty.
+        let self_ = self.clone();
+        let wallet_state = move |req: &mut Request| -> IronResult<Response> {
+            STATE_REQUESTS.inc();
+
+            let public_key_result = {
+                let wallet_key = req.extensions
+                    .get::<Router>()
+                    .unwrap()
+                    .find("pub_key")
+                    .unwrap();
+                PublicKey::from_hex(wallet_key)
+            };
+            let result: WalletStateResponse = match public_key_result {
+                Ok(public_key) => {
+                    let wallet = self_.wallet(&public_key);
+                    let max_assets = self_.limits.max_wallet_assets;
+                    Ok(WalletStateResponseBody::from(wallet, max_assets))
+                }
+                Err(_) => Err(ApiError::WalletHexInvalid),
+            };
+
+            let mut res = Response::with((
+                result
+                    .clone()
+                    .err()
+                    .map(|e| e.to_status())
+                    .unwrap_or(status::Ok),
+                serde_json::to_string_pretty(&result).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            STATE_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        // Gets transactions touching the wallet, oldest first.
+        let self_ = self.clone();
+        let wallet_history = move |req: &mut Request| -> IronResult<Response> {
+            HISTORY_REQUESTS.inc();
+
+            let public_key_result = {
+                let wallet_key = req.extensions
+                    .get::<Router>()
+                    .unwrap()
+                    .find("pub_key")
+                    .unwrap();
+                PublicKey::from_hex(wallet_key)
+            };
+            let result: WalletHistoryResponse = match public_key_result {
+                Ok(public_key) => {
+                    let (offset, limit) = ServiceApi::pagination_params(req);
+                    let (transactions, total) =
+                        self_.pagination_history(&public_key, offset, limit);
+                    Ok(WalletHistoryResponseBody {
+                        total,
+                        count: transactions.len() as u64,
+                        transactions,
+                    })
+                }
+                Err(_) => Err(ApiError::WalletHexInvalid),
+            };
+
+            let mut res = Response::with((
+                result
+                    .clone()
+                    .err()
+                    .map(|e| e.to_status())
+                    .unwrap_or(status::Ok),
+                serde_json::to_string_pretty(&result).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            HISTORY_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        // Gets the wallet snapshot at or before the block `height`, the
+        // latest one if no height is given.
+        let self_ = self.clone();
+        let wallet_snapshot = move |req: &mut Request| -> IronResult<Response> {
+            SNAPSHOT_REQUESTS.inc();
+
+            let public_key_result = {
+                let wallet_key = req.extensions
+                    .get::<Router>()
+                    .unwrap()
+                    .find("pub_key")
+                    .unwrap();
+                PublicKey::from_hex(wallet_key)
+            };
+            let result: WalletSnapshotResponse = match public_key_result {
+                Ok(public_key) => {
+                    let height: u64 = ServiceApi::read_parameter(req, "height", u64::MAX);
+                    self_
+                        .snapshot_at(&public_key, height)
+                        .ok_or(ApiError::SnapshotNotFound)
+                }
+                Err(_) => Err(ApiError::WalletHexInvalid),
+            };
+
+            let mut res = Response::with((
+                result
+                    .clone()
+                    .err()
+                    .map(|e| e.to_status())
+                    .unwrap_or(status::Ok),
+                serde_json::to_string_pretty(&result).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            SNAPSHOT_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        // Gets balances and assets of the wallets in the body, a JSON array
+        // of keys, in the same order.
+        let self_ = self.clone();
+        let wallet_balances = move |req: &mut Request| -> IronResult<Response> {
+            BALANCES_REQUESTS.inc();
+
+            let max_wallets = self_.limits.max_batch_wallets;
+            let body = req.get::<bodyparser::Struct<Vec<String>>>();
+            let result: WalletBalancesResponse = match body {
+                Ok(Some(ref keys)) if keys.len() > max_wallets => Err(ApiError::TooManyWallets),
+                Ok(Some(keys)) => keys
+                    .iter()
+                    .map(|key| PublicKey::from_hex(key).map_err(|_| ApiError::WalletHexInvalid))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|pub_keys| self_.balances(&pub_keys)),
+                Ok(None) => Err(ApiError::EmptyRequestBody),
+                Err(_) => Err(ApiError::IncorrectRequest),
+            };
+
+            let mut res = Response::with((
+                result
+                    .clone()
+                    .err()
+                    .map(|e| e.to_status())
+                    .unwrap_or(status::Ok),
+                serde_json::to_string_pretty(&result).unwrap(),
+            ));
+            res.headers.set(ContentType::json());
+            res.headers.set(AccessControlAllowOrigin::Any);
+
+            BALANCES_RESPONSES.inc();
+
+            Ok(res)
+        };
+
         // Gets status of all wallets.
         let self_ = self.clone();
         let wallets_info = move |req: &mut Request| -> IronResult<Response> {
@@ -350,7 +653,19 @@ impl Api for WalletApi {
         };
 
         router.get("/v1/wallets", wallets_info, "wallets_info");
+        router.post("/v1/wallets/balances", wallet_balances, "wallet_balances");
         router.get("/v1/wallets/:pub_key", wallet_info, "get_balance");
+        router.get("/v1/wallets/:pub_key/state", wallet_state, "wallet_state");
+        router.get(
+            "/v1/wallets/:pub_key/history",
+            wallet_history,
+            "wallet_history",
+        );
+        router.get(
+            "/v1/wallets/:pub_key/snapshot",
+            wallet_snapshot,
+            "wallet_snapshot",
+        );
         router.get(
             "/v1/wallets/:pub_key/assets",
             wallet_assets_info,
@@ -57,4 +57,15 @@ impl AssetInfo {
             self.data(),
         ))
     }
+
+    /// Info of `amount` units of this asset, e.g. split off into a tranche.
+    pub fn part(&self, amount: u64) -> Self {
+        AssetInfo::new(
+            self.creator(),
+            self.origin(),
+            amount,
+            self.fees(),
+            self.data(),
+        )
+    }
 }
@@ -10,6 +10,12 @@ encoding_struct! {
 
 encoding_struct! {
     /// Third party fee data, part of `AssetInfo`.
+    ///
+    /// `transfer` is the creator's royalty on secondary transfers: every
+    /// transfer of the asset by anyone but its creator pays `fixed` per unit
+    /// from the sender to the creator. It comes on top of the blockchain
+    /// transfer fee, which goes to the platform and is never reduced by it.
+    /// Transfers carry no price, so `fraction` is not used for them.
     struct Fees {
         trade:    Fee,
         exchange: Fee,
@@ -18,8 +24,8 @@ encoding_struct! {
 }
 
 impl Fee {
-    /// Calculate fee value for specific price.
-    pub fn for_price(&self, price: u64) -> u64 {
-        self.fixed() + self.fraction() * price
+    /// Calculate fee value for specific price, `None` if it overflows.
+    pub fn for_price(&self, price: u64) -> Option<u64> {
+        self.fixed().checked_add(self.fraction() * price)
     }
 }
@@ -5,13 +5,17 @@ mod asset_id;
 mod asset_info;
 mod fees;
 mod meta_asset;
+mod reference_price;
 mod schema;
 mod trade_asset;
+mod tranche;
 
 pub use currency::assets::asset_bundle::AssetBundle;
 pub use currency::assets::asset_id::AssetId;
 pub use currency::assets::asset_info::AssetInfo;
 pub use currency::assets::fees::{Fee, Fees};
 pub use currency::assets::meta_asset::MetaAsset;
+pub use currency::assets::reference_price::PriceDeviation;
 pub use currency::assets::schema::Schema;
 pub use currency::assets::trade_asset::TradeAsset;
+pub use currency::assets::tranche::Tranche;
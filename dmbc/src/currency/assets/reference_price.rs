@@ -0,0 +1,27 @@
+use currency::assets::AssetId;
+
+encoding_struct! {
+    /// Price of a traded asset deviating from the reference price set by
+    /// its creator, see `Configuration::price_deviation_bps`.
+    struct PriceDeviation {
+        id:              AssetId,
+        price:           u64,
+        reference_price: u64,
+    }
+}
+
+impl PriceDeviation {
+    /// Whether `price` deviates from `reference_price` by more than `bps`
+    /// basis points of the reference price.
+    pub fn exceeds(price: u64, reference_price: u64, bps: u64) -> bool {
+        let allowed = (reference_price / 10_000)
+            .saturating_mul(bps)
+            .saturating_add((reference_price % 10_000).saturating_mul(bps) / 10_000);
+        let deviation = if price > reference_price {
+            price - reference_price
+        } else {
+            reference_price - price
+        };
+        deviation > allowed
+    }
+}
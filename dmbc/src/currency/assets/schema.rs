@@ -1,6 +1,7 @@
-use exonum::storage::{Fork, MapIndex, Snapshot};
+use exonum::crypto::PublicKey;
+use exonum::storage::{Entry, Fork, KeySetIndex, MapIndex, Snapshot};
 
-use currency::assets::{AssetId, AssetInfo};
+use currency::assets::{AssetId, AssetInfo, PriceDeviation, TradeAsset};
 use currency::SERVICE_NAME;
 
 /// Schema for accessing global asset information.
@@ -22,6 +23,45 @@ where
     pub fn fetch(self, id: &AssetId) -> Option<AssetInfo> {
         self.index().get(id)
     }
+
+    /// Internal `MapIndex` of the reference prices set by asset creators.
+    pub fn reference_prices(self) -> MapIndex<S, AssetId, u64> {
+        let key = SERVICE_NAME.to_string() + ".reference_prices";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Reference price of a unit of the asset, if its creator set one.
+    pub fn reference_price(self, id: &AssetId) -> Option<u64> {
+        self.reference_prices().get(id)
+    }
+
+    /// Assets in `assets` priced more than `bps` basis points off their
+    /// reference price. Assets without a reference price are never listed.
+    pub fn price_deviations(self, assets: &[TradeAsset], bps: u64) -> Vec<PriceDeviation> {
+        let prices = self.reference_prices();
+        assets
+            .iter()
+            .filter_map(|asset| match prices.get(&asset.id()) {
+                Some(reference) if PriceDeviation::exceeds(asset.price(), reference, bps) => {
+                    Some(PriceDeviation::new(asset.id(), asset.price(), reference))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Internal `KeySetIndex` of the assets created by `creator`.
+    pub fn created(self, creator: &PublicKey) -> KeySetIndex<S, AssetId> {
+        let key = SERVICE_NAME.to_string() + ".assets_by_creator";
+        KeySetIndex::with_prefix(key, creator.as_ref().to_vec(), self.0)
+    }
+
+    /// Internal `Entry` set once every stored asset is listed for its
+    /// creator, see `index_creators`.
+    pub fn creators_indexed(self) -> Entry<S, ()> {
+        let key = SERVICE_NAME.to_string() + ".assets_by_creator.indexed";
+        Entry::new(key, self.0)
+    }
 }
 
 impl<'a> Schema<&'a mut Fork> {
@@ -31,8 +71,61 @@ impl<'a> Schema<&'a mut Fork> {
         MapIndex::new(key, self.0)
     }
 
-    /// Store asset info in the database.
+    /// Internal `KeySetIndex` of the assets created by `creator`, with
+    /// mutable access.
+    pub fn created_mut(&mut self, creator: &PublicKey) -> KeySetIndex<&mut Fork, AssetId> {
+        let key = SERVICE_NAME.to_string() + ".assets_by_creator";
+        KeySetIndex::with_prefix(key, creator.as_ref().to_vec(), self.0)
+    }
+
+    /// Internal `Entry` marking the assets as listed for their creators,
+    /// with mutable access.
+    pub fn creators_indexed_mut(&mut self) -> Entry<&mut Fork, ()> {
+        let key = SERVICE_NAME.to_string() + ".assets_by_creator.indexed";
+        Entry::new(key, &mut *self.0)
+    }
+
+    /// List the assets stored before the assets by creator index existed
+    /// for their creators. Runs once, at genesis for new chains and at
+    /// startup for older ones.
+    ///
+    /// Returns the number of assets listed.
+    pub fn index_creators(&mut self) -> usize {
+        if Schema(&*self.0).creators_indexed().exists() {
+            return 0;
+        }
+
+        let assets = Schema(&*self.0)
+            .index()
+            .iter()
+            .map(|(id, info)| (id, *info.creator()))
+            .collect::<Vec<_>>();
+        for &(id, ref creator) in &assets {
+            self.created_mut(creator).insert(id);
+        }
+        self.creators_indexed_mut().set(());
+
+        assets.len()
+    }
+
+    /// Internal `MapIndex` of the reference prices, with mutable access.
+    pub fn reference_prices_mut(&mut self) -> MapIndex<&mut Fork, AssetId, u64> {
+        let key = SERVICE_NAME.to_string() + ".reference_prices";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Set the reference price of a unit of the asset, 0 removes it.
+    pub fn set_reference_price(&mut self, id: &AssetId, price: u64) {
+        match price {
+            0 => self.reference_prices_mut().remove(id),
+            _ => self.reference_prices_mut().put(id, price),
+        }
+    }
+
+    /// Store asset info in the database. The asset stays listed for its
+    /// creator even once none of it is left.
     pub fn store(&mut self, id: &AssetId, asset: AssetInfo) {
+        self.created_mut(asset.creator()).insert(*id);
         match asset.amount() {
             0 => self.remove(id),
             _ => self.index_mut().put(&*id, asset),
@@ -15,9 +15,10 @@ impl TradeAsset {
         TradeAsset::new(bundle.id(), bundle.amount(), price)
     }
 
-    /// Get total value of the `TradeAsset`.
-    pub fn total_price(&self) -> u64 {
-        self.amount() * self.price()
+    /// Get total value of the `TradeAsset`, `None` if it doesn't fit into
+    /// `u64`.
+    pub fn total_price(&self) -> Option<u64> {
+        self.amount().checked_mul(self.price())
     }
 
     pub fn to_bundle(&self) -> AssetBundle {
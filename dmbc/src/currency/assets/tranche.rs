@@ -0,0 +1,19 @@
+use exonum::crypto::PublicKey;
+
+use currency::assets::AssetId;
+
+encoding_struct! {
+    /// Labeled part of a holding, see `split_asset` transactions.
+    struct Tranche {
+        label:  &str,
+        amount: u64,
+    }
+}
+
+impl Tranche {
+    /// Id of the tranche of `asset`, created by `creator`. A label always
+    /// maps to the same id, so tranches stay fungible between wallets.
+    pub fn id(&self, asset: &AssetId, creator: &PublicKey) -> AssetId {
+        AssetId::from_data(&format!("{}/{}", asset.to_hex(), self.label()), creator)
+    }
+}
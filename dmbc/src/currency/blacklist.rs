@@ -0,0 +1,62 @@
+//! Blacklisted assets.
+
+use exonum::storage::{Fork, KeySetIndex, Snapshot};
+
+use currency::assets::AssetId;
+use currency::error::Error;
+use currency::SERVICE_NAME;
+
+/// Schema for accessing the asset blacklist.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `KeySetIndex` for this `Schema`.
+    pub fn index(self) -> KeySetIndex<S, AssetId> {
+        let key = SERVICE_NAME.to_string() + ".blacklist";
+        KeySetIndex::new(key, self.0)
+    }
+
+    /// Check whether the asset is blacklisted.
+    pub fn contains(self, id: &AssetId) -> bool {
+        self.index().contains(id)
+    }
+
+    /// Make sure none of the assets is blacklisted.
+    ///
+    /// # Errors
+    /// Returns `AssetBlacklisted` if any of the assets is blacklisted.
+    pub fn check<I>(self, ids: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = AssetId>,
+    {
+        let index = self.index();
+        if ids.into_iter().any(|id| index.contains(&id)) {
+            return Err(Error::AssetBlacklisted);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `KeySetIndex` for this `Schema`, with mutable access.
+    pub fn index_mut(&mut self) -> KeySetIndex<&mut Fork, AssetId> {
+        let key = SERVICE_NAME.to_string() + ".blacklist";
+        KeySetIndex::new(key, self.0)
+    }
+
+    /// Add the asset to the blacklist.
+    pub fn insert(&mut self, id: &AssetId) {
+        self.index_mut().insert(*id)
+    }
+
+    /// Remove the asset from the blacklist.
+    pub fn remove(&mut self, id: &AssetId) {
+        self.index_mut().remove(id)
+    }
+}
@@ -1,5 +1,7 @@
 //! Currency service configuration.
 
+use std::collections::BTreeMap;
+
 use serde_json;
 
 use exonum::blockchain::Schema;
@@ -8,6 +10,8 @@ use exonum::encoding::serialize::FromHex;
 use exonum::storage::Snapshot;
 
 use currency;
+use currency::assets::AssetId;
+use currency::wallet::Wallet;
 
 encoding_struct! {
     /// Fixed fees to be paid to the genesis wallet when transaction is executed.
@@ -58,14 +62,176 @@ impl Default for TransactionFees {
     }
 }
 
-encoding_struct! {
-    /// Currency service configuration.
-    #[derive(Eq, PartialOrd, Ord)]
-    struct Configuration {
-        fees: TransactionFees,
+/// Determines when the blockchain fee for `transfer` is taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferFeePolicy {
+    /// The fee is paid on top of the transferred amount. Sender must hold
+    /// `amount + fee`, recipient receives the full `amount`.
+    BeforeAmount,
+    /// The fee is deducted from the transferred amount. Sender must hold
+    /// `amount`, recipient receives `amount - fee`.
+    FromAmount,
+}
+
+impl Default for TransferFeePolicy {
+    fn default() -> Self {
+        TransferFeePolicy::BeforeAmount
+    }
+}
+
+/// Determines what is kept of a transaction which fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// The blockchain fee is collected before anything else and kept when
+    /// the transaction fails afterwards.
+    KeepFee,
+    /// A failed transaction leaves every wallet as it was, the blockchain
+    /// fee included.
+    FullRollback,
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        FailurePolicy::KeepFee
+    }
+}
+
+/// Discount on the blockchain fee for wallets holding an asset.
+///
+/// A payer holding at least `min_amount` units of `asset_id` pays its share
+/// of the blockchain fee less `discount_bps` basis points, rounded in favor
+/// of the fee recipient.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeDiscount {
+    pub asset_id: AssetId,
+    pub min_amount: u64,
+    pub discount_bps: u64,
+}
+
+impl FeeDiscount {
+    /// Whether `wallet` holds enough of the asset to get the discount.
+    pub fn applies_to(&self, wallet: &Wallet) -> bool {
+        wallet
+            .assets()
+            .iter()
+            .find(|a| a.id() == self.asset_id)
+            .map_or(false, |a| a.amount() >= self.min_amount)
+    }
+
+    /// `fee` less the discount. Discounts above 100% waive the fee.
+    pub fn apply(&self, fee: u64) -> u64 {
+        let bps = self.discount_bps.min(10_000);
+        let discount = fee / 10_000 * bps + fee % 10_000 * bps / 10_000;
+        fee - discount
+    }
+}
+
+/// Share of the blockchain fee paid to the validators.
+///
+/// The fee recipient keeps `platform_bps` basis points of every blockchain
+/// fee, rounded down, and the rest goes to `wallet`. Fees paid to asset
+/// creators aren't split.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorReward {
+    pub wallet: PublicKey,
+    pub platform_bps: u64,
+}
+
+impl ValidatorReward {
+    /// Shares of the fee recipient and of the validators in `fee`.
+    pub fn split(&self, fee: u64) -> (u64, u64) {
+        let bps = self.platform_bps.min(10_000);
+        let platform = fee / 10_000 * bps + fee % 10_000 * bps / 10_000;
+        (platform, fee - platform)
+    }
+}
+
+/// Currency in which the blockchain fee of a transaction type is paid.
+///
+/// Fees paid in an asset move the asset's units from the payers to the fee
+/// recipient. They aren't shared with the validators nor counted as platform
+/// revenue, which are kept in coins. Fees to asset creators and
+/// intermediaries, and the fee deducted from the amount of a `transfer`
+/// under `TransferFeePolicy::FromAmount`, are always paid in coins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeCurrency {
+    /// The base coin.
+    Coin,
+    /// Units of the asset.
+    Asset(AssetId),
+}
+
+impl Default for FeeCurrency {
+    fn default() -> Self {
+        FeeCurrency::Coin
+    }
+}
+
+impl FeeCurrency {
+    /// Coins in a fee of `amount` in this currency.
+    pub fn coins(&self, amount: u64) -> u64 {
+        match *self {
+            FeeCurrency::Coin => amount,
+            FeeCurrency::Asset(_) => 0,
+        }
     }
 }
 
+/// Currency service configuration.
+///
+/// Parameters other than `fees` are optional in the stored configuration
+/// and fall back to their defaults when absent.
+///
+/// The configuration is a part of the blockchain configuration, so validators
+/// change fees by voting for a new one. It is reloaded on every commit and
+/// applies to transactions from the next block on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Configuration {
+    fees: TransactionFees,
+    #[serde(default)]
+    transfer_fee_policy: TransferFeePolicy,
+    #[serde(default)]
+    fee_rate_bps: u64,
+    #[serde(default)]
+    delete_assets_per_entry: u64,
+    #[serde(default)]
+    reserve: u64,
+    #[serde(default)]
+    fee_discount: Option<FeeDiscount>,
+    #[serde(default)]
+    failure_policy: FailurePolicy,
+    #[serde(default)]
+    sponsor: Option<PublicKey>,
+    #[serde(default)]
+    intermediary_registry: bool,
+    #[serde(default)]
+    min_balance: u64,
+    #[serde(default)]
+    price_deviation_bps: Option<u64>,
+    #[serde(default)]
+    royalty_bps: u64,
+    #[serde(default)]
+    validator_reward: Option<ValidatorReward>,
+    #[serde(default)]
+    fee_currencies: BTreeMap<u16, FeeCurrency>,
+    #[serde(default)]
+    operation_fees: BTreeMap<u16, u64>,
+    #[serde(default)]
+    seed_nonces: bool,
+    #[serde(default = "default_max_assets")]
+    max_assets: u64,
+}
+
+/// Default of `Configuration::max_assets`.
+pub const DEFAULT_MAX_ASSETS: u64 = 100;
+
+fn default_max_assets() -> u64 {
+    DEFAULT_MAX_ASSETS
+}
+
 /// Hexadecimal representation of the public key for genesis wallet.
 pub const GENESIS_WALLET_PUB_KEY: &str =
     "36a05e418393fb4b23819753f6e6dd51550ce030d53842c43dd1349857a96a61";
@@ -77,6 +243,264 @@ impl Default for Configuration {
 }
 
 impl Configuration {
+    /// Create a new `Configuration` with default values for everything but fees.
+    pub fn new(fees: TransactionFees) -> Self {
+        Configuration {
+            fees,
+            transfer_fee_policy: TransferFeePolicy::default(),
+            fee_rate_bps: 0,
+            delete_assets_per_entry: 0,
+            reserve: 0,
+            fee_discount: None,
+            failure_policy: FailurePolicy::default(),
+            sponsor: None,
+            intermediary_registry: false,
+            min_balance: 0,
+            price_deviation_bps: None,
+            royalty_bps: 0,
+            validator_reward: None,
+            fee_currencies: BTreeMap::new(),
+            operation_fees: BTreeMap::new(),
+            seed_nonces: false,
+            max_assets: DEFAULT_MAX_ASSETS,
+        }
+    }
+
+    /// Fixed fees to be paid to the genesis wallet.
+    pub fn fees(&self) -> TransactionFees {
+        self.fees.clone()
+    }
+
+    /// Policy for taking the blockchain fee for `transfer`.
+    pub fn transfer_fee_policy(&self) -> TransferFeePolicy {
+        self.transfer_fee_policy
+    }
+
+    /// Set the policy for taking the blockchain fee for `transfer`.
+    pub fn with_transfer_fee_policy(self, transfer_fee_policy: TransferFeePolicy) -> Self {
+        Configuration {
+            transfer_fee_policy,
+            ..self
+        }
+    }
+
+    /// Rate in basis points of the price charged on top of the fixed
+    /// `trade` and `exchange` fees.
+    pub fn fee_rate_bps(&self) -> u64 {
+        self.fee_rate_bps
+    }
+
+    /// Set the rate in basis points charged for `trade` and `exchange`.
+    pub fn with_fee_rate_bps(self, fee_rate_bps: u64) -> Self {
+        Configuration {
+            fee_rate_bps,
+            ..self
+        }
+    }
+
+    /// Fee per deleted unit of an asset, on top of the fixed `delete_assets` fee.
+    pub fn delete_assets_per_entry(&self) -> u64 {
+        self.delete_assets_per_entry
+    }
+
+    /// Set the fee per deleted unit of an asset.
+    pub fn with_delete_assets_per_entry(self, delete_assets_per_entry: u64) -> Self {
+        Configuration {
+            delete_assets_per_entry,
+            ..self
+        }
+    }
+
+    /// Minimum balance a wallet has to keep, it can't be spent.
+    pub fn reserve(&self) -> u64 {
+        self.reserve
+    }
+
+    /// Set the minimum balance a wallet has to keep.
+    pub fn with_reserve(self, reserve: u64) -> Self {
+        Configuration { reserve, ..self }
+    }
+
+    /// Discount on the blockchain fee for holders of an asset, if any.
+    pub fn fee_discount(&self) -> Option<FeeDiscount> {
+        self.fee_discount.clone()
+    }
+
+    /// Set the discount on the blockchain fee for holders of an asset.
+    pub fn with_fee_discount(self, fee_discount: FeeDiscount) -> Self {
+        Configuration {
+            fee_discount: Some(fee_discount),
+            ..self
+        }
+    }
+
+    /// What is kept of a transaction which fails.
+    pub fn failure_policy(&self) -> FailurePolicy {
+        self.failure_policy
+    }
+
+    /// Set what is kept of a transaction which fails.
+    pub fn with_failure_policy(self, failure_policy: FailurePolicy) -> Self {
+        Configuration {
+            failure_policy,
+            ..self
+        }
+    }
+
+    /// Wallet paying the blockchain fee of `SponsoredTransfer`, if any.
+    pub fn sponsor(&self) -> Option<PublicKey> {
+        self.sponsor
+    }
+
+    /// Set the wallet paying the blockchain fee of `SponsoredTransfer`.
+    pub fn with_sponsor(self, sponsor: &PublicKey) -> Self {
+        Configuration {
+            sponsor: Some(*sponsor),
+            ..self
+        }
+    }
+
+    /// Whether only registered intermediaries may take part in
+    /// `_intermediary` transactions, see `intermediaries::Schema`.
+    pub fn intermediary_registry(&self) -> bool {
+        self.intermediary_registry
+    }
+
+    /// Enable or disable the registry of intermediaries.
+    pub fn with_intermediary_registry(self, intermediary_registry: bool) -> Self {
+        Configuration {
+            intermediary_registry,
+            ..self
+        }
+    }
+
+    /// Whether seeds are nonces: every transaction must have a seed above
+    /// the last one of its sender, see `seeds::Schema`.
+    pub fn seed_nonces(&self) -> bool {
+        self.seed_nonces
+    }
+
+    /// Require the seeds of every sender to increase.
+    pub fn with_seed_nonces(self, seed_nonces: bool) -> Self {
+        Configuration {
+            seed_nonces,
+            ..self
+        }
+    }
+
+    /// Maximum number of assets a single transaction may list. Transactions
+    /// listing more fail with `TooManyAssets`.
+    pub fn max_assets(&self) -> u64 {
+        self.max_assets
+    }
+
+    /// Set the maximum number of assets a single transaction may list.
+    pub fn with_max_assets(self, max_assets: u64) -> Self {
+        Configuration {
+            max_assets,
+            ..self
+        }
+    }
+
+    /// Smallest non-zero balance `transfer` may leave in the sending
+    /// wallet. Emptying the wallet is always allowed.
+    pub fn min_balance(&self) -> u64 {
+        self.min_balance
+    }
+
+    /// Set the smallest non-zero balance `transfer` may leave.
+    pub fn with_min_balance(self, min_balance: u64) -> Self {
+        Configuration {
+            min_balance,
+            ..self
+        }
+    }
+
+    /// Deviation in basis points from the reference price of an asset above
+    /// which a trade is flagged, see `price_warnings::Schema`. `None` if
+    /// trades aren't checked.
+    pub fn price_deviation_bps(&self) -> Option<u64> {
+        self.price_deviation_bps
+    }
+
+    /// Set the deviation from the reference price above which a trade is
+    /// flagged.
+    pub fn with_price_deviation_bps(self, price_deviation_bps: u64) -> Self {
+        Configuration {
+            price_deviation_bps: Some(price_deviation_bps),
+            ..self
+        }
+    }
+
+    /// Royalty in basis points of the price paid to the creator of an
+    /// asset on every trade in which someone else sells it.
+    pub fn royalty_bps(&self) -> u64 {
+        self.royalty_bps
+    }
+
+    /// Set the royalty paid to creators on secondary trades.
+    pub fn with_royalty_bps(self, royalty_bps: u64) -> Self {
+        Configuration {
+            royalty_bps,
+            ..self
+        }
+    }
+
+    /// Share of the blockchain fee paid to the validators, if any. The fee
+    /// recipient gets all of it otherwise.
+    pub fn validator_reward(&self) -> Option<ValidatorReward> {
+        self.validator_reward.clone()
+    }
+
+    /// Set the share of the blockchain fee paid to the validators.
+    pub fn with_validator_reward(self, validator_reward: ValidatorReward) -> Self {
+        Configuration {
+            validator_reward: Some(validator_reward),
+            ..self
+        }
+    }
+
+    /// Currency of the blockchain fee of transactions with `message_id`,
+    /// coins unless configured otherwise.
+    pub fn fee_currency(&self, message_id: u16) -> FeeCurrency {
+        self.fee_currencies
+            .get(&message_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Set the currency of the blockchain fee of transactions with
+    /// `message_id`.
+    pub fn with_fee_currency(mut self, message_id: u16, fee_currency: FeeCurrency) -> Self {
+        self.fee_currencies.insert(message_id, fee_currency);
+        self
+    }
+
+    /// Fixed blockchain fee of transactions with `message_id` which have no
+    /// fee in `TransactionFees`, such as `split_asset` or `set_multisig`.
+    /// Free unless configured otherwise.
+    pub fn operation_fee(&self, message_id: u16) -> u64 {
+        self.operation_fees
+            .get(&message_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Set the fixed blockchain fee of transactions with `message_id`.
+    pub fn with_operation_fee(mut self, message_id: u16, fee: u64) -> Self {
+        self.operation_fees.insert(message_id, fee);
+        self
+    }
+
+    /// Share `fee` of the blockchain fee as paid by `payer`, discounted if
+    /// the payer qualifies for the `fee_discount`.
+    pub fn payer_fee(&self, fee: u64, payer: &Wallet) -> u64 {
+        match self.fee_discount {
+            Some(ref discount) if discount.applies_to(payer) => discount.apply(fee),
+            _ => fee,
+        }
+    }
+
     /// Extract the `Configuration`.
     ///
     /// # Panics
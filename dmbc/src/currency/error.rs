@@ -29,6 +29,55 @@ pub enum Error {
     /// Unable to verify transaction
     UnableToVerifyTransaction = 7,
 
+    /// Asset is blacklisted and can not be moved.
+    AssetBlacklisted = 8,
+
+    /// Arithmetic overflow while computing amounts or fees.
+    Overflow = 9,
+
+    /// Fee strategy is unknown or not allowed for the transaction.
+    InvalidFeeStrategy = 10,
+
+    /// Condition attached to the transaction does not hold.
+    ConditionNotMet = 11,
+
+    /// Wallet required by the transaction does not exist.
+    WalletNotFound = 12,
+
+    /// Escrow is not found, it was never created or is already settled.
+    EscrowNotFound = 13,
+
+    /// Asset is frozen by its creator and can not be moved.
+    AssetFrozen = 14,
+
+    /// Wallet requires signatures of more of its multisig keys than the
+    /// transaction carries.
+    InsufficientSignatures = 15,
+
+    /// Intermediary of the transaction is not in the registry.
+    IntermediaryNotRegistered = 16,
+
+    /// Offers matched against each other don't give what the other side
+    /// wants.
+    OffersMismatch = 17,
+
+    /// Transaction would leave a wallet with a non-zero balance below the
+    /// configured minimum.
+    DustBalance = 18,
+
+    /// Offer is executed after the height it is valid until.
+    OfferExpired = 19,
+
+    /// Seed is not above the last seed of the sender, see
+    /// `Configuration::seed_nonces`.
+    StaleSeed = 20,
+
+    /// Trade buys more units than are left in the offer.
+    OfferOverfilled = 21,
+
+    /// Transaction lists more assets than `Configuration::max_assets`.
+    TooManyAssets = 22,
+
     /// Requested operation is not implemented. Must not happen in production
     /// setting.
     NotImplemented = 255,
@@ -45,6 +94,21 @@ impl Error {
             5 => Some(Error::InsufficientAssets),
             6 => Some(Error::InvalidTransaction),
             7 => Some(Error::UnableToVerifyTransaction),
+            8 => Some(Error::AssetBlacklisted),
+            9 => Some(Error::Overflow),
+            10 => Some(Error::InvalidFeeStrategy),
+            11 => Some(Error::ConditionNotMet),
+            12 => Some(Error::WalletNotFound),
+            13 => Some(Error::EscrowNotFound),
+            14 => Some(Error::AssetFrozen),
+            15 => Some(Error::InsufficientSignatures),
+            16 => Some(Error::IntermediaryNotRegistered),
+            17 => Some(Error::OffersMismatch),
+            18 => Some(Error::DustBalance),
+            19 => Some(Error::OfferExpired),
+            20 => Some(Error::StaleSeed),
+            21 => Some(Error::OfferOverfilled),
+            22 => Some(Error::TooManyAssets),
             255 => Some(Error::NotImplemented),
             _ => None,
         }
@@ -62,6 +126,21 @@ impl error::Error for Error {
             &Error::NotImplemented => "not implemented",
             &Error::InvalidTransaction => "invalid transaction",
             &Error::UnableToVerifyTransaction => "unable to verify transaction",
+            &Error::AssetBlacklisted => "asset is blacklisted",
+            &Error::Overflow => "arithmetic overflow",
+            &Error::InvalidFeeStrategy => "invalid fee strategy",
+            &Error::ConditionNotMet => "condition not met",
+            &Error::WalletNotFound => "wallet not found",
+            &Error::EscrowNotFound => "escrow not found",
+            &Error::AssetFrozen => "asset is frozen",
+            &Error::InsufficientSignatures => "insufficient signatures",
+            &Error::IntermediaryNotRegistered => "intermediary is not registered",
+            &Error::OffersMismatch => "offers do not match",
+            &Error::DustBalance => "balance left below the minimum",
+            &Error::OfferExpired => "offer expired",
+            &Error::StaleSeed => "seed not above the last seed of the sender",
+            &Error::OfferOverfilled => "offer has fewer units left",
+            &Error::TooManyAssets => "too many assets listed",
         }
     }
 }
@@ -0,0 +1,93 @@
+//! Funds held until released to the recipient or refunded to the sender.
+
+use exonum::crypto::{Hash, PublicKey};
+use exonum::storage::{Fork, MapIndex, Snapshot};
+
+use currency::assets::AssetBundle;
+use currency::error::Error;
+use currency::wallet::Wallet;
+use currency::SERVICE_NAME;
+
+encoding_struct! {
+    /// Coins and assets locked by an `EscrowCreate` transaction.
+    ///
+    /// The locked funds are no longer part of the sender's wallet, so they
+    /// can't be spent until the escrow is refunded.
+    struct Escrow {
+        from:   &PublicKey,
+        to:     &PublicKey,
+        amount: u64,
+        assets: Vec<AssetBundle>,
+    }
+}
+
+impl Escrow {
+    /// Pay the locked funds into `wallet`.
+    ///
+    /// # Errors
+    /// Returns `Overflow` if the wallet balance or the amount of one of its
+    /// assets would exceed `u64::MAX`.
+    pub fn credit(&self, wallet: Wallet) -> Result<Wallet, Error> {
+        let balance = wallet
+            .balance()
+            .checked_add(self.amount())
+            .ok_or(Error::Overflow)?;
+
+        let mut assets = wallet.assets();
+        for locked in self.assets() {
+            match assets.iter().position(|a| a.id() == locked.id()) {
+                Some(index) => {
+                    let amount = assets[index]
+                        .amount()
+                        .checked_add(locked.amount())
+                        .ok_or(Error::Overflow)?;
+                    assets[index] = AssetBundle::new(locked.id(), amount);
+                }
+                None => assets.push(locked),
+            }
+        }
+
+        Ok(Wallet::new(balance, assets))
+    }
+}
+
+/// Schema for accessing open escrows by the hash of the transaction which
+/// created them.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `MapIndex` for this `Schema`.
+    pub fn index(self) -> MapIndex<S, Hash, Escrow> {
+        let key = SERVICE_NAME.to_string() + ".escrows";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Fetch the open escrow created by the transaction with `tx_hash`.
+    pub fn fetch(self, tx_hash: &Hash) -> Option<Escrow> {
+        self.index().get(tx_hash)
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `MapIndex` for this `Schema`, with mutable access.
+    pub fn index_mut(&mut self) -> MapIndex<&mut Fork, Hash, Escrow> {
+        let key = SERVICE_NAME.to_string() + ".escrows";
+        MapIndex::new(key, &mut *self.0)
+    }
+
+    /// Store a new escrow.
+    pub fn store(&mut self, tx_hash: &Hash, escrow: Escrow) {
+        self.index_mut().put(tx_hash, escrow)
+    }
+
+    /// Close the escrow once its funds are released or refunded.
+    pub fn remove(&mut self, tx_hash: &Hash) {
+        self.index_mut().remove(tx_hash)
+    }
+}
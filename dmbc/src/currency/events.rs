@@ -0,0 +1,136 @@
+//! Applied transactions, pushed to subscribers of the events API.
+//!
+//! Events are published from `handle_commit`, once per committed block, and
+//! kept in memory for the last `MAX_BLOCKS` blocks. Nothing is persisted, a
+//! subscriber that falls further behind is told about the gap and has to
+//! query statuses instead.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use exonum::blockchain;
+use exonum::crypto::{Hash, PublicKey};
+use exonum::storage::Snapshot;
+
+use currency::error::Error;
+use currency::history;
+
+/// Number of committed blocks kept for subscribers.
+pub const MAX_BLOCKS: usize = 64;
+
+/// Most subscribers waiting for a block at once. Each of them holds an API
+/// thread, the ones above the limit are answered right away.
+pub const MAX_WAITING: usize = 4;
+
+/// A transaction applied in a committed block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub height: u64,
+    pub tx_hash: Hash,
+    pub tx_type: u16,
+    pub status: Result<(), Error>,
+}
+
+/// Events of a committed block.
+#[derive(Clone, Debug)]
+struct BlockEvents {
+    height: u64,
+    events: Vec<Event>,
+}
+
+/// Events of the most recent blocks, shared between the service and its API.
+#[derive(Clone, Default)]
+pub struct Events {
+    inner: Arc<(Mutex<VecDeque<BlockEvents>>, Condvar)>,
+    waiting: Arc<AtomicUsize>,
+}
+
+impl Events {
+    /// Create an empty event log.
+    pub fn new() -> Self {
+        Events::default()
+    }
+
+    /// Record the events of the block at `height` and wake up the waiting
+    /// subscribers.
+    pub fn publish(&self, height: u64, events: Vec<Event>) {
+        let &(ref blocks, ref published) = &*self.inner;
+        let mut blocks = blocks.lock().unwrap();
+        blocks.push_back(BlockEvents { height, events });
+        while blocks.len() > MAX_BLOCKS {
+            blocks.pop_front();
+        }
+        published.notify_all();
+    }
+
+    /// Events of the blocks above `height`, waiting up to `timeout` for a
+    /// block to be committed if there are none yet. No more than
+    /// `MAX_WAITING` callers wait at once, the others don't wait at all.
+    ///
+    /// Returns the height of the last known block, which is where the next
+    /// call should continue from, and whether some of the blocks above
+    /// `height` were already dropped and their events are missing.
+    pub fn after(&self, height: u64, timeout: Duration) -> (u64, bool, Vec<Event>) {
+        let waits = self.waiting.fetch_add(1, Ordering::SeqCst) < MAX_WAITING;
+        let timeout = if waits { timeout } else { Duration::from_millis(0) };
+        let result = self.collect(height, timeout);
+        self.waiting.fetch_sub(1, Ordering::SeqCst);
+        result
+    }
+
+    fn collect(&self, height: u64, timeout: Duration) -> (u64, bool, Vec<Event>) {
+        let &(ref blocks, ref published) = &*self.inner;
+        let deadline = Instant::now() + timeout;
+        let mut blocks = blocks.lock().unwrap();
+        loop {
+            let last = blocks.back().map_or(height, |block| block.height);
+            let now = Instant::now();
+            if last > height || now >= deadline {
+                let gap = blocks
+                    .front()
+                    .map_or(false, |block| block.height > height.saturating_add(1));
+                let events = blocks
+                    .iter()
+                    .filter(|block| block.height > height)
+                    .flat_map(|block| block.events.iter().cloned())
+                    .collect();
+                return (last.max(height), gap, events);
+            }
+            blocks = published.wait_timeout(blocks, deadline - now).unwrap().0;
+        }
+    }
+}
+
+/// Keep the `events` of the blocks above `height` that involve the wallet
+/// with `pub_key`.
+///
+/// Walks the wallet history back from its latest entry, so only the entries
+/// above `height` are looked at.
+pub fn involving(
+    view: &Snapshot,
+    pub_key: &PublicKey,
+    height: u64,
+    events: Vec<Event>,
+) -> Vec<Event> {
+    let locations = blockchain::Schema::new(view).tx_location_by_tx_hash();
+    let history = history::Schema(view).index(pub_key);
+
+    let mut recent = Vec::new();
+    for i in (0..history.len()).rev() {
+        let tx_hash = match history.get(i) {
+            Some(tx_hash) => tx_hash,
+            None => break,
+        };
+        match locations.get(&tx_hash) {
+            Some(location) if location.block_height().0 > height => recent.push(tx_hash),
+            _ => break,
+        }
+    }
+
+    events
+        .into_iter()
+        .filter(|event| recent.contains(&event.tx_hash))
+        .collect()
+}
@@ -0,0 +1,135 @@
+//! Wallets funded at block zero.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use exonum::crypto::{Hash, PublicKey};
+use exonum::encoding::serialize::FromHex;
+use exonum::storage::Fork;
+use serde_json;
+
+use currency::assets;
+use currency::assets::{AssetId, Fees, MetaAsset};
+use currency::configuration::GENESIS_WALLET_PUB_KEY;
+use currency::wallet;
+use currency::wallet::Wallet;
+
+/// Asset issued to a wallet at genesis, with the wallet as its creator.
+#[derive(Deserialize, Clone, Debug)]
+pub struct GenesisAsset {
+    pub data: String,
+    pub amount: u64,
+    pub fees: Fees,
+}
+
+/// Initial balance and assets of a single wallet.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Allocation {
+    pub pub_key: PublicKey,
+    pub balance: u64,
+    #[serde(default)]
+    pub assets: Vec<GenesisAsset>,
+}
+
+/// Wallets funded when the service is initialized.
+///
+/// The allocation file is a JSON list of `Allocation` entries:
+///
+/// ```json
+/// [
+///     { "pub_key": "...", "balance": 1000 },
+///     { "pub_key": "...", "balance": 0, "assets": [
+///         { "data": "...", "amount": 10, "fees": { ... } }
+///     ] }
+/// ]
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GenesisAllocation(Vec<Allocation>);
+
+impl GenesisAllocation {
+    /// Create an allocation from a list of entries.
+    ///
+    /// # Errors
+    /// Fails if a wallet is listed twice, the genesis wallet is listed, a
+    /// wallet gets the same asset twice or asset data is too long.
+    pub fn new(allocations: Vec<Allocation>) -> Result<Self, String> {
+        let genesis_wallet = PublicKey::from_hex(GENESIS_WALLET_PUB_KEY).unwrap();
+        let mut keys = HashSet::new();
+
+        for allocation in &allocations {
+            let pub_key = allocation.pub_key;
+            if pub_key == genesis_wallet {
+                return Err(format!("{} is the genesis wallet", pub_key.to_hex()));
+            }
+            if !keys.insert(pub_key) {
+                return Err(format!("duplicate wallet {}", pub_key.to_hex()));
+            }
+
+            let mut ids = HashSet::new();
+            for asset in allocation.meta_assets() {
+                if !asset.verify() {
+                    return Err(format!("asset data of {} is too long", pub_key.to_hex()));
+                }
+                if !ids.insert(AssetId::from_data(asset.data(), &pub_key)) {
+                    return Err(format!(
+                        "duplicate asset {:?} of {}",
+                        asset.data(),
+                        pub_key.to_hex()
+                    ));
+                }
+            }
+        }
+
+        Ok(GenesisAllocation(allocations))
+    }
+
+    /// Parse an allocation from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let allocations: Vec<Allocation> =
+            serde_json::from_str(json).map_err(|e| e.to_string())?;
+        GenesisAllocation::new(allocations)
+    }
+
+    /// Read an allocation from a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let mut json = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut json))
+            .map_err(|e| e.to_string())?;
+        GenesisAllocation::from_json(&json)
+    }
+
+    /// Entries of this allocation.
+    pub fn allocations(&self) -> &[Allocation] {
+        &self.0
+    }
+
+    /// Store the allocated wallets and the infos of their assets.
+    pub fn apply(&self, fork: &mut Fork) {
+        for allocation in &self.0 {
+            let pub_key = &allocation.pub_key;
+            let mut wallet = Wallet::new(allocation.balance, Vec::new());
+
+            for meta in allocation.meta_assets() {
+                let id = AssetId::from_data(meta.data(), pub_key);
+                wallet.add_assets(Some(meta.to_bundle(id)));
+                assets::Schema(&mut *fork).store(&id, meta.to_info(pub_key, &Hash::zero()));
+            }
+
+            wallet::Schema(&mut *fork).store(pub_key, wallet);
+        }
+    }
+}
+
+impl Allocation {
+    fn meta_assets(&self) -> Vec<MetaAsset> {
+        self.assets
+            .iter()
+            .map(|asset| {
+                MetaAsset::new(&self.pub_key, &asset.data, asset.amount, asset.fees.clone())
+            })
+            .collect()
+    }
+}
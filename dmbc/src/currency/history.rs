@@ -0,0 +1,52 @@
+//! Transaction history of wallets.
+
+use exonum::crypto::{Hash, PublicKey};
+use exonum::storage::{Fork, ListIndex, Snapshot};
+
+use currency::SERVICE_NAME;
+
+/// Schema for accessing transactions by the wallets they touch.
+///
+/// Every wallet has its own list of transaction hashes in execution order,
+/// regardless of the transaction status.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `ListIndex` of transactions touching the wallet with `pub_key`.
+    pub fn index(self, pub_key: &PublicKey) -> ListIndex<S, Hash> {
+        let key = SERVICE_NAME.to_string() + ".history";
+        ListIndex::with_prefix(key, pub_key.as_ref().to_vec(), self.0)
+    }
+
+    /// Fetch hashes of the transactions touching the wallet with `pub_key`.
+    pub fn fetch(self, pub_key: &PublicKey) -> Vec<Hash> {
+        self.index(pub_key).iter().collect()
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `ListIndex` of transactions touching the wallet with
+    /// `pub_key`, with mutable access.
+    pub fn index_mut(&mut self, pub_key: &PublicKey) -> ListIndex<&mut Fork, Hash> {
+        let key = SERVICE_NAME.to_string() + ".history";
+        ListIndex::with_prefix(key, pub_key.as_ref().to_vec(), &mut *self.0)
+    }
+
+    /// Record a transaction in the history of every wallet in `pub_keys`.
+    ///
+    /// A wallet listed more than once gets the transaction recorded once.
+    pub fn append(&mut self, pub_keys: &[PublicKey], tx_hash: &Hash) {
+        for (i, pub_key) in pub_keys.iter().enumerate() {
+            if pub_keys[..i].contains(pub_key) {
+                continue;
+            }
+            self.index_mut(pub_key).push(*tx_hash);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Intermediaries approved by the platform.
+
+use exonum::crypto::PublicKey;
+use exonum::storage::{Fork, KeySetIndex, Snapshot};
+
+use currency::error::Error;
+use currency::SERVICE_NAME;
+
+/// Schema for accessing the registry of intermediaries.
+///
+/// The registry is only enforced with `Configuration::intermediary_registry`
+/// set, otherwise any wallet may act as an intermediary.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `KeySetIndex` for this `Schema`.
+    pub fn index(self) -> KeySetIndex<S, PublicKey> {
+        let key = SERVICE_NAME.to_string() + ".intermediaries";
+        KeySetIndex::new(key, self.0)
+    }
+
+    /// Check whether the wallet is a registered intermediary.
+    pub fn contains(self, pub_key: &PublicKey) -> bool {
+        self.index().contains(pub_key)
+    }
+
+    /// Make sure the wallet is a registered intermediary.
+    ///
+    /// # Errors
+    /// Returns `IntermediaryNotRegistered` otherwise.
+    pub fn check(self, pub_key: &PublicKey) -> Result<(), Error> {
+        if !self.contains(pub_key) {
+            return Err(Error::IntermediaryNotRegistered);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `KeySetIndex` for this `Schema`, with mutable access.
+    pub fn index_mut(&mut self) -> KeySetIndex<&mut Fork, PublicKey> {
+        let key = SERVICE_NAME.to_string() + ".intermediaries";
+        KeySetIndex::new(key, self.0)
+    }
+
+    /// Register the intermediary.
+    pub fn insert(&mut self, pub_key: &PublicKey) {
+        self.index_mut().insert(*pub_key)
+    }
+
+    /// Remove the intermediary from the registry.
+    pub fn remove(&mut self, pub_key: &PublicKey) {
+        self.index_mut().remove(pub_key)
+    }
+}
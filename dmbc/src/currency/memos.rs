@@ -0,0 +1,46 @@
+//! Memos attached to intermediary trades and exchanges.
+
+use exonum::crypto::Hash;
+use exonum::storage::{Fork, MapIndex, Snapshot};
+
+use currency::SERVICE_NAME;
+
+/// Schema for accessing the `data_info` memos of executed transactions.
+///
+/// Memos are signed as part of the offer and are only recorded for
+/// off-chain systems, they don't affect processing.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `MapIndex` of memos by transaction hash.
+    pub fn index(self) -> MapIndex<S, Hash, String> {
+        let key = SERVICE_NAME.to_string() + ".memos";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Memo of the transaction, `None` if it has none.
+    pub fn fetch(self, tx_id: &Hash) -> Option<String> {
+        self.index().get(tx_id)
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `MapIndex` of memos by transaction hash, with mutable access.
+    pub fn index_mut(&mut self) -> MapIndex<&mut Fork, Hash, String> {
+        let key = SERVICE_NAME.to_string() + ".memos";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Record the memo of the transaction. Empty memos aren't stored.
+    pub fn store(&mut self, tx_id: &Hash, memo: &str) {
+        if !memo.is_empty() {
+            self.index_mut().put(tx_id, memo.to_string());
+        }
+    }
+}
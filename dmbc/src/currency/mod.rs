@@ -2,9 +2,25 @@
 
 pub mod api;
 pub mod assets;
+pub mod blacklist;
 pub mod configuration;
 pub mod error;
+pub mod escrow;
+pub mod events;
+pub mod frozen;
+pub mod genesis;
+pub mod history;
+pub mod intermediaries;
+pub mod memos;
+pub mod multisig;
+pub mod non_fungible;
+pub mod offer_fills;
+pub mod pending;
+pub mod price_warnings;
+pub mod revenue;
+pub mod seeds;
 pub mod status;
+pub mod tags;
 pub mod transactions;
 pub mod wallet;
 
@@ -0,0 +1,114 @@
+//! Wallets which can only be spent from with M-of-N signatures.
+
+use exonum::crypto::{PublicKey, Signature};
+use exonum::storage::{Fork, MapIndex, Snapshot};
+
+use currency::error::Error;
+use currency::SERVICE_NAME;
+
+encoding_struct! {
+    /// Key authorized to sign for a multisig wallet.
+    struct MultiSigKey {
+        pub_key: &PublicKey,
+    }
+}
+
+encoding_struct! {
+    /// Detached signature of one of the keys of a multisig wallet.
+    struct MultiSigSignature {
+        pub_key:   &PublicKey,
+        signature: &Signature,
+    }
+}
+
+encoding_struct! {
+    /// Keys authorized to spend from a wallet and how many of them must sign.
+    struct MultiSig {
+        keys:      Vec<MultiSigKey>,
+        threshold: u8,
+    }
+}
+
+impl MultiSig {
+    /// Check that `threshold` is between one and the number of distinct keys.
+    pub fn is_valid(&self) -> bool {
+        let keys = self.keys();
+        let distinct = keys
+            .iter()
+            .enumerate()
+            .all(|(i, key)| keys[..i].iter().all(|k| k.pub_key() != key.pub_key()));
+        distinct && self.threshold() > 0 && self.threshold() as usize <= keys.len()
+    }
+
+    /// Check whether `pub_key` is one of the authorized keys.
+    pub fn contains(&self, pub_key: &PublicKey) -> bool {
+        self.keys().iter().any(|key| key.pub_key() == pub_key)
+    }
+
+    /// Make sure `signatures` come from at least `threshold` distinct
+    /// authorized keys. The signatures themselves must already be verified.
+    ///
+    /// # Errors
+    /// Returns `InsufficientSignatures` otherwise.
+    pub fn check(&self, signatures: &[MultiSigSignature]) -> Result<(), Error> {
+        let signed = self.keys()
+            .iter()
+            .filter(|key| signatures.iter().any(|s| s.pub_key() == key.pub_key()))
+            .count();
+        if signed < self.threshold() as usize {
+            return Err(Error::InsufficientSignatures);
+        }
+        Ok(())
+    }
+}
+
+/// Schema for accessing the multisig configuration of wallets.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `MapIndex` for this `Schema`.
+    pub fn index(self) -> MapIndex<S, PublicKey, MultiSig> {
+        let key = SERVICE_NAME.to_string() + ".multisig";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Fetch the multisig configuration of the wallet, if it has one.
+    pub fn fetch(self, pub_key: &PublicKey) -> Option<MultiSig> {
+        self.index().get(pub_key)
+    }
+
+    /// Make sure none of the wallets is a multisig wallet, for transactions
+    /// spending from wallets with their own signature only.
+    ///
+    /// # Errors
+    /// Returns `InsufficientSignatures` if any of the wallets is one.
+    pub fn check<'k, I>(self, pub_keys: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = &'k PublicKey>,
+    {
+        let index = self.index();
+        if pub_keys.into_iter().any(|pub_key| index.contains(pub_key)) {
+            return Err(Error::InsufficientSignatures);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `MapIndex` for this `Schema`, with mutable access.
+    pub fn index_mut(&mut self) -> MapIndex<&mut Fork, PublicKey, MultiSig> {
+        let key = SERVICE_NAME.to_string() + ".multisig";
+        MapIndex::new(key, &mut *self.0)
+    }
+
+    /// Store the multisig configuration of the wallet.
+    pub fn store(&mut self, pub_key: &PublicKey, multisig: MultiSig) {
+        self.index_mut().put(pub_key, multisig)
+    }
+}
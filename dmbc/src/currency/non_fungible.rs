@@ -0,0 +1,64 @@
+//! Non-fungible assets.
+
+use exonum::storage::{Fork, KeySetIndex, Snapshot};
+
+use currency::assets::{AssetBundle, AssetId};
+use currency::error::Error;
+use currency::SERVICE_NAME;
+
+/// Schema for accessing the set of non-fungible assets.
+///
+/// A non-fungible asset is issued exactly once with an amount of 1 and can
+/// never be issued again, so its `AssetInfo` stays unique.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `KeySetIndex` for this `Schema`.
+    pub fn index(self) -> KeySetIndex<S, AssetId> {
+        let key = SERVICE_NAME.to_string() + ".non_fungible";
+        KeySetIndex::new(key, self.0)
+    }
+
+    /// Check whether the asset is non-fungible.
+    pub fn contains(self, id: &AssetId) -> bool {
+        self.index().contains(id)
+    }
+
+    /// Make sure non-fungible assets are only moved whole.
+    ///
+    /// # Errors
+    /// Returns `InvalidTransaction` if a non-fungible asset is given with an
+    /// amount other than 1.
+    pub fn check<'b, I>(self, bundles: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = &'b AssetBundle>,
+    {
+        let index = self.index();
+        if bundles
+            .into_iter()
+            .any(|bundle| bundle.amount() != 1 && index.contains(&bundle.id()))
+        {
+            return Err(Error::InvalidTransaction);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `KeySetIndex` for this `Schema`, with mutable access.
+    pub fn index_mut(&mut self) -> KeySetIndex<&mut Fork, AssetId> {
+        let key = SERVICE_NAME.to_string() + ".non_fungible";
+        KeySetIndex::new(key, self.0)
+    }
+
+    /// Mark the asset as non-fungible.
+    pub fn insert(&mut self, id: &AssetId) {
+        self.index_mut().insert(*id)
+    }
+}
@@ -0,0 +1,141 @@
+//! Filled amounts of partially fillable trade offers.
+//!
+//! Only offers of a single asset with a `valid_until_height` can be filled
+//! partially, so every record here is dropped once its offer expires.
+
+use exonum::blockchain;
+use exonum::crypto::Hash;
+use exonum::storage::{Fork, KeySetIndex, MapIndex, Snapshot, StorageKey};
+
+use currency::error::Error;
+use currency::SERVICE_NAME;
+
+/// Most expired offers forgotten by a single `prune`.
+pub const PRUNE_LIMIT: usize = 16;
+
+/// Schema for accessing the units already sold out of single asset trade
+/// offers, by hash of the offer.
+///
+/// Such an offer may be filled by several `trade` transactions, each
+/// buying a `quantity` of it, until all its units are sold. Units sold are
+/// kept while the offer is partially filled. Sold out offers only keep
+/// their entry in the `expiries` index, which also marks them as filled
+/// until they expire and can't be traded anymore anyway.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+/// Key of an offer in the `expiries` index, ordered by expiry height.
+fn expiry_key(offer: &Hash, valid_until_height: u64) -> Vec<u8> {
+    let mut key = vec![0; 8];
+    valid_until_height.write(&mut key);
+    key.extend_from_slice(offer.as_ref());
+    key
+}
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `MapIndex` for this `Schema`.
+    pub fn index(self) -> MapIndex<S, Hash, u64> {
+        let key = SERVICE_NAME.to_string() + ".offer_fills";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Internal `KeySetIndex` of the offers filled at least once, ordered
+    /// by their `valid_until_height`.
+    pub fn expiries(self) -> KeySetIndex<S, Vec<u8>> {
+        let key = SERVICE_NAME.to_string() + ".offer_fills.expiries";
+        KeySetIndex::new(key, self.0)
+    }
+
+    /// Units sold so far out of an offer of `amount` units, `0` for
+    /// a fresh offer.
+    pub fn fetch(self, offer: &Hash, valid_until_height: u64, amount: u64) -> u64 {
+        let key = expiry_key(offer, valid_until_height);
+        if !Schema(self.0.as_ref()).expiries().contains(&key) {
+            return 0;
+        }
+        self.index().get(offer).unwrap_or(amount)
+    }
+
+    /// Units of an offer of `amount` units still available.
+    pub fn remaining(self, offer: &Hash, valid_until_height: u64, amount: u64) -> u64 {
+        amount.saturating_sub(self.fetch(offer, valid_until_height, amount))
+    }
+
+    /// Make sure `quantity` more units can be sold out of an offer of
+    /// `amount` units.
+    ///
+    /// # Errors
+    /// Returns `OfferOverfilled` otherwise.
+    pub fn check(
+        self,
+        offer: &Hash,
+        valid_until_height: u64,
+        amount: u64,
+        quantity: u64,
+    ) -> Result<(), Error> {
+        if quantity > self.remaining(offer, valid_until_height, amount) {
+            return Err(Error::OfferOverfilled);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `MapIndex` for this `Schema`, with mutable access.
+    pub fn index_mut(&mut self) -> MapIndex<&mut Fork, Hash, u64> {
+        let key = SERVICE_NAME.to_string() + ".offer_fills";
+        MapIndex::new(key, &mut *self.0)
+    }
+
+    /// Internal `KeySetIndex` of the offers filled at least once, with
+    /// mutable access.
+    pub fn expiries_mut(&mut self) -> KeySetIndex<&mut Fork, Vec<u8>> {
+        let key = SERVICE_NAME.to_string() + ".offer_fills.expiries";
+        KeySetIndex::new(key, &mut *self.0)
+    }
+
+    /// Record `quantity` more units sold out of an offer of `amount` units.
+    /// The units sold are dropped once the offer is sold out.
+    ///
+    /// # Errors
+    /// Returns `OfferOverfilled` if there aren't that many left, and
+    /// nothing is recorded.
+    pub fn fill(
+        &mut self,
+        offer: &Hash,
+        valid_until_height: u64,
+        amount: u64,
+        quantity: u64,
+    ) -> Result<(), Error> {
+        Schema(&*self.0).check(offer, valid_until_height, amount, quantity)?;
+        let filled = Schema(&*self.0).fetch(offer, valid_until_height, amount) + quantity;
+        if filled < amount {
+            self.index_mut().put(offer, filled);
+        } else {
+            self.index_mut().remove(offer);
+        }
+        self.expiries_mut().insert(expiry_key(offer, valid_until_height));
+        Ok(())
+    }
+
+    /// Forget up to `PRUNE_LIMIT` offers expired in the block being built.
+    pub fn prune(&mut self) {
+        let height = blockchain::Schema::new(&*self.0).block_hashes_by_height().len();
+        let expired = Schema(&*self.0)
+            .expiries()
+            .iter()
+            .take(PRUNE_LIMIT)
+            .take_while(|key| u64::read(&key[..8]) < height)
+            .collect::<Vec<_>>();
+        for key in expired {
+            let offer = Hash::from_slice(&key[8..]).unwrap();
+            self.index_mut().remove(&offer);
+            self.expiries_mut().remove(&key);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Transactions submitted through the API and not committed yet.
+//!
+//! Submissions are remembered from the API until `handle_commit` sees them
+//! in a committed block. Nothing is persisted, and a transaction the node
+//! never commits is forgotten after `MAX_BLOCKS` blocks.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use exonum::crypto::Hash;
+
+/// Number of committed blocks a submission is remembered for.
+pub const MAX_BLOCKS: u64 = 64;
+
+/// Pending submissions by hash, with the height they were submitted at,
+/// shared between the service and its API.
+#[derive(Clone, Default)]
+pub struct Pending {
+    inner: Arc<RwLock<HashMap<Hash, u64>>>,
+}
+
+impl Pending {
+    /// Create an empty set of submissions.
+    pub fn new() -> Self {
+        Pending::default()
+    }
+
+    /// Whether the transaction was submitted and isn't committed yet.
+    pub fn contains(&self, tx_hash: &Hash) -> bool {
+        self.inner.read().unwrap().contains_key(tx_hash)
+    }
+
+    /// Remember a transaction submitted at `height`.
+    ///
+    /// Returns `false` if it is already pending, and nothing changes.
+    pub fn insert(&self, tx_hash: Hash, height: u64) -> bool {
+        let mut pending = self.inner.write().unwrap();
+        if pending.contains_key(&tx_hash) {
+            return false;
+        }
+        pending.insert(tx_hash, height);
+        true
+    }
+
+    /// Forget a transaction, when it couldn't be passed to the node.
+    pub fn remove(&self, tx_hash: &Hash) {
+        self.inner.write().unwrap().remove(tx_hash);
+    }
+
+    /// Forget the transactions committed in the block at `height`, and the
+    /// ones submitted more than `MAX_BLOCKS` blocks before it.
+    pub fn commit<I>(&self, height: u64, committed: I)
+    where
+        I: IntoIterator<Item = Hash>,
+    {
+        let mut pending = self.inner.write().unwrap();
+        for tx_hash in committed {
+            pending.remove(&tx_hash);
+        }
+        pending.retain(|_, submitted| height.saturating_sub(*submitted) <= MAX_BLOCKS);
+    }
+}
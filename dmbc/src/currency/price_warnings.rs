@@ -0,0 +1,46 @@
+//! Soft warnings about trades priced off their reference prices.
+
+use exonum::crypto::Hash;
+use exonum::storage::{Fork, ListIndex, Snapshot};
+
+use currency::assets::PriceDeviation;
+use currency::SERVICE_NAME;
+
+/// Schema for accessing the price deviations of executed trades.
+///
+/// Deviations don't fail a trade, they are only recorded for review. See
+/// `Configuration::price_deviation_bps`.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `ListIndex` of the deviations of the transaction `tx_id`.
+    pub fn index(self, tx_id: &Hash) -> ListIndex<S, PriceDeviation> {
+        let key = SERVICE_NAME.to_string() + ".price_warnings";
+        ListIndex::with_prefix(key, tx_id.as_ref().to_vec(), self.0)
+    }
+
+    /// Price deviations recorded for the transaction, empty if none.
+    pub fn fetch(self, tx_id: &Hash) -> Vec<PriceDeviation> {
+        self.index(tx_id).iter().collect()
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `ListIndex` of the deviations of the transaction `tx_id`,
+    /// with mutable access.
+    pub fn index_mut(&mut self, tx_id: &Hash) -> ListIndex<&mut Fork, PriceDeviation> {
+        let key = SERVICE_NAME.to_string() + ".price_warnings";
+        ListIndex::with_prefix(key, tx_id.as_ref().to_vec(), self.0)
+    }
+
+    /// Record the price deviations of the transaction.
+    pub fn store(&mut self, tx_id: &Hash, deviations: Vec<PriceDeviation>) {
+        self.index_mut(tx_id).extend(deviations)
+    }
+}
@@ -0,0 +1,54 @@
+//! Blockchain fees collected by the platform.
+
+use exonum::storage::{Entry, Fork, Snapshot};
+
+use currency::SERVICE_NAME;
+
+/// Schema for accessing the platform revenue, the total of the blockchain
+/// fees moved to the fee recipient wallet.
+///
+/// The total is updated together with storing the credited wallet, and
+/// restored with it when a transaction is rolled back, so it always matches
+/// the fees actually collected.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `Entry` of the total.
+    pub fn index(self) -> Entry<S, u64> {
+        let key = SERVICE_NAME.to_string() + ".revenue";
+        Entry::new(key, self.0)
+    }
+
+    /// Total of the blockchain fees collected so far.
+    pub fn total(self) -> u64 {
+        self.index().get().unwrap_or(0)
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `Entry` of the total, with mutable access.
+    pub fn index_mut(&mut self) -> Entry<&mut Fork, u64> {
+        let key = SERVICE_NAME.to_string() + ".revenue";
+        Entry::new(key, self.0)
+    }
+
+    /// Count `fee` as collected.
+    pub fn add(&mut self, fee: u64) {
+        if fee == 0 {
+            return;
+        }
+        let total = Schema(&*self.0).total();
+        self.index_mut().set(total.saturating_add(fee));
+    }
+
+    /// Reset the total to `total`, as it was before a rolled back transaction.
+    pub fn restore(&mut self, total: u64) {
+        self.index_mut().set(total);
+    }
+}
@@ -0,0 +1,62 @@
+//! Last seeds of the senders, when seeds are used as nonces.
+
+use exonum::crypto::PublicKey;
+use exonum::storage::{Fork, MapIndex, Snapshot};
+
+use currency::error::Error;
+use currency::SERVICE_NAME;
+
+/// Schema for accessing the last seed of every sender.
+///
+/// Only maintained while `Configuration::seed_nonces` is enabled. A seed
+/// is used up once its transaction is executed, whether it succeeds or not.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `MapIndex` for this `Schema`.
+    pub fn index(self) -> MapIndex<S, PublicKey, u64> {
+        let key = SERVICE_NAME.to_string() + ".seeds";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Last seed of the sender, if it sent any.
+    pub fn fetch(self, sender: &PublicKey) -> Option<u64> {
+        self.index().get(sender)
+    }
+
+    /// Make sure `seed` is above the last seed of the sender.
+    ///
+    /// # Errors
+    /// Returns `StaleSeed` otherwise.
+    pub fn check(self, sender: &PublicKey, seed: u64) -> Result<(), Error> {
+        match self.fetch(sender) {
+            Some(last) if seed <= last => Err(Error::StaleSeed),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `MapIndex` for this `Schema`, with mutable access.
+    pub fn index_mut(&mut self) -> MapIndex<&mut Fork, PublicKey, u64> {
+        let key = SERVICE_NAME.to_string() + ".seeds";
+        MapIndex::new(key, &mut *self.0)
+    }
+
+    /// Record `seed` as the last seed of the sender, if it is above the
+    /// previous one.
+    ///
+    /// # Errors
+    /// Returns `StaleSeed` otherwise, and nothing is recorded.
+    pub fn advance(&mut self, sender: &PublicKey, seed: u64) -> Result<(), Error> {
+        Schema(&*self.0).check(sender, seed)?;
+        self.index_mut().put(sender, seed);
+        Ok(())
+    }
+}
@@ -15,13 +15,26 @@ use std::sync::RwLock;
 
 use super::nats;
 use config;
-use currency::api::ServiceApi;
+use currency::api::admin::AdminApi;
+use currency::api::{ApiLimits, ServiceApi};
+use currency::assets;
 use currency::configuration;
 use currency::configuration::Configuration;
+use currency::events::{Event, Events};
+use currency::pending::Pending;
+use currency::genesis::GenesisAllocation;
 use currency::status;
-use currency::transactions::{AddAssets, DeleteAssets, Exchange, ExchangeIntermediary, Trade,
-                             TradeIntermediary, Transfer, ADD_ASSETS_ID, DELETE_ASSETS_ID,
-                             EXCHANGE_ID, EXCHANGE_INTERMEDIARY_ID, TRADE_ID,
+use currency::transactions::{AddAssets, DeleteAssets, EscrowCreate, EscrowRefund,
+                             EscrowRelease, Exchange, ExchangeIntermediary, FreezeAsset,
+                             MatchOffers, MergeAsset, MultiSigTransfer, SetAssetBlacklist,
+                             SetIntermediary, SetMultiSig, SetReferencePrice, SplitAsset,
+                             SponsoredTransfer, Trade, TradeIntermediary, Transfer,
+                             ADD_ASSETS_ID, DELETE_ASSETS_ID, ESCROW_CREATE_ID,
+                             ESCROW_REFUND_ID, ESCROW_RELEASE_ID, EXCHANGE_ID,
+                             EXCHANGE_INTERMEDIARY_ID, FREEZE_ASSET_ID, MATCH_OFFERS_ID,
+                             MERGE_ASSET_ID, MULTISIG_TRANSFER_ID, SET_ASSET_BLACKLIST_ID,
+                             SET_INTERMEDIARY_ID, SET_MULTISIG_ID, SET_REFERENCE_PRICE_ID,
+                             SPLIT_ASSET_ID, SPONSORED_TRANSFER_ID, TRADE_ID,
                              TRADE_INTERMEDIARY_ID, TRANSFER_ID};
 use currency::wallet;
 use currency::wallet::Wallet;
@@ -34,12 +47,33 @@ pub const SERVICE_ID: u16 = 2;
 pub const SERVICE_NAME: &str = "cryptocurrency";
 
 /// Service data.
-pub struct Service();
+pub struct Service {
+    allocation: GenesisAllocation,
+    events: Events,
+    pending: Pending,
+    api_limits: ApiLimits,
+}
 
 impl Service {
     /// Create a new cryptocurrency service.
     pub fn new() -> Self {
-        Service()
+        Service::with_allocation(GenesisAllocation::default())
+    }
+
+    /// Create a new cryptocurrency service which funds the wallets in
+    /// `allocation` at genesis.
+    pub fn with_allocation(allocation: GenesisAllocation) -> Self {
+        Service {
+            allocation,
+            events: Events::new(),
+            pending: Pending::new(),
+            api_limits: ApiLimits::from_config(),
+        }
+    }
+
+    /// Replace the public API limits taken from the node config.
+    pub fn with_api_limits(self, api_limits: ApiLimits) -> Self {
+        Service { api_limits, ..self }
     }
 
     /// Genesis wallet public key.
@@ -47,6 +81,16 @@ impl Service {
         let config = Configuration::extract(view.as_ref());
         *config.fees().recipient()
     }
+
+    /// Bring the service tables of an existing chain up to date with the
+    /// indexes added since it was created. They aren't part of the state
+    /// hash, so every node does this on its own at startup.
+    pub fn migrate(fork: &mut Fork) {
+        let listed = assets::Schema(&mut *fork).index_creators();
+        if listed > 0 {
+            info!("Listed {} assets for their creators.", listed);
+        }
+    }
 }
 
 lazy_static! {
@@ -80,6 +124,19 @@ impl blockchain::Service for Service {
             TRADE_ID => Box::new(Trade::from_raw(raw)?),
             TRADE_INTERMEDIARY_ID => Box::new(TradeIntermediary::from_raw(raw)?),
             TRANSFER_ID => Box::new(Transfer::from_raw(raw)?),
+            SET_ASSET_BLACKLIST_ID => Box::new(SetAssetBlacklist::from_raw(raw)?),
+            FREEZE_ASSET_ID => Box::new(FreezeAsset::from_raw(raw)?),
+            SET_INTERMEDIARY_ID => Box::new(SetIntermediary::from_raw(raw)?),
+            ESCROW_CREATE_ID => Box::new(EscrowCreate::from_raw(raw)?),
+            ESCROW_RELEASE_ID => Box::new(EscrowRelease::from_raw(raw)?),
+            ESCROW_REFUND_ID => Box::new(EscrowRefund::from_raw(raw)?),
+            SPONSORED_TRANSFER_ID => Box::new(SponsoredTransfer::from_raw(raw)?),
+            SET_MULTISIG_ID => Box::new(SetMultiSig::from_raw(raw)?),
+            MULTISIG_TRANSFER_ID => Box::new(MultiSigTransfer::from_raw(raw)?),
+            MATCH_OFFERS_ID => Box::new(MatchOffers::from_raw(raw)?),
+            SET_REFERENCE_PRICE_ID => Box::new(SetReferencePrice::from_raw(raw)?),
+            SPLIT_ASSET_ID => Box::new(SplitAsset::from_raw(raw)?),
+            MERGE_ASSET_ID => Box::new(MergeAsset::from_raw(raw)?),
             _ => {
                 return Err(encoding::Error::IncorrectMessageType {
                     message_type: raw.message_type(),
@@ -94,6 +151,18 @@ impl blockchain::Service for Service {
         let api = ServiceApi {
             channel: ctx.node_channel().clone(),
             blockchain: ctx.blockchain().clone(),
+            events: self.events.clone(),
+            pending: self.pending.clone(),
+            limits: self.api_limits,
+        };
+        api.wire(&mut router);
+        Some(Box::new(router))
+    }
+
+    fn private_api_handler(&self, ctx: &ApiContext) -> Option<Box<Handler>> {
+        let mut router = Router::new();
+        let api = AdminApi {
+            blockchain: ctx.blockchain().clone(),
         };
         api.wire(&mut router);
         Some(Box::new(router))
@@ -109,19 +178,35 @@ impl blockchain::Service for Service {
         *CONFIGURATION.write().unwrap() = Configuration::extract(ctx.snapshot());
 
         let txs = schema.block_txs(last_block.height());
+        let mut events = Vec::new();
         for hash in txs.iter() {
             let status = status::Schema(ctx.snapshot()).fetch(&hash);
             let msg = json!({ "tx_hash": hash, "status": status }).to_string();
             let queuename = config::config().nats().queuename();
             nats::publish(queuename, msg);
-            info!("Made transaction {:?}", hash.to_hex());
+            info!("Made transaction {}", hash.to_hex());
+
+            if let (Some(raw), Some(status)) = (schema.transactions().get(&hash), status) {
+                events.push(Event {
+                    height: last_block.height().0,
+                    tx_hash: hash,
+                    tx_type: raw.message_type(),
+                    status,
+                });
+            }
         }
+        self.events.publish(last_block.height().0, events);
+        self.pending.commit(last_block.height().0, txs.iter());
     }
 
     fn initialize(&self, fork: &mut Fork) -> serde_json::Value {
         let genesis_wallet = PublicKey::from_hex(configuration::GENESIS_WALLET_PUB_KEY).unwrap();
         let wallet = Wallet::new(56_921_773_17197150, Vec::new());
-        wallet::Schema(fork).store(&genesis_wallet, wallet);
+        wallet::Schema(&mut *fork).store(&genesis_wallet, wallet);
+
+        self.allocation.apply(fork);
+        wallet::Schema(&mut *fork).record_snapshots();
+        Service::migrate(fork);
 
         serde_json::to_value(Configuration::default()).unwrap()
     }
@@ -12,6 +12,8 @@ pub struct Schema<S>(pub S)
 where
     S: AsRef<Snapshot>;
 
+/// On-disk status encoding: `0` for a successful transaction, otherwise
+/// the `u8` code of the `Error` it failed with.
 pub type ResultRepr = u8;
 
 fn to_repr(result: Result<(), Error>) -> ResultRepr {
@@ -41,6 +43,14 @@ where
     pub fn fetch(self, tx_id: &Hash) -> Option<Result<(), Error>> {
         self.index().get(tx_id).map(|repr| from_repr(repr))
     }
+
+    /// Check whether the transaction was already executed.
+    ///
+    /// Every execution stores a status in the same fork as its effects, so
+    /// the statuses double as the set of processed transaction hashes.
+    pub fn contains(self, tx_id: &Hash) -> bool {
+        self.index().contains(tx_id)
+    }
 }
 
 impl<'a> Schema<&'a mut Fork> {
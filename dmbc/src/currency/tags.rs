@@ -0,0 +1,49 @@
+//! Transaction tags for reporting.
+
+use exonum::crypto;
+use exonum::crypto::Hash;
+use exonum::storage::{Fork, ListIndex, Snapshot};
+
+use currency::SERVICE_NAME;
+
+/// Schema for accessing transactions by their tag.
+///
+/// Every tag has its own list of transaction hashes in execution order,
+/// regardless of the transaction status.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+fn prefix(tag: &str) -> Vec<u8> {
+    crypto::hash(tag.as_bytes()).as_ref().to_vec()
+}
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `ListIndex` of transactions with the `tag`.
+    pub fn index(self, tag: &str) -> ListIndex<S, Hash> {
+        let key = SERVICE_NAME.to_string() + ".tags";
+        ListIndex::with_prefix(key, prefix(tag), self.0)
+    }
+
+    /// Fetch hashes of the transactions with the `tag`.
+    pub fn fetch(self, tag: &str) -> Vec<Hash> {
+        self.index(tag).iter().collect()
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `ListIndex` of transactions with the `tag`, with mutable access.
+    pub fn index_mut(&mut self, tag: &str) -> ListIndex<&mut Fork, Hash> {
+        let key = SERVICE_NAME.to_string() + ".tags";
+        ListIndex::with_prefix(key, prefix(tag), &mut *self.0)
+    }
+
+    /// Record a transaction with the `tag`.
+    pub fn append(&mut self, tag: &str, tx_hash: &Hash) {
+        self.index_mut(tag).push(*tx_hash)
+    }
+}
@@ -10,35 +10,51 @@ use prometheus::{IntCounter, Histogram};
 use currency::assets;
 use currency::assets::{AssetId, AssetInfo, MetaAsset};
 use currency::error::Error;
+use currency::history;
+use currency::multisig;
+use currency::non_fungible;
 use currency::status;
-use currency::transactions::components::{FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{advance_seed, process_with_policy,
+                                         settle_blockchain_fee, Apply, FeesCalculator,
+                                         ThirdPartyFees};
 use currency::wallet;
 use currency::SERVICE_ID;
 use currency::service::CONFIGURATION;
+use keys;
 
 /// Transaction ID.
-pub const ADD_ASSETS_ID: u16 = 300;
+pub const ADD_ASSETS_ID: u16 = 301;
 
 message!{
     /// `add_assets` transaction.
+    ///
+    /// Replaces the retired layout with ID 300, see the wire format notes of
+    /// `transactions`.
+    ///
+    /// With `non_fungible` set every asset is issued once with an amount
+    /// of 1 and can not be issued again.
     struct AddAssets {
         const TYPE = SERVICE_ID;
         const ID = ADD_ASSETS_ID;
 
-        pub_key:     &PublicKey,
-        meta_assets: Vec<MetaAsset>,
-        seed:        u64,
+        pub_key:      &PublicKey,
+        meta_assets:  Vec<MetaAsset>,
+        non_fungible: bool,
+        seed:         u64,
     }
 }
 
 impl FeesCalculator for AddAssets {
     fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
         let fees = ThirdPartyFees::new_add_assets(&view, self.meta_assets())?;
 
         let mut fees_table = HashMap::new();
         if genesis_fees.recipient() != self.pub_key() {
-            fees_table.insert(*self.pub_key(), genesis_fees.add_assets());
+            let creator = wallet::Schema(&*view).fetch(self.pub_key());
+            let fee = configuration.payer_fee(genesis_fees.add_assets(), &creator);
+            fees_table.insert(*self.pub_key(), fee);
         }
 
         for (pub_key, fee) in fees.0 {
@@ -52,22 +68,31 @@ impl FeesCalculator for AddAssets {
 
 impl AddAssets {
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
-        let tx_fee = genesis_fees.add_assets();
+        advance_seed(view, self.pub_key(), self.seed())?;
+
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+
+        multisig::Schema(&*view).check(&[*self.pub_key()])?;
 
         let genesis_pub = genesis_fees.recipient();
         let creator_pub = self.pub_key();
 
+        let fee_currency = configuration.fee_currency(ADD_ASSETS_ID);
         let mut genesis = wallet::Schema(&*view).fetch(&genesis_pub);
+        let genesis_balance = genesis.balance();
         let mut creator = wallet::Schema(&*view).fetch(&creator_pub);
 
-        wallet::move_coins(&mut creator, &mut genesis, tx_fee)?;
+        let tx_fee = configuration.payer_fee(genesis_fees.add_assets(), &creator);
+
+        wallet::move_fee(&mut creator, &mut genesis, tx_fee, fee_currency)?;
 
         let fees = ThirdPartyFees::new_add_assets(&view, self.meta_assets())?;
 
+        let collected = genesis.balance() - genesis_balance;
         wallet::Schema(&mut *view).store(&genesis_pub, genesis);
         wallet::Schema(&mut *view).store(&creator_pub, creator);
+        settle_blockchain_fee(view, collected)?;
 
         let mut wallets = fees.collect(view, &creator_pub)?;
         let mut infos: HashMap<AssetId, AssetInfo> = HashMap::new();
@@ -77,6 +102,14 @@ impl AddAssets {
         for meta in self.meta_assets() {
             let id = AssetId::from_data(meta.data(), key);
 
+            // Non-fungible assets are never issued twice.
+            if non_fungible::Schema(&*view).contains(&id)
+                || (self.non_fungible()
+                    && (infos.contains_key(&id) || assets::Schema(&*view).fetch(&id).is_some()))
+            {
+                return Err(Error::InvalidAssetInfo);
+            }
+
             let wallet = wallets
                 .entry(*meta.receiver())
                 .or_insert_with(|| wallet::Schema(&*view).fetch(meta.receiver()));
@@ -102,8 +135,15 @@ impl AddAssets {
         for (key, wallet) in wallets {
             wallet::Schema(&mut *view).store(&key, wallet);
         }
+        // The per asset fee goes to the fee recipient too.
+        if creator_pub != genesis_pub {
+            settle_blockchain_fee(view, fees.0.get(genesis_pub).cloned().unwrap_or(0))?;
+        }
 
         for (id, info) in infos {
+            if self.non_fungible() {
+                non_fungible::Schema(&mut *view).insert(&id);
+            }
             assets::Schema(&mut *view).store(&id, info);
         }
 
@@ -111,6 +151,38 @@ impl AddAssets {
     }
 }
 
+impl Apply for AddAssets {
+    fn check(&self) -> bool {
+        for asset in self.meta_assets() {
+            if !asset.verify() {
+                return false;
+            }
+            if self.non_fungible() && asset.amount() != 1 {
+                return false;
+            }
+        }
+
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.verify_signature(&self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.pub_key()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+
+        let mut pub_keys = vec![*self.pub_key()];
+        pub_keys.extend(self.meta_assets().iter().map(|meta| *meta.receiver()));
+        history::Schema(view).append(&pub_keys, &self.hash());
+
+        result
+    }
+}
+
 lazy_static! {
     static ref VERIFY_COUNT: IntCounter = register_int_counter!(
         "dmbc_transaction_add_assets_verify_count",
@@ -142,36 +214,32 @@ impl Transaction for AddAssets {
     fn verify(&self) -> bool {
         VERIFY_COUNT.inc();
 
-        for asset in self.meta_assets() {
-            if !asset.verify() {
-                return false;
-            }
-        }
-
-        if cfg!(fuzzing) {
-            return true;
-        }
-
-        if !self.verify_signature(&self.pub_key()) {
-            return false;
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
         }
-
-        VERIFY_SUCCESS_COUNT.inc();
-
-        true
     }
 
     fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
         EXECUTE_COUNT.inc();
         let timer = EXECUTE_DURATION.start_timer();
 
-        let result = self.process(view);
+        debug!("Processing add_assets {} by {}", self.hash().to_hex(), keys::log(self.pub_key()));
+
+        let result = self.apply(view);
 
         if let &Ok(_) = &result {
             EXECUTE_SUCCESS_COUNT.inc();
         }
 
-        status::Schema(view).store(self.hash(), result);
+        info!("Applied add_assets {}: {:?}", self.hash().to_hex(), result);
 
         timer.observe_duration();
         EXECUTE_FINISH_COUNT.inc();
@@ -1,21 +1,62 @@
 #![allow(missing_docs)]
 
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use exonum::crypto;
-use exonum::crypto::{PublicKey, SecretKey};
+use exonum::crypto::{Hash, PublicKey, SecretKey};
 use exonum::storage::StorageValue;
 
 use currency;
-use currency::assets::{AssetBundle, AssetId, Fees, MetaAsset, TradeAsset};
+use currency::assets::{AssetBundle, AssetId, Fees, MetaAsset, TradeAsset, Tranche};
+use currency::multisig::{MultiSigKey, MultiSigSignature};
 use currency::transactions::add_assets::AddAssets;
-use currency::transactions::components::{FeeStrategy, Intermediary};
+use currency::transactions::components::{offer_signing_bytes, FeeStrategy, Intermediary};
 use currency::transactions::delete_assets::DeleteAssets;
-use currency::transactions::exchange::{Exchange, ExchangeOffer};
+use currency::transactions::escrow_create::EscrowCreate;
+use currency::transactions::escrow_refund::EscrowRefund;
+use currency::transactions::escrow_release::EscrowRelease;
+use currency::transactions::exchange::{Exchange, ExchangeOffer, EXCHANGE_ID};
 use currency::transactions::exchange_intermediary::{ExchangeIntermediary,
-                                                    ExchangeOfferIntermediary};
-use currency::transactions::trade::{Trade, TradeOffer};
-use currency::transactions::trade_intermediary::{TradeIntermediary, TradeOfferIntermediary};
+                                                    ExchangeOfferIntermediary,
+                                                    EXCHANGE_INTERMEDIARY_ID};
+use currency::transactions::freeze_asset::FreezeAsset;
+use currency::transactions::match_offers::{MatchOffer, MatchOffers, MATCH_OFFERS_ID};
+use currency::transactions::merge_asset::MergeAsset;
+use currency::transactions::multisig_transfer::{MultiSigTransfer, MultiSigTransferOffer,
+                                                MULTISIG_TRANSFER_ID};
+use currency::transactions::set_asset_blacklist::SetAssetBlacklist;
+use currency::transactions::set_intermediary::SetIntermediary;
+use currency::transactions::set_multisig::SetMultiSig;
+use currency::transactions::set_reference_price::SetReferencePrice;
+use currency::transactions::split_asset::SplitAsset;
+use currency::transactions::sponsored_transfer::SponsoredTransfer;
+use currency::transactions::trade::{Trade, TradeOffer, TRADE_ID};
+use currency::transactions::trade_intermediary::{TradeIntermediary, TradeOfferIntermediary,
+                                                 TRADE_INTERMEDIARY_ID};
 use currency::transactions::transfer::Transfer;
 
+lazy_static! {
+    static ref LAST_SEED: Mutex<u64> = Mutex::new(0);
+}
+
+/// Generate a seed for a new transaction.
+///
+/// Transactions are identified by hash, so two transactions signed by the same
+/// key with equal contents and seed are the same transaction and only the first
+/// one is ever executed. Seeds produced here are taken from the wall clock and
+/// strictly increase within the process, which keeps otherwise identical
+/// transactions from the same sender distinct.
+pub fn generate_seed() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() * 1_000_000_000 + u64::from(d.subsec_nanos()))
+        .unwrap_or(0);
+    let mut last = LAST_SEED.lock().unwrap();
+    *last = if now > *last { now } else { *last + 1 };
+    *last
+}
+
 pub struct Builder {
     public_key: Option<PublicKey>,
     secret_key: Option<SecretKey>,
@@ -122,6 +163,71 @@ impl Builder {
         TransferBuilder::new(self.into())
     }
 
+    pub fn tx_set_asset_blacklist(self) -> SetAssetBlacklistBuilder {
+        self.validate();
+        SetAssetBlacklistBuilder::new(self.into())
+    }
+
+    pub fn tx_set_intermediary(self) -> SetIntermediaryBuilder {
+        self.validate();
+        SetIntermediaryBuilder::new(self.into())
+    }
+
+    pub fn tx_freeze_asset(self) -> FreezeAssetBuilder {
+        self.validate();
+        FreezeAssetBuilder::new(self.into())
+    }
+
+    pub fn tx_set_reference_price(self) -> SetReferencePriceBuilder {
+        self.validate();
+        SetReferencePriceBuilder::new(self.into())
+    }
+
+    pub fn tx_split_asset(self) -> SplitAssetBuilder {
+        self.validate();
+        SplitAssetBuilder::new(self.into())
+    }
+
+    pub fn tx_merge_asset(self) -> MergeAssetBuilder {
+        self.validate();
+        MergeAssetBuilder::new(self.into())
+    }
+
+    pub fn tx_escrow_create(self) -> EscrowCreateBuilder {
+        self.validate();
+        EscrowCreateBuilder::new(self.into())
+    }
+
+    pub fn tx_sponsored_transfer(self) -> SponsoredTransferBuilder {
+        self.validate();
+        SponsoredTransferBuilder::new(self.into())
+    }
+
+    pub fn tx_set_multisig(self) -> SetMultiSigBuilder {
+        self.validate();
+        SetMultiSigBuilder::new(self.into())
+    }
+
+    pub fn tx_multisig_transfer(self) -> MultiSigTransferBuilder {
+        self.validate();
+        MultiSigTransferBuilder::new(self.into())
+    }
+
+    pub fn tx_match_offers(self) -> MatchOffersBuilder {
+        self.validate();
+        MatchOffersBuilder::new(self.into())
+    }
+
+    pub fn tx_escrow_release(self) -> EscrowReleaseBuilder {
+        self.validate();
+        EscrowReleaseBuilder::new(self.into())
+    }
+
+    pub fn tx_escrow_refund(self) -> EscrowRefundBuilder {
+        self.validate();
+        EscrowRefundBuilder::new(self.into())
+    }
+
     fn validate(&self) {
         match (&self.public_key, &self.secret_key) {
             (&Some(_), &Some(_)) => (),
@@ -133,6 +239,7 @@ impl Builder {
 pub struct AddAssetBuilder {
     meta: TransactionMetadata,
     assets: Vec<MetaAsset>,
+    non_fungible: bool,
     seed: u64,
 }
 
@@ -141,6 +248,7 @@ impl AddAssetBuilder {
         AddAssetBuilder {
             meta,
             assets: Vec::new(),
+            non_fungible: false,
             seed: 0,
         }
     }
@@ -166,6 +274,13 @@ impl AddAssetBuilder {
         self.add_asset_value(asset)
     }
 
+    pub fn non_fungible(self, non_fungible: bool) -> Self {
+        AddAssetBuilder {
+            non_fungible,
+            ..self
+        }
+    }
+
     pub fn seed(self, seed: u64) -> Self {
         AddAssetBuilder { seed, ..self }
     }
@@ -175,6 +290,7 @@ impl AddAssetBuilder {
         AddAssets::new(
             &self.meta.public_key,
             self.assets,
+            self.non_fungible,
             self.seed,
             &self.meta.secret_key,
         )
@@ -241,6 +357,7 @@ pub struct ExchangeBuilder {
     seed: u64,
 
     data_info: Option<String>,
+    valid_until_height: u64,
 }
 
 impl ExchangeBuilder {
@@ -261,6 +378,7 @@ impl ExchangeBuilder {
             seed: 0,
 
             data_info: None,
+            valid_until_height: 0,
         }
     }
     pub fn sender(self, pub_key: PublicKey) -> Self {
@@ -322,6 +440,13 @@ impl ExchangeBuilder {
         }
     }
 
+    pub fn valid_until_height(self, valid_until_height: u64) -> Self {
+        ExchangeBuilder {
+            valid_until_height,
+            ..self
+        }
+    }
+
     pub fn build(self) -> Exchange {
         self.verify();
         let offer = ExchangeOffer::new(
@@ -333,9 +458,10 @@ impl ExchangeBuilder {
             self.fee_strategy as u8,
             self.seed,
             &self.data_info.unwrap_or_default(),
+            self.valid_until_height,
         );
-        let sender_signature =
-            crypto::sign(&offer.clone().into_bytes(), &self.sender_secret.unwrap());
+        let signing_bytes = offer_signing_bytes(EXCHANGE_ID, &offer.clone().into_bytes());
+        let sender_signature = crypto::sign(&signing_bytes, &self.sender_secret.unwrap());
         Exchange::new(
             offer,
             &sender_signature,
@@ -368,6 +494,7 @@ pub struct ExchangeIntermediaryBuilder {
     seed: u64,
 
     data_info: Option<String>,
+    valid_until_height: u64,
 }
 
 impl ExchangeIntermediaryBuilder {
@@ -391,6 +518,7 @@ impl ExchangeIntermediaryBuilder {
             seed: 0,
 
             data_info: None,
+            valid_until_height: 0,
         }
     }
 
@@ -423,6 +551,17 @@ impl ExchangeIntermediaryBuilder {
         ExchangeIntermediaryBuilder { commission, ..self }
     }
 
+    /// Go without an intermediary: use the null intermediary, which signs
+    /// nothing and takes no commission.
+    pub fn without_intermediary(self) -> Self {
+        ExchangeIntermediaryBuilder {
+            intermediary_public_key: Some(Intermediary::null_wallet()),
+            intermediary_secret_key: None,
+            commission: 0,
+            ..self
+        }
+    }
+
     pub fn sender_key_pair(self, public_key: PublicKey, secret_key: SecretKey) -> Self {
         ExchangeIntermediaryBuilder {
             sender_public_key: Some(public_key),
@@ -459,6 +598,13 @@ impl ExchangeIntermediaryBuilder {
         }
     }
 
+    pub fn valid_until_height(self, valid_until_height: u64) -> Self {
+        ExchangeIntermediaryBuilder {
+            valid_until_height,
+            ..self
+        }
+    }
+
     pub fn build(self) -> ExchangeIntermediary {
         self.verify();
 
@@ -475,15 +621,15 @@ impl ExchangeIntermediaryBuilder {
             self.fee_strategy as u8,
             self.seed,
             &self.data_info.unwrap_or_default(),
+            self.valid_until_height,
         );
-        let sender_signature = crypto::sign(
-            &offer.clone().into_bytes(),
-            &self.sender_secret_key.unwrap(),
-        );
-        let intermediary_signature = crypto::sign(
-            &offer.clone().into_bytes(),
-            &self.intermediary_secret_key.unwrap(),
-        );
+        let signing_bytes =
+            offer_signing_bytes(EXCHANGE_INTERMEDIARY_ID, &offer.clone().into_bytes());
+        let sender_signature = crypto::sign(&signing_bytes, &self.sender_secret_key.unwrap());
+        let intermediary_signature = match self.intermediary_secret_key {
+            Some(ref secret_key) => crypto::sign(&signing_bytes, secret_key),
+            None => Intermediary::null_signature(),
+        };
         ExchangeIntermediary::new(
             offer,
             &sender_signature,
@@ -496,7 +642,11 @@ impl ExchangeIntermediaryBuilder {
         assert!(self.sender_public_key.is_some());
         assert!(self.sender_secret_key.is_some());
         assert!(self.intermediary_public_key.is_some());
-        assert!(self.intermediary_secret_key.is_some());
+        assert!(
+            self.intermediary_secret_key.is_some()
+                || Intermediary::new(&self.intermediary_public_key.unwrap(), self.commission)
+                    .is_none()
+        );
     }
 }
 
@@ -509,7 +659,9 @@ pub struct TradeBuilder {
     fee_strategy: FeeStrategy,
     seed: u64,
     data_info: Option<String>,
-
+    valid_until_height: u64,
+    quantity: u64,
+    trade_seed: Option<u64>,
 }
 
 impl TradeBuilder {
@@ -523,6 +675,9 @@ impl TradeBuilder {
             fee_strategy: FeeStrategy::Recipient,
             seed: 0,
             data_info: None,
+            valid_until_height: 0,
+            quantity: 0,
+            trade_seed: None,
         }
     }
 
@@ -562,6 +717,24 @@ impl TradeBuilder {
         }
     }
 
+    pub fn valid_until_height(self, valid_until_height: u64) -> Self {
+        TradeBuilder {
+            valid_until_height,
+            ..self
+        }
+    }
+
+    pub fn quantity(self, quantity: u64) -> Self {
+        TradeBuilder { quantity, ..self }
+    }
+
+    pub fn trade_seed(self, trade_seed: u64) -> Self {
+        TradeBuilder {
+            trade_seed: Some(trade_seed),
+            ..self
+        }
+    }
+
     pub fn build(mut self) -> Trade {
         self.verify();
 
@@ -578,9 +751,12 @@ impl TradeBuilder {
             self.fee_strategy as u8,
             self.seed,
             &self.data_info.unwrap_or_default(),
+            self.valid_until_height,
         );
-        let signature = crypto::sign(&offer.clone().into_bytes(), &self.seller_secret.unwrap());
-        Trade::new(offer, &signature, &self.meta.secret_key)
+        let signing_bytes = offer_signing_bytes(TRADE_ID, &offer.clone().into_bytes());
+        let signature = crypto::sign(&signing_bytes, &self.seller_secret.unwrap());
+        let trade_seed = self.trade_seed.unwrap_or(self.seed);
+        Trade::new(offer, &signature, self.quantity, trade_seed, &self.meta.secret_key)
     }
 
     fn verify(&self) {
@@ -602,6 +778,7 @@ pub struct TradeIntermediaryBuilder {
     fee_strategy: FeeStrategy,
     seed: u64,
     data_info: Option<String>,
+    valid_until_height: u64,
 }
 
 impl TradeIntermediaryBuilder {
@@ -618,6 +795,7 @@ impl TradeIntermediaryBuilder {
             fee_strategy: FeeStrategy::Recipient,
             seed: 0,
             data_info: None,
+            valid_until_height: 0,
         }
     }
 
@@ -642,6 +820,17 @@ impl TradeIntermediaryBuilder {
             commission: commission,
             ..self
         }
+
+    /// Go without an intermediary: use the null intermediary, which signs
+    /// nothing and takes no commission.
+    pub fn without_intermediary(self) -> Self {
+        TradeIntermediaryBuilder {
+            intermediary_public_key: Some(Intermediary::null_wallet()),
+            intermediary_secret_key: None,
+            commission: 0,
+            ..self
+        }
+    }
     }
 
     pub fn add_asset(mut self, name: &str, count: u64, price: u64) -> Self {
@@ -672,6 +861,13 @@ impl TradeIntermediaryBuilder {
         }
     }
 
+    pub fn valid_until_height(self, valid_until_height: u64) -> Self {
+        TradeIntermediaryBuilder {
+            valid_until_height,
+            ..self
+        }
+    }
+
     pub fn build(mut self) -> TradeIntermediary {
         self.verify();
 
@@ -692,13 +888,15 @@ impl TradeIntermediaryBuilder {
             self.fee_strategy as u8,
             self.seed,
             &self.data_info.unwrap_or_default(),
+            self.valid_until_height,
         );
-        let seller_signature =
-            crypto::sign(&offer.clone().into_bytes(), &self.seller_secret.unwrap());
-        let intermediary_signature = crypto::sign(
-            &offer.clone().into_bytes(),
-            &self.intermediary_secret_key.unwrap(),
-        );
+        let signing_bytes =
+            offer_signing_bytes(TRADE_INTERMEDIARY_ID, &offer.clone().into_bytes());
+        let seller_signature = crypto::sign(&signing_bytes, &self.seller_secret.unwrap());
+        let intermediary_signature = match self.intermediary_secret_key {
+            Some(ref secret_key) => crypto::sign(&signing_bytes, secret_key),
+            None => Intermediary::null_signature(),
+        };
         TradeIntermediary::new(
             offer,
             &seller_signature,
@@ -711,7 +909,11 @@ impl TradeIntermediaryBuilder {
         assert!(self.seller_public.is_some());
         assert!(self.seller_secret.is_some());
         assert!(self.intermediary_public_key.is_some());
-        assert!(self.intermediary_secret_key.is_some());
+        assert!(
+            self.intermediary_secret_key.is_some()
+                || Intermediary::new(&self.intermediary_public_key.unwrap(), self.commission)
+                    .is_none()
+        );
     }
 }
 
@@ -719,9 +921,13 @@ pub struct TransferBuilder {
     meta: TransactionMetadata,
     recipient: Option<PublicKey>,
     amount: u64,
+    send_all: bool,
+    strict: bool,
+    only_if_recipient_below: u64,
     assets: Vec<AssetBundle>,
     seed: u64,
     data_info: Option<String>,
+    tag: Option<String>,
 }
 
 impl TransferBuilder {
@@ -730,9 +936,13 @@ impl TransferBuilder {
             meta,
             recipient: None,
             amount: 0,
+            send_all: false,
+            strict: false,
+            only_if_recipient_below: 0,
             assets: Vec::new(),
             seed: 0,
             data_info: None,
+            tag: None,
         }
     }
 
@@ -747,6 +957,21 @@ impl TransferBuilder {
         TransferBuilder { amount, ..self }
     }
 
+    pub fn send_all(self, send_all: bool) -> Self {
+        TransferBuilder { send_all, ..self }
+    }
+
+    pub fn strict(self, strict: bool) -> Self {
+        TransferBuilder { strict, ..self }
+    }
+
+    pub fn only_if_recipient_below(self, only_if_recipient_below: u64) -> Self {
+        TransferBuilder {
+            only_if_recipient_below,
+            ..self
+        }
+    }
+
     pub fn add_asset(self, name: &str, count: u64) -> Self {
         let asset = AssetBundle::from_data(name, count, &self.meta.public_key);
         self.add_asset_value(asset)
@@ -768,6 +993,13 @@ impl TransferBuilder {
         }
     }
 
+    pub fn tag(self, tag: &str) -> Self {
+        TransferBuilder {
+            tag: Some(tag.to_string()),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Transfer {
         self.verify();
 
@@ -775,9 +1007,13 @@ impl TransferBuilder {
             &self.meta.public_key,
             self.recipient.as_ref().unwrap(),
             self.amount,
+            self.send_all,
+            self.strict,
+            self.only_if_recipient_below,
             self.assets,
             self.seed,
             &self.data_info.unwrap_or_default(),
+            &self.tag.unwrap_or_default(),
             &self.meta.secret_key,
         )
     }
@@ -787,125 +1023,924 @@ impl TransferBuilder {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use exonum::crypto;
-    use exonum::storage::StorageValue;
-
-    use currency::assets::{AssetBundle, MetaAsset, TradeAsset};
+pub struct SetAssetBlacklistBuilder {
+    meta: TransactionMetadata,
+    asset_id: Option<AssetId>,
+    blacklisted: bool,
+    seed: u64,
+}
 
-    use currency::transactions::add_assets::AddAssets;
-    use currency::transactions::components::{FeeStrategy, Intermediary};
-    use currency::transactions::delete_assets::DeleteAssets;
-    use currency::transactions::exchange::{Exchange, ExchangeOffer};
-    use currency::transactions::exchange_intermediary::{ExchangeIntermediary,
-                                                        ExchangeOfferIntermediary};
-    use currency::transactions::trade::{Trade, TradeOffer};
-    use currency::transactions::trade_intermediary::{TradeIntermediary, TradeOfferIntermediary};
-    use currency::transactions::transfer::Transfer;
+impl SetAssetBlacklistBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        SetAssetBlacklistBuilder {
+            meta,
+            asset_id: None,
+            blacklisted: true,
+            seed: 0,
+        }
+    }
 
-    use currency::transactions::builders::fee;
-    use currency::transactions::builders::transaction;
+    pub fn asset_id(self, asset_id: AssetId) -> Self {
+        SetAssetBlacklistBuilder {
+            asset_id: Some(asset_id),
+            ..self
+        }
+    }
 
-    #[test]
-    #[should_panic]
-    fn meta_underspecified() {
-        transaction::Builder::new().tx_add_assets();
+    pub fn blacklisted(self, blacklisted: bool) -> Self {
+        SetAssetBlacklistBuilder {
+            blacklisted,
+            ..self
+        }
     }
 
-    #[test]
-    fn not_equal() {
-        let (public_key, secret_key) = crypto::gen_keypair();
-        let (receiver_key, _) = crypto::gen_keypair();
+    pub fn seed(self, seed: u64) -> Self {
+        SetAssetBlacklistBuilder { seed, ..self }
+    }
 
-        let fees_foobar = fee::Builder::new()
-            .trade(10, "0.1".parse().unwrap())
-            .exchange(10, "0.1".parse().unwrap())
-            .transfer(10, "0.1".parse().unwrap())
-            .build();
+    pub fn build(self) -> SetAssetBlacklist {
+        self.verify();
 
-        let fees_bazqux = fee::Builder::new()
-            .trade(11, "0.1".parse().unwrap())
-            .exchange(11, "0.1".parse().unwrap())
-            .transfer(11, "0.1".parse().unwrap())
-            .build();
+        SetAssetBlacklist::new(
+            &self.meta.public_key,
+            self.asset_id.unwrap(),
+            self.blacklisted,
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
 
-        let asset_foobar = MetaAsset::new(&receiver_key, "foobar", 9, fees_foobar);
-        let asset_bazqux = MetaAsset::new(&receiver_key, "bazqux", 18, fees_bazqux);
+    fn verify(&self) {
+        assert!(self.asset_id.is_some());
+    }
+}
 
-        let transaction = transaction::Builder::new()
-            .keypair(public_key, secret_key.clone())
-            .tx_add_assets()
-            .add_asset_value(asset_foobar.clone())
-            .build();
+pub struct SetIntermediaryBuilder {
+    meta: TransactionMetadata,
+    intermediary: Option<PublicKey>,
+    registered: bool,
+    seed: u64,
+}
 
-        let assets = vec![asset_bazqux];
-        let equivalent = AddAssets::new(&public_key, assets, 0, &secret_key);
+impl SetIntermediaryBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        SetIntermediaryBuilder {
+            meta,
+            intermediary: None,
+            registered: true,
+            seed: 0,
+        }
+    }
 
-        assert_ne!(transaction, equivalent);
+    pub fn intermediary(self, pub_key: PublicKey) -> Self {
+        SetIntermediaryBuilder {
+            intermediary: Some(pub_key),
+            ..self
+        }
     }
 
-    #[test]
-    fn add_assets() {
-        let (public_key, secret_key) = crypto::gen_keypair();
-        let (receiver_key, _) = crypto::gen_keypair();
+    pub fn registered(self, registered: bool) -> Self {
+        SetIntermediaryBuilder {
+            registered,
+            ..self
+        }
+    }
 
-        let fees_foobar = fee::Builder::new()
-            .trade(10, "0.1".parse().unwrap())
-            .exchange(10, "0.1".parse().unwrap())
-            .transfer(10, "0.1".parse().unwrap())
-            .build();
+    pub fn seed(self, seed: u64) -> Self {
+        SetIntermediaryBuilder { seed, ..self }
+    }
 
-        let fees_bazqux = fee::Builder::new()
-            .trade(11, "0.1".parse().unwrap())
-            .exchange(11, "0.1".parse().unwrap())
-            .transfer(11, "0.1".parse().unwrap())
-            .build();
+    pub fn build(self) -> SetIntermediary {
+        self.verify();
 
-        let asset_foobar = MetaAsset::new(&receiver_key, "foobar", 9, fees_foobar);
-        let asset_bazqux = MetaAsset::new(&receiver_key, "bazqux", 18, fees_bazqux);
+        SetIntermediary::new(
+            &self.meta.public_key,
+            self.intermediary.as_ref().unwrap(),
+            self.registered,
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
 
-        let transaction = transaction::Builder::new()
-            .keypair(public_key, secret_key.clone())
-            .tx_add_assets()
-            .add_asset_value(asset_foobar.clone())
-            .add_asset_value(asset_bazqux.clone())
-            .build();
+    fn verify(&self) {
+        assert!(self.intermediary.is_some());
+    }
+}
 
-        let assets = vec![asset_foobar, asset_bazqux];
-        let equivalent = AddAssets::new(&public_key, assets, 0, &secret_key);
+pub struct FreezeAssetBuilder {
+    meta: TransactionMetadata,
+    asset_id: Option<AssetId>,
+    frozen: bool,
+    seed: u64,
+}
 
-        assert_eq!(transaction, equivalent);
+impl FreezeAssetBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        FreezeAssetBuilder {
+            meta,
+            asset_id: None,
+            frozen: true,
+            seed: 0,
+        }
     }
 
-    #[test]
-    fn del_assets() {
-        let (public_key, secret_key) = crypto::gen_keypair();
-        let asset = AssetBundle::from_data("foobar", 9, &public_key);
-        let transaction = transaction::Builder::new()
-            .keypair(public_key, secret_key.clone())
-            .tx_del_assets()
-            .add_asset_value(asset.clone())
-            .seed(6)
-            .build();
-
-        let assets = vec![asset];
-        let equivalent = DeleteAssets::new(&public_key, assets, 6, &secret_key);
+    pub fn asset_id(self, asset_id: AssetId) -> Self {
+        FreezeAssetBuilder {
+            asset_id: Some(asset_id),
+            ..self
+        }
+    }
 
-        assert_eq!(transaction, equivalent);
+    pub fn frozen(self, frozen: bool) -> Self {
+        FreezeAssetBuilder { frozen, ..self }
     }
 
-    #[test]
-    fn exchange() {
-        let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    pub fn seed(self, seed: u64) -> Self {
+        FreezeAssetBuilder { seed, ..self }
+    }
 
-        let (sender_pk, sender_sk) = crypto::gen_keypair();
-        let sender_asset = AssetBundle::from_data("foobar", 9, &sender_pk);
-        let recipient_asset = AssetBundle::from_data("bazqux", 13, &recipient_pk);
+    pub fn build(self) -> FreezeAsset {
+        self.verify();
 
-        let transaction = transaction::Builder::new()
-            .keypair(recipient_pk, recipient_sk.clone())
-            .tx_exchange()
+        FreezeAsset::new(
+            &self.meta.public_key,
+            self.asset_id.unwrap(),
+            self.frozen,
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.asset_id.is_some());
+    }
+}
+
+pub struct SetReferencePriceBuilder {
+    meta: TransactionMetadata,
+    asset_id: Option<AssetId>,
+    price: u64,
+    seed: u64,
+}
+
+impl SetReferencePriceBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        SetReferencePriceBuilder {
+            meta,
+            asset_id: None,
+            price: 0,
+            seed: 0,
+        }
+    }
+
+    pub fn asset_id(self, asset_id: AssetId) -> Self {
+        SetReferencePriceBuilder {
+            asset_id: Some(asset_id),
+            ..self
+        }
+    }
+
+    pub fn price(self, price: u64) -> Self {
+        SetReferencePriceBuilder { price, ..self }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        SetReferencePriceBuilder { seed, ..self }
+    }
+
+    pub fn build(self) -> SetReferencePrice {
+        self.verify();
+
+        SetReferencePrice::new(
+            &self.meta.public_key,
+            self.asset_id.unwrap(),
+            self.price,
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.asset_id.is_some());
+    }
+}
+
+pub struct SplitAssetBuilder {
+    meta: TransactionMetadata,
+    asset: Option<AssetBundle>,
+    tranches: Vec<Tranche>,
+    seed: u64,
+}
+
+impl SplitAssetBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        SplitAssetBuilder {
+            meta,
+            asset: None,
+            tranches: Vec::new(),
+            seed: 0,
+        }
+    }
+
+    pub fn asset(self, asset: AssetBundle) -> Self {
+        SplitAssetBuilder {
+            asset: Some(asset),
+            ..self
+        }
+    }
+
+    pub fn add_tranche(mut self, label: &str, amount: u64) -> Self {
+        self.tranches.push(Tranche::new(label, amount));
+        self
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        SplitAssetBuilder { seed, ..self }
+    }
+
+    pub fn build(self) -> SplitAsset {
+        self.verify();
+
+        SplitAsset::new(
+            &self.meta.public_key,
+            self.asset.unwrap(),
+            self.tranches,
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.asset.is_some());
+    }
+}
+
+pub struct MergeAssetBuilder {
+    meta: TransactionMetadata,
+    lots: Vec<AssetBundle>,
+    into: Option<AssetId>,
+    seed: u64,
+}
+
+impl MergeAssetBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        MergeAssetBuilder {
+            meta,
+            lots: Vec::new(),
+            into: None,
+            seed: 0,
+        }
+    }
+
+    pub fn add_lot(mut self, lot: AssetBundle) -> Self {
+        self.lots.push(lot);
+        self
+    }
+
+    pub fn into_asset(self, into: AssetId) -> Self {
+        MergeAssetBuilder {
+            into: Some(into),
+            ..self
+        }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        MergeAssetBuilder { seed, ..self }
+    }
+
+    pub fn build(self) -> MergeAsset {
+        self.verify();
+
+        MergeAsset::new(
+            &self.meta.public_key,
+            self.lots,
+            self.into.unwrap(),
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.into.is_some());
+    }
+}
+
+pub struct EscrowCreateBuilder {
+    meta: TransactionMetadata,
+    recipient: Option<PublicKey>,
+    amount: u64,
+    assets: Vec<AssetBundle>,
+    seed: u64,
+    data_info: Option<String>,
+}
+
+impl EscrowCreateBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        EscrowCreateBuilder {
+            meta,
+            recipient: None,
+            amount: 0,
+            assets: Vec::new(),
+            seed: 0,
+            data_info: None,
+        }
+    }
+
+    pub fn recipient(self, pub_key: PublicKey) -> Self {
+        EscrowCreateBuilder {
+            recipient: Some(pub_key),
+            ..self
+        }
+    }
+
+    pub fn amount(self, amount: u64) -> Self {
+        EscrowCreateBuilder { amount, ..self }
+    }
+
+    pub fn add_asset(self, name: &str, count: u64) -> Self {
+        let asset = AssetBundle::from_data(name, count, &self.meta.public_key);
+        self.add_asset_value(asset)
+    }
+
+    pub fn add_asset_value(mut self, asset: AssetBundle) -> Self {
+        self.assets.push(asset);
+        self
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        EscrowCreateBuilder { seed, ..self }
+    }
+
+    pub fn data_info(self, data_info: &str) -> Self {
+        EscrowCreateBuilder {
+            data_info: Some(data_info.to_string()),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> EscrowCreate {
+        self.verify();
+
+        EscrowCreate::new(
+            &self.meta.public_key,
+            self.recipient.as_ref().unwrap(),
+            self.amount,
+            self.assets,
+            self.seed,
+            &self.data_info.unwrap_or_default(),
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.recipient.is_some());
+    }
+}
+
+pub struct SponsoredTransferBuilder {
+    meta: TransactionMetadata,
+    recipient: Option<PublicKey>,
+    amount: u64,
+    assets: Vec<AssetBundle>,
+    seed: u64,
+    data_info: Option<String>,
+}
+
+impl SponsoredTransferBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        SponsoredTransferBuilder {
+            meta,
+            recipient: None,
+            amount: 0,
+            assets: Vec::new(),
+            seed: 0,
+            data_info: None,
+        }
+    }
+
+    pub fn recipient(self, pub_key: PublicKey) -> Self {
+        SponsoredTransferBuilder {
+            recipient: Some(pub_key),
+            ..self
+        }
+    }
+
+    pub fn amount(self, amount: u64) -> Self {
+        SponsoredTransferBuilder { amount, ..self }
+    }
+
+    pub fn add_asset(self, name: &str, count: u64) -> Self {
+        let asset = AssetBundle::from_data(name, count, &self.meta.public_key);
+        self.add_asset_value(asset)
+    }
+
+    pub fn add_asset_value(mut self, asset: AssetBundle) -> Self {
+        self.assets.push(asset);
+        self
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        SponsoredTransferBuilder { seed, ..self }
+    }
+
+    pub fn data_info(self, data_info: &str) -> Self {
+        SponsoredTransferBuilder {
+            data_info: Some(data_info.to_string()),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> SponsoredTransfer {
+        self.verify();
+
+        SponsoredTransfer::new(
+            &self.meta.public_key,
+            self.recipient.as_ref().unwrap(),
+            self.amount,
+            self.assets,
+            self.seed,
+            &self.data_info.unwrap_or_default(),
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.recipient.is_some());
+    }
+}
+
+pub struct SetMultiSigBuilder {
+    meta: TransactionMetadata,
+    keys: Vec<MultiSigKey>,
+    threshold: u8,
+    seed: u64,
+}
+
+impl SetMultiSigBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        SetMultiSigBuilder {
+            meta,
+            keys: Vec::new(),
+            threshold: 0,
+            seed: 0,
+        }
+    }
+
+    pub fn add_key(mut self, pub_key: PublicKey) -> Self {
+        self.keys.push(MultiSigKey::new(&pub_key));
+        self
+    }
+
+    pub fn threshold(self, threshold: u8) -> Self {
+        SetMultiSigBuilder { threshold, ..self }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        SetMultiSigBuilder { seed, ..self }
+    }
+
+    pub fn build(self) -> SetMultiSig {
+        self.verify();
+
+        SetMultiSig::new(
+            &self.meta.public_key,
+            self.keys,
+            self.threshold,
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(!self.keys.is_empty());
+    }
+}
+
+pub struct MultiSigTransferBuilder {
+    meta: TransactionMetadata,
+    from: Option<PublicKey>,
+    recipient: Option<PublicKey>,
+    amount: u64,
+    assets: Vec<AssetBundle>,
+    cosigners: Vec<(PublicKey, SecretKey)>,
+    seed: u64,
+    data_info: Option<String>,
+}
+
+impl MultiSigTransferBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        MultiSigTransferBuilder {
+            meta,
+            from: None,
+            recipient: None,
+            amount: 0,
+            assets: Vec::new(),
+            cosigners: Vec::new(),
+            seed: 0,
+            data_info: None,
+        }
+    }
+
+    pub fn wallet(self, pub_key: PublicKey) -> Self {
+        MultiSigTransferBuilder {
+            from: Some(pub_key),
+            ..self
+        }
+    }
+
+    pub fn recipient(self, pub_key: PublicKey) -> Self {
+        MultiSigTransferBuilder {
+            recipient: Some(pub_key),
+            ..self
+        }
+    }
+
+    pub fn amount(self, amount: u64) -> Self {
+        MultiSigTransferBuilder { amount, ..self }
+    }
+
+    pub fn add_asset_value(mut self, asset: AssetBundle) -> Self {
+        self.assets.push(asset);
+        self
+    }
+
+    /// Sign the offer with another key besides the one the transaction
+    /// is signed with.
+    pub fn cosigner(mut self, pub_key: PublicKey, secret_key: SecretKey) -> Self {
+        self.cosigners.push((pub_key, secret_key));
+        self
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        MultiSigTransferBuilder { seed, ..self }
+    }
+
+    pub fn data_info(self, data_info: &str) -> Self {
+        MultiSigTransferBuilder {
+            data_info: Some(data_info.to_string()),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> MultiSigTransfer {
+        self.verify();
+
+        let offer = MultiSigTransferOffer::new(
+            self.from.as_ref().unwrap(),
+            self.recipient.as_ref().unwrap(),
+            self.amount,
+            self.assets,
+            self.seed,
+            &self.data_info.unwrap_or_default(),
+        );
+        let signing_bytes = offer_signing_bytes(MULTISIG_TRANSFER_ID, &offer.clone().into_bytes());
+        let signer = (self.meta.public_key, self.meta.secret_key.clone());
+        let signatures = Some(signer)
+            .into_iter()
+            .chain(self.cosigners.into_iter())
+            .map(|(pub_key, secret_key)| {
+                let signature = crypto::sign(&signing_bytes, &secret_key);
+                MultiSigSignature::new(&pub_key, &signature)
+            })
+            .collect();
+
+        MultiSigTransfer::new(offer, signatures, &self.meta.secret_key)
+    }
+
+    fn verify(&self) {
+        assert!(self.from.is_some());
+        assert!(self.recipient.is_some());
+    }
+}
+
+/// Builds a `match_offers` transaction from mirrored offers: each side
+/// wants exactly what the other side gives. The transaction is signed by
+/// the taker.
+pub struct MatchOffersBuilder {
+    meta: TransactionMetadata,
+    maker: Option<(PublicKey, SecretKey)>,
+    maker_assets: Vec<AssetBundle>,
+    maker_value: u64,
+    taker_assets: Vec<AssetBundle>,
+    taker_value: u64,
+    fee_strategy: FeeStrategy,
+    seed: u64,
+}
+
+impl MatchOffersBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        MatchOffersBuilder {
+            meta,
+            maker: None,
+            maker_assets: Vec::new(),
+            maker_value: 0,
+            taker_assets: Vec::new(),
+            taker_value: 0,
+            fee_strategy: FeeStrategy::Recipient,
+            seed: 0,
+        }
+    }
+
+    pub fn maker_key_pair(self, pub_key: PublicKey, secret_key: SecretKey) -> Self {
+        MatchOffersBuilder {
+            maker: Some((pub_key, secret_key)),
+            ..self
+        }
+    }
+
+    pub fn maker_add_asset_value(mut self, asset: AssetBundle) -> Self {
+        self.maker_assets.push(asset);
+        self
+    }
+
+    pub fn maker_value(self, maker_value: u64) -> Self {
+        MatchOffersBuilder {
+            maker_value,
+            ..self
+        }
+    }
+
+    pub fn taker_add_asset_value(mut self, asset: AssetBundle) -> Self {
+        self.taker_assets.push(asset);
+        self
+    }
+
+    pub fn taker_value(self, taker_value: u64) -> Self {
+        MatchOffersBuilder {
+            taker_value,
+            ..self
+        }
+    }
+
+    pub fn fee_strategy(self, fee_strategy: FeeStrategy) -> Self {
+        MatchOffersBuilder {
+            fee_strategy,
+            ..self
+        }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        MatchOffersBuilder { seed, ..self }
+    }
+
+    pub fn build(self) -> MatchOffers {
+        self.verify();
+
+        let (maker_pk, maker_sk) = self.maker.unwrap();
+        let fee_strategy = self.fee_strategy as u8;
+        let maker = MatchOffer::new(
+            &maker_pk,
+            self.maker_assets.clone(),
+            self.maker_value,
+            self.taker_assets.clone(),
+            self.taker_value,
+            fee_strategy,
+            self.seed,
+        );
+        let taker = MatchOffer::new(
+            &self.meta.public_key,
+            self.taker_assets,
+            self.taker_value,
+            self.maker_assets,
+            self.maker_value,
+            fee_strategy,
+            self.seed,
+        );
+        let signing_bytes = offer_signing_bytes(MATCH_OFFERS_ID, &maker.clone().into_bytes());
+        let maker_signature = crypto::sign(&signing_bytes, &maker_sk);
+
+        MatchOffers::new(maker, &maker_signature, taker, &self.meta.secret_key)
+    }
+
+    fn verify(&self) {
+        assert!(self.maker.is_some());
+    }
+}
+
+pub struct EscrowReleaseBuilder {
+    meta: TransactionMetadata,
+    escrow: Option<Hash>,
+    seed: u64,
+}
+
+impl EscrowReleaseBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        EscrowReleaseBuilder {
+            meta,
+            escrow: None,
+            seed: 0,
+        }
+    }
+
+    pub fn escrow(self, tx_hash: Hash) -> Self {
+        EscrowReleaseBuilder {
+            escrow: Some(tx_hash),
+            ..self
+        }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        EscrowReleaseBuilder { seed, ..self }
+    }
+
+    pub fn build(self) -> EscrowRelease {
+        self.verify();
+
+        EscrowRelease::new(
+            &self.meta.public_key,
+            self.escrow.as_ref().unwrap(),
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.escrow.is_some());
+    }
+}
+
+pub struct EscrowRefundBuilder {
+    meta: TransactionMetadata,
+    escrow: Option<Hash>,
+    seed: u64,
+}
+
+impl EscrowRefundBuilder {
+    fn new(meta: TransactionMetadata) -> Self {
+        EscrowRefundBuilder {
+            meta,
+            escrow: None,
+            seed: 0,
+        }
+    }
+
+    pub fn escrow(self, tx_hash: Hash) -> Self {
+        EscrowRefundBuilder {
+            escrow: Some(tx_hash),
+            ..self
+        }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        EscrowRefundBuilder { seed, ..self }
+    }
+
+    pub fn build(self) -> EscrowRefund {
+        self.verify();
+
+        EscrowRefund::new(
+            &self.meta.public_key,
+            self.escrow.as_ref().unwrap(),
+            self.seed,
+            &self.meta.secret_key,
+        )
+    }
+
+    fn verify(&self) {
+        assert!(self.escrow.is_some());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use exonum::crypto;
+    use exonum::storage::StorageValue;
+
+    use currency::assets::{AssetBundle, MetaAsset, TradeAsset, Tranche};
+
+    use currency::transactions::add_assets::AddAssets;
+    use currency::transactions::components::{offer_signing_bytes, FeeStrategy, Intermediary};
+    use currency::transactions::delete_assets::DeleteAssets;
+    use currency::transactions::escrow_create::EscrowCreate;
+    use currency::transactions::escrow_release::EscrowRelease;
+    use currency::transactions::exchange::{Exchange, ExchangeOffer, EXCHANGE_ID};
+    use currency::transactions::exchange_intermediary::{ExchangeIntermediary,
+                                                        ExchangeOfferIntermediary,
+                                                        EXCHANGE_INTERMEDIARY_ID};
+    use currency::multisig::{MultiSigKey, MultiSigSignature};
+    use currency::transactions::freeze_asset::FreezeAsset;
+    use currency::transactions::match_offers::{MatchOffer, MatchOffers, MATCH_OFFERS_ID};
+    use currency::transactions::merge_asset::MergeAsset;
+    use currency::transactions::multisig_transfer::{MultiSigTransfer, MultiSigTransferOffer,
+                                                    MULTISIG_TRANSFER_ID};
+    use currency::transactions::set_intermediary::SetIntermediary;
+    use currency::transactions::set_multisig::SetMultiSig;
+    use currency::transactions::set_reference_price::SetReferencePrice;
+    use currency::transactions::split_asset::SplitAsset;
+    use currency::transactions::sponsored_transfer::SponsoredTransfer;
+    use currency::transactions::trade::{Trade, TradeOffer, TRADE_ID};
+    use currency::transactions::trade_intermediary::{TradeIntermediary, TradeOfferIntermediary,
+                                                     TRADE_INTERMEDIARY_ID};
+    use currency::transactions::transfer::Transfer;
+
+    use currency::transactions::builders::fee;
+    use currency::transactions::builders::transaction;
+
+    #[test]
+    fn generate_seed_is_unique() {
+        let seeds = (0..1000).map(|_| transaction::generate_seed()).collect::<Vec<_>>();
+        assert!(seeds.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn meta_underspecified() {
+        transaction::Builder::new().tx_add_assets();
+    }
+
+    #[test]
+    fn not_equal() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let (receiver_key, _) = crypto::gen_keypair();
+
+        let fees_foobar = fee::Builder::new()
+            .trade(10, "0.1".parse().unwrap())
+            .exchange(10, "0.1".parse().unwrap())
+            .transfer(10, "0.1".parse().unwrap())
+            .build();
+
+        let fees_bazqux = fee::Builder::new()
+            .trade(11, "0.1".parse().unwrap())
+            .exchange(11, "0.1".parse().unwrap())
+            .transfer(11, "0.1".parse().unwrap())
+            .build();
+
+        let asset_foobar = MetaAsset::new(&receiver_key, "foobar", 9, fees_foobar);
+        let asset_bazqux = MetaAsset::new(&receiver_key, "bazqux", 18, fees_bazqux);
+
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_add_assets()
+            .add_asset_value(asset_foobar.clone())
+            .build();
+
+        let assets = vec![asset_bazqux];
+        let equivalent = AddAssets::new(&public_key, assets, false, 0, &secret_key);
+
+        assert_ne!(transaction, equivalent);
+    }
+
+    #[test]
+    fn add_assets() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let (receiver_key, _) = crypto::gen_keypair();
+
+        let fees_foobar = fee::Builder::new()
+            .trade(10, "0.1".parse().unwrap())
+            .exchange(10, "0.1".parse().unwrap())
+            .transfer(10, "0.1".parse().unwrap())
+            .build();
+
+        let fees_bazqux = fee::Builder::new()
+            .trade(11, "0.1".parse().unwrap())
+            .exchange(11, "0.1".parse().unwrap())
+            .transfer(11, "0.1".parse().unwrap())
+            .build();
+
+        let asset_foobar = MetaAsset::new(&receiver_key, "foobar", 9, fees_foobar);
+        let asset_bazqux = MetaAsset::new(&receiver_key, "bazqux", 18, fees_bazqux);
+
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_add_assets()
+            .add_asset_value(asset_foobar.clone())
+            .add_asset_value(asset_bazqux.clone())
+            .build();
+
+        let assets = vec![asset_foobar, asset_bazqux];
+        let equivalent = AddAssets::new(&public_key, assets, false, 0, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn del_assets() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let asset = AssetBundle::from_data("foobar", 9, &public_key);
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_del_assets()
+            .add_asset_value(asset.clone())
+            .seed(6)
+            .build();
+
+        let assets = vec![asset];
+        let equivalent = DeleteAssets::new(&public_key, assets, 6, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn exchange() {
+        let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+
+        let (sender_pk, sender_sk) = crypto::gen_keypair();
+        let sender_asset = AssetBundle::from_data("foobar", 9, &sender_pk);
+        let recipient_asset = AssetBundle::from_data("bazqux", 13, &recipient_pk);
+
+        let transaction = transaction::Builder::new()
+            .keypair(recipient_pk, recipient_sk.clone())
+            .tx_exchange()
             .sender(sender_pk)
             .sender_secret(sender_sk.clone())
             .sender_add_asset_value(sender_asset.clone())
@@ -925,8 +1960,10 @@ mod test {
             1,
             1,
             "test_exchange",
+            0,
         );
-        let sender_signature = crypto::sign(&offer.clone().into_bytes(), &sender_sk.clone());
+        let signing_bytes = offer_signing_bytes(EXCHANGE_ID, &offer.clone().into_bytes());
+        let sender_signature = crypto::sign(&signing_bytes, &sender_sk.clone());
         let equivalent = Exchange::new(offer, &sender_signature, &recipient_sk);
 
         assert_eq!(transaction, equivalent);
@@ -967,9 +2004,12 @@ mod test {
             1,
             1,
             "test_exchange",
+            0,
         );
-        let sender_signature = crypto::sign(&offer.clone().into_bytes(), &sender_sk);
-        let intermediary_signature = crypto::sign(&offer.clone().into_bytes(), &intermediary_sk);
+        let signing_bytes =
+            offer_signing_bytes(EXCHANGE_INTERMEDIARY_ID, &offer.clone().into_bytes());
+        let sender_signature = crypto::sign(&signing_bytes, &sender_sk);
+        let intermediary_signature = crypto::sign(&signing_bytes, &intermediary_sk);
         let equivalent = ExchangeIntermediary::new(
             offer,
             &sender_signature,
@@ -1003,9 +2043,11 @@ mod test {
             FeeStrategy::Recipient as u8,
             1,
             "test_trade",
+            0,
         );
-        let signature = crypto::sign(&offer.clone().into_bytes(), &seller_secret);
-        let equivalent = Trade::new(offer, &signature, &secret_key);
+        let signing_bytes = offer_signing_bytes(TRADE_ID, &offer.clone().into_bytes());
+        let signature = crypto::sign(&signing_bytes, &seller_secret);
+        let equivalent = Trade::new(offer, &signature, 0, 1, &secret_key);
 
         assert_eq!(transaction, equivalent);
     }
@@ -1038,10 +2080,12 @@ mod test {
             FeeStrategy::Recipient as u8,
             1,
             "trade_test",
+            0,
         );
-        let seller_signature = crypto::sign(&offer.clone().into_bytes(), &seller_secret_key);
-        let intermediary_signature =
-            crypto::sign(&offer.clone().into_bytes(), &intermediary_secret_key);
+        let signing_bytes =
+            offer_signing_bytes(TRADE_INTERMEDIARY_ID, &offer.clone().into_bytes());
+        let seller_signature = crypto::sign(&signing_bytes, &seller_secret_key);
+        let intermediary_signature = crypto::sign(&signing_bytes, &intermediary_secret_key);
         let equivalent = TradeIntermediary::new(
             offer,
             &seller_signature,
@@ -1062,12 +2106,179 @@ mod test {
             .tx_transfer()
             .recipient(recipient)
             .amount(9)
+            .strict(true)
+            .only_if_recipient_below(100)
             .add_asset_value(asset.clone())
             .seed(1)
             .data_info("info")
+            .tag("payroll")
             .build();
 
         let equivalent = Transfer::new(
+            &public_key,
+            &recipient,
+            9,
+            false,
+            true,
+            100,
+            vec![asset],
+            1,
+            "info",
+            "payroll",
+            &secret_key,
+        );
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn escrow_create() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let (recipient, _) = crypto::gen_keypair();
+        let asset = AssetBundle::from_data("foobar", 9, &public_key);
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_escrow_create()
+            .recipient(recipient)
+            .amount(9)
+            .add_asset_value(asset.clone())
+            .seed(1)
+            .data_info("info")
+            .build();
+
+        let equivalent = EscrowCreate::new(
+            &public_key,
+            &recipient,
+            9,
+            vec![asset],
+            1,
+            "info",
+            &secret_key,
+        );
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn escrow_release() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let escrow = crypto::hash(&[1, 2, 3]);
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_escrow_release()
+            .escrow(escrow)
+            .seed(1)
+            .build();
+
+        let equivalent = EscrowRelease::new(&public_key, &escrow, 1, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn set_intermediary() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let (intermediary, _) = crypto::gen_keypair();
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_set_intermediary()
+            .intermediary(intermediary)
+            .registered(false)
+            .seed(1)
+            .build();
+
+        let equivalent = SetIntermediary::new(&public_key, &intermediary, false, 1, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn freeze_asset() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let asset = AssetBundle::from_data("foobar", 9, &public_key);
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_freeze_asset()
+            .asset_id(asset.id())
+            .frozen(false)
+            .seed(1)
+            .build();
+
+        let equivalent = FreezeAsset::new(&public_key, asset.id(), false, 1, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn set_reference_price() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let asset = AssetBundle::from_data("foobar", 9, &public_key);
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_set_reference_price()
+            .asset_id(asset.id())
+            .price(100)
+            .seed(1)
+            .build();
+
+        let equivalent = SetReferencePrice::new(&public_key, asset.id(), 100, 1, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn split_asset() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let asset = AssetBundle::from_data("foobar", 9, &public_key);
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_split_asset()
+            .asset(asset.clone())
+            .add_tranche("a", 4)
+            .add_tranche("b", 5)
+            .seed(1)
+            .build();
+
+        let tranches = vec![Tranche::new("a", 4), Tranche::new("b", 5)];
+        let equivalent = SplitAsset::new(&public_key, asset, tranches, 1, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn merge_asset() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let lot = AssetBundle::from_data("foobar/a", 4, &public_key);
+        let into = AssetBundle::from_data("foobar", 0, &public_key).id();
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_merge_asset()
+            .add_lot(lot.clone())
+            .into_asset(into)
+            .seed(1)
+            .build();
+
+        let equivalent = MergeAsset::new(&public_key, vec![lot], into, 1, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn sponsored_transfer() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let (recipient, _) = crypto::gen_keypair();
+        let asset = AssetBundle::from_data("foobar", 9, &public_key);
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_sponsored_transfer()
+            .recipient(recipient)
+            .amount(9)
+            .add_asset_value(asset.clone())
+            .seed(1)
+            .data_info("info")
+            .build();
+
+        let equivalent = SponsoredTransfer::new(
             &public_key,
             &recipient,
             9,
@@ -1079,4 +2290,97 @@ mod test {
 
         assert_eq!(transaction, equivalent);
     }
+
+    #[test]
+    fn set_multisig() {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let (first, _) = crypto::gen_keypair();
+        let (second, _) = crypto::gen_keypair();
+        let transaction = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_set_multisig()
+            .add_key(first)
+            .add_key(second)
+            .threshold(2)
+            .seed(1)
+            .build();
+
+        let keys = vec![MultiSigKey::new(&first), MultiSigKey::new(&second)];
+        let equivalent = SetMultiSig::new(&public_key, keys, 2, 1, &secret_key);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn multisig_transfer() {
+        let (wallet, _) = crypto::gen_keypair();
+        let (recipient, _) = crypto::gen_keypair();
+        let (signer_pk, signer_sk) = crypto::gen_keypair();
+        let (cosigner_pk, cosigner_sk) = crypto::gen_keypair();
+        let asset = AssetBundle::from_data("foobar", 9, &wallet);
+        let transaction = transaction::Builder::new()
+            .keypair(signer_pk, signer_sk.clone())
+            .tx_multisig_transfer()
+            .wallet(wallet)
+            .recipient(recipient)
+            .amount(9)
+            .add_asset_value(asset.clone())
+            .cosigner(cosigner_pk, cosigner_sk.clone())
+            .seed(1)
+            .data_info("info")
+            .build();
+
+        let offer = MultiSigTransferOffer::new(&wallet, &recipient, 9, vec![asset], 1, "info");
+        let signing_bytes = offer_signing_bytes(MULTISIG_TRANSFER_ID, &offer.clone().into_bytes());
+        let signatures = vec![
+            MultiSigSignature::new(&signer_pk, &crypto::sign(&signing_bytes, &signer_sk)),
+            MultiSigSignature::new(&cosigner_pk, &crypto::sign(&signing_bytes, &cosigner_sk)),
+        ];
+        let equivalent = MultiSigTransfer::new(offer, signatures, &signer_sk);
+
+        assert_eq!(transaction, equivalent);
+    }
+
+    #[test]
+    fn match_offers() {
+        let (maker_pk, maker_sk) = crypto::gen_keypair();
+        let (taker_pk, taker_sk) = crypto::gen_keypair();
+        let maker_asset = AssetBundle::from_data("foo", 3, &maker_pk);
+        let taker_asset = AssetBundle::from_data("bar", 5, &taker_pk);
+        let transaction = transaction::Builder::new()
+            .keypair(taker_pk, taker_sk.clone())
+            .tx_match_offers()
+            .maker_key_pair(maker_pk, maker_sk.clone())
+            .maker_add_asset_value(maker_asset.clone())
+            .taker_add_asset_value(taker_asset.clone())
+            .taker_value(100)
+            .fee_strategy(FeeStrategy::RecipientAndSender)
+            .seed(1)
+            .build();
+
+        let fee_strategy = FeeStrategy::RecipientAndSender as u8;
+        let maker = MatchOffer::new(
+            &maker_pk,
+            vec![maker_asset.clone()],
+            0,
+            vec![taker_asset.clone()],
+            100,
+            fee_strategy,
+            1,
+        );
+        let taker = MatchOffer::new(
+            &taker_pk,
+            vec![taker_asset],
+            100,
+            vec![maker_asset],
+            0,
+            fee_strategy,
+            1,
+        );
+        let signing_bytes = offer_signing_bytes(MATCH_OFFERS_ID, &maker.clone().into_bytes());
+        let maker_signature = crypto::sign(&signing_bytes, &maker_sk);
+        let equivalent = MatchOffers::new(maker, &maker_signature, taker, &taker_sk);
+
+        assert_eq!(transaction, equivalent);
+    }
 }
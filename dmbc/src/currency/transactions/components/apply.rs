@@ -0,0 +1,18 @@
+use exonum::blockchain::Transaction;
+use exonum::storage::Fork;
+
+use currency::error::Error;
+
+/// Checks and state changes of a transaction, without the metrics and
+/// logging of `Transaction::verify` and `Transaction::execute`.
+///
+/// Both are run as-is by dry runs, which must leave no trace on the node.
+pub trait Apply: Transaction {
+    /// Stateless checks of `Transaction::verify`.
+    fn check(&self) -> bool;
+
+    /// Changes of `Transaction::execute`, recording the status, history and
+    /// wallet snapshots of the transaction. The transaction must not be
+    /// executed yet.
+    fn apply(&self, view: &mut Fork) -> Result<(), Error>;
+}
@@ -0,0 +1,20 @@
+use exonum::blockchain;
+use exonum::storage::Snapshot;
+
+use currency::error::Error;
+
+/// Check that an offer valid until `valid_until_height` may still be
+/// executed in the block being built. Zero means the offer never expires.
+///
+/// # Errors
+/// Returns `OfferExpired` if the block is above `valid_until_height`.
+pub fn check_offer_expiry(view: &Snapshot, valid_until_height: u64) -> Result<(), Error> {
+    if valid_until_height == 0 {
+        return Ok(());
+    }
+    let height = blockchain::Schema::new(view).block_hashes_by_height().len();
+    if height > valid_until_height {
+        return Err(Error::OfferExpired);
+    }
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! Transaction fees.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use exonum::crypto::PublicKey;
 use exonum::storage::{Fork, Snapshot};
@@ -9,6 +9,8 @@ use currency::assets;
 use currency::assets::{AssetBundle, MetaAsset, TradeAsset};
 use currency::configuration::Configuration;
 use currency::error::Error;
+use currency::revenue;
+use currency::service::CONFIGURATION;
 use currency::wallet;
 use currency::wallet::Wallet;
 use currency::Service;
@@ -21,7 +23,8 @@ pub enum FeeStrategy {
     Recipient = 1,
     /// Sender pays.
     Sender = 2,
-    /// Recipient and sender share paying the fee.
+    /// Recipient and sender share paying the fee. The first party of the
+    /// offer pays the odd coin, see `split_fee`.
     RecipientAndSender = 3,
     /// Intermediary pays.
     Intermediary = 4,
@@ -41,8 +44,134 @@ impl FeeStrategy {
     }
 }
 
+/// Split a fee between `parties`, so that the parts always sum up to the
+/// `total`.
+///
+/// Every party pays `total / parties` coins and the remainder of the
+/// division goes to the first party, so no coin is ever created or lost.
+/// No parties get no parts.
+pub fn split_fee(total: u64, parties: usize) -> Vec<u64> {
+    if parties == 0 {
+        return Vec::new();
+    }
+    let share = total / parties as u64;
+    let remainder = total % parties as u64;
+    let mut parts = vec![share; parties];
+    parts[0] += remainder;
+    parts
+}
+
+/// Split coins between two parties, see `split_fee`. The odd coin, if any,
+/// goes to the first party.
+pub fn split_coins(coins: u64) -> (u64, u64) {
+    let parts = split_fee(coins, 2);
+    (parts[0], parts[1])
+}
+
+/// Blockchain fee for a transaction of the given `price`: the fixed `base`
+/// plus `bps` basis points of the price, rounded half up.
+pub fn percentage_fee(base: u64, price: u64, bps: u64) -> Result<u64, Error> {
+    price
+        .checked_mul(bps)
+        .and_then(|value| value.checked_add(5_000))
+        .map(|value| value / 10_000)
+        .and_then(|rate_fee| base.checked_add(rate_fee))
+        .ok_or(Error::Overflow)
+}
+
+/// Settle `fee`, a blockchain fee the fee recipient has just collected:
+/// pay the validators their share of it, see
+/// `Configuration::validator_reward`, and count the rest as revenue.
+///
+/// Must be called once the fee recipient's wallet is stored, the wallets
+/// are read and written through `view`.
+pub fn settle_blockchain_fee(view: &mut Fork, fee: u64) -> Result<(), Error> {
+    let configuration = CONFIGURATION.read().unwrap().clone();
+    let recipient = *configuration.fees().recipient();
+    let platform_share = match configuration.validator_reward() {
+        Some(ref reward) if reward.wallet != recipient => {
+            let (platform_share, validators_share) = reward.split(fee);
+            if validators_share != 0 {
+                let platform = wallet::Schema(&*view).fetch(&recipient);
+                let validators = wallet::Schema(&*view).fetch(&reward.wallet);
+
+                let platform_balance = platform
+                    .balance()
+                    .checked_sub(validators_share)
+                    .ok_or(Error::InsufficientFunds)?;
+                let validators_balance = validators
+                    .balance()
+                    .checked_add(validators_share)
+                    .ok_or(Error::Overflow)?;
+
+                wallet::Schema(&mut *view)
+                    .store(&recipient, Wallet::new(platform_balance, platform.assets()));
+                wallet::Schema(&mut *view).store(
+                    &reward.wallet,
+                    Wallet::new(validators_balance, validators.assets()),
+                );
+            }
+            platform_share
+        }
+        _ => fee,
+    };
+    revenue::Schema(&mut *view).add(platform_share);
+    Ok(())
+}
+
+/// Blockchain fee `payer` owes for a transaction with `message_id`, see
+/// `Configuration::operation_fee`. Empty if the payer is the fee recipient.
+pub fn operation_fees_table<S>(
+    view: S,
+    payer: &PublicKey,
+    message_id: u16,
+) -> HashMap<PublicKey, u64>
+where
+    S: AsRef<Snapshot>,
+{
+    let configuration = CONFIGURATION.read().unwrap().clone();
+    let mut fees_table = HashMap::new();
+    if configuration.fees().recipient() != payer {
+        let wallet = wallet::Schema(view).fetch(payer);
+        let fee = configuration.payer_fee(configuration.operation_fee(message_id), &wallet);
+        fees_table.insert(*payer, fee);
+    }
+    fees_table
+}
+
+/// Collect the blockchain fee of a transaction with `message_id` from
+/// `payer` and settle it, see `Configuration::operation_fee`.
+///
+/// # Errors
+/// Returns `InsufficientFunds` if the payer can't pay the fee, and nothing
+/// is changed.
+pub fn collect_operation_fee(
+    view: &mut Fork,
+    payer: &PublicKey,
+    message_id: u16,
+) -> Result<(), Error> {
+    let configuration = CONFIGURATION.read().unwrap().clone();
+    let recipient = *configuration.fees().recipient();
+    if *payer == recipient {
+        return Ok(());
+    }
+
+    let fee_currency = configuration.fee_currency(message_id);
+    let mut genesis = wallet::Schema(&*view).fetch(&recipient);
+    let mut wallet = wallet::Schema(&*view).fetch(payer);
+    let fee = configuration.payer_fee(configuration.operation_fee(message_id), &wallet);
+    wallet::move_fee(&mut wallet, &mut genesis, fee, fee_currency)?;
+
+    wallet::Schema(&mut *view).store(payer, wallet);
+    wallet::Schema(&mut *view).store(&recipient, genesis);
+    settle_blockchain_fee(view, fee_currency.coins(fee))
+}
+
 /// Transaction fees.
-pub struct ThirdPartyFees(pub HashMap<PublicKey, u64>);
+///
+/// Fees are kept ordered by the bytes of the receiving key, so that every
+/// node pays them out and writes the wallets in the same order.
+pub struct ThirdPartyFees(pub BTreeMap<PublicKey, u64>);
 
 impl ThirdPartyFees {
     /// Create `ThirdPartyFees` for an `add_assets` transaction.
@@ -73,7 +202,7 @@ impl ThirdPartyFees {
         S: AsRef<Snapshot>,
         I: IntoIterator<Item = AssetBundle>,
     {
-        let to_third_party = HashMap::new();
+        let to_third_party = BTreeMap::new();
 
         let fees = ThirdPartyFees(to_third_party);
 
@@ -81,28 +210,42 @@ impl ThirdPartyFees {
     }
 
     /// Create `ThirdPartyFees` for `trade` transactions.
-    pub fn new_trade<'a, S, I>(view: S, assets: I) -> Result<ThirdPartyFees, Error>
+    ///
+    /// On top of the asset's own trade fee, the creator receives the
+    /// configured royalty on the price of the asset when it is sold by
+    /// anyone else, see `Configuration::royalty_bps`.
+    pub fn new_trade<'a, S, I>(
+        view: S,
+        seller: &PublicKey,
+        assets: I,
+    ) -> Result<ThirdPartyFees, Error>
     where
         S: AsRef<Snapshot>,
         I: IntoIterator<Item = &'a TradeAsset>,
         <I as IntoIterator>::IntoIter: Clone,
     {
         let view = view.as_ref();
+        let royalty_bps = Configuration::extract(view).royalty_bps();
         let assets = assets.into_iter();
-        let mut to_third_party = HashMap::new();
+        let mut to_third_party = BTreeMap::new();
 
         for asset in assets {
             let info = assets::Schema(view)
                 .fetch(&asset.id())
                 .ok_or_else(|| Error::AssetNotFound)?;
 
-            let fee = info.fees().trade().for_price(asset.price()) * asset.amount() ;
-            to_third_party
-                .entry(*info.creator())
-                .and_modify(|prev_fee| {
-                    *prev_fee += fee;
-                })
-                .or_insert(fee);
+            let mut fee = info.fees()
+                .trade()
+                .for_price(asset.price())
+                .and_then(|fee| fee.checked_mul(asset.amount()))
+                .ok_or(Error::Overflow)?;
+            if info.creator() != seller {
+                let price = asset.price().checked_mul(asset.amount()).ok_or(Error::Overflow)?;
+                let royalty = percentage_fee(0, price, royalty_bps)?;
+                fee = fee.checked_add(royalty).ok_or(Error::Overflow)?;
+            }
+            let creator_fee = to_third_party.entry(*info.creator()).or_insert(0u64);
+            *creator_fee = creator_fee.checked_add(fee).ok_or(Error::Overflow)?;
         }
 
         let fees = ThirdPartyFees(to_third_party);
@@ -117,20 +260,20 @@ impl ThirdPartyFees {
         I: IntoIterator<Item = AssetBundle>,
     {
         let view = view.as_ref();
-        let mut to_third_party = HashMap::new();
+        let mut to_third_party = BTreeMap::new();
 
         for asset in assets {
             let info = assets::Schema(view)
                 .fetch(&asset.id())
                 .ok_or_else(|| Error::AssetNotFound)?;
 
-            let fee = info.fees().exchange().fixed() * asset.amount();
-            to_third_party
-                .entry(*info.creator())
-                .and_modify(|prev_fee| {
-                    *prev_fee += fee;
-                })
-                .or_insert(fee);
+            let fee = info.fees()
+                .exchange()
+                .fixed()
+                .checked_mul(asset.amount())
+                .ok_or(Error::Overflow)?;
+            let creator_fee = to_third_party.entry(*info.creator()).or_insert(0u64);
+            *creator_fee = creator_fee.checked_add(fee).ok_or(Error::Overflow)?;
         }
 
         let fees = ThirdPartyFees(to_third_party);
@@ -138,27 +281,28 @@ impl ThirdPartyFees {
         Ok(fees)
     }
 
-    /// Create `ThirdPartyFees` for `transfer` transactions.
+    /// Create `ThirdPartyFees` for `transfer` transactions, the royalties
+    /// owed to the creators of `assets`. See `assets::Fees`.
     pub fn new_transfer<S, I>(view: S, assets: I) -> Result<Self, Error>
     where
         S: AsRef<Snapshot>,
         I: IntoIterator<Item = AssetBundle>,
     {
         let view = view.as_ref();
-        let mut to_third_party = HashMap::new();
+        let mut to_third_party = BTreeMap::new();
 
         for asset in assets {
             let info = assets::Schema(view)
                 .fetch(&asset.id())
                 .ok_or_else(|| Error::AssetNotFound)?;
 
-            let fee = info.fees().transfer().fixed() * asset.amount();
-            to_third_party
-                .entry(*info.creator())
-                .and_modify(|prev_fee| {
-                    *prev_fee += fee;
-                })
-                .or_insert(fee);
+            let fee = info.fees()
+                .transfer()
+                .fixed()
+                .checked_mul(asset.amount())
+                .ok_or(Error::Overflow)?;
+            let creator_fee = to_third_party.entry(*info.creator()).or_insert(0u64);
+            *creator_fee = creator_fee.checked_add(fee).ok_or(Error::Overflow)?;
         }
 
         let fees = ThirdPartyFees(to_third_party);
@@ -179,18 +323,18 @@ impl ThirdPartyFees {
     }
 
     /// Add a new fee to the list of third party payments.
-    pub fn add_fee(&mut self, key: &PublicKey, fee: u64) {
-        self.0
-            .entry(*key)
-            .and_modify(|prev_fee| {
-                *prev_fee += fee;
-            })
-            .or_insert(fee);
+    ///
+    /// # Errors
+    /// Returns `Overflow` if the fees of `key` no longer fit in `u64`.
+    pub fn add_fee(&mut self, key: &PublicKey, fee: u64) -> Result<(), Error> {
+        let prev_fee = self.0.entry(*key).or_insert(0);
+        *prev_fee = prev_fee.checked_add(fee).ok_or(Error::Overflow)?;
+        Ok(())
     }
 
     /// Collect fees to third party wallets.
     ///
-    /// Returns a list of wallets modified by fee withdrawal.
+    /// Returns a list of wallets modified by fee withdrawal, ordered by key.
     /// This list must usually not be committed or discarded before
     /// the transaction has otherwise successfully executed.
     ///
@@ -200,7 +344,7 @@ impl ThirdPartyFees {
         &self,
         view: &Fork,
         payer_key: &PublicKey,
-    ) -> Result<HashMap<PublicKey, Wallet>, Error> {
+    ) -> Result<BTreeMap<PublicKey, Wallet>, Error> {
         let mut payer = wallet::Schema(&*view).fetch(&payer_key);
 
         let mut updated_wallets = self.0
@@ -213,7 +357,7 @@ impl ThirdPartyFees {
 
                 Ok((*key, wallet))
             })
-            .collect::<Result<HashMap<_, _>, _>>()?;
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
 
         updated_wallets.entry(*payer_key).or_insert(payer);
 
@@ -221,35 +365,40 @@ impl ThirdPartyFees {
     }
 
     /// Split fees to third party wallets between two payers.
+    ///
+    /// Each fee is split with `split_coins`, the first payer covers the odd coin.
     pub fn collect2(
         &self,
         view: &mut Fork,
         payer_key_1: &PublicKey,
         payer_key_2: &PublicKey,
-    ) -> Result<HashMap<PublicKey, Wallet>, Error> {
+    ) -> Result<BTreeMap<PublicKey, Wallet>, Error> {
         let mut payer_1 = wallet::Schema(&*view).fetch(&payer_key_1);
         let mut payer_2 = wallet::Schema(&*view).fetch(&payer_key_2);
 
         let mut to_third_party = self.0.clone();
 
         if let Some(fee) = to_third_party.remove(payer_key_1) {
-            wallet::move_coins(&mut payer_2, &mut payer_1, fee / 2)?;
+            let (_, second) = split_coins(fee);
+            wallet::move_coins(&mut payer_2, &mut payer_1, second)?;
         }
 
         if let Some(fee) = to_third_party.remove(payer_key_2) {
-            wallet::move_coins(&mut payer_1, &mut payer_2, fee / 2)?;
+            let (first, _) = split_coins(fee);
+            wallet::move_coins(&mut payer_1, &mut payer_2, first)?;
         }
 
         let mut updated_wallets = to_third_party
             .iter()
             .map(|(key, fee)| {
                 let mut wallet = wallet::Schema(&*view).fetch(&key);
-                wallet::move_coins(&mut payer_1, &mut wallet, fee / 2)?;
-                wallet::move_coins(&mut payer_2, &mut wallet, fee / 2)?;
+                let (first, second) = split_coins(*fee);
+                wallet::move_coins(&mut payer_1, &mut wallet, first)?;
+                wallet::move_coins(&mut payer_2, &mut wallet, second)?;
 
                 Ok((*key, wallet))
             })
-            .collect::<Result<HashMap<_, _>, _>>()?;
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
 
         updated_wallets.insert(*payer_key_1, payer_1);
         updated_wallets.insert(*payer_key_2, payer_2);
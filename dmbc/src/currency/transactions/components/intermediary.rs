@@ -1,4 +1,4 @@
-use exonum::crypto::PublicKey;
+use exonum::crypto::{PublicKey, Signature, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
 
 encoding_struct! {
     /// Intermediary specification for `_intermediary` transactions.
@@ -7,3 +7,29 @@ encoding_struct! {
         commission: u64,
     }
 }
+
+impl Intermediary {
+    /// Intermediary standing for no intermediary at all, see `is_none`.
+    pub fn none() -> Self {
+        Intermediary::new(&Intermediary::null_wallet(), 0)
+    }
+
+    /// Designated wallet of the null intermediary: the all-zero key, which
+    /// nobody can sign for.
+    pub fn null_wallet() -> PublicKey {
+        PublicKey::new([0; PUBLIC_KEY_LENGTH])
+    }
+
+    /// Signature to put in place of the null intermediary's one. It is
+    /// never checked.
+    pub fn null_signature() -> Signature {
+        Signature::new([0; SIGNATURE_LENGTH])
+    }
+
+    /// Whether this is the null intermediary: the null wallet with no
+    /// commission. Transactions with it skip the intermediary signature and
+    /// commission and behave like their plain counterparts.
+    pub fn is_none(&self) -> bool {
+        self.commission() == 0 && *self.wallet() == Intermediary::null_wallet()
+    }
+}
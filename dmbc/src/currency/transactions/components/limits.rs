@@ -0,0 +1,43 @@
+use std::collections::HashSet;
+
+use currency::assets::AssetId;
+use currency::configuration::Configuration;
+use currency::error::Error;
+
+/// Maximum length of the `data_info` memo of a transaction, in bytes.
+pub const MAX_DATA_INFO_LENGTH: usize = 256;
+
+/// Check that a transaction listing `count` assets stays within the
+/// `max_assets` of the on-chain `configuration`.
+///
+/// # Errors
+///
+/// Returns `TooManyAssets` if it lists more.
+pub fn check_assets_limit(configuration: &Configuration, count: usize) -> Result<(), Error> {
+    if count as u64 > configuration.max_assets() {
+        return Err(Error::TooManyAssets);
+    }
+    Ok(())
+}
+
+/// Whether every listed asset has a non-zero amount and is listed only once.
+/// Zero amounts pass the wallet checks trivially, and both would be charged
+/// per-asset fees for nothing.
+pub fn assets_distinct_non_zero<I>(assets: I) -> bool
+where
+    I: IntoIterator<Item = (AssetId, u64)>,
+{
+    let mut ids = HashSet::new();
+    assets.into_iter().all(|(id, amount)| amount != 0 && ids.insert(id))
+}
+
+/// Whether no asset is listed on both sides of an exchange. Such an asset
+/// would be moved back and forth, leaving only the fees charged for it.
+pub fn assets_disjoint<I, J>(first: I, second: J) -> bool
+where
+    I: IntoIterator<Item = AssetId>,
+    J: IntoIterator<Item = AssetId>,
+{
+    let ids = first.into_iter().collect::<HashSet<_>>();
+    second.into_iter().all(|id| !ids.contains(&id))
+}
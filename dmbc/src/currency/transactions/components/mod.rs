@@ -1,7 +1,25 @@
 //! Common transaction components.
 
+mod apply;
+mod expiry;
 mod fees;
 mod intermediary;
+mod limits;
+mod rollback;
+mod seeds;
+mod signing;
 
-pub use currency::transactions::components::fees::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+pub use currency::transactions::components::apply::Apply;
+pub use currency::transactions::components::expiry::check_offer_expiry;
+pub use currency::transactions::components::fees::{collect_operation_fee,
+                                                   operation_fees_table, percentage_fee,
+                                                   settle_blockchain_fee, split_coins,
+                                                   split_fee, FeeStrategy, FeesCalculator,
+                                                   ThirdPartyFees};
 pub use currency::transactions::components::intermediary::Intermediary;
+pub use currency::transactions::components::limits::{assets_disjoint, assets_distinct_non_zero,
+                                                     check_assets_limit, MAX_DATA_INFO_LENGTH};
+pub use currency::transactions::components::rollback::{process_with_policy,
+                                                        process_with_rollback};
+pub use currency::transactions::components::seeds::advance_seed;
+pub use currency::transactions::components::signing::offer_signing_bytes;
@@ -0,0 +1,69 @@
+use exonum::crypto::PublicKey;
+use exonum::storage::Fork;
+
+use currency::configuration::FailurePolicy;
+use currency::error::Error;
+use currency::revenue;
+use currency::service::CONFIGURATION;
+use currency::wallet;
+use currency::wallet::Wallet;
+
+/// Run `process` according to the configured `FailurePolicy`.
+///
+/// Under `FullRollback` the wallets of `payers`, of the fee recipient and
+/// of the validator reward, and the platform revenue, are restored if
+/// `process` fails. Nothing else is restored: the seed consumed by
+/// `advance_seed` stays used on purpose, so a failed transaction can't be
+/// signed again with the same seed, and offers pruned from `offer_fills`
+/// had expired anyway.
+pub fn process_with_policy<F>(
+    view: &mut Fork,
+    payers: &[PublicKey],
+    process: F,
+) -> Result<(), Error>
+where
+    F: FnOnce(&mut Fork) -> Result<(), Error>,
+{
+    let configuration = CONFIGURATION.read().unwrap().clone();
+    if configuration.failure_policy() == FailurePolicy::KeepFee {
+        return process(view);
+    }
+    process_with_rollback(view, payers, process)
+}
+
+/// Run `process`, restoring the wallets of `payers`, of the fee recipient
+/// and of the validator reward, and the platform revenue if it fails,
+/// whatever the configured `FailurePolicy`.
+pub fn process_with_rollback<F>(
+    view: &mut Fork,
+    payers: &[PublicKey],
+    process: F,
+) -> Result<(), Error>
+where
+    F: FnOnce(&mut Fork) -> Result<(), Error>,
+{
+    let configuration = CONFIGURATION.read().unwrap().clone();
+    let mut keys = payers.to_vec();
+    keys.push(*configuration.fees().recipient());
+    if let Some(reward) = configuration.validator_reward() {
+        keys.push(reward.wallet);
+    }
+    let before: Vec<(PublicKey, Wallet)> = keys.into_iter()
+        .map(|key| {
+            let wallet = wallet::Schema(&*view).fetch(&key);
+            (key, wallet)
+        })
+        .collect();
+    let revenue_before = revenue::Schema(&*view).total();
+
+    let result = process(view);
+    if result.is_err() {
+        for (key, wallet) in before {
+            wallet::Schema(&mut *view).store(&key, wallet);
+        }
+        if revenue::Schema(&*view).total() != revenue_before {
+            revenue::Schema(&mut *view).restore(revenue_before);
+        }
+    }
+    result
+}
@@ -0,0 +1,18 @@
+use exonum::crypto::PublicKey;
+use exonum::storage::Fork;
+
+use currency::error::Error;
+use currency::seeds;
+use currency::service::CONFIGURATION;
+
+/// Use up the `seed` of a transaction from `sender` if seeds are nonces,
+/// see `Configuration::seed_nonces`. Does nothing otherwise.
+///
+/// # Errors
+/// Returns `StaleSeed` if the seed is not above the last seed of `sender`.
+pub fn advance_seed(view: &mut Fork, sender: &PublicKey, seed: u64) -> Result<(), Error> {
+    if !CONFIGURATION.read().unwrap().seed_nonces() {
+        return Ok(());
+    }
+    seeds::Schema(view).advance(sender, seed)
+}
@@ -0,0 +1,17 @@
+use currency::SERVICE_ID;
+
+/// Bytes the parties of an offer sign.
+///
+/// The signature of a transaction covers the message header with the
+/// service and message ids, but offers are signed detached from the
+/// message. The same ids are put in front of the offer so a signature
+/// given for one transaction type doesn't verify for another one.
+pub fn offer_signing_bytes(message_id: u16, offer_raw: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(4 + offer_raw.len());
+    bytes.push(SERVICE_ID as u8);
+    bytes.push((SERVICE_ID >> 8) as u8);
+    bytes.push(message_id as u8);
+    bytes.push((message_id >> 8) as u8);
+    bytes.extend_from_slice(offer_raw);
+    bytes
+}
@@ -9,11 +9,15 @@ use prometheus::{IntCounter, Histogram};
 use currency::assets;
 use currency::assets::AssetBundle;
 use currency::error::Error;
+use currency::history;
+use currency::multisig;
 use currency::status;
-use currency::transactions::components::FeesCalculator;
+use currency::transactions::components::{advance_seed, process_with_policy,
+                                         settle_blockchain_fee, Apply, FeesCalculator};
 use currency::wallet;
 use currency::SERVICE_ID;
 use currency::service::CONFIGURATION;
+use keys;
 
 /// Transaction ID.
 pub const DELETE_ASSETS_ID: u16 = 400;
@@ -31,34 +35,58 @@ message! {
 }
 
 impl FeesCalculator for DeleteAssets {
-    fn calculate_fees(&self, _view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
-        let tx_fee = genesis_fees.delete_assets();
 
         let mut fees_table = HashMap::new();
         if genesis_fees.recipient() != self.pub_key() {
-            fees_table.insert(*self.pub_key(), tx_fee);
+            let creator = wallet::Schema(&*view).fetch(self.pub_key());
+            fees_table.insert(*self.pub_key(), self.tx_fee(&creator)?);
         }
         Ok(fees_table)
     }
 }
 
 impl DeleteAssets {
+    /// Blockchain fee: the fixed `delete_assets` fee plus the per-unit fee
+    /// for every deleted unit, discounted for a qualifying `payer`.
+    fn tx_fee(&self, payer: &wallet::Wallet) -> Result<u64, Error> {
+        let configuration = CONFIGURATION.read().unwrap();
+        let per_entry = configuration.delete_assets_per_entry();
+        let fee = self.assets()
+            .iter()
+            .try_fold(configuration.fees().delete_assets(), |fee, asset| {
+                asset
+                    .amount()
+                    .checked_mul(per_entry)
+                    .and_then(|asset_fee| fee.checked_add(asset_fee))
+            })
+            .ok_or(Error::Overflow)?;
+        Ok(configuration.payer_fee(fee, payer))
+    }
+
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
+        advance_seed(view, self.pub_key(), self.seed())?;
 
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
 
+        multisig::Schema(&*view).check(&[*self.pub_key()])?;
+
         let genesis_pub = genesis_fees.recipient();
         let creator_pub = self.pub_key();
 
+        let fee_currency = CONFIGURATION.read().unwrap().fee_currency(DELETE_ASSETS_ID);
         let mut genesis = wallet::Schema(&*view).fetch(&genesis_pub);
+        let genesis_balance = genesis.balance();
         let mut creator = wallet::Schema(&*view).fetch(&creator_pub);
 
-        wallet::move_coins(&mut creator, &mut genesis, genesis_fees.delete_assets())?;
+        let tx_fee = self.tx_fee(&creator)?;
+        wallet::move_fee(&mut creator, &mut genesis, tx_fee, fee_currency)?;
 
+        let collected = genesis.balance() - genesis_balance;
         wallet::Schema(&mut *view).store(&genesis_pub, genesis);
         wallet::Schema(&mut *view).store(&creator_pub, creator.clone());
+        settle_blockchain_fee(view, collected)?;
 
         let mut infos = HashMap::new();
 
@@ -84,6 +112,26 @@ impl DeleteAssets {
     }
 }
 
+impl Apply for DeleteAssets {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.verify_signature(self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.pub_key()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.pub_key()], &self.hash());
+
+        result
+    }
+}
+
 lazy_static! {
     static ref VERIFY_COUNT: IntCounter = register_int_counter!(
         "dmbc_transaction_delete_assets_verify_count",
@@ -115,11 +163,7 @@ impl Transaction for DeleteAssets {
     fn verify(&self) -> bool {
         VERIFY_COUNT.inc();
 
-        if cfg!(fuzzing) {
-            return true;
-        }
-
-        if self.verify_signature(self.pub_key()) {
+        if self.check() {
             VERIFY_SUCCESS_COUNT.inc();
             true
         } else {
@@ -128,16 +172,27 @@ impl Transaction for DeleteAssets {
     }
 
     fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
         EXECUTE_COUNT.inc();
         let timer = EXECUTE_DURATION.start_timer();
 
-        let result = self.process(view);
+        debug!(
+            "Processing delete_assets {} by {}",
+            self.hash().to_hex(),
+            keys::log(self.pub_key())
+        );
+
+        let result = self.apply(view);
 
         if let &Ok(_) = &result {
             EXECUTE_SUCCESS_COUNT.inc();
         }
 
-        status::Schema(view).store(self.hash(), result);
+        info!("Applied delete_assets {}: {:?}", self.hash().to_hex(), result);
 
         timer.observe_duration();
         EXECUTE_FINISH_COUNT.inc();
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets::AssetBundle;
+use currency::blacklist;
+use currency::error::Error;
+use currency::frozen;
+use currency::escrow;
+use currency::escrow::Escrow;
+use currency::history;
+use currency::multisig;
+use currency::non_fungible;
+use currency::status;
+use currency::transactions::components::{advance_seed, assets_distinct_non_zero,
+                                         check_assets_limit, process_with_policy,
+                                         settle_blockchain_fee, Apply, FeesCalculator,
+                                         ThirdPartyFees};
+use currency::wallet;
+use currency::wallet::Wallet;
+use currency::SERVICE_ID;
+use currency::service::CONFIGURATION;
+use keys;
+
+/// Transaction ID.
+pub const ESCROW_CREATE_ID: u16 = 800;
+
+message! {
+    /// `escrow_create` transaction.
+    ///
+    /// Locks `amount` coins and `assets` of the sender in an escrow keyed by
+    /// the hash of this transaction. The funds leave the sender's wallet
+    /// immediately and are paid out by either `EscrowRelease` or
+    /// `EscrowRefund`. Fees are the same as for `Transfer`.
+    struct EscrowCreate {
+        const TYPE = SERVICE_ID;
+        const ID = ESCROW_CREATE_ID;
+
+        from:      &PublicKey,
+        to:        &PublicKey,
+        amount:    u64,
+        assets:    Vec<AssetBundle>,
+        seed:      u64,
+        data_info: &str,
+    }
+}
+
+impl FeesCalculator for EscrowCreate {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fees = ThirdPartyFees::new_transfer(&*view, self.assets())?;
+
+        let mut fees_table = HashMap::new();
+        if genesis_fees.recipient() != self.from() {
+            let wallet_from = wallet::Schema(&*view).fetch(self.from());
+            let fee = configuration.payer_fee(genesis_fees.transfer(), &wallet_from);
+            fees_table.insert(*self.from(), fee);
+        }
+
+        for (pub_key, fee) in fees.0 {
+            if pub_key != *self.from() {
+                let total = fees_table.entry(*self.from()).or_insert(0);
+                *total = total.checked_add(fee).ok_or(Error::Overflow)?;
+            }
+        }
+
+        Ok(fees_table)
+    }
+}
+
+impl EscrowCreate {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.from(), self.seed())?;
+
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+
+        multisig::Schema(&*view).check(&[*self.from()])?;
+        check_assets_limit(&configuration, self.assets().len())?;
+        blacklist::Schema(&*view).check(self.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(self.assets().iter().map(|a| a.id()))?;
+        non_fungible::Schema(&*view).check(self.assets().iter())?;
+
+        let fee_currency = configuration.fee_currency(ESCROW_CREATE_ID);
+        let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
+        let genesis_balance = genesis.balance();
+
+        // Collect the blockchain fee. Execution shall not continue if this fails.
+        let mut wallet_from = wallet::Schema(&*view).fetch(self.from());
+        let fee = configuration.payer_fee(genesis_fees.transfer(), &wallet_from);
+        wallet::move_fee(&mut wallet_from, &mut genesis, fee, fee_currency)?;
+
+        wallet::Schema(&mut *view).store(self.from(), wallet_from);
+        let collected = genesis.balance() - genesis_balance;
+        wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
+        settle_blockchain_fee(view, collected)?;
+
+        let fees = ThirdPartyFees::new_transfer(&*view, self.assets())?;
+
+        // Operations bellow must either all succeed, or return an error without
+        // saving anything to the database.
+
+        // Process third party fees.
+        let mut updated_wallets = fees.collect(view, self.from())?;
+
+        // Lock the funds.
+        let mut wallet_from = updated_wallets
+            .remove(&self.from())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(&self.from()));
+
+        let mut locked = Wallet::new_empty();
+        wallet::spend_coins(&mut wallet_from, &mut locked, self.amount(), configuration.reserve())?;
+        wallet::move_assets(&mut wallet_from, &mut locked, &self.assets())?;
+
+        updated_wallets.insert(*self.from(), wallet_from);
+
+        // Save changes to the database.
+        for (key, wallet) in updated_wallets {
+            wallet::Schema(&mut *view).store(&key, wallet);
+        }
+
+        let escrow = Escrow::new(self.from(), self.to(), locked.balance(), locked.assets());
+        escrow::Schema(&mut *view).store(&self.hash(), escrow);
+
+        Ok(())
+    }
+}
+
+impl Apply for EscrowCreate {
+    fn check(&self) -> bool {
+        let wallets_ok = self.from() != self.to();
+        // An escrow locking nothing would only pay the fee.
+        let contents_ok = self.amount() != 0 || !self.assets().is_empty();
+        let assets_ok =
+            assets_distinct_non_zero(self.assets().iter().map(|a| (a.id(), a.amount())));
+
+        if cfg!(fuzzing) {
+            return wallets_ok && contents_ok && assets_ok;
+        }
+
+        let verify_ok = self.verify_signature(&self.from());
+
+        wallets_ok && contents_ok && assets_ok && verify_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.from()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.from(), *self.to()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_create_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_create_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_create_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_create_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_create_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_escrow_create_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for EscrowCreate {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing escrow_create {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(self.from()),
+            keys::log(self.to())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied escrow_create {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
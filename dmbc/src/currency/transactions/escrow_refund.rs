@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::{Hash, PublicKey};
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::blacklist;
+use currency::error::Error;
+use currency::escrow;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::status;
+use currency::transactions::components::{advance_seed, collect_operation_fee,
+                                         operation_fees_table, process_with_policy,
+                                         Apply, FeesCalculator};
+use currency::wallet;
+use currency::SERVICE_ID;
+
+/// Transaction ID.
+pub const ESCROW_REFUND_ID: u16 = 802;
+
+message! {
+    /// `escrow_refund` transaction.
+    ///
+    /// Returns the funds locked by an `EscrowCreate` to its sender.
+    /// Must be signed by the recipient of the escrow.
+    /// The signer pays the `Configuration::operation_fee` of the
+    /// transaction, so it can't be a multisig wallet.
+    struct EscrowRefund {
+        const TYPE = SERVICE_ID;
+        const ID = ESCROW_REFUND_ID;
+
+        to:     &PublicKey,
+        escrow: &Hash,
+        seed:   u64,
+    }
+}
+
+impl FeesCalculator for EscrowRefund {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(operation_fees_table(&*view, self.to(), ESCROW_REFUND_ID))
+    }
+}
+
+impl EscrowRefund {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.to(), self.seed())?;
+
+        let escrow = escrow::Schema(&*view)
+            .fetch(self.escrow())
+            .ok_or(Error::EscrowNotFound)?;
+
+        if escrow.to() != self.to() {
+            return Err(Error::InvalidTransaction);
+        }
+
+        multisig::Schema(&*view).check(&[*self.to()])?;
+        // Assets frozen or blacklisted while locked stay in the escrow.
+        blacklist::Schema(&*view).check(escrow.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(escrow.assets().iter().map(|a| a.id()))?;
+        collect_operation_fee(view, self.to(), ESCROW_REFUND_ID)?;
+
+        let wallet = wallet::Schema(&*view).fetch(escrow.from());
+        let wallet = escrow.credit(wallet)?;
+
+        wallet::Schema(&mut *view).store(escrow.from(), wallet);
+        escrow::Schema(&mut *view).remove(self.escrow());
+
+        Ok(())
+    }
+}
+
+impl Apply for EscrowRefund {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.verify_signature(self.to())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        // The escrow is closed by a successful refund, look up its
+        // sender beforehand.
+        let mut pub_keys = vec![*self.to()];
+        if let Some(escrow) = escrow::Schema(&*view).fetch(self.escrow()) {
+            pub_keys.push(*escrow.from());
+        }
+
+        let result = process_with_policy(view, &[*self.to()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&pub_keys, &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_refund_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_refund_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_refund_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_refund_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_refund_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_escrow_refund_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for EscrowRefund {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied escrow_refund {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
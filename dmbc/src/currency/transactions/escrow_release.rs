@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::{Hash, PublicKey};
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::blacklist;
+use currency::error::Error;
+use currency::escrow;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::status;
+use currency::transactions::components::{advance_seed, collect_operation_fee,
+                                         operation_fees_table, process_with_policy,
+                                         Apply, FeesCalculator};
+use currency::wallet;
+use currency::SERVICE_ID;
+
+/// Transaction ID.
+pub const ESCROW_RELEASE_ID: u16 = 801;
+
+message! {
+    /// `escrow_release` transaction.
+    ///
+    /// Pays the funds locked by an `EscrowCreate` out to its recipient.
+    /// Must be signed by the sender of the escrow.
+    /// The signer pays the `Configuration::operation_fee` of the
+    /// transaction, so it can't be a multisig wallet.
+    struct EscrowRelease {
+        const TYPE = SERVICE_ID;
+        const ID = ESCROW_RELEASE_ID;
+
+        from:   &PublicKey,
+        escrow: &Hash,
+        seed:   u64,
+    }
+}
+
+impl FeesCalculator for EscrowRelease {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(operation_fees_table(&*view, self.from(), ESCROW_RELEASE_ID))
+    }
+}
+
+impl EscrowRelease {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.from(), self.seed())?;
+
+        let escrow = escrow::Schema(&*view)
+            .fetch(self.escrow())
+            .ok_or(Error::EscrowNotFound)?;
+
+        if escrow.from() != self.from() {
+            return Err(Error::InvalidTransaction);
+        }
+
+        multisig::Schema(&*view).check(&[*self.from()])?;
+        // Assets frozen or blacklisted while locked stay in the escrow.
+        blacklist::Schema(&*view).check(escrow.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(escrow.assets().iter().map(|a| a.id()))?;
+        collect_operation_fee(view, self.from(), ESCROW_RELEASE_ID)?;
+
+        let wallet = wallet::Schema(&*view).fetch(escrow.to());
+        let wallet = escrow.credit(wallet)?;
+
+        wallet::Schema(&mut *view).store(escrow.to(), wallet);
+        escrow::Schema(&mut *view).remove(self.escrow());
+
+        Ok(())
+    }
+}
+
+impl Apply for EscrowRelease {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.verify_signature(self.from())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        // The escrow is closed by a successful release, look up its
+        // recipient beforehand.
+        let mut pub_keys = vec![*self.from()];
+        if let Some(escrow) = escrow::Schema(&*view).fetch(self.escrow()) {
+            pub_keys.push(*escrow.to());
+        }
+
+        let result = process_with_policy(view, &[*self.from()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&pub_keys, &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_release_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_release_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_release_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_release_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_escrow_release_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_escrow_release_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for EscrowRelease {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied escrow_release {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
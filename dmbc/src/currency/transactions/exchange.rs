@@ -8,15 +8,25 @@ use exonum::storage::Fork;
 use prometheus::{IntCounter, Histogram};
 
 use currency::assets::AssetBundle;
+use currency::blacklist;
 use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
 use currency::status;
-use currency::transactions::components::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{advance_seed, assets_disjoint,
+                                         assets_distinct_non_zero, check_assets_limit,
+                                         check_offer_expiry, offer_signing_bytes,
+                                         percentage_fee, process_with_policy,
+                                         settle_blockchain_fee, split_coins, Apply, FeeStrategy,
+                                         FeesCalculator, ThirdPartyFees};
 use currency::wallet;
 use currency::SERVICE_ID;
 use currency::service::CONFIGURATION;
+use keys;
 
 /// Transaction ID.
-pub const EXCHANGE_ID: u16 = 601;
+pub const EXCHANGE_ID: u16 = 604;
 
 encoding_struct! {
     struct ExchangeOffer {
@@ -30,11 +40,15 @@ encoding_struct! {
         fee_strategy:     u8,
         seed:              u64,
         data_info:         &str,
+        valid_until_height: u64,
     }
 }
 
 message! {
     /// `exchange` transaction.
+    ///
+    /// Replaces the retired layout with ID 601, see the wire format notes of
+    /// `transactions`.
     struct Exchange {
         const TYPE = SERVICE_ID;
         const ID = EXCHANGE_ID;
@@ -47,7 +61,11 @@ message! {
 impl FeesCalculator for Exchange {
     fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
         let offer = self.offer();
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fee_rate_bps = configuration.fee_rate_bps();
+        let exchange_fee =
+            percentage_fee(genesis_fees.exchange(), offer.sender_value(), fee_rate_bps)?;
         let fees = ThirdPartyFees::new_exchange(
             &*view,
             offer
@@ -56,11 +74,11 @@ impl FeesCalculator for Exchange {
                 .chain(offer.recipient_assets().into_iter()),
         )?;
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
 
         let mut fees_table = HashMap::new();
 
-        let payers = self.payers(&fee_strategy, genesis_fees.exchange())?;
+        let payers = self.genesis_payers(&*view, &fee_strategy, exchange_fee)?;
         for (payer_key, fee) in payers {
             if genesis_fees.recipient() != &payer_key {
                 fees_table.insert(payer_key, fee);
@@ -72,7 +90,8 @@ impl FeesCalculator for Exchange {
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    let total = fees_table.entry(payer_key).or_insert(0);
+                    *total = total.checked_add(fee).ok_or(Error::Overflow)?;
                 }
             }
         }
@@ -88,43 +107,96 @@ impl Exchange {
             FeeStrategy::Recipient => vec![(*offer.recipient(), fee)],
             FeeStrategy::Sender => vec![(*offer.sender(), fee)],
             FeeStrategy::RecipientAndSender => {
-                vec![(*offer.sender(), fee / 2), (*offer.recipient(), fee / 2)]
+                let (first, second) = split_coins(fee);
+                vec![(*offer.sender(), first), (*offer.recipient(), second)]
             }
-            FeeStrategy::Intermediary => return Err(Error::InvalidTransaction),
+            FeeStrategy::Intermediary => return Err(Error::InvalidFeeStrategy),
         };
         Ok(payers)
     }
 
+    /// Shares of the blockchain fee by payer, discounted for the payers
+    /// qualifying for the configured fee discount.
+    fn genesis_payers(
+        &self,
+        view: &Fork,
+        fee_strategy: &FeeStrategy,
+        fee: u64,
+    ) -> Result<Vec<(PublicKey, u64)>, Error> {
+        let configuration = CONFIGURATION.read().unwrap();
+        let payers = self.payers(fee_strategy, fee)?
+            .into_iter()
+            .map(|(key, fee)| {
+                let payer = wallet::Schema(view).fetch(&key);
+                (key, configuration.payer_fee(fee, &payer))
+            })
+            .collect();
+        Ok(payers)
+    }
+
     /// Get raw bytes of the offer.
     pub fn offer_raw(&self) -> Vec<u8> {
         self.offer().raw
     }
 
+    /// Bytes of the offer signed by its parties, see `offer_signing_bytes`.
+    pub fn offer_signing_bytes(&self) -> Vec<u8> {
+        offer_signing_bytes(EXCHANGE_ID, &self.offer().raw)
+    }
+
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
+        advance_seed(view, self.offer().recipient(), self.offer().seed())?;
 
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fee_rate_bps = configuration.fee_rate_bps();
 
         let offer = self.offer();
+        multisig::Schema(&*view).check(&[*offer.sender(), *offer.recipient()])?;
+        let count = offer.sender_assets().len() + offer.recipient_assets().len();
+        check_assets_limit(&configuration, count)?;
+        check_offer_expiry(&*view, offer.valid_until_height())?;
+
+        let exchange_fee =
+            percentage_fee(genesis_fees.exchange(), offer.sender_value(), fee_rate_bps)?;
 
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
+
+        blacklist::Schema(&*view).check(
+            offer
+                .sender_assets()
+                .iter()
+                .chain(offer.recipient_assets().iter())
+                .map(|a| a.id()),
+        )?;
+        frozen::Schema(&*view).check(
+            offer
+                .sender_assets()
+                .iter()
+                .chain(offer.recipient_assets().iter())
+                .map(|a| a.id()),
+        )?;
 
+        let fee_currency = configuration.fee_currency(EXCHANGE_ID);
         let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
+        let genesis_balance = genesis.balance();
 
         // Collect the blockchain fee. Execution shall not continue if this fails.
         match fee_strategy {
             FeeStrategy::Recipient => {
                 let mut recipient = wallet::Schema(&*view).fetch(offer.recipient());
 
-                wallet::move_coins(&mut recipient, &mut genesis, genesis_fees.exchange())?;
+                let fee = configuration.payer_fee(exchange_fee, &recipient);
+                wallet::move_fee(&mut recipient, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.recipient(), recipient);
             }
             FeeStrategy::Sender => {
                 let mut sender = wallet::Schema(&*view).fetch(offer.sender());
 
-                wallet::move_coins(&mut sender, &mut genesis, genesis_fees.exchange())?;
+                let fee = configuration.payer_fee(exchange_fee, &sender);
+                wallet::move_fee(&mut sender, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.sender(), sender);
             }
@@ -132,16 +204,21 @@ impl Exchange {
                 let mut recipient = wallet::Schema(&*view).fetch(offer.recipient());
                 let mut sender = wallet::Schema(&*view).fetch(offer.sender());
 
-                wallet::move_coins(&mut recipient, &mut genesis, genesis_fees.exchange() / 2)?;
-                wallet::move_coins(&mut sender, &mut genesis, genesis_fees.exchange() / 2)?;
+                let (first, second) = split_coins(exchange_fee);
+                let first = configuration.payer_fee(first, &sender);
+                let second = configuration.payer_fee(second, &recipient);
+                wallet::move_fee(&mut sender, &mut genesis, first, fee_currency)?;
+                wallet::move_fee(&mut recipient, &mut genesis, second, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.sender(), sender);
                 wallet::Schema(&mut *view).store(offer.recipient(), recipient);
             }
-            FeeStrategy::Intermediary => return Err(Error::InvalidTransaction),
+            FeeStrategy::Intermediary => return Err(Error::InvalidFeeStrategy),
         }
 
+        let collected = genesis.balance() - genesis_balance;
         wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
+        settle_blockchain_fee(view, collected)?;
 
         let fees = ThirdPartyFees::new_exchange(
             &*view,
@@ -172,7 +249,12 @@ impl Exchange {
             .remove(&offer.recipient())
             .unwrap_or_else(|| wallet::Schema(&*view).fetch(&offer.recipient()));
 
-        wallet::move_coins(&mut sender, &mut recipient, offer.sender_value())?;
+        wallet::spend_coins(
+            &mut sender,
+            &mut recipient,
+            offer.sender_value(),
+            configuration.reserve(),
+        )?;
         wallet::move_assets(&mut sender, &mut recipient, &offer.sender_assets())?;
         wallet::move_assets(&mut recipient, &mut sender, &offer.recipient_assets())?;
 
@@ -188,6 +270,49 @@ impl Exchange {
     }
 }
 
+impl Apply for Exchange {
+    fn check(&self) -> bool {
+        let offer = self.offer();
+
+        let wallets_ok = offer.sender() != offer.recipient();
+        let fee_strategy_ok = match FeeStrategy::try_from(offer.fee_strategy()) {
+            Some(FeeStrategy::Recipient)
+            | Some(FeeStrategy::Sender)
+            | Some(FeeStrategy::RecipientAndSender) => true,
+            _ => false,
+        };
+        let sender_assets = offer.sender_assets();
+        let recipient_assets = offer.recipient_assets();
+        let assets_ok =
+            assets_distinct_non_zero(sender_assets.iter().map(|a| (a.id(), a.amount())))
+                && assets_distinct_non_zero(recipient_assets.iter().map(|a| (a.id(), a.amount())))
+                && assets_disjoint(
+                    sender_assets.iter().map(|a| a.id()),
+                    recipient_assets.iter().map(|a| a.id()),
+                );
+
+        if cfg!(fuzzing) {
+            return wallets_ok && fee_strategy_ok && assets_ok;
+        }
+
+        let recipient_ok = self.verify_signature(offer.recipient());
+        let sender_ok = crypto::verify(self.sender_signature(), &self.offer_signing_bytes(), offer.sender());
+
+        wallets_ok && fee_strategy_ok && assets_ok && recipient_ok && sender_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let payers = [*self.offer().sender(), *self.offer().recipient()];
+        let result = process_with_policy(view, &payers, |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.offer().sender(), *self.offer().recipient()], &self.hash());
+
+        result
+    }
+}
+
 lazy_static! {
     static ref VERIFY_COUNT: IntCounter = register_int_counter!(
         "dmbc_transaction_exchange_verify_count",
@@ -219,22 +344,7 @@ impl Transaction for Exchange {
     fn verify(&self) -> bool {
         VERIFY_COUNT.inc();
 
-        let offer = self.offer();
-
-        let wallets_ok = offer.sender() != offer.recipient();
-        let fee_strategy_ok = match FeeStrategy::try_from(offer.fee_strategy()).unwrap() {
-            FeeStrategy::Recipient | FeeStrategy::Sender | FeeStrategy::RecipientAndSender => true,
-            _ => false,
-        };
-
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
-
-        let recipient_ok = self.verify_signature(offer.recipient());
-        let sender_ok = crypto::verify(self.sender_signature(), &offer.raw, offer.sender());
-
-        if wallets_ok && fee_strategy_ok && recipient_ok && sender_ok {
+        if self.check() {
             VERIFY_SUCCESS_COUNT.inc();
             true
         } else {
@@ -243,16 +353,28 @@ impl Transaction for Exchange {
     }
 
     fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
         EXECUTE_COUNT.inc();
         let timer = EXECUTE_DURATION.start_timer();
 
-        let result = self.process(view);
+        debug!(
+            "Processing exchange {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(self.offer().sender()),
+            keys::log(self.offer().recipient())
+        );
+
+        let result = self.apply(view);
 
         if let &Ok(_) = &result {
             EXECUTE_SUCCESS_COUNT.inc();
         }
 
-        status::Schema(view).store(self.hash(), result);
+        info!("Applied exchange {}: {:?}", self.hash().to_hex(), result);
 
         timer.observe_duration();
         EXECUTE_FINISH_COUNT.inc();
@@ -8,15 +8,28 @@ use exonum::storage::Fork;
 use prometheus::{IntCounter, Histogram};
 
 use currency::assets::AssetBundle;
+use currency::blacklist;
 use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::intermediaries;
+use currency::memos;
+use currency::multisig;
 use currency::status;
-use currency::transactions::components::{FeeStrategy, FeesCalculator, Intermediary, ThirdPartyFees};
+use currency::transactions::components::{advance_seed, assets_disjoint,
+                                         assets_distinct_non_zero, check_assets_limit,
+                                         check_offer_expiry, offer_signing_bytes,
+                                         percentage_fee, process_with_rollback,
+                                         settle_blockchain_fee, split_coins, Apply, FeeStrategy,
+                                         FeesCalculator, Intermediary, ThirdPartyFees,
+                                         MAX_DATA_INFO_LENGTH};
 use currency::wallet;
 use currency::{Service, SERVICE_ID};
 use currency::service::CONFIGURATION;
+use keys;
 
 /// Transaction ID.
-pub const EXCHANGE_INTERMEDIARY_ID: u16 = 602;
+pub const EXCHANGE_INTERMEDIARY_ID: u16 = 605;
 
 encoding_struct! {
     struct ExchangeOfferIntermediary {
@@ -32,11 +45,27 @@ encoding_struct! {
         fee_strategy:     u8,
         seed:             u64,
         data_info:        &str,
+        valid_until_height: u64,
     }
 }
 
 message! {
     /// `exchange_intermediary` transaction.
+    ///
+    /// Replaces the retired layout with ID 602, see the wire format notes of
+    /// `transactions`.
+    ///
+    /// The blockchain fee, the intermediary commission and the exchange
+    /// itself are applied atomically: if any of them fails, the wallets of
+    /// the parties and of the fee recipient are restored, so no fee is kept
+    /// whatever the configured `FailurePolicy`.
+    ///
+    /// With the null intermediary (see `Intermediary::is_none`) neither the
+    /// intermediary signature nor its commission is involved, and the
+    /// transaction behaves like a plain `exchange`.
+    ///
+    /// Fails with `InvalidTransaction` if the intermediary wallet is the
+    /// fee recipient of the configuration.
     struct ExchangeIntermediary {
         const TYPE = SERVICE_ID;
         const ID = EXCHANGE_INTERMEDIARY_ID;
@@ -50,7 +79,12 @@ message! {
 impl FeesCalculator for ExchangeIntermediary {
     fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
         let offer = self.offer();
-        let genesis_fee = CONFIGURATION.read().unwrap().fees().exchange();
+        let fee_rate_bps = CONFIGURATION.read().unwrap().fee_rate_bps();
+        let genesis_fee = percentage_fee(
+            CONFIGURATION.read().unwrap().fees().exchange(),
+            offer.sender_value(),
+            fee_rate_bps,
+        )?;
         let mut fees = ThirdPartyFees::new_exchange(
             &*view,
             offer
@@ -58,16 +92,18 @@ impl FeesCalculator for ExchangeIntermediary {
                 .into_iter()
                 .chain(offer.recipient_assets().into_iter()),
         )?;
-        fees.add_fee(
-            offer.intermediary().wallet(),
-            offer.intermediary().commission()
-        );
+        if !offer.intermediary().is_none() {
+            fees.add_fee(
+                offer.intermediary().wallet(),
+                offer.intermediary().commission()
+            )?;
+        }
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
 
         let mut fees_table = HashMap::new();
 
-        let payers = self.payers(&fee_strategy, genesis_fee)?;
+        let payers = self.genesis_payers(&*view, &fee_strategy, genesis_fee)?;
         for (payer_key, fee) in payers {
             if Service::genesis_wallet(&*view) != payer_key {
                 fees_table.insert(payer_key, fee);
@@ -79,7 +115,8 @@ impl FeesCalculator for ExchangeIntermediary {
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    let total = fees_table.entry(payer_key).or_insert(0);
+                    *total = total.checked_add(fee).ok_or(Error::Overflow)?;
                 }
             }
         }
@@ -95,43 +132,190 @@ impl ExchangeIntermediary {
             FeeStrategy::Recipient => vec![(*offer.recipient(), fee)],
             FeeStrategy::Sender => vec![(*offer.sender(), fee)],
             FeeStrategy::RecipientAndSender => {
-                vec![(*offer.sender(), fee / 2), (*offer.recipient(), fee / 2)]
+                let (first, second) = split_coins(fee);
+                vec![(*offer.sender(), first), (*offer.recipient(), second)]
             }
             FeeStrategy::Intermediary => vec![(*offer.intermediary().wallet(), fee)],
         };
         Ok(payers)
     }
 
+    /// Shares of the blockchain fee by payer, discounted for the payers
+    /// qualifying for the configured fee discount.
+    fn genesis_payers(
+        &self,
+        view: &Fork,
+        fee_strategy: &FeeStrategy,
+        fee: u64,
+    ) -> Result<Vec<(PublicKey, u64)>, Error> {
+        let configuration = CONFIGURATION.read().unwrap();
+        let payers = self.payers(fee_strategy, fee)?
+            .into_iter()
+            .map(|(key, fee)| {
+                let payer = wallet::Schema(view).fetch(&key);
+                (key, configuration.payer_fee(fee, &payer))
+            })
+            .collect();
+        Ok(payers)
+    }
+
+    /// Check that every payer is able to cover everything it owes in this
+    /// transaction at once: the blockchain fee if paid in coins, third party
+    /// fees, the intermediary commission and the `sender_value`. Nothing is
+    /// written.
+    ///
+    /// # Errors
+    /// Returns `InsufficientFunds` if any of the payers can't cover the total,
+    /// or `Overflow` if the total doesn't fit into `u64`.
+    fn check_funds(
+        &self,
+        view: &Fork,
+        fee_strategy: &FeeStrategy,
+        genesis_fee: u64,
+        fees: &ThirdPartyFees,
+    ) -> Result<(), Error> {
+        let offer = self.offer();
+
+        let fee_currency = CONFIGURATION.read().unwrap().fee_currency(EXCHANGE_INTERMEDIARY_ID);
+        let mut debits: Vec<_> = self.genesis_payers(view, fee_strategy, genesis_fee)?
+            .into_iter()
+            .map(|(key, fee)| (key, fee_currency.coins(fee)))
+            .collect();
+        for (receiver_key, fee) in &fees.0 {
+            let payers = self.payers(fee_strategy, *fee)?;
+            debits.extend(payers.into_iter().filter(|&(key, _)| key != *receiver_key));
+        }
+        debits.push((*offer.sender(), offer.sender_value()));
+
+        let mut required: HashMap<PublicKey, u64> = HashMap::new();
+        for (key, amount) in debits {
+            let total = required.entry(key).or_insert(0);
+            *total = total.checked_add(amount).ok_or(Error::Overflow)?;
+        }
+
+        // Fees may dip into the reserve, the payment keeps it when it moves.
+        for (key, amount) in required {
+            if !wallet::can_spend(&wallet::Schema(&*view).fetch(&key), amount, 0) {
+                return Err(Error::InsufficientFunds);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get raw bytes of the offer.
     pub fn offer_raw(&self) -> Vec<u8> {
         self.offer().raw
     }
 
+    /// Bytes of the offer signed by its parties, see `offer_signing_bytes`.
+    pub fn offer_signing_bytes(&self) -> Vec<u8> {
+        offer_signing_bytes(EXCHANGE_INTERMEDIARY_ID, &self.offer().raw)
+    }
+
+    /// Wallets involved in the transaction, without the null intermediary.
+    fn parties(&self) -> Vec<PublicKey> {
+        let offer = self.offer();
+        let mut parties = vec![*offer.sender(), *offer.recipient()];
+        if !offer.intermediary().is_none() {
+            parties.push(*offer.intermediary().wallet());
+        }
+        parties
+    }
+
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
+        advance_seed(view, self.offer().recipient(), self.offer().seed())?;
 
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fee_rate_bps = configuration.fee_rate_bps();
 
         let offer = self.offer();
+        multisig::Schema(&*view).check(&[*offer.sender(), *offer.recipient()])?;
+        let count = offer.sender_assets().len() + offer.recipient_assets().len();
+        check_assets_limit(&configuration, count)?;
+        check_offer_expiry(&*view, offer.valid_until_height())?;
+
+        if configuration.intermediary_registry() && !offer.intermediary().is_none() {
+            intermediaries::Schema(&*view).check(offer.intermediary().wallet())?;
+        }
+
+        // The fee recipient can't stand in as the intermediary too.
+        if offer.intermediary().wallet() == genesis_fees.recipient() {
+            return Err(Error::InvalidTransaction);
+        }
+
+        let exchange_fee =
+            percentage_fee(genesis_fees.exchange(), offer.sender_value(), fee_rate_bps)?;
 
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
 
+        blacklist::Schema(&*view).check(
+            offer
+                .sender_assets()
+                .iter()
+                .chain(offer.recipient_assets().iter())
+                .map(|a| a.id()),
+        )?;
+        frozen::Schema(&*view).check(
+            offer
+                .sender_assets()
+                .iter()
+                .chain(offer.recipient_assets().iter())
+                .map(|a| a.id()),
+        )?;
+
+        let fees = ThirdPartyFees::new_exchange(
+            &*view,
+            offer
+                .sender_assets()
+                .into_iter()
+                .chain(offer.recipient_assets().into_iter()),
+        ).and_then(|mut fees| {
+            // Insert intermediary as one of third party fees.
+            if !offer.intermediary().is_none() {
+                fees.add_fee(
+                    offer.intermediary().wallet(),
+                    offer.intermediary().commission(),
+                )?;
+            }
+            Ok(fees)
+        });
+
+        // Under the intermediary fee strategy the intermediary pays everything,
+        // so its wallet has to exist.
+        if fee_strategy == FeeStrategy::Intermediary
+            && !wallet::Schema(&*view).contains(offer.intermediary().wallet())
+        {
+            return Err(Error::WalletNotFound);
+        }
+
+        // Payers must be able to cover all of their debits together, otherwise
+        // the transaction fails before any of the wallets is touched.
+        if let Ok(ref fees) = fees {
+            self.check_funds(view, &fee_strategy, exchange_fee, fees)?;
+        }
+
+        let fee_currency = configuration.fee_currency(EXCHANGE_INTERMEDIARY_ID);
         let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
+        let genesis_balance = genesis.balance();
 
         // Collect the blockchain fee. Execution shall not continue if this fails.
         match fee_strategy {
             FeeStrategy::Recipient => {
                 let mut recipient = wallet::Schema(&*view).fetch(offer.recipient());
 
-                wallet::move_coins(&mut recipient, &mut genesis, genesis_fees.exchange())?;
+                let fee = configuration.payer_fee(exchange_fee, &recipient);
+                wallet::move_fee(&mut recipient, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.recipient(), recipient);
             }
             FeeStrategy::Sender => {
                 let mut sender = wallet::Schema(&*view).fetch(offer.sender());
 
-                wallet::move_coins(&mut sender, &mut genesis, genesis_fees.exchange())?;
+                let fee = configuration.payer_fee(exchange_fee, &sender);
+                wallet::move_fee(&mut sender, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.sender(), sender);
             }
@@ -139,8 +323,11 @@ impl ExchangeIntermediary {
                 let mut recipient = wallet::Schema(&*view).fetch(offer.recipient());
                 let mut sender = wallet::Schema(&*view).fetch(offer.sender());
 
-                wallet::move_coins(&mut recipient, &mut genesis, genesis_fees.exchange() / 2)?;
-                wallet::move_coins(&mut sender, &mut genesis, genesis_fees.exchange() / 2)?;
+                let (first, second) = split_coins(exchange_fee);
+                let first = configuration.payer_fee(first, &sender);
+                let second = configuration.payer_fee(second, &recipient);
+                wallet::move_fee(&mut sender, &mut genesis, first, fee_currency)?;
+                wallet::move_fee(&mut recipient, &mut genesis, second, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.sender(), sender);
                 wallet::Schema(&mut *view).store(offer.recipient(), recipient);
@@ -148,27 +335,18 @@ impl ExchangeIntermediary {
             FeeStrategy::Intermediary => {
                 let mut intermediary = wallet::Schema(&*view).fetch(offer.intermediary().wallet());
 
-                wallet::move_coins(&mut intermediary, &mut genesis, genesis_fees.exchange())?;
+                let fee = configuration.payer_fee(exchange_fee, &intermediary);
+                wallet::move_fee(&mut intermediary, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.intermediary().wallet(), intermediary);
             }
         }
 
+        let collected = genesis.balance() - genesis_balance;
         wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
+        settle_blockchain_fee(view, collected)?;
 
-        let mut fees = ThirdPartyFees::new_exchange(
-            &*view,
-            offer
-                .sender_assets()
-                .into_iter()
-                .chain(offer.recipient_assets().into_iter()),
-        )?;
-
-        // Insert intermediary as one of third party fees.
-        fees.add_fee(
-            offer.intermediary().wallet(),
-            offer.intermediary().commission(),
-        );
+        let fees = fees?;
 
         // Operations bellow must either all succeed, or return an error without
         // saving anything to the database.
@@ -191,7 +369,12 @@ impl ExchangeIntermediary {
             .remove(&offer.recipient())
             .unwrap_or_else(|| wallet::Schema(&*view).fetch(&offer.recipient()));
 
-        wallet::move_coins(&mut sender, &mut recipient, offer.sender_value())?;
+        wallet::spend_coins(
+            &mut sender,
+            &mut recipient,
+            offer.sender_value(),
+            configuration.reserve(),
+        )?;
         wallet::move_assets(&mut sender, &mut recipient, &offer.sender_assets())?;
         wallet::move_assets(&mut recipient, &mut sender, &offer.recipient_assets())?;
 
@@ -207,6 +390,62 @@ impl ExchangeIntermediary {
     }
 }
 
+impl Apply for ExchangeIntermediary {
+    fn check(&self) -> bool {
+        let offer = self.offer();
+        let no_intermediary = offer.intermediary().is_none();
+
+        let wallets_ok = offer.sender() != offer.recipient()
+            && offer.intermediary().wallet() != offer.sender()
+            && offer.intermediary().wallet() != offer.recipient();
+        // The null intermediary can't pay the fees.
+        let fee_strategy_ok = match FeeStrategy::try_from(offer.fee_strategy()) {
+            Some(FeeStrategy::Intermediary) => !no_intermediary,
+            Some(_) => true,
+            None => false,
+        };
+        let sender_assets = offer.sender_assets();
+        let recipient_assets = offer.recipient_assets();
+        let assets_ok =
+            assets_distinct_non_zero(sender_assets.iter().map(|a| (a.id(), a.amount())))
+                && assets_distinct_non_zero(recipient_assets.iter().map(|a| (a.id(), a.amount())))
+                && assets_disjoint(
+                    sender_assets.iter().map(|a| a.id()),
+                    recipient_assets.iter().map(|a| a.id()),
+                );
+
+        // Memos are stored with the transaction, so keep them small.
+        let data_info_ok = offer.data_info().len() <= MAX_DATA_INFO_LENGTH;
+
+        if cfg!(fuzzing) {
+            return wallets_ok && fee_strategy_ok && assets_ok && data_info_ok;
+        }
+
+        let recipient_ok = self.verify_signature(offer.recipient());
+        let sender_ok = crypto::verify(self.sender_signature(), &self.offer_signing_bytes(), offer.sender());
+        let intermediary_ok = no_intermediary || crypto::verify(
+            self.intermediary_signature(),
+            &self.offer_signing_bytes(),
+            offer.intermediary().wallet(),
+        );
+
+        wallets_ok && fee_strategy_ok && assets_ok && data_info_ok && recipient_ok && sender_ok
+            && intermediary_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let parties = self.parties();
+        let result = process_with_rollback(view, &parties, |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        memos::Schema(&mut *view).store(&self.hash(), self.offer().data_info());
+        history::Schema(view).append(&parties, &self.hash());
+
+        result
+    }
+}
+
 lazy_static! {
     static ref VERIFY_COUNT: IntCounter = register_int_counter!(
         "dmbc_transaction_exchange_intermediary_verify_count",
@@ -238,26 +477,7 @@ impl Transaction for ExchangeIntermediary {
     fn verify(&self) -> bool {
         VERIFY_COUNT.inc();
 
-        let offer = self.offer();
-
-        let wallets_ok = offer.sender() != offer.recipient()
-            && offer.intermediary().wallet() != offer.sender()
-            && offer.intermediary().wallet() != offer.recipient();
-        let fee_strategy_ok = FeeStrategy::try_from(offer.fee_strategy()).is_some();
-
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
-
-        let recipient_ok = self.verify_signature(offer.recipient());
-        let sender_ok = crypto::verify(self.sender_signature(), &offer.raw, offer.sender());
-        let intermediary_ok = crypto::verify(
-            self.intermediary_signature(),
-            &offer.raw,
-            offer.intermediary().wallet(),
-        );
-
-        if wallets_ok && fee_strategy_ok && recipient_ok && sender_ok && intermediary_ok {
+        if self.check() {
             VERIFY_SUCCESS_COUNT.inc();
             true
         } else {
@@ -266,16 +486,28 @@ impl Transaction for ExchangeIntermediary {
     }
 
     fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
         EXECUTE_COUNT.inc();
         let timer = EXECUTE_DURATION.start_timer();
 
-        let result = self.process(view);
+        debug!(
+            "Processing exchange_intermediary {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(self.offer().sender()),
+            keys::log(self.offer().recipient())
+        );
+
+        let result = self.apply(view);
 
         if let &Ok(_) = &result {
             EXECUTE_SUCCESS_COUNT.inc();
         }
 
-        status::Schema(view).store(self.hash(), result);
+        info!("Applied exchange_intermediary {}: {:?}", self.hash().to_hex(), result);
 
         timer.observe_duration();
         EXECUTE_FINISH_COUNT.inc();
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+use exonum::crypto::{PublicKey, Signature};
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets::{AssetBundle, AssetId};
+use currency::blacklist;
+use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::status;
+use currency::transactions::components::{advance_seed, offer_signing_bytes, percentage_fee,
+                                         process_with_policy, settle_blockchain_fee,
+                                         split_coins, Apply, FeeStrategy, FeesCalculator,
+                                         ThirdPartyFees};
+use currency::wallet;
+use currency::SERVICE_ID;
+use currency::service::CONFIGURATION;
+use keys;
+
+/// Transaction ID.
+pub const MATCH_OFFERS_ID: u16 = 603;
+
+encoding_struct! {
+    /// One side of a `match_offers` transaction: what `owner` gives in
+    /// return for what it wants.
+    struct MatchOffer {
+        owner:        &PublicKey,
+
+        give_assets:  Vec<AssetBundle>,
+        give_value:   u64,
+
+        want_assets:  Vec<AssetBundle>,
+        want_value:   u64,
+
+        fee_strategy: u8,
+        seed:         u64,
+    }
+}
+
+message! {
+    /// `match_offers` transaction.
+    ///
+    /// Swaps what two opposing offers give, if each side gives exactly what
+    /// the other side wants. The `maker` offer is signed detached by its
+    /// owner, the transaction itself by the owner of the `taker` offer.
+    ///
+    /// Both offers must agree on `fee_strategy`, where the maker stands for
+    /// the sender and the taker for the recipient. If the third party fees
+    /// or the swap fail, the blockchain fee is kept or refunded according
+    /// to the configured `FailurePolicy`.
+    struct MatchOffers {
+        const TYPE = SERVICE_ID;
+        const ID = MATCH_OFFERS_ID;
+
+        maker:           MatchOffer,
+        maker_signature: &Signature,
+        taker:           MatchOffer,
+    }
+}
+
+impl FeesCalculator for MatchOffers {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fee_strategy =
+            FeeStrategy::try_from(self.maker().fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
+        let exchange_fee = self.exchange_fee()?;
+        let fees = ThirdPartyFees::new_exchange(&*view, self.assets())?;
+
+        let mut fees_table = HashMap::new();
+
+        for (payer_key, fee) in self.payers(&fee_strategy, exchange_fee)? {
+            if genesis_fees.recipient() != &payer_key {
+                let payer = wallet::Schema(&*view).fetch(&payer_key);
+                fees_table.insert(payer_key, configuration.payer_fee(fee, &payer));
+            }
+        }
+
+        for (receiver_key, fee) in fees.0 {
+            for (payer_key, fee) in self.payers(&fee_strategy, fee)? {
+                if payer_key != receiver_key {
+                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                }
+            }
+        }
+
+        Ok(fees_table)
+    }
+}
+
+impl MatchOffers {
+    fn payers(&self, fee_strategy: &FeeStrategy, fee: u64) -> Result<Vec<(PublicKey, u64)>, Error> {
+        let payers = match *fee_strategy {
+            FeeStrategy::Recipient => vec![(*self.taker().owner(), fee)],
+            FeeStrategy::Sender => vec![(*self.maker().owner(), fee)],
+            FeeStrategy::RecipientAndSender => {
+                let (first, second) = split_coins(fee);
+                vec![(*self.maker().owner(), first), (*self.taker().owner(), second)]
+            }
+            FeeStrategy::Intermediary => return Err(Error::InvalidFeeStrategy),
+        };
+        Ok(payers)
+    }
+
+    /// Blockchain fee, charged on the coins changing hands.
+    fn exchange_fee(&self) -> Result<u64, Error> {
+        let configuration = CONFIGURATION.read().unwrap();
+        let value = self.maker()
+            .give_value()
+            .checked_add(self.taker().give_value())
+            .ok_or(Error::Overflow)?;
+        percentage_fee(
+            configuration.fees().exchange(),
+            value,
+            configuration.fee_rate_bps(),
+        )
+    }
+
+    /// Assets given by both sides.
+    fn assets(&self) -> Vec<AssetBundle> {
+        let mut assets = self.maker().give_assets();
+        assets.extend(self.taker().give_assets());
+        assets
+    }
+
+    /// Bytes of the maker offer signed by its owner, see `offer_signing_bytes`.
+    pub fn offer_signing_bytes(&self) -> Vec<u8> {
+        offer_signing_bytes(MATCH_OFFERS_ID, &self.maker().raw)
+    }
+
+    /// Check that each side gives exactly what the other side wants, on the
+    /// same terms.
+    fn check_match(&self) -> Result<(), Error> {
+        let maker = self.maker();
+        let taker = self.taker();
+
+        let values_ok =
+            maker.give_value() == taker.want_value() && taker.give_value() == maker.want_value();
+        let assets_ok = totals(&maker.give_assets())? == totals(&taker.want_assets())?
+            && totals(&taker.give_assets())? == totals(&maker.want_assets())?;
+        let fee_strategy_ok = maker.fee_strategy() == taker.fee_strategy();
+
+        if values_ok && assets_ok && fee_strategy_ok {
+            Ok(())
+        } else {
+            Err(Error::OffersMismatch)
+        }
+    }
+
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.taker().owner(), self.taker().seed())?;
+
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+
+        let maker = self.maker();
+        let taker = self.taker();
+        multisig::Schema(&*view).check(&[*maker.owner(), *taker.owner()])?;
+
+        self.check_match()?;
+
+        let fee_strategy =
+            FeeStrategy::try_from(maker.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
+        let exchange_fee = self.exchange_fee()?;
+
+        let assets = self.assets();
+        blacklist::Schema(&*view).check(assets.iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(assets.iter().map(|a| a.id()))?;
+
+        // Collect the blockchain fee. Execution shall not continue if this fails.
+        let fee_currency = configuration.fee_currency(MATCH_OFFERS_ID);
+        let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
+        let genesis_balance = genesis.balance();
+        for (payer_key, fee) in self.payers(&fee_strategy, exchange_fee)? {
+            let mut payer = wallet::Schema(&*view).fetch(&payer_key);
+
+            let fee = configuration.payer_fee(fee, &payer);
+            wallet::move_fee(&mut payer, &mut genesis, fee, fee_currency)?;
+
+            wallet::Schema(&mut *view).store(&payer_key, payer);
+        }
+        let collected = genesis.balance() - genesis_balance;
+        wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
+        settle_blockchain_fee(view, collected)?;
+
+        let fees = ThirdPartyFees::new_exchange(&*view, assets)?;
+
+        // Process third party fees.
+        let mut updated_wallets = match fee_strategy {
+            FeeStrategy::Recipient => fees.collect(view, taker.owner())?,
+            FeeStrategy::Sender => fees.collect(view, maker.owner())?,
+            FeeStrategy::RecipientAndSender => fees.collect2(view, maker.owner(), taker.owner())?,
+            FeeStrategy::Intermediary => unreachable!(),
+        };
+
+        // Process the swap.
+        let mut wallet_maker = updated_wallets
+            .remove(maker.owner())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(maker.owner()));
+        let mut wallet_taker = updated_wallets
+            .remove(taker.owner())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(taker.owner()));
+
+        let reserve = configuration.reserve();
+        wallet::spend_coins(&mut wallet_maker, &mut wallet_taker, maker.give_value(), reserve)?;
+        wallet::spend_coins(&mut wallet_taker, &mut wallet_maker, taker.give_value(), reserve)?;
+        wallet::move_assets(&mut wallet_maker, &mut wallet_taker, &maker.give_assets())?;
+        wallet::move_assets(&mut wallet_taker, &mut wallet_maker, &taker.give_assets())?;
+
+        updated_wallets.insert(*maker.owner(), wallet_maker);
+        updated_wallets.insert(*taker.owner(), wallet_taker);
+
+        // Save changes to the database.
+        for (key, wallet) in updated_wallets {
+            wallet::Schema(&mut *view).store(&key, wallet);
+        }
+
+        Ok(())
+    }
+}
+
+/// Amounts of `assets` summed by asset.
+fn totals(assets: &[AssetBundle]) -> Result<HashMap<AssetId, u64>, Error> {
+    let mut totals = HashMap::new();
+    for asset in assets {
+        let total = totals.entry(asset.id()).or_insert(0u64);
+        *total = total.checked_add(asset.amount()).ok_or(Error::Overflow)?;
+    }
+    Ok(totals)
+}
+
+impl Apply for MatchOffers {
+    fn check(&self) -> bool {
+        let maker = self.maker();
+        let taker = self.taker();
+
+        let wallets_ok = maker.owner() != taker.owner();
+        let fee_strategy_ok = match FeeStrategy::try_from(maker.fee_strategy()) {
+            Some(FeeStrategy::Recipient)
+            | Some(FeeStrategy::Sender)
+            | Some(FeeStrategy::RecipientAndSender) => true,
+            _ => false,
+        };
+
+        if cfg!(fuzzing) {
+            return wallets_ok && fee_strategy_ok;
+        }
+
+        let taker_ok = self.verify_signature(taker.owner());
+        let maker_ok = crypto::verify(
+            self.maker_signature(),
+            &self.offer_signing_bytes(),
+            maker.owner(),
+        );
+
+        wallets_ok && fee_strategy_ok && taker_ok && maker_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let parties = [*self.maker().owner(), *self.taker().owner()];
+        let result = process_with_policy(view, &parties, |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&parties, &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_match_offers_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_match_offers_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_match_offers_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_match_offers_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_match_offers_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_match_offers_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for MatchOffers {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing match_offers {} of {} and {}",
+            self.hash().to_hex(),
+            keys::log(self.maker().owner()),
+            keys::log(self.taker().owner())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied match_offers {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
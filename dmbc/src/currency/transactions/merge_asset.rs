@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets;
+use currency::assets::{AssetBundle, AssetId};
+use currency::blacklist;
+use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::status;
+use currency::transactions::components::{advance_seed, check_assets_limit,
+                                         collect_operation_fee, operation_fees_table,
+                                         process_with_policy, Apply, FeesCalculator};
+use currency::wallet;
+use currency::SERVICE_ID;
+use currency::service::CONFIGURATION;
+use keys;
+
+/// Transaction ID.
+pub const MERGE_ASSET_ID: u16 = 402;
+
+message! {
+    /// `merge_asset` transaction.
+    ///
+    /// Merges distinct lots of an asset, such as the tranches made by
+    /// `split_asset`, into `into` within the signer's wallet. Lots must share
+    /// the creator, fees and data of `into`; the merged holding grows by
+    /// exactly their total amount. The signer pays the
+    /// `Configuration::operation_fee` of the transaction.
+    struct MergeAsset {
+        const TYPE = SERVICE_ID;
+        const ID = MERGE_ASSET_ID;
+
+        pub_key:     &PublicKey,
+        lots:        Vec<AssetBundle>,
+        into:        AssetId,
+        seed:        u64,
+    }
+}
+
+impl FeesCalculator for MergeAsset {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(operation_fees_table(&*view, self.pub_key(), MERGE_ASSET_ID))
+    }
+}
+
+impl MergeAsset {
+    /// Total amount of the lots, `None` if they are empty, listed twice,
+    /// hold nothing, include `into` or don't fit into `u64`.
+    fn total(&self) -> Option<u64> {
+        let lots = self.lots();
+        if lots.is_empty() {
+            return None;
+        }
+
+        let mut ids = HashSet::new();
+        let mut total = 0u64;
+        for lot in &lots {
+            if lot.amount() == 0 || lot.id() == self.into() || !ids.insert(lot.id()) {
+                return None;
+            }
+            total = total.checked_add(lot.amount())?;
+        }
+        Some(total)
+    }
+
+    fn lots_ok(&self) -> bool {
+        self.total().is_some()
+    }
+
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.pub_key(), self.seed())?;
+
+        multisig::Schema(&*view).check(&[*self.pub_key()])?;
+        check_assets_limit(&CONFIGURATION.read().unwrap(), self.lots().len())?;
+
+        collect_operation_fee(view, self.pub_key(), MERGE_ASSET_ID)?;
+
+        let total = self.total().ok_or(Error::InvalidTransaction)?;
+        let mut merged = assets::Schema(&*view)
+            .fetch(&self.into())
+            .ok_or(Error::AssetNotFound)?;
+
+        let mut ids: Vec<AssetId> = self.lots().iter().map(|lot| lot.id()).collect();
+        ids.push(self.into());
+        blacklist::Schema(&*view).check(ids.iter().cloned())?;
+        frozen::Schema(&*view).check(ids)?;
+
+        let mut owner = wallet::Schema(&*view).fetch(self.pub_key());
+        owner.remove_assets(self.lots())?;
+        owner.add_assets(vec![AssetBundle::new(self.into(), total)]);
+
+        let mut infos = HashMap::new();
+        for lot in self.lots() {
+            let info = assets::Schema(&*view)
+                .fetch(&lot.id())
+                .ok_or(Error::AssetNotFound)?;
+            merged = merged.merge(info.part(lot.amount()))?;
+            infos.insert(lot.id(), info.decrease(lot.amount())?);
+        }
+        infos.insert(self.into(), merged);
+
+        wallet::Schema(&mut *view).store(self.pub_key(), owner);
+        for (id, info) in infos {
+            assets::Schema(&mut *view).store(&id, info);
+        }
+
+        Ok(())
+    }
+}
+
+impl Apply for MergeAsset {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.lots_ok() && self.verify_signature(self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.pub_key()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.pub_key()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_merge_asset_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_merge_asset_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_merge_asset_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_merge_asset_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_merge_asset_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_merge_asset_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for MergeAsset {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing merge_asset {} by {}",
+            self.hash().to_hex(),
+            keys::log(self.pub_key())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied merge_asset {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
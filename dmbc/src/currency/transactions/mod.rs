@@ -1,21 +1,82 @@
 //! Blockchain transactions.
+//!
+//! # Wire format
+//!
+//! The message ID of a transaction fixes the layout of its fields, and the
+//! layout under an ID never changes. A message which gains fields moves to
+//! a new ID, and its old ID is retired, see `RETIRED_IDS`. Nodes reject
+//! payloads with a retired ID instead of reading them in the new layout,
+//! so a chain holding such transactions can't be replayed by this version.
+//! The API tells clients submitting them which ID to use instead.
+//!
+//! - `transfer` moved from 200 to 203, adding `send_all`, `strict` and
+//!   `only_if_recipient_below` after `amount`, and `tag` after `data_info`.
+//! - `add_assets` moved from 300 to 301, adding `non_fungible` after
+//!   `meta_assets`.
+//! - `trade` moved from 501 to 503, adding `quantity` and `seed` after
+//!   `seller_signature`, and `valid_until_height` at the end of the offer.
+//!   Offers are signed for the ID of their transaction, so offers signed
+//!   for 501 don't carry over either.
+//! - `trade_intermediary` moved from 502 to 504, `exchange` from 601 to 604
+//!   and `exchange_intermediary` from 602 to 605, adding
+//!   `valid_until_height` at the end of their offers.
 
 pub mod builders;
 pub mod components;
 
 mod add_assets;
 mod delete_assets;
+mod escrow_create;
+mod escrow_refund;
+mod escrow_release;
 mod exchange;
 mod exchange_intermediary;
+mod freeze_asset;
+mod match_offers;
+mod merge_asset;
+mod multisig_transfer;
+mod set_asset_blacklist;
+mod set_intermediary;
+mod set_multisig;
+mod set_reference_price;
+mod split_asset;
+mod sponsored_transfer;
 mod trade;
 mod trade_intermediary;
 mod transfer;
 
 pub use currency::transactions::add_assets::{AddAssets, ADD_ASSETS_ID};
 pub use currency::transactions::delete_assets::{DeleteAssets, DELETE_ASSETS_ID};
-pub use currency::transactions::exchange::{Exchange, EXCHANGE_ID};
+pub use currency::transactions::escrow_create::{EscrowCreate, ESCROW_CREATE_ID};
+pub use currency::transactions::escrow_refund::{EscrowRefund, ESCROW_REFUND_ID};
+pub use currency::transactions::escrow_release::{EscrowRelease, ESCROW_RELEASE_ID};
+pub use currency::transactions::exchange::{Exchange, ExchangeOffer, EXCHANGE_ID};
 pub use currency::transactions::exchange_intermediary::{ExchangeIntermediary,
+                                                        ExchangeOfferIntermediary,
                                                         EXCHANGE_INTERMEDIARY_ID};
-pub use currency::transactions::trade::{Trade, TRADE_ID};
+pub use currency::transactions::freeze_asset::{FreezeAsset, FREEZE_ASSET_ID};
+pub use currency::transactions::match_offers::{MatchOffer, MatchOffers, MATCH_OFFERS_ID};
+pub use currency::transactions::merge_asset::{MergeAsset, MERGE_ASSET_ID};
+pub use currency::transactions::multisig_transfer::{MultiSigTransfer, MultiSigTransferOffer,
+                                                    MULTISIG_TRANSFER_ID};
+pub use currency::transactions::set_asset_blacklist::{SetAssetBlacklist,
+                                                      SET_ASSET_BLACKLIST_ID};
+pub use currency::transactions::set_intermediary::{SetIntermediary, SET_INTERMEDIARY_ID};
+pub use currency::transactions::set_multisig::{SetMultiSig, SET_MULTISIG_ID};
+pub use currency::transactions::set_reference_price::{SetReferencePrice,
+                                                      SET_REFERENCE_PRICE_ID};
+pub use currency::transactions::split_asset::{SplitAsset, SPLIT_ASSET_ID};
+pub use currency::transactions::sponsored_transfer::{SponsoredTransfer, SPONSORED_TRANSFER_ID};
+pub use currency::transactions::trade::{Trade, TradeOffer, TRADE_ID};
 pub use currency::transactions::trade_intermediary::{TradeIntermediary, TRADE_INTERMEDIARY_ID};
-pub use currency::transactions::transfer::{Transfer, TRANSFER_ID};
+pub use currency::transactions::transfer::{Transfer, MAX_TAG_LENGTH, TRANSFER_ID};
+
+/// Retired message IDs, each with the ID which replaced it.
+pub const RETIRED_IDS: &[(u16, u16)] = &[
+    (200, TRANSFER_ID),
+    (300, ADD_ASSETS_ID),
+    (501, TRADE_ID),
+    (502, TRADE_INTERMEDIARY_ID),
+    (601, EXCHANGE_ID),
+    (602, EXCHANGE_INTERMEDIARY_ID),
+];
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets::AssetBundle;
+use currency::blacklist;
+use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::multisig::MultiSigSignature;
+use currency::non_fungible;
+use currency::status;
+use currency::transactions::components::{advance_seed, offer_signing_bytes,
+                                         settle_blockchain_fee, Apply, FeesCalculator,
+                                         ThirdPartyFees};
+use currency::wallet;
+use currency::SERVICE_ID;
+use currency::service::CONFIGURATION;
+use keys;
+
+/// Transaction ID.
+pub const MULTISIG_TRANSFER_ID: u16 = 202;
+
+encoding_struct! {
+    struct MultiSigTransferOffer {
+        from:      &PublicKey,
+        to:        &PublicKey,
+        amount:    u64,
+        assets:    Vec<AssetBundle>,
+        seed:      u64,
+        data_info: &str,
+    }
+}
+
+message! {
+    /// `multisig_transfer` transaction.
+    ///
+    /// Transfer from a multisig wallet, see `SetMultiSig`. Every key in
+    /// `signatures` signs the offer, and the transaction itself is signed by
+    /// the first of them. Fails with `InsufficientSignatures` unless
+    /// the threshold of the wallet's keys signed.
+    struct MultiSigTransfer {
+        const TYPE = SERVICE_ID;
+        const ID = MULTISIG_TRANSFER_ID;
+
+        offer:      MultiSigTransferOffer,
+        signatures: Vec<MultiSigSignature>,
+    }
+}
+
+impl FeesCalculator for MultiSigTransfer {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        let offer = self.offer();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fees = ThirdPartyFees::new_transfer(&*view, offer.assets())?;
+
+        let mut fees_table = HashMap::new();
+        if genesis_fees.recipient() != offer.from() {
+            let wallet_from = wallet::Schema(&*view).fetch(offer.from());
+            let fee = configuration.payer_fee(genesis_fees.transfer(), &wallet_from);
+            fees_table.insert(*offer.from(), fee);
+        }
+
+        for (pub_key, fee) in fees.0 {
+            if pub_key != *offer.from() {
+                *fees_table.entry(*offer.from()).or_insert(0) += fee;
+            }
+        }
+
+        Ok(fees_table)
+    }
+}
+
+impl MultiSigTransfer {
+    /// Bytes each of the `signatures` signs.
+    pub fn offer_signing_bytes(&self) -> Vec<u8> {
+        offer_signing_bytes(MULTISIG_TRANSFER_ID, &self.offer().raw)
+    }
+
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        let offer = self.offer();
+
+        advance_seed(view, offer.from(), offer.seed())?;
+
+        let multisig = multisig::Schema(&*view)
+            .fetch(offer.from())
+            .ok_or(Error::InvalidTransaction)?;
+        multisig.check(&self.signatures())?;
+
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fee = configuration.payer_fee(
+            genesis_fees.transfer(),
+            &wallet::Schema(&*view).fetch(offer.from()),
+        );
+
+        blacklist::Schema(&*view).check(offer.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(offer.assets().iter().map(|a| a.id()))?;
+        non_fungible::Schema(&*view).check(offer.assets().iter())?;
+
+        let fees = ThirdPartyFees::new_transfer(&*view, offer.assets())?;
+
+        // Operations bellow must either all succeed, or return an error without
+        // saving anything to the database.
+
+        // Process third party fees.
+        let mut updated_wallets = fees.collect(view, offer.from())?;
+
+        // Process the main transaction.
+        let mut wallet_from = updated_wallets
+            .remove(offer.from())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(offer.from()));
+
+        let mut wallet_to = updated_wallets
+            .remove(offer.to())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(offer.to()));
+
+        wallet::spend_coins(
+            &mut wallet_from,
+            &mut wallet_to,
+            offer.amount(),
+            configuration.reserve(),
+        )?;
+        wallet::move_assets(&mut wallet_from, &mut wallet_to, &offer.assets())?;
+
+        updated_wallets.insert(*offer.from(), wallet_from);
+        updated_wallets.insert(*offer.to(), wallet_to);
+
+        // Collect the blockchain fee. It is settled once stored.
+        let mut collected = 0;
+        if offer.from() != genesis_fees.recipient() {
+            let mut wallet_from = updated_wallets.remove(offer.from()).unwrap();
+            let fee_currency = configuration.fee_currency(MULTISIG_TRANSFER_ID);
+            let mut genesis = updated_wallets
+                .remove(genesis_fees.recipient())
+                .unwrap_or_else(|| wallet::Schema(&*view).fetch(genesis_fees.recipient()));
+
+            wallet::move_fee(&mut wallet_from, &mut genesis, fee, fee_currency)?;
+
+            updated_wallets.insert(*offer.from(), wallet_from);
+            updated_wallets.insert(*genesis_fees.recipient(), genesis);
+            collected = fee_currency.coins(fee);
+        }
+
+        // Save changes to the database.
+        for (key, wallet) in updated_wallets {
+            wallet::Schema(&mut *view).store(&key, wallet);
+        }
+        settle_blockchain_fee(view, collected)?;
+
+        Ok(())
+    }
+}
+
+impl Apply for MultiSigTransfer {
+    fn check(&self) -> bool {
+        let offer = self.offer();
+        let signatures = self.signatures();
+
+        let wallets_ok = offer.from() != offer.to();
+        let signatures_ok = !signatures.is_empty();
+
+        if cfg!(fuzzing) {
+            return wallets_ok && signatures_ok;
+        }
+
+        let signing_bytes = self.offer_signing_bytes();
+        let verify_ok = signatures_ok
+            && self.verify_signature(signatures[0].pub_key())
+            && signatures
+                .iter()
+                .all(|s| crypto::verify(s.signature(), &signing_bytes, s.pub_key()));
+
+        wallets_ok && verify_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = self.process(view);
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        let offer = self.offer();
+        history::Schema(view).append(&[*offer.from(), *offer.to()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_multisig_transfer_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_multisig_transfer_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_multisig_transfer_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_multisig_transfer_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_multisig_transfer_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_multisig_transfer_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for MultiSigTransfer {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        let offer = self.offer();
+        debug!(
+            "Processing multisig_transfer {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(offer.from()),
+            keys::log(offer.to())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied multisig_transfer {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
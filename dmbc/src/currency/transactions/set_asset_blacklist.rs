@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets::AssetId;
+use currency::blacklist;
+use currency::error::Error;
+use currency::history;
+use currency::status;
+use currency::transactions::components::{advance_seed, Apply, FeesCalculator};
+use currency::wallet;
+use currency::{Service, SERVICE_ID};
+use keys;
+
+/// Transaction ID.
+pub const SET_ASSET_BLACKLIST_ID: u16 = 700;
+
+message! {
+    /// `set_asset_blacklist` transaction.
+    ///
+    /// Adds the asset to the blacklist or removes it from there.
+    /// Must be signed by the platform wallet.
+    struct SetAssetBlacklist {
+        const TYPE = SERVICE_ID;
+        const ID = SET_ASSET_BLACKLIST_ID;
+
+        pub_key:     &PublicKey,
+        asset_id:    AssetId,
+        blacklisted: bool,
+        seed:        u64,
+    }
+}
+
+impl FeesCalculator for SetAssetBlacklist {
+    fn calculate_fees(&self, _view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(HashMap::new())
+    }
+}
+
+impl SetAssetBlacklist {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.pub_key(), self.seed())?;
+
+        if *self.pub_key() != Service::genesis_wallet(&*view) {
+            return Err(Error::InvalidTransaction);
+        }
+
+        if self.blacklisted() {
+            blacklist::Schema(&mut *view).insert(&self.asset_id());
+        } else {
+            blacklist::Schema(&mut *view).remove(&self.asset_id());
+        }
+
+        Ok(())
+    }
+}
+
+impl Apply for SetAssetBlacklist {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.verify_signature(self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = self.process(view);
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.pub_key()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_asset_blacklist_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_asset_blacklist_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_asset_blacklist_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_asset_blacklist_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_asset_blacklist_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_set_asset_blacklist_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for SetAssetBlacklist {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing set_asset_blacklist {} by {}",
+            self.hash().to_hex(),
+            keys::log(self.pub_key())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied set_asset_blacklist {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
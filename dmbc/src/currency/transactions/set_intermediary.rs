@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::error::Error;
+use currency::history;
+use currency::intermediaries;
+use currency::status;
+use currency::transactions::components::{advance_seed, Apply, FeesCalculator};
+use currency::wallet;
+use currency::{Service, SERVICE_ID};
+use keys;
+
+/// Transaction ID.
+pub const SET_INTERMEDIARY_ID: u16 = 702;
+
+message! {
+    /// `set_intermediary` transaction.
+    ///
+    /// Adds the intermediary to the registry or removes it from there.
+    /// Must be signed by the platform wallet.
+    struct SetIntermediary {
+        const TYPE = SERVICE_ID;
+        const ID = SET_INTERMEDIARY_ID;
+
+        pub_key:      &PublicKey,
+        intermediary: &PublicKey,
+        registered:   bool,
+        seed:         u64,
+    }
+}
+
+impl FeesCalculator for SetIntermediary {
+    fn calculate_fees(&self, _view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(HashMap::new())
+    }
+}
+
+impl SetIntermediary {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.pub_key(), self.seed())?;
+
+        if *self.pub_key() != Service::genesis_wallet(&*view) {
+            return Err(Error::InvalidTransaction);
+        }
+
+        if self.registered() {
+            intermediaries::Schema(&mut *view).insert(self.intermediary());
+        } else {
+            intermediaries::Schema(&mut *view).remove(self.intermediary());
+        }
+
+        Ok(())
+    }
+}
+
+impl Apply for SetIntermediary {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.verify_signature(self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = self.process(view);
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.pub_key()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_intermediary_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_intermediary_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_intermediary_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_intermediary_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_intermediary_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_set_intermediary_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for SetIntermediary {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing set_intermediary {} by {}",
+            self.hash().to_hex(),
+            keys::log(self.pub_key())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied set_intermediary {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::error::Error;
+use currency::history;
+use currency::multisig;
+use currency::multisig::{MultiSig, MultiSigKey};
+use currency::status;
+use currency::transactions::components::{advance_seed, collect_operation_fee,
+                                         operation_fees_table, process_with_policy,
+                                         Apply, FeesCalculator};
+use currency::wallet;
+use currency::SERVICE_ID;
+use keys;
+
+/// Transaction ID.
+pub const SET_MULTISIG_ID: u16 = 900;
+
+message! {
+    /// `set_multisig` transaction.
+    ///
+    /// Turns the wallet into a multisig wallet: afterwards coins and assets
+    /// can only leave it through `MultiSigTransfer` signed by `threshold` of
+    /// `keys`. Must be signed by the wallet itself. The configuration is
+    /// permanent, so a wallet which already is a multisig wallet can't set
+    /// another one. The wallet pays the `Configuration::operation_fee` of
+    /// the transaction.
+    struct SetMultiSig {
+        const TYPE = SERVICE_ID;
+        const ID = SET_MULTISIG_ID;
+
+        pub_key:     &PublicKey,
+        keys:        Vec<MultiSigKey>,
+        threshold:   u8,
+        seed:        u64,
+    }
+}
+
+impl FeesCalculator for SetMultiSig {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(operation_fees_table(&*view, self.pub_key(), SET_MULTISIG_ID))
+    }
+}
+
+impl SetMultiSig {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.pub_key(), self.seed())?;
+
+        if multisig::Schema(&*view).fetch(self.pub_key()).is_some() {
+            return Err(Error::InvalidTransaction);
+        }
+
+        collect_operation_fee(view, self.pub_key(), SET_MULTISIG_ID)?;
+
+        let multisig = MultiSig::new(self.keys(), self.threshold());
+        multisig::Schema(&mut *view).store(self.pub_key(), multisig);
+
+        Ok(())
+    }
+}
+
+impl Apply for SetMultiSig {
+    fn check(&self) -> bool {
+        let multisig_ok = MultiSig::new(self.keys(), self.threshold()).is_valid();
+
+        if cfg!(fuzzing) {
+            return multisig_ok;
+        }
+
+        multisig_ok && self.verify_signature(self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.pub_key()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.pub_key()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_multisig_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_multisig_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_multisig_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_multisig_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_multisig_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_set_multisig_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for SetMultiSig {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!("Processing set_multisig {} by {}", self.hash().to_hex(), keys::log(self.pub_key()));
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied set_multisig {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets;
+use currency::assets::AssetId;
+use currency::error::Error;
+use currency::history;
+use currency::multisig;
+use currency::status;
+use currency::transactions::components::{advance_seed, collect_operation_fee,
+                                         operation_fees_table, process_with_policy,
+                                         Apply, FeesCalculator};
+use currency::wallet;
+use currency::SERVICE_ID;
+use keys;
+
+/// Transaction ID.
+pub const SET_REFERENCE_PRICE_ID: u16 = 703;
+
+message! {
+    /// `set_reference_price` transaction.
+    ///
+    /// Sets the reference price of a unit of the asset, trades deviating
+    /// from it are flagged. A price of 0 removes it. Must be signed by the
+    /// creator of the asset, who pays the `Configuration::operation_fee` of
+    /// the transaction.
+    struct SetReferencePrice {
+        const TYPE = SERVICE_ID;
+        const ID = SET_REFERENCE_PRICE_ID;
+
+        pub_key:     &PublicKey,
+        asset_id:    AssetId,
+        price:       u64,
+        seed:        u64,
+    }
+}
+
+impl FeesCalculator for SetReferencePrice {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(operation_fees_table(&*view, self.pub_key(), SET_REFERENCE_PRICE_ID))
+    }
+}
+
+impl SetReferencePrice {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.pub_key(), self.seed())?;
+
+        let info = assets::Schema(&*view)
+            .fetch(&self.asset_id())
+            .ok_or(Error::AssetNotFound)?;
+
+        if info.creator() != self.pub_key() {
+            return Err(Error::InvalidTransaction);
+        }
+
+        multisig::Schema(&*view).check(&[*self.pub_key()])?;
+
+        collect_operation_fee(view, self.pub_key(), SET_REFERENCE_PRICE_ID)?;
+
+        assets::Schema(&mut *view).set_reference_price(&self.asset_id(), self.price());
+
+        Ok(())
+    }
+}
+
+impl Apply for SetReferencePrice {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.verify_signature(self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.pub_key()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.pub_key()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_reference_price_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_reference_price_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_reference_price_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_reference_price_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_set_reference_price_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_set_reference_price_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for SetReferencePrice {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing set_reference_price {} by {}",
+            self.hash().to_hex(),
+            keys::log(self.pub_key())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied set_reference_price {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
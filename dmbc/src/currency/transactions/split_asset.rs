@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet};
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets;
+use currency::assets::{AssetBundle, Tranche};
+use currency::blacklist;
+use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::non_fungible;
+use currency::status;
+use currency::transactions::components::{advance_seed, check_assets_limit,
+                                         collect_operation_fee, operation_fees_table,
+                                         process_with_policy, Apply, FeesCalculator};
+use currency::wallet;
+use currency::SERVICE_ID;
+use currency::service::CONFIGURATION;
+use keys;
+
+/// Transaction ID.
+pub const SPLIT_ASSET_ID: u16 = 401;
+
+message! {
+    /// `split_asset` transaction.
+    ///
+    /// Splits `asset.amount()` units of a holding into labeled tranches
+    /// within the signer's wallet. The tranches must add up to exactly the
+    /// split amount, see `Tranche::id` for the ids they are stored under.
+    /// Non-fungible assets can't be split. The signer pays the
+    /// `Configuration::operation_fee` of the transaction.
+    struct SplitAsset {
+        const TYPE = SERVICE_ID;
+        const ID = SPLIT_ASSET_ID;
+
+        pub_key:     &PublicKey,
+        asset:       AssetBundle,
+        tranches:    Vec<Tranche>,
+        seed:        u64,
+    }
+}
+
+impl FeesCalculator for SplitAsset {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        Ok(operation_fees_table(&*view, self.pub_key(), SPLIT_ASSET_ID))
+    }
+}
+
+impl SplitAsset {
+    /// Tranches must be labeled uniquely, hold something and add up to the
+    /// split amount.
+    fn tranches_ok(&self) -> bool {
+        let tranches = self.tranches();
+        if tranches.is_empty() {
+            return false;
+        }
+
+        let mut labels = HashSet::new();
+        let mut total = 0u64;
+        for tranche in &tranches {
+            if tranche.label().is_empty() || tranche.amount() == 0 {
+                return false;
+            }
+            if !labels.insert(tranche.label().to_string()) {
+                return false;
+            }
+            total = match total.checked_add(tranche.amount()) {
+                Some(total) => total,
+                None => return false,
+            };
+        }
+        total == self.asset().amount()
+    }
+
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.pub_key(), self.seed())?;
+
+        multisig::Schema(&*view).check(&[*self.pub_key()])?;
+        check_assets_limit(&CONFIGURATION.read().unwrap(), self.tranches().len())?;
+
+        collect_operation_fee(view, self.pub_key(), SPLIT_ASSET_ID)?;
+
+        let asset = self.asset();
+        let info = assets::Schema(&*view)
+            .fetch(&asset.id())
+            .ok_or(Error::AssetNotFound)?;
+        if non_fungible::Schema(&*view).contains(&asset.id()) {
+            return Err(Error::InvalidTransaction);
+        }
+
+        let parts: Vec<_> = self.tranches()
+            .iter()
+            .map(|tranche| {
+                let id = tranche.id(&asset.id(), info.creator());
+                (id, AssetBundle::new(id, tranche.amount()))
+            })
+            .collect();
+
+        let mut ids = vec![asset.id()];
+        ids.extend(parts.iter().map(|&(id, _)| id));
+        blacklist::Schema(&*view).check(ids.iter().cloned())?;
+        frozen::Schema(&*view).check(ids)?;
+
+        let mut owner = wallet::Schema(&*view).fetch(self.pub_key());
+        owner.remove_assets(vec![asset.clone()])?;
+        owner.add_assets(parts.iter().map(|&(_, ref bundle)| bundle.clone()));
+
+        let mut infos = HashMap::new();
+        for &(id, ref bundle) in &parts {
+            let part = info.part(bundle.amount());
+            let entry = match assets::Schema(&*view).fetch(&id) {
+                Some(existing) => existing.merge(part)?,
+                None => part,
+            };
+            infos.insert(id, entry);
+        }
+        infos.insert(asset.id(), info.decrease(asset.amount())?);
+
+        wallet::Schema(&mut *view).store(self.pub_key(), owner);
+        for (id, info) in infos {
+            assets::Schema(&mut *view).store(&id, info);
+        }
+
+        Ok(())
+    }
+}
+
+impl Apply for SplitAsset {
+    fn check(&self) -> bool {
+        if cfg!(fuzzing) {
+            return true;
+        }
+
+        self.tranches_ok() && self.verify_signature(self.pub_key())
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.pub_key()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.pub_key()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_split_asset_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_split_asset_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_split_asset_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_split_asset_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_split_asset_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_split_asset_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for SplitAsset {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing split_asset {} by {}",
+            self.hash().to_hex(),
+            keys::log(self.pub_key())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied split_asset {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
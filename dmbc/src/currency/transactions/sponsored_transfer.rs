@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto::PublicKey;
+use exonum::messages::Message;
+use exonum::storage::Fork;
+use prometheus::{IntCounter, Histogram};
+
+use currency::assets::AssetBundle;
+use currency::blacklist;
+use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::non_fungible;
+use currency::status;
+use currency::transactions::components::{advance_seed, settle_blockchain_fee, Apply, FeesCalculator,
+                                         ThirdPartyFees};
+use currency::wallet;
+use currency::SERVICE_ID;
+use currency::service::CONFIGURATION;
+use keys;
+
+/// Transaction ID.
+pub const SPONSORED_TRANSFER_ID: u16 = 201;
+
+message! {
+    /// `sponsored_transfer` transaction.
+    ///
+    /// Same as `Transfer`, except that the blockchain fee is paid by the
+    /// sponsor wallet of the configuration instead of the sender. The sender
+    /// pays the third party fees of the assets and needs `amount` only.
+    /// Fails with `WalletNotFound` if no sponsor is configured and with
+    /// `InsufficientFunds` if the sponsor can't cover the fee, in either case
+    /// without moving anything.
+    struct SponsoredTransfer {
+        const TYPE = SERVICE_ID;
+        const ID = SPONSORED_TRANSFER_ID;
+
+        from:      &PublicKey,
+        to:        &PublicKey,
+        amount:    u64,
+        assets:    Vec<AssetBundle>,
+        seed:      u64,
+        data_info: &str,
+    }
+}
+
+impl FeesCalculator for SponsoredTransfer {
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let sponsor_key = configuration.sponsor().ok_or(Error::WalletNotFound)?;
+        let fees = ThirdPartyFees::new_transfer(&*view, self.assets())?;
+
+        let mut fees_table = HashMap::new();
+        if genesis_fees.recipient() != &sponsor_key {
+            let sponsor = wallet::Schema(&*view).fetch(&sponsor_key);
+            let fee = configuration.payer_fee(genesis_fees.transfer(), &sponsor);
+            fees_table.insert(sponsor_key, fee);
+        }
+
+        for (pub_key, fee) in fees.0 {
+            if pub_key != *self.from() {
+                *fees_table.entry(*self.from()).or_insert(0) += fee;
+            }
+        }
+
+        Ok(fees_table)
+    }
+}
+
+impl SponsoredTransfer {
+    fn process(&self, view: &mut Fork) -> Result<(), Error> {
+        advance_seed(view, self.from(), self.seed())?;
+
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let sponsor_key = configuration.sponsor().ok_or(Error::WalletNotFound)?;
+
+        multisig::Schema(&*view).check(&[*self.from()])?;
+        blacklist::Schema(&*view).check(self.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(self.assets().iter().map(|a| a.id()))?;
+        non_fungible::Schema(&*view).check(self.assets().iter())?;
+
+        let fees = ThirdPartyFees::new_transfer(&*view, self.assets())?;
+
+        // Operations bellow must either all succeed, or return an error without
+        // saving anything to the database.
+
+        // Process third party fees.
+        let mut updated_wallets = fees.collect(view, self.from())?;
+
+        // Process the main transaction.
+        let mut wallet_from = updated_wallets
+            .remove(&self.from())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(&self.from()));
+
+        let mut wallet_to = updated_wallets
+            .remove(&self.to())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(&self.to()));
+
+        wallet::spend_coins(
+            &mut wallet_from,
+            &mut wallet_to,
+            self.amount(),
+            configuration.reserve(),
+        )?;
+        wallet::move_assets(&mut wallet_from, &mut wallet_to, &self.assets())?;
+
+        updated_wallets.insert(*self.from(), wallet_from);
+        updated_wallets.insert(*self.to(), wallet_to);
+
+        // Collect the blockchain fee from the sponsor, which may be one of
+        // the parties. It is settled once stored.
+        let mut collected = 0;
+        if sponsor_key != *genesis_fees.recipient() {
+            let mut sponsor = updated_wallets
+                .remove(&sponsor_key)
+                .unwrap_or_else(|| wallet::Schema(&*view).fetch(&sponsor_key));
+            let fee_currency = configuration.fee_currency(SPONSORED_TRANSFER_ID);
+            let mut genesis = updated_wallets
+                .remove(genesis_fees.recipient())
+                .unwrap_or_else(|| wallet::Schema(&*view).fetch(genesis_fees.recipient()));
+
+            let fee = configuration.payer_fee(genesis_fees.transfer(), &sponsor);
+            wallet::move_fee(&mut sponsor, &mut genesis, fee, fee_currency)?;
+
+            updated_wallets.insert(sponsor_key, sponsor);
+            updated_wallets.insert(*genesis_fees.recipient(), genesis);
+            collected = fee_currency.coins(fee);
+        }
+
+        // Save changes to the database.
+        for (key, wallet) in updated_wallets {
+            wallet::Schema(&mut *view).store(&key, wallet);
+        }
+        settle_blockchain_fee(view, collected)?;
+
+        Ok(())
+    }
+}
+
+impl Apply for SponsoredTransfer {
+    fn check(&self) -> bool {
+        let wallets_ok = self.from() != self.to();
+
+        if cfg!(fuzzing) {
+            return wallets_ok;
+        }
+
+        let verify_ok = self.verify_signature(&self.from());
+
+        wallets_ok && verify_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = self.process(view);
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.from(), *self.to()], &self.hash());
+
+        result
+    }
+}
+
+lazy_static! {
+    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_sponsored_transfer_verify_count",
+        "Times .verify() was called on a transaction."
+    ).unwrap();
+    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_sponsored_transfer_verify_success_count",
+        "Times verification was successfull on a transaction."
+    ).unwrap();
+    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_sponsored_transfer_execute_count",
+        "Transactions executed."
+    ).unwrap();
+    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_sponsored_transfer_execute_success_count",
+        "Times transaction execution reported a success."
+    ).unwrap();
+    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
+        "dmbc_transaction_sponsored_transfer_execute_finish_count",
+        "Times transaction has finished executing without panicking."
+    ).unwrap();
+    static ref EXECUTE_DURATION: Histogram = register_histogram!(
+        "dmbc_transaction_sponsored_transfer_execute_duration_seconds",
+        "Duration of transaction execution."
+    ).unwrap();
+}
+
+impl Transaction for SponsoredTransfer {
+    fn verify(&self) -> bool {
+        VERIFY_COUNT.inc();
+
+        if self.check() {
+            VERIFY_SUCCESS_COUNT.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
+        EXECUTE_COUNT.inc();
+        let timer = EXECUTE_DURATION.start_timer();
+
+        debug!(
+            "Processing sponsored_transfer {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(self.from()),
+            keys::log(self.to())
+        );
+
+        let result = self.apply(view);
+
+        if let &Ok(_) = &result {
+            EXECUTE_SUCCESS_COUNT.inc();
+        }
+
+        info!("Applied sponsored_transfer {}: {:?}", self.hash().to_hex(), result);
+
+        timer.observe_duration();
+        EXECUTE_FINISH_COUNT.inc();
+    }
+}
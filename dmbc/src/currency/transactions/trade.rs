@@ -1,22 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use exonum::blockchain::Transaction;
 use exonum::crypto;
-use exonum::crypto::{PublicKey, Signature};
+use exonum::crypto::{Hash, PublicKey, Signature};
 use exonum::messages::Message;
 use exonum::storage::Fork;
 use prometheus::{IntCounter, Histogram};
 
+use currency::assets;
 use currency::assets::TradeAsset;
+use currency::blacklist;
 use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::offer_fills;
+use currency::price_warnings;
 use currency::status;
-use currency::transactions::components::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{advance_seed, assets_distinct_non_zero,
+                                         check_assets_limit, check_offer_expiry,
+                                         offer_signing_bytes, percentage_fee,
+                                         process_with_policy, settle_blockchain_fee,
+                                         split_coins, Apply, FeeStrategy, FeesCalculator,
+                                         ThirdPartyFees};
 use currency::wallet;
 use currency::SERVICE_ID;
 use currency::service::CONFIGURATION;
+use keys;
 
 /// Transaction ID.
-pub const TRADE_ID: u16 = 501;
+pub const TRADE_ID: u16 = 503;
 
 encoding_struct! {
     struct TradeOffer {
@@ -27,17 +40,34 @@ encoding_struct! {
         fee_strategy: u8,
         seed:         u64,
         data_info:    &str,
+        valid_until_height: u64,
     }
 }
 
 message! {
     /// `trade` transaction.
+    ///
+    /// Replaces the retired layout with ID 501, see the wire format notes of
+    /// `transactions`.
+    ///
+    /// A non-zero `quantity` buys only that many units of an offer of a
+    /// single asset with a `valid_until_height`, at its price per unit, and
+    /// fees are charged on the units bought. The rest of the offer stays
+    /// available to later trades until all its units are sold or it
+    /// expires, see `offer_fills`.
+    ///
+    /// `seed` is chosen by the buyer for every partial trade, while the seed
+    /// of the offer is shared by all the fills of the offer. It is the seed
+    /// checked under `Configuration::seed_nonces`. A trade of the whole offer
+    /// must use the seed of the offer, so it can't be replayed.
     struct Trade {
         const TYPE = SERVICE_ID;
         const ID = TRADE_ID;
 
         offer:              TradeOffer,
         seller_signature:   &Signature,
+        quantity:           u64,
+        seed:               u64,
     }
 }
 
@@ -45,13 +75,15 @@ impl FeesCalculator for Trade {
     fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
         let offer = self.offer();
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
-        let fees = ThirdPartyFees::new_trade(&*view, &offer.assets())?;
+        let fee_rate_bps = CONFIGURATION.read().unwrap().fee_rate_bps();
+        let trade_fee = percentage_fee(genesis_fees.trade(), self.total_price()?, fee_rate_bps)?;
+        let fees = ThirdPartyFees::new_trade(&*view, offer.seller(), &self.filled_assets())?;
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
 
         let mut fees_table = HashMap::new();
 
-        let payers = self.payers(&fee_strategy, genesis_fees.trade())?;
+        let payers = self.genesis_payers(&*view, &fee_strategy, trade_fee)?;
         for (payer_key, fee) in payers {
             if genesis_fees.recipient() != &payer_key {
                 fees_table.insert(payer_key, fee);
@@ -63,7 +95,8 @@ impl FeesCalculator for Trade {
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    let total = fees_table.entry(payer_key).or_insert(0u64);
+                    *total = total.checked_add(fee).ok_or(Error::Overflow)?;
                 }
             }
         }
@@ -79,24 +112,90 @@ impl Trade {
             FeeStrategy::Recipient => vec![(*offer.buyer(), fee)],
             FeeStrategy::Sender => vec![(*offer.seller(), fee)],
             FeeStrategy::RecipientAndSender => {
-                vec![(*offer.seller(), fee / 2), (*offer.buyer(), fee / 2)]
+                let (first, second) = split_coins(fee);
+                vec![(*offer.seller(), first), (*offer.buyer(), second)]
             }
-            FeeStrategy::Intermediary => return Err(Error::InvalidTransaction),
+            FeeStrategy::Intermediary => return Err(Error::InvalidFeeStrategy),
         };
         Ok(payers)
     }
 
+    /// Shares of the blockchain fee by payer, discounted for the payers
+    /// qualifying for the configured fee discount.
+    fn genesis_payers(
+        &self,
+        view: &Fork,
+        fee_strategy: &FeeStrategy,
+        fee: u64,
+    ) -> Result<Vec<(PublicKey, u64)>, Error> {
+        let configuration = CONFIGURATION.read().unwrap();
+        let payers = self.payers(fee_strategy, fee)?
+            .into_iter()
+            .map(|(key, fee)| {
+                let payer = wallet::Schema(view).fetch(&key);
+                (key, configuration.payer_fee(fee, &payer))
+            })
+            .collect();
+        Ok(payers)
+    }
+
     /// Raw bytes of the offer.
     pub fn offer_raw(&self) -> Vec<u8> {
         self.offer().raw
     }
 
+    /// Bytes of the offer signed by its parties, see `offer_signing_bytes`.
+    pub fn offer_signing_bytes(&self) -> Vec<u8> {
+        offer_signing_bytes(TRADE_ID, &self.offer().raw)
+    }
+
+    /// Assets bought by the trade: all the offered ones, or `quantity`
+    /// units of the offered asset.
+    pub fn filled_assets(&self) -> Vec<TradeAsset> {
+        let assets = self.offer().assets();
+        if self.quantity() == 0 {
+            return assets;
+        }
+        assets
+            .into_iter()
+            .map(|a| TradeAsset::new(a.id(), self.quantity(), a.price()))
+            .collect()
+    }
+
+    /// Hash of the offer, its expiry height, units it offers and units
+    /// bought, for offers that can be filled partially: the ones of a single
+    /// asset which expire.
+    fn offer_fill(&self) -> Option<(Hash, u64, u64, u64)> {
+        let offer = self.offer();
+        let assets = offer.assets();
+        if assets.len() != 1 || offer.valid_until_height() == 0 {
+            return None;
+        }
+        let amount = assets[0].amount();
+        let quantity = if self.quantity() == 0 { amount } else { self.quantity() };
+        Some((crypto::hash(&offer.raw), offer.valid_until_height(), amount, quantity))
+    }
+
+    /// Total price of the bought assets.
+    ///
+    /// # Errors
+    /// Returns `Overflow` if the total doesn't fit into `u64`.
+    fn total_price(&self) -> Result<u64, Error> {
+        let mut total = 0u64;
+        for asset in self.filled_assets() {
+            total = asset
+                .total_price()
+                .and_then(|price| total.checked_add(price))
+                .ok_or(Error::Overflow)?;
+        }
+        Ok(total)
+    }
+
     fn can_move_assets(&self, view: &mut Fork) -> Result<(), Error> {
         let mut wallet_buyer = wallet::Schema(&*view).fetch(self.offer().buyer());
         let mut wallet_seller = wallet::Schema(&*view).fetch(self.offer().seller());
 
-        let assets = self.offer()
-                    .assets()
+        let assets = self.filled_assets()
                     .into_iter()
                     .map(|a| a.to_bundle())
                     .collect::<Vec<_>>();
@@ -107,28 +206,47 @@ impl Trade {
     }
 
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
+        advance_seed(view, self.offer().buyer(), self.seed())?;
 
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fee_rate_bps = configuration.fee_rate_bps();
 
         let offer = self.offer();
+        multisig::Schema(&*view).check(&[*offer.buyer(), *offer.seller()])?;
+        check_assets_limit(&configuration, offer.assets().len())?;
+        check_offer_expiry(&*view, offer.valid_until_height())?;
+        offer_fills::Schema(&mut *view).prune();
+        if let Some((hash, valid_until_height, amount, quantity)) = self.offer_fill() {
+            offer_fills::Schema(&*view).check(&hash, valid_until_height, amount, quantity)?;
+        }
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
+
+        blacklist::Schema(&*view).check(offer.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(offer.assets().iter().map(|a| a.id()))?;
 
+        let total = self.total_price()?;
+        let trade_fee = percentage_fee(genesis_fees.trade(), total, fee_rate_bps)?;
+
+        let fee_currency = configuration.fee_currency(TRADE_ID);
         let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
+        let genesis_balance = genesis.balance();
         // Collect the blockchain fee. Execution shall not continue if this fails.
         match fee_strategy {
             FeeStrategy::Recipient => {
                 let mut buyer = wallet::Schema(&*view).fetch(offer.buyer());
 
-                wallet::move_coins(&mut buyer, &mut genesis, genesis_fees.trade())?;
+                let fee = configuration.payer_fee(trade_fee, &buyer);
+                wallet::move_fee(&mut buyer, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.buyer(), buyer);
             }
             FeeStrategy::Sender => {
                 let mut seller = wallet::Schema(&*view).fetch(offer.seller());
 
-                wallet::move_coins(&mut seller, &mut genesis, genesis_fees.trade())?;
+                let fee = configuration.payer_fee(trade_fee, &seller);
+                wallet::move_fee(&mut seller, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.seller(), seller);
             }
@@ -136,31 +254,30 @@ impl Trade {
                 let mut buyer = wallet::Schema(&*view).fetch(offer.buyer());
                 let mut seller = wallet::Schema(&*view).fetch(offer.seller());
 
-                wallet::move_coins(&mut seller, &mut genesis, genesis_fees.trade() / 2)?;
-                wallet::move_coins(&mut buyer, &mut genesis, genesis_fees.trade() / 2)?;
+                let (first, second) = split_coins(trade_fee);
+                let first = configuration.payer_fee(first, &seller);
+                let second = configuration.payer_fee(second, &buyer);
+                wallet::move_fee(&mut seller, &mut genesis, first, fee_currency)?;
+                wallet::move_fee(&mut buyer, &mut genesis, second, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.seller(), seller);
                 wallet::Schema(&mut *view).store(offer.buyer(), buyer);
             }
-            FeeStrategy::Intermediary => return Err(Error::InvalidTransaction),
+            FeeStrategy::Intermediary => return Err(Error::InvalidFeeStrategy),
         }
 
+        let collected = genesis.balance() - genesis_balance;
         wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
+        settle_blockchain_fee(view, collected)?;
 
-        let fees = ThirdPartyFees::new_trade(&*view, &offer.assets())?;
+        let fees = ThirdPartyFees::new_trade(&*view, offer.seller(), &self.filled_assets())?;
 
         self.can_move_assets(view)?;
 
         let mut wallet_buyer = wallet::Schema(&*view).fetch(offer.buyer());
         let mut wallet_seller = wallet::Schema(&*view).fetch(offer.seller());
 
-        let total = offer
-            .assets()
-            .iter()
-            .map(|asset| asset.amount() * asset.price())
-            .sum();
-
-        wallet::move_coins(&mut wallet_buyer, &mut wallet_seller, total)
+        wallet::spend_coins(&mut wallet_buyer, &mut wallet_seller, total, configuration.reserve())
             .or_else(|e| {
                 wallet::Schema(&mut *view).store(&offer.seller(), wallet_seller.clone());
                 wallet::Schema(&mut *view).store(&offer.buyer(), wallet_buyer.clone());
@@ -177,7 +294,7 @@ impl Trade {
                     FeeStrategy::RecipientAndSender => {
                         fees.collect2(view, offer.seller(), offer.buyer())?
                     }
-                    FeeStrategy::Intermediary => HashMap::<PublicKey, wallet::Wallet>::new(),
+                    FeeStrategy::Intermediary => BTreeMap::<PublicKey, wallet::Wallet>::new(),
                 };
 
                 let mut wallet_seller = updated_wallets
@@ -186,8 +303,7 @@ impl Trade {
                 let mut wallet_buyer = updated_wallets
                     .remove(&offer.buyer())
                     .unwrap_or_else(|| wallet::Schema(&*view).fetch(&offer.buyer()));
-                let assets = offer
-                    .assets()
+                let assets = self.filled_assets()
                     .into_iter()
                     .map(|a| a.to_bundle())
                     .collect::<Vec<_>>();
@@ -205,10 +321,71 @@ impl Trade {
                 Ok(())
             })?;
 
+        if let Some((hash, valid_until_height, amount, quantity)) = self.offer_fill() {
+            offer_fills::Schema(&mut *view).fill(&hash, valid_until_height, amount, quantity)?;
+        }
+
         Ok(())
     }
 }
 
+impl Apply for Trade {
+    fn check(&self) -> bool {
+        let wallets_ok = self.offer().buyer() != self.offer().seller();
+        let fee_strategy_ok = match FeeStrategy::try_from(self.offer().fee_strategy()) {
+            Some(FeeStrategy::Recipient)
+            | Some(FeeStrategy::Sender)
+            | Some(FeeStrategy::RecipientAndSender) => true,
+            _ => false,
+        };
+        let assets = self.offer().assets();
+        let assets_ok = assets_distinct_non_zero(assets.iter().map(|a| (a.id(), a.amount())));
+        let quantity_ok = if self.quantity() == 0 {
+            self.seed() == self.offer().seed()
+        } else {
+            assets.len() == 1
+                && self.offer().valid_until_height() != 0
+                && self.quantity() <= assets[0].amount()
+        };
+
+        if cfg!(fuzzing) {
+            return wallets_ok && fee_strategy_ok && assets_ok && quantity_ok;
+        }
+
+        let seller_verify_ok = crypto::verify(
+            self.seller_signature(),
+            &self.offer_signing_bytes(),
+            self.offer().seller(),
+        );
+        let buyer_verify_ok = self.verify_signature(&self.offer().buyer());
+
+        wallets_ok
+            && fee_strategy_ok
+            && assets_ok
+            && quantity_ok
+            && buyer_verify_ok
+            && seller_verify_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let payers = [*self.offer().buyer(), *self.offer().seller()];
+        let result = process_with_policy(view, &payers, |view| self.process(view));
+
+        // Off-market prices don't fail the trade, they are only flagged.
+        let price_deviation_bps = CONFIGURATION.read().unwrap().price_deviation_bps();
+        if let (&Ok(_), Some(bps)) = (&result, price_deviation_bps) {
+            let deviations = assets::Schema(&*view).price_deviations(&self.filled_assets(), bps);
+            price_warnings::Schema(&mut *view).store(&self.hash(), deviations);
+        }
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.offer().buyer(), *self.offer().seller()], &self.hash());
+
+        result
+    }
+}
+
 lazy_static! {
     static ref VERIFY_COUNT: IntCounter = register_int_counter!(
         "dmbc_transaction_trade_verify_count",
@@ -240,24 +417,7 @@ impl Transaction for Trade {
     fn verify(&self) -> bool {
         VERIFY_COUNT.inc();
 
-        let wallets_ok = self.offer().buyer() != self.offer().seller();
-        let fee_strategy_ok = match FeeStrategy::try_from(self.offer().fee_strategy()).unwrap() {
-            FeeStrategy::Recipient | FeeStrategy::Sender | FeeStrategy::RecipientAndSender => true,
-            _ => false,
-        };
-
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
-
-        let seller_verify_ok = crypto::verify(
-            self.seller_signature(),
-            &self.offer().raw,
-            self.offer().seller(),
-        );
-        let buyer_verify_ok = self.verify_signature(&self.offer().buyer());
-
-        if wallets_ok && fee_strategy_ok && buyer_verify_ok && seller_verify_ok {
+        if self.check() {
             VERIFY_SUCCESS_COUNT.inc();
             true
         } else {
@@ -266,16 +426,28 @@ impl Transaction for Trade {
     }
 
     fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
         EXECUTE_COUNT.inc();
         let timer = EXECUTE_DURATION.start_timer();
 
-        let result = self.process(view);
+        debug!(
+            "Processing trade {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(self.offer().seller()),
+            keys::log(self.offer().buyer())
+        );
+
+        let result = self.apply(view);
 
         if let &Ok(_) = &result {
             EXECUTE_SUCCESS_COUNT.inc();
         }
 
-        status::Schema(view).store(self.hash(), result);
+        info!("Applied trade {}: {:?}", self.hash().to_hex(), result);
 
         timer.observe_duration();
         EXECUTE_FINISH_COUNT.inc();
@@ -7,17 +7,30 @@ use exonum::messages::Message;
 use exonum::storage::Fork;
 use prometheus::{IntCounter, Histogram};
 
+use currency::assets;
 use currency::assets::TradeAsset;
+use currency::blacklist;
 use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::intermediaries;
+use currency::memos;
+use currency::multisig;
+use currency::price_warnings;
 use currency::status;
-use currency::transactions::components::Intermediary;
-use currency::transactions::components::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{advance_seed, assets_distinct_non_zero,
+                                         check_assets_limit, check_offer_expiry,
+                                         process_with_policy, settle_blockchain_fee,
+                                         Apply, Intermediary, MAX_DATA_INFO_LENGTH};
+use currency::transactions::components::{offer_signing_bytes, percentage_fee, split_coins,
+                                         FeeStrategy, FeesCalculator, ThirdPartyFees};
 use currency::wallet;
 use currency::SERVICE_ID;
 use currency::service::CONFIGURATION;
+use keys;
 
 /// Transaction ID.
-pub const TRADE_INTERMEDIARY_ID: u16 = 502;
+pub const TRADE_INTERMEDIARY_ID: u16 = 504;
 
 encoding_struct! {
     struct TradeOfferIntermediary {
@@ -29,11 +42,22 @@ encoding_struct! {
         fee_strategy: u8,
         seed:         u64,
         data_info:    &str,
+        valid_until_height: u64,
     }
 }
 
 message! {
     /// `trade_intermediary` transaction.
+    ///
+    /// Replaces the retired layout with ID 502, see the wire format notes of
+    /// `transactions`.
+    ///
+    /// With the null intermediary (see `Intermediary::is_none`) neither the
+    /// intermediary signature nor its commission is involved, and the
+    /// transaction behaves like a plain `trade`.
+    ///
+    /// Fails with `InvalidTransaction` if the intermediary wallet is the
+    /// fee recipient of the configuration.
     struct TradeIntermediary {
         const TYPE = SERVICE_ID;
         const ID = TRADE_INTERMEDIARY_ID;
@@ -48,17 +72,21 @@ impl FeesCalculator for TradeIntermediary {
     fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
         let offer = self.offer();
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
-        let mut fees = ThirdPartyFees::new_trade(&*view, &offer.assets())?;
-        fees.add_fee(
-            offer.intermediary().wallet(),
-            offer.intermediary().commission()
-        );
+        let fee_rate_bps = CONFIGURATION.read().unwrap().fee_rate_bps();
+        let trade_fee = percentage_fee(genesis_fees.trade(), self.total_price()?, fee_rate_bps)?;
+        let mut fees = ThirdPartyFees::new_trade(&*view, offer.seller(), &offer.assets())?;
+        if !offer.intermediary().is_none() {
+            fees.add_fee(
+                offer.intermediary().wallet(),
+                offer.intermediary().commission()
+            )?;
+        }
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
 
         let mut fees_table = HashMap::new();
 
-        let payers = self.payers(&fee_strategy, genesis_fees.trade())?;
+        let payers = self.genesis_payers(&*view, &fee_strategy, trade_fee)?;
         for (payer_key, fee) in payers {
             if genesis_fees.recipient() != &payer_key {
                 fees_table.insert(payer_key, fee);
@@ -70,7 +98,8 @@ impl FeesCalculator for TradeIntermediary {
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    let total = fees_table.entry(payer_key).or_insert(0u64);
+                    *total = total.checked_add(fee).ok_or(Error::Overflow)?;
                 }
             }
         }
@@ -86,18 +115,68 @@ impl TradeIntermediary {
             FeeStrategy::Recipient => vec![(*offer.buyer(), fee)],
             FeeStrategy::Sender => vec![(*offer.seller(), fee)],
             FeeStrategy::RecipientAndSender => {
-                vec![(*offer.seller(), fee / 2), (*offer.buyer(), fee / 2)]
+                let (first, second) = split_coins(fee);
+                vec![(*offer.seller(), first), (*offer.buyer(), second)]
             }
             FeeStrategy::Intermediary => vec![(*offer.intermediary().wallet(), fee)],
         };
         Ok(payers)
     }
 
+    /// Shares of the blockchain fee by payer, discounted for the payers
+    /// qualifying for the configured fee discount.
+    fn genesis_payers(
+        &self,
+        view: &Fork,
+        fee_strategy: &FeeStrategy,
+        fee: u64,
+    ) -> Result<Vec<(PublicKey, u64)>, Error> {
+        let configuration = CONFIGURATION.read().unwrap();
+        let payers = self.payers(fee_strategy, fee)?
+            .into_iter()
+            .map(|(key, fee)| {
+                let payer = wallet::Schema(view).fetch(&key);
+                (key, configuration.payer_fee(fee, &payer))
+            })
+            .collect();
+        Ok(payers)
+    }
+
     /// Raw bytes of the offer.
     pub fn offer_raw(&self) -> Vec<u8> {
         self.offer().raw
     }
 
+    /// Bytes of the offer signed by its parties, see `offer_signing_bytes`.
+    pub fn offer_signing_bytes(&self) -> Vec<u8> {
+        offer_signing_bytes(TRADE_INTERMEDIARY_ID, &self.offer().raw)
+    }
+
+    /// Wallets involved in the transaction, without the null intermediary.
+    fn parties(&self) -> Vec<PublicKey> {
+        let offer = self.offer();
+        let mut parties = vec![*offer.buyer(), *offer.seller()];
+        if !offer.intermediary().is_none() {
+            parties.push(*offer.intermediary().wallet());
+        }
+        parties
+    }
+
+    /// Total price of the offered assets.
+    ///
+    /// # Errors
+    /// Returns `Overflow` if the total doesn't fit into `u64`.
+    fn total_price(&self) -> Result<u64, Error> {
+        let mut total = 0u64;
+        for asset in self.offer().assets() {
+            total = asset
+                .total_price()
+                .and_then(|price| total.checked_add(price))
+                .ok_or(Error::Overflow)?;
+        }
+        Ok(total)
+    }
+
     fn can_move_assets(&self, view: &mut Fork) -> Result<(), Error> {
         let mut wallet_buyer = wallet::Schema(&*view).fetch(self.offer().buyer());
         let mut wallet_seller = wallet::Schema(&*view).fetch(self.offer().seller());
@@ -113,37 +192,95 @@ impl TradeIntermediary {
         Ok(())
     }
 
+    /// Check that the intermediary wallet exists and is able to cover the
+    /// blockchain fee, if paid in coins, together with the third party fees it
+    /// pays under the intermediary fee strategy. Nothing is written.
+    ///
+    /// # Errors
+    /// Returns `WalletNotFound` if the intermediary has no wallet,
+    /// `InsufficientFunds` if it can't cover the fees, or `Overflow` if the
+    /// total doesn't fit into `u64`.
+    fn check_intermediary(&self, view: &Fork, trade_fee: u64) -> Result<(), Error> {
+        let offer = self.offer();
+        let intermediary_key = offer.intermediary().wallet();
+
+        if !wallet::Schema(&*view).contains(intermediary_key) {
+            return Err(Error::WalletNotFound);
+        }
+
+        let intermediary = wallet::Schema(&*view).fetch(intermediary_key);
+        let configuration = CONFIGURATION.read().unwrap();
+        let fee = configuration.payer_fee(trade_fee, &intermediary);
+        let mut required = configuration.fee_currency(TRADE_INTERMEDIARY_ID).coins(fee);
+        if let Ok(fees) = ThirdPartyFees::new_trade(&*view, offer.seller(), &offer.assets()) {
+            for (key, fee) in &fees.0 {
+                if key != intermediary_key {
+                    required = required.checked_add(*fee).ok_or(Error::Overflow)?;
+                }
+            }
+        }
+
+        // The intermediary only pays fees, which may dip into the reserve.
+        if !wallet::can_spend(&intermediary, required, 0) {
+            return Err(Error::InsufficientFunds);
+        }
+
+        Ok(())
+    }
+
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
+        advance_seed(view, self.offer().buyer(), self.offer().seed())?;
 
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let fee_rate_bps = configuration.fee_rate_bps();
 
         let offer = self.offer();
+        multisig::Schema(&*view).check(&[*offer.buyer(), *offer.seller()])?;
+        check_assets_limit(&configuration, offer.assets().len())?;
+        check_offer_expiry(&*view, offer.valid_until_height())?;
+
+        if configuration.intermediary_registry() && !offer.intermediary().is_none() {
+            intermediaries::Schema(&*view).check(offer.intermediary().wallet())?;
+        }
+
+        // The fee recipient can't stand in as the intermediary too.
+        if offer.intermediary().wallet() == genesis_fees.recipient() {
+            return Err(Error::InvalidTransaction);
+        }
 
         let fee_strategy =
-            FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
+            FeeStrategy::try_from(offer.fee_strategy()).ok_or(Error::InvalidFeeStrategy)?;
 
-        let total = offer
-            .assets()
-            .iter()
-            .map(|asset| asset.amount() * asset.price())
-            .sum::<u64>();
+        let total = self.total_price()?;
+        let trade_fee = percentage_fee(genesis_fees.trade(), total, fee_rate_bps)?;
+
+        blacklist::Schema(&*view).check(offer.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(offer.assets().iter().map(|a| a.id()))?;
 
+        if fee_strategy == FeeStrategy::Intermediary {
+            self.check_intermediary(view, trade_fee)?;
+        }
+
+        let fee_currency = configuration.fee_currency(TRADE_INTERMEDIARY_ID);
         let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
+        let genesis_balance = genesis.balance();
 
         // Collect the blockchain fee. Execution shall not continue if this fails.
         match fee_strategy {
             FeeStrategy::Recipient => {
                 let mut buyer = wallet::Schema(&*view).fetch(offer.buyer());
 
-                wallet::move_coins(&mut buyer, &mut genesis, genesis_fees.trade())?;
+                let fee = configuration.payer_fee(trade_fee, &buyer);
+                wallet::move_fee(&mut buyer, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.buyer(), buyer);
             }
             FeeStrategy::Sender => {
                 let mut seller = wallet::Schema(&*view).fetch(offer.seller());
 
-                wallet::move_coins(&mut seller, &mut genesis, genesis_fees.trade())?;
+                let fee = configuration.payer_fee(trade_fee, &seller);
+                wallet::move_fee(&mut seller, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.seller(), seller);
             }
@@ -151,8 +288,11 @@ impl TradeIntermediary {
                 let mut buyer = wallet::Schema(&*view).fetch(offer.buyer());
                 let mut seller = wallet::Schema(&*view).fetch(offer.seller());
 
-                wallet::move_coins(&mut seller, &mut genesis, genesis_fees.trade() / 2)?;
-                wallet::move_coins(&mut buyer, &mut genesis, genesis_fees.trade() / 2)?;
+                let (first, second) = split_coins(trade_fee);
+                let first = configuration.payer_fee(first, &seller);
+                let second = configuration.payer_fee(second, &buyer);
+                wallet::move_fee(&mut seller, &mut genesis, first, fee_currency)?;
+                wallet::move_fee(&mut buyer, &mut genesis, second, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.seller(), seller);
                 wallet::Schema(&mut *view).store(offer.buyer(), buyer);
@@ -160,74 +300,125 @@ impl TradeIntermediary {
             FeeStrategy::Intermediary => {
                 let mut intermediary = wallet::Schema(&*view).fetch(offer.intermediary().wallet());
 
-                wallet::move_coins(&mut intermediary, &mut genesis, genesis_fees.trade())?;
+                let fee = configuration.payer_fee(trade_fee, &intermediary);
+                wallet::move_fee(&mut intermediary, &mut genesis, fee, fee_currency)?;
 
                 wallet::Schema(&mut *view).store(offer.intermediary().wallet(), intermediary);
             }
         }
 
+        let collected = genesis.balance() - genesis_balance;
         wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
-
-        let mut fees = ThirdPartyFees::new_trade(&*view, &offer.assets())?;
-        fees.add_fee(
-            offer.intermediary().wallet(),
-            offer.intermediary().commission(),
-        );
+        settle_blockchain_fee(view, collected)?;
+
+        let mut fees = ThirdPartyFees::new_trade(&*view, offer.seller(), &offer.assets())?;
+        if !offer.intermediary().is_none() {
+            fees.add_fee(
+                offer.intermediary().wallet(),
+                offer.intermediary().commission(),
+            )?;
+        }
 
         self.can_move_assets(view)?;
 
-        let mut wallet_buyer = wallet::Schema(&*view).fetch(self.offer().buyer());
-        let mut wallet_seller = wallet::Schema(&*view).fetch(self.offer().seller());
-
-        wallet::move_coins(&mut wallet_buyer, &mut wallet_seller, total)
-            .or_else(|e| {
-                wallet::Schema(&mut *view).store(&offer.seller(), wallet_seller.clone());
-                wallet::Schema(&mut *view).store(&offer.buyer(), wallet_buyer.clone());
+        // Operations bellow must either all succeed, or return an error without
+        // saving anything to the database.
 
-                Err(e)
-            })
-            .and_then(|_| {
-                wallet::Schema(&mut *view).store(&offer.seller(), wallet_seller);
-                wallet::Schema(&mut *view).store(&offer.buyer(), wallet_buyer);
-
-                let mut updated_wallets = match fee_strategy {
-                    FeeStrategy::Recipient => fees.collect(view, offer.buyer())?,
-                    FeeStrategy::Sender => fees.collect(view, offer.seller())?,
-                    FeeStrategy::RecipientAndSender => {
-                        fees.collect2(view, offer.seller(), offer.buyer())?
-                    }
-                    FeeStrategy::Intermediary => fees.collect(view, offer.intermediary().wallet())?,
-                };
-
-                let mut wallet_seller = updated_wallets
-                    .remove(&offer.seller())
-                    .unwrap_or_else(|| wallet::Schema(&*view).fetch(&offer.seller()));
-                let mut wallet_buyer = updated_wallets
-                    .remove(&offer.buyer())
-                    .unwrap_or_else(|| wallet::Schema(&*view).fetch(&offer.buyer()));
-                let assets = offer
-                    .assets()
-                    .into_iter()
-                    .map(|a| a.to_bundle())
-                    .collect::<Vec<_>>();
+        // Process third party fees.
+        let mut updated_wallets = match fee_strategy {
+            FeeStrategy::Recipient => fees.collect(view, offer.buyer())?,
+            FeeStrategy::Sender => fees.collect(view, offer.seller())?,
+            FeeStrategy::RecipientAndSender => {
+                fees.collect2(view, offer.seller(), offer.buyer())?
+            }
+            FeeStrategy::Intermediary => fees.collect(view, offer.intermediary().wallet())?,
+        };
 
-                wallet::move_assets(&mut wallet_seller, &mut wallet_buyer, &assets)?;
+        // Process the main transaction.
+        let mut wallet_seller = updated_wallets
+            .remove(&offer.seller())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(&offer.seller()));
+        let mut wallet_buyer = updated_wallets
+            .remove(&offer.buyer())
+            .unwrap_or_else(|| wallet::Schema(&*view).fetch(&offer.buyer()));
+        let assets = offer
+            .assets()
+            .into_iter()
+            .map(|a| a.to_bundle())
+            .collect::<Vec<_>>();
 
-                updated_wallets.insert(*offer.seller(), wallet_seller);
-                updated_wallets.insert(*offer.buyer(), wallet_buyer);
+        wallet::move_assets(&mut wallet_seller, &mut wallet_buyer, &assets)?;
+        wallet::spend_coins(&mut wallet_buyer, &mut wallet_seller, total, configuration.reserve())?;
 
-                // Save changes to the database.
-                for (key, wallet) in updated_wallets {
-                    wallet::Schema(&mut *view).store(&key, wallet);
-                }
+        updated_wallets.insert(*offer.seller(), wallet_seller);
+        updated_wallets.insert(*offer.buyer(), wallet_buyer);
 
-                Ok(())
-            })?;
+        // Save changes to the database.
+        for (key, wallet) in updated_wallets {
+            wallet::Schema(&mut *view).store(&key, wallet);
+        }
 
         Ok(())
     }
 }
 
+impl Apply for TradeIntermediary {
+    fn check(&self) -> bool {
+        let offer = self.offer();
+        let no_intermediary = offer.intermediary().is_none();
+
+        let wallets_ok = offer.seller() != offer.buyer()
+            && offer.intermediary().wallet() != offer.seller()
+            && offer.intermediary().wallet() != offer.buyer();
+        // The null intermediary can't pay the fees.
+        let fee_strategy_ok = match FeeStrategy::try_from(offer.fee_strategy()) {
+            Some(FeeStrategy::Intermediary) => !no_intermediary,
+            Some(_) => true,
+            None => false,
+        };
+        let assets_ok =
+            assets_distinct_non_zero(offer.assets().iter().map(|a| (a.id(), a.amount())));
+
+        // Memos are stored with the transaction, so keep them small.
+        let data_info_ok = offer.data_info().len() <= MAX_DATA_INFO_LENGTH;
+
+        if cfg!(fuzzing) {
+            return wallets_ok && fee_strategy_ok && assets_ok && data_info_ok;
+        }
+
+        let buyer_ok = self.verify_signature(offer.buyer());
+
+        let seller_ok = crypto::verify(self.seller_signature(), &self.offer_signing_bytes(), offer.seller());
+        let intermediary_ok = no_intermediary || crypto::verify(
+            self.intermediary_signature(),
+            &self.offer_signing_bytes(),
+            offer.intermediary().wallet(),
+        );
+
+        wallets_ok && fee_strategy_ok && assets_ok && data_info_ok && buyer_ok && seller_ok
+            && intermediary_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let parties = self.parties();
+        let result = process_with_policy(view, &parties, |view| self.process(view));
+
+        // Off-market prices don't fail the trade, they are only flagged.
+        let price_deviation_bps = CONFIGURATION.read().unwrap().price_deviation_bps();
+        if let (&Ok(_), Some(bps)) = (&result, price_deviation_bps) {
+            let deviations = assets::Schema(&*view).price_deviations(&self.offer().assets(), bps);
+            price_warnings::Schema(&mut *view).store(&self.hash(), deviations);
+        }
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        memos::Schema(&mut *view).store(&self.hash(), self.offer().data_info());
+        history::Schema(view).append(&parties, &self.hash());
+
+        result
+    }
+}
+
 lazy_static! {
     static ref VERIFY_COUNT: IntCounter = register_int_counter!(
         "dmbc_transaction_trade_intermediary_verify_count",
@@ -259,27 +450,7 @@ impl Transaction for TradeIntermediary {
     fn verify(&self) -> bool {
         VERIFY_COUNT.inc();
 
-        let offer = self.offer();
-
-        let wallets_ok = offer.seller() != offer.buyer()
-            && offer.intermediary().wallet() != offer.seller()
-            && offer.intermediary().wallet() != offer.buyer();
-        let fee_strategy_ok = FeeStrategy::try_from(offer.fee_strategy()).is_some();
-
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
-
-        let buyer_ok = self.verify_signature(offer.buyer());
-
-        let seller_ok = crypto::verify(self.seller_signature(), &offer.raw, offer.seller());
-        let intermediary_ok = crypto::verify(
-            self.intermediary_signature(),
-            &offer.raw,
-            offer.intermediary().wallet(),
-        );
-
-        if wallets_ok && fee_strategy_ok && buyer_ok && seller_ok && intermediary_ok {
+        if self.check() {
             VERIFY_SUCCESS_COUNT.inc();
             true
         } else {
@@ -288,16 +459,28 @@ impl Transaction for TradeIntermediary {
     }
 
     fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
         EXECUTE_COUNT.inc();
         let timer = EXECUTE_DURATION.start_timer();
 
-        let result = self.process(view);
+        debug!(
+            "Processing trade_intermediary {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(self.offer().seller()),
+            keys::log(self.offer().buyer())
+        );
+
+        let result = self.apply(view);
 
         if let &Ok(_) = &result {
             EXECUTE_SUCCESS_COUNT.inc();
         }
 
-        status::Schema(view).store(self.hash(), result);
+        info!("Applied trade_intermediary {}: {:?}", self.hash().to_hex(), result);
 
         timer.observe_duration();
         EXECUTE_FINISH_COUNT.inc();
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 
 use exonum::blockchain::Transaction;
 use exonum::crypto::PublicKey;
@@ -6,19 +7,52 @@ use exonum::messages::Message;
 use exonum::storage::Fork;
 use prometheus::{IntCounter, Histogram};
 
-use currency::assets::AssetBundle;
+use currency::assets::{AssetBundle, AssetId};
+use currency::blacklist;
+use currency::configuration::TransferFeePolicy;
 use currency::error::Error;
+use currency::frozen;
+use currency::history;
+use currency::multisig;
+use currency::non_fungible;
 use currency::status;
-use currency::transactions::components::{FeesCalculator, ThirdPartyFees};
+use currency::tags;
+use currency::transactions::components::{advance_seed, assets_distinct_non_zero,
+                                         check_assets_limit, process_with_policy,
+                                         settle_blockchain_fee, Apply, FeesCalculator,
+                                         ThirdPartyFees};
 use currency::wallet;
 use currency::SERVICE_ID;
 use currency::service::CONFIGURATION;
+use keys;
 
 /// Transaction ID.
-pub const TRANSFER_ID: u16 = 200;
+pub const TRANSFER_ID: u16 = 203;
+
+/// Maximum length of a transfer tag, in bytes.
+pub const MAX_TAG_LENGTH: usize = 32;
 
 message! {
     /// `transfer` transaction.
+    ///
+    /// Replaces the retired layout with ID 200, see the wire format notes of
+    /// `transactions`.
+    ///
+    /// `seed` must differ between transfers with otherwise equal contents,
+    /// since equal transactions share a hash and only the first is executed.
+    /// See `builders::transaction::generate_seed`.
+    ///
+    /// Non-zero `only_if_recipient_below` makes the transfer apply only if
+    /// the recipient's balance is below it.
+    ///
+    /// With `strict` set the transfer applies only if the sender holds
+    /// exactly the listed amount of every listed asset, which catches
+    /// transfers built from stale wallet state.
+    ///
+    /// A transfer must move coins or assets, unless `send_all` is set.
+    ///
+    /// Non-empty `tag` categorizes the transfer for reporting, it is limited
+    /// to `MAX_TAG_LENGTH` bytes. See `tags::Schema`.
     struct Transfer {
         const TYPE = SERVICE_ID;
         const ID = TRANSFER_ID;
@@ -26,25 +60,33 @@ message! {
         from:      &PublicKey,
         to:        &PublicKey,
         amount:    u64,
+        send_all:  bool,
+        strict:    bool,
+        only_if_recipient_below: u64,
         assets:    Vec<AssetBundle>,
         seed:      u64,
         data_info: &str,
+        tag:       &str,
     }
 }
 
 impl FeesCalculator for Transfer {
     fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
         let fees = ThirdPartyFees::new_transfer(&*view, self.assets())?;
 
         let mut fees_table = HashMap::new();
         if genesis_fees.recipient() != self.from() {
-            fees_table.insert(*self.from(), genesis_fees.transfer());
+            let wallet_from = wallet::Schema(&*view).fetch(self.from());
+            let fee = configuration.payer_fee(genesis_fees.transfer(), &wallet_from);
+            fees_table.insert(*self.from(), fee);
         }
 
         for (pub_key, fee) in fees.0 {
             if pub_key != *self.from() {
-                *fees_table.entry(*self.from()).or_insert(0) += fee;
+                let total = fees_table.entry(*self.from()).or_insert(0);
+                *total = total.checked_add(fee).ok_or(Error::Overflow)?;
             }
         }
 
@@ -54,16 +96,44 @@ impl FeesCalculator for Transfer {
 
 impl Transfer {
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        let genesis_fees = CONFIGURATION.read().unwrap().fees();
+        advance_seed(view, self.from(), self.seed())?;
+
+        let configuration = CONFIGURATION.read().unwrap().clone();
+        let genesis_fees = configuration.fees();
+        let policy = configuration.transfer_fee_policy();
+        let fee_currency = configuration.fee_currency(TRANSFER_ID);
+
+        multisig::Schema(&*view).check(&[*self.from()])?;
+        check_assets_limit(&configuration, self.assets().len())?;
+
+        let fee = configuration.payer_fee(
+            genesis_fees.transfer(),
+            &wallet::Schema(&*view).fetch(self.from()),
+        );
+
+        // Amount that overflows together with the blockchain fee can never
+        // be covered, so fail before any wallet is touched.
+        if policy == TransferFeePolicy::BeforeAmount && !self.send_all() {
+            fee
+                .checked_add(self.amount())
+                .ok_or(Error::Overflow)?;
+        }
+
+        blacklist::Schema(&*view).check(self.assets().iter().map(|a| a.id()))?;
+        frozen::Schema(&*view).check(self.assets().iter().map(|a| a.id()))?;
+        non_fungible::Schema(&*view).check(self.assets().iter())?;
 
-        let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
+        if policy == TransferFeePolicy::BeforeAmount {
+            let mut genesis = wallet::Schema(&*view).fetch(genesis_fees.recipient());
 
-        // Collect the blockchain fee. Execution shall not continue if this fails.
-        let mut wallet_from = wallet::Schema(&*view).fetch(self.from());
-        wallet::move_coins(&mut wallet_from, &mut genesis, genesis_fees.transfer())?;
+            // Collect the blockchain fee. Execution shall not continue if this fails.
+            let mut wallet_from = wallet::Schema(&*view).fetch(self.from());
+            wallet::move_fee(&mut wallet_from, &mut genesis, fee, fee_currency)?;
 
-        wallet::Schema(&mut *view).store(self.from(), wallet_from);
-        wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
+            wallet::Schema(&mut *view).store(self.from(), wallet_from);
+            wallet::Schema(&mut *view).store(genesis_fees.recipient(), genesis);
+            settle_blockchain_fee(view, fee_currency.coins(fee))?;
+        }
 
         let fees = ThirdPartyFees::new_transfer(&*view, self.assets())?;
 
@@ -82,10 +152,67 @@ impl Transfer {
             .remove(&self.to())
             .unwrap_or_else(|| wallet::Schema(&*view).fetch(&self.to()));
 
-        //wallet::Schema(&*view).fetch(self.to());
-        wallet::move_coins(&mut wallet_from, &mut wallet_to, self.amount())?;
+        let threshold = self.only_if_recipient_below();
+        if threshold != 0 && wallet_to.balance() >= threshold {
+            return Err(Error::ConditionNotMet);
+        }
+
+        if self.strict() && !holds_exactly(&wallet_from, &self.assets()) {
+            return Err(Error::ConditionNotMet);
+        }
+
+        // With `send_all` everything left after the fees and above the
+        // reserve goes to the recipient.
+        let amount = if self.send_all() {
+            wallet_from.spendable_balance(configuration.reserve())
+        } else {
+            self.amount()
+        };
+
+        // Blockchain fee collected from the amount, counted once stored.
+        let mut collected = 0;
+        match policy {
+            TransferFeePolicy::BeforeAmount => {
+                wallet::spend_coins(
+                    &mut wallet_from,
+                    &mut wallet_to,
+                    amount,
+                    configuration.reserve(),
+                )?;
+            }
+            TransferFeePolicy::FromAmount => {
+                // Only a fee in coins comes out of the amount, a fee in
+                // asset units is paid on top of it.
+                let coins = fee_currency.coins(fee);
+                if amount < coins {
+                    return Err(Error::InsufficientFunds);
+                }
+
+                let mut genesis = updated_wallets
+                    .remove(genesis_fees.recipient())
+                    .unwrap_or_else(|| wallet::Schema(&*view).fetch(genesis_fees.recipient()));
+
+                wallet::move_fee(&mut wallet_from, &mut genesis, fee, fee_currency)?;
+                wallet::spend_coins(
+                    &mut wallet_from,
+                    &mut wallet_to,
+                    amount - coins,
+                    configuration.reserve(),
+                )?;
+
+                updated_wallets.insert(*genesis_fees.recipient(), genesis);
+                collected = coins;
+            }
+        }
         wallet::move_assets(&mut wallet_from, &mut wallet_to, &self.assets())?;
 
+        // No dust may be left behind, the wallet is either emptied or keeps
+        // at least the minimum balance.
+        let left = wallet_from.balance();
+        if left != 0 && left < configuration.min_balance() {
+            return Err(Error::DustBalance);
+        }
+
         updated_wallets.insert(*self.from(), wallet_from);
         updated_wallets.insert(*self.to(), wallet_to);
 
@@ -93,11 +220,69 @@ impl Transfer {
         for (key, wallet) in updated_wallets {
             wallet::Schema(&mut *view).store(&key, wallet);
         }
+        settle_blockchain_fee(view, collected)?;
 
         Ok(())
     }
 }
 
+/// Whether `wallet` holds exactly the amounts of the assets in `assets`,
+/// summing the bundles that repeat an asset.
+fn holds_exactly(wallet: &wallet::Wallet, assets: &[AssetBundle]) -> bool {
+    let mut expected: HashMap<AssetId, u64> = HashMap::new();
+    for asset in assets {
+        match expected.entry(asset.id()) {
+            Entry::Occupied(mut entry) => match entry.get().checked_add(asset.amount()) {
+                Some(amount) => *entry.get_mut() = amount,
+                None => return false,
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(asset.amount());
+            }
+        }
+    }
+
+    let held = wallet.assets();
+    expected.into_iter().all(|(id, amount)| {
+        held.iter()
+            .find(|a| a.id() == id)
+            .map_or(amount == 0, |a| a.amount() == amount)
+    })
+}
+
+impl Apply for Transfer {
+    fn check(&self) -> bool {
+        let wallets_ok = self.from() != self.to();
+        let tag_ok = self.tag().len() <= MAX_TAG_LENGTH;
+        // A transfer moving nothing would only pay the fee.
+        let contents_ok = self.send_all() || self.amount() != 0 || !self.assets().is_empty();
+        let assets_ok =
+            assets_distinct_non_zero(self.assets().iter().map(|a| (a.id(), a.amount())));
+
+        if cfg!(fuzzing) {
+            return wallets_ok && tag_ok && contents_ok && assets_ok;
+        }
+
+        let verify_ok = self.verify_signature(&self.from());
+
+        wallets_ok && tag_ok && contents_ok && assets_ok && verify_ok
+    }
+
+    fn apply(&self, view: &mut Fork) -> Result<(), Error> {
+        let result = process_with_policy(view, &[*self.from()], |view| self.process(view));
+
+        status::Schema(view).store(self.hash(), result);
+        wallet::Schema(&mut *view).record_snapshots();
+        history::Schema(view).append(&[*self.from(), *self.to()], &self.hash());
+
+        if !self.tag().is_empty() {
+            tags::Schema(view).append(self.tag(), &self.hash());
+        }
+
+        result
+    }
+}
+
 lazy_static! {
     static ref VERIFY_COUNT: IntCounter = register_int_counter!(
         "dmbc_transaction_transfer_verify_count",
@@ -129,15 +314,7 @@ impl Transaction for Transfer {
     fn verify(&self) -> bool {
         VERIFY_COUNT.inc();
 
-        let wallets_ok = self.from() != self.to();
-
-        if cfg!(fuzzing) {
-            return wallets_ok;
-        }
-
-        let verify_ok = self.verify_signature(&self.from());
-
-        if wallets_ok && verify_ok {
+        if self.check() {
             VERIFY_SUCCESS_COUNT.inc();
             true
         } else {
@@ -146,16 +323,28 @@ impl Transaction for Transfer {
     }
 
     fn execute(&self, view: &mut Fork) {
+        // A replayed transaction keeps the status of its first execution.
+        if status::Schema(&*view).contains(&self.hash()) {
+            return;
+        }
+
         EXECUTE_COUNT.inc();
         let timer = EXECUTE_DURATION.start_timer();
 
-        let result = self.process(view);
+        debug!(
+            "Processing transfer {} from {} to {}",
+            self.hash().to_hex(),
+            keys::log(self.from()),
+            keys::log(self.to())
+        );
+
+        let result = self.apply(view);
 
         if let &Ok(_) = &result {
             EXECUTE_SUCCESS_COUNT.inc();
         }
 
-        status::Schema(view).store(self.hash(), result);
+        info!("Applied transfer {}: {:?}", self.hash().to_hex(), result);
 
         timer.observe_duration();
         EXECUTE_FINISH_COUNT.inc();
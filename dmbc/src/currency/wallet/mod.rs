@@ -1,7 +1,10 @@
 //! Types and operations on wallets in the blockchain network.
 
 mod schema;
+mod snapshot;
 mod wallet;
 
 pub use currency::wallet::schema::Schema;
-pub use currency::wallet::wallet::{move_assets, move_coins, Wallet};
+pub use currency::wallet::snapshot::BalanceSnapshot;
+pub use currency::wallet::wallet::{can_spend, move_assets, move_coins, move_fee, spend_coins,
+                                          Wallet};
@@ -1,7 +1,8 @@
+use exonum::blockchain;
 use exonum::crypto::PublicKey;
-use exonum::storage::{Fork, MapIndex, Snapshot};
+use exonum::storage::{Fork, ListIndex, MapIndex, Snapshot};
 
-use currency::wallet::Wallet;
+use currency::wallet::{BalanceSnapshot, Wallet};
 use currency::SERVICE_NAME;
 
 /// The schema for accessing wallets data.
@@ -19,12 +20,51 @@ where
         MapIndex::new(key, self.0)
     }
 
+    /// Check whether the wallet has any state stored. Wallets with neither
+    /// coins nor assets are not stored.
+    pub fn contains(self, pub_key: &PublicKey) -> bool {
+        self.index().contains(pub_key)
+    }
+
     /// Fetch state for the specified wallet from the snapshot.
+    ///
+    /// A stored wallet is returned as it is. A wallet which isn't stored
+    /// reads as an empty one, without being created, so transactions fetch
+    /// their recipients this way and credit them on top of what they hold.
     pub fn fetch(self, pub_key: &PublicKey) -> Wallet {
         self.index()
             .get(pub_key)
             .unwrap_or_else(|| Wallet::new_empty())
     }
+
+    /// Internal `ListIndex` of the snapshots of the wallet with `pub_key`,
+    /// oldest first.
+    pub fn snapshots(self, pub_key: &PublicKey) -> ListIndex<S, BalanceSnapshot> {
+        let key = SERVICE_NAME.to_string() + ".wallet_snapshots";
+        ListIndex::with_prefix(key, pub_key.as_ref().to_vec(), self.0)
+    }
+
+    /// Latest snapshot of the wallet with `pub_key` taken at or before
+    /// `height`, if the wallet was changed by then.
+    pub fn snapshot_at(self, pub_key: &PublicKey, height: u64) -> Option<BalanceSnapshot> {
+        let snapshots = self.snapshots(pub_key);
+
+        // Heights never decrease along the list, find the first one above.
+        let (mut low, mut high) = (0, snapshots.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if snapshots.get(mid).unwrap().height() <= height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        match low {
+            0 => None,
+            _ => snapshots.get(low - 1),
+        }
+    }
 }
 
 impl<'a> Schema<&'a mut Fork> {
@@ -34,14 +74,72 @@ impl<'a> Schema<&'a mut Fork> {
         MapIndex::new(key, &mut *self.0)
     }
 
+    /// Internal `ListIndex` of the snapshots of the wallet with `pub_key`,
+    /// with mutable access.
+    pub fn snapshots_mut(
+        &mut self,
+        pub_key: &PublicKey,
+    ) -> ListIndex<&mut Fork, BalanceSnapshot> {
+        let key = SERVICE_NAME.to_string() + ".wallet_snapshots";
+        ListIndex::with_prefix(key, pub_key.as_ref().to_vec(), &mut *self.0)
+    }
+
+    /// Internal `MapIndex` of the wallets stored since the last call to
+    /// `record_snapshots`, with their state from before that.
+    fn pending_mut(&mut self) -> MapIndex<&mut Fork, PublicKey, Wallet> {
+        let key = SERVICE_NAME.to_string() + ".wallet_snapshots.pending";
+        MapIndex::new(key, &mut *self.0)
+    }
+
     /// Store the new state for a wallet in the database.
+    ///
+    /// The change is snapshotted by the next `record_snapshots`.
     pub fn store(&mut self, pub_key: &PublicKey, wallet: Wallet) {
+        if !self.pending_mut().contains(pub_key) {
+            let old = Schema(&*self.0).fetch(pub_key);
+            self.pending_mut().put(pub_key, old);
+        }
+
         match (wallet.balance(), wallet.assets().len()) {
             (0, 0) => self.remove(pub_key),
             (_, _) => self.index_mut().put(pub_key, wallet),
         };
     }
 
+    /// Append a snapshot for every wallet stored since the last call, with
+    /// the height of the block being built.
+    ///
+    /// Called once a transaction is executed, so that each one leaves a
+    /// single snapshot per wallet it changed, and none for wallets it
+    /// restored after a failure.
+    pub fn record_snapshots(&mut self) {
+        let height = blockchain::Schema::new(&*self.0)
+            .block_hashes_by_height()
+            .len();
+        let pending: Vec<(PublicKey, Wallet)> = self.pending_mut().iter().collect();
+
+        for (pub_key, old) in pending {
+            let wallet = Schema(&*self.0).fetch(&pub_key);
+            if old != wallet {
+                let snapshot = BalanceSnapshot::from_change(height, &old, &wallet);
+                self.snapshots_mut(&pub_key).push(snapshot);
+            }
+        }
+        self.pending_mut().clear();
+    }
+
+    /// Keys of the wallets changed in the fork so far, stored or removed.
+    pub fn changed(&self) -> Vec<PublicKey> {
+        let key = SERVICE_NAME.to_string() + ".wallets";
+        self.0
+            .patch()
+            .iter()
+            .filter(|&(name, _)| *name == key)
+            .flat_map(|(_, changes)| changes.iter())
+            .filter_map(|(key, _)| PublicKey::from_slice(key))
+            .collect()
+    }
+
     /// Remove wallet state from the database.
     pub fn remove(&mut self, pub_key: &PublicKey) {
         self.index_mut().remove(pub_key);
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use currency::assets::{AssetBundle, AssetId};
+use currency::wallet::Wallet;
+
+encoding_struct! {
+    /// Wallet state after a change, recorded for audit.
+    ///
+    /// `height` is the height of the block the change was made in. The
+    /// assets moved in or out by the change are listed in `assets_added`
+    /// and `assets_removed`.
+    struct BalanceSnapshot {
+        height:         u64,
+        balance:        u64,
+        assets_added:   Vec<AssetBundle>,
+        assets_removed: Vec<AssetBundle>,
+    }
+}
+
+impl BalanceSnapshot {
+    /// Snapshot of the change from `old` to `new` at `height`.
+    pub fn from_change(height: u64, old: &Wallet, new: &Wallet) -> Self {
+        let old_amounts: HashMap<AssetId, u64> =
+            old.assets().iter().map(|a| (a.id(), a.amount())).collect();
+        let mut new_amounts: HashMap<AssetId, u64> =
+            new.assets().iter().map(|a| (a.id(), a.amount())).collect();
+
+        let mut assets_added = Vec::new();
+        let mut assets_removed = Vec::new();
+        for asset in old.assets() {
+            let new_amount = new_amounts.remove(&asset.id()).unwrap_or(0);
+            if new_amount > asset.amount() {
+                assets_added.push(AssetBundle::new(asset.id(), new_amount - asset.amount()));
+            } else if new_amount < asset.amount() {
+                assets_removed.push(AssetBundle::new(asset.id(), asset.amount() - new_amount));
+            }
+        }
+        for asset in new.assets() {
+            if !old_amounts.contains_key(&asset.id()) && asset.amount() > 0 {
+                assets_added.push(asset);
+            }
+        }
+
+        BalanceSnapshot::new(height, new.balance(), assets_added, assets_removed)
+    }
+}
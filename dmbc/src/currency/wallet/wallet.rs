@@ -1,6 +1,7 @@
 use exonum::encoding::Field;
 
 use currency::assets::AssetBundle;
+use currency::configuration::FeeCurrency;
 use currency::error::Error;
 
 encoding_struct! {
@@ -18,6 +19,14 @@ impl Wallet {
         Wallet::new(0, Vec::new())
     }
 
+    /// Coins that can be spent, the balance less what is locked in it.
+    ///
+    /// Escrowed funds leave the wallet, so the only locked coins are the
+    /// `reserve` it has to keep. The balance itself still counts them.
+    pub fn spendable_balance(&self, reserve: u64) -> u64 {
+        self.balance().saturating_sub(reserve)
+    }
+
     /// Push assets into the wallet.
     pub fn add_assets<I>(&mut self, new_assets: I)
     where
@@ -66,18 +75,45 @@ impl Wallet {
     }
 }
 
-/// Move funds between wallets.
+/// Check that `amount` can be spent from the wallet without touching the
+/// `reserve` it has to keep. Spending nothing is always allowed.
+pub fn can_spend(wallet: &Wallet, amount: u64, reserve: u64) -> bool {
+    amount == 0 || amount <= wallet.spendable_balance(reserve)
+}
+
+/// Move coins the `from` wallet pays of its own accord, transfers and
+/// payments, between wallets. These can't touch the `reserve`, fees and
+/// other moves go through `move_coins` and can.
 ///
 /// # Errors
 ///
-/// Returns `InsufficientFunds` if the `from` wallet balance is less than `amount`.
-pub fn move_coins(from: &mut Wallet, to: &mut Wallet, amount: u64) -> Result<(), Error> {
-    if from.balance() < amount {
+/// Returns `InsufficientFunds` if the `from` wallet can't spend `amount`
+/// without going below `reserve`, or the errors of `move_coins`.
+pub fn spend_coins(
+    from: &mut Wallet,
+    to: &mut Wallet,
+    amount: u64,
+    reserve: u64,
+) -> Result<(), Error> {
+    if !can_spend(from, amount, reserve) {
         return Err(Error::InsufficientFunds);
     }
+    move_coins(from, to, amount)
+}
 
-    let from_balance = from.balance() - amount;
-    let to_balance = to.balance() + amount;
+/// Move funds between wallets.
+///
+/// # Errors
+///
+/// Returns `InsufficientFunds` if the `from` wallet balance is less than
+/// `amount`, or `Overflow` if the `to` wallet balance would exceed
+/// `u64::MAX`. Wallets are unchanged on error.
+pub fn move_coins(from: &mut Wallet, to: &mut Wallet, amount: u64) -> Result<(), Error> {
+    let from_balance = from
+        .balance()
+        .checked_sub(amount)
+        .ok_or(Error::InsufficientFunds)?;
+    let to_balance = to.balance().checked_add(amount).ok_or(Error::Overflow)?;
 
     Field::write(&from_balance, &mut from.raw, 0, 8);
     Field::write(&to_balance, &mut to.raw, 0, 8);
@@ -85,6 +121,25 @@ pub fn move_coins(from: &mut Wallet, to: &mut Wallet, amount: u64) -> Result<(),
     Ok(())
 }
 
+/// Move a fee of `amount` between wallets in `currency`, coins with
+/// `move_coins` or asset units with `move_assets`.
+///
+/// # Errors
+///
+/// Returns the error of the underlying move. Wallets are unchanged on error.
+pub fn move_fee(
+    from: &mut Wallet,
+    to: &mut Wallet,
+    amount: u64,
+    currency: FeeCurrency,
+) -> Result<(), Error> {
+    match currency {
+        FeeCurrency::Coin => move_coins(from, to, amount),
+        FeeCurrency::Asset(_) if amount == 0 => Ok(()),
+        FeeCurrency::Asset(id) => move_assets(from, to, &[AssetBundle::new(id, amount)]),
+    }
+}
+
 /// Move assets between wallets.
 ///
 /// # Errors
@@ -0,0 +1,59 @@
+//! Canonical rendering of public keys in logs and responses.
+//!
+//! Keys are written as lowercase hex, the form the API accepts in paths and
+//! bodies. Logs may shorten them to a fingerprint, the leading
+//! `FINGERPRINT_BYTES` of the key, see `config::Log::key_fingerprint`.
+
+use std::fmt;
+
+use exonum::crypto::PublicKey;
+
+use config;
+
+/// Bytes of the key shown in a fingerprint.
+pub const FINGERPRINT_BYTES: usize = 8;
+
+/// `Display` adapter for a public key.
+pub struct KeyDisplay<'a> {
+    key: &'a PublicKey,
+    fingerprint: bool,
+}
+
+impl<'a> fmt::Display for KeyDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.key.as_ref();
+        let bytes = if self.fingerprint {
+            &bytes[..FINGERPRINT_BYTES]
+        } else {
+            bytes
+        };
+        for byte in bytes {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Full hex of `key`. Use it for anything clients parse.
+pub fn hex(key: &PublicKey) -> KeyDisplay {
+    KeyDisplay {
+        key,
+        fingerprint: false,
+    }
+}
+
+/// Fingerprint of `key`, a prefix of its full hex.
+pub fn fingerprint(key: &PublicKey) -> KeyDisplay {
+    KeyDisplay {
+        key,
+        fingerprint: true,
+    }
+}
+
+/// `key` rendered for logs, full or fingerprinted as configured.
+pub fn log(key: &PublicKey) -> KeyDisplay {
+    KeyDisplay {
+        key,
+        fingerprint: config::config().log().key_fingerprint(),
+    }
+}
@@ -33,3 +33,5 @@ extern crate extprim;
 pub mod config;
 pub mod currency;
 pub mod decimal;
+pub mod keys;
+pub mod metrics;
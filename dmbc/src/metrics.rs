@@ -0,0 +1,148 @@
+//! Periodic dumps of the collected metrics for offline analysis.
+//!
+//! Every dump is a single JSON line with the unix timestamp and the current
+//! value of every registered metric. The dumps are written from a separate
+//! thread which only reads the metrics registry, so consensus is never
+//! blocked by slow disks.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+use prometheus;
+use prometheus::proto::{Metric, MetricType};
+
+use config;
+
+/// Spawn the dump thread if a dump interval is configured.
+pub fn start() -> Option<thread::JoinHandle<()>> {
+    let metrics = config::config().metrics();
+    let interval = metrics.clone().dump_interval()?;
+    let path = metrics.clone().dump_path().map(PathBuf::from);
+    let max_size = metrics.dump_max_size();
+
+    Some(spawn(Duration::from_secs(interval), path, max_size))
+}
+
+/// Dump the metrics every `interval` to the file at `path`, or to the log
+/// if no path is given. The file is rotated to `<path>.1` once it grows
+/// beyond `max_size` bytes.
+pub fn spawn(interval: Duration, path: Option<PathBuf>, max_size: u64) -> thread::JoinHandle<()> {
+    thread::Builder::new()
+        .name("Metrics dump".to_string())
+        .spawn(move || loop {
+            thread::sleep(interval);
+            let line = dump_line();
+            match path {
+                Some(ref path) => {
+                    if let Err(e) = write_line(path, &line, max_size) {
+                        warn!("Unable to dump metrics to {:?}: {}", path, e);
+                    }
+                }
+                None => info!("Metrics: {}", line),
+            }
+        })
+        .unwrap()
+}
+
+/// Current metrics as a single JSON line.
+pub fn dump_line() -> String {
+    let mut values = BTreeMap::new();
+    for family in prometheus::gather() {
+        for metric in family.get_metric() {
+            let name = metric_name(family.get_name(), metric);
+            match family.get_field_type() {
+                MetricType::COUNTER => {
+                    values.insert(name, metric.get_counter().get_value());
+                }
+                MetricType::GAUGE => {
+                    values.insert(name, metric.get_gauge().get_value());
+                }
+                MetricType::HISTOGRAM => {
+                    let histogram = metric.get_histogram();
+                    values.insert(name.clone() + "_count", histogram.get_sample_count() as f64);
+                    values.insert(name + "_sum", histogram.get_sample_sum());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    json!({ "timestamp": Utc::now().timestamp(), "metrics": values }).to_string()
+}
+
+fn metric_name(family: &str, metric: &Metric) -> String {
+    let labels = metric
+        .get_label()
+        .iter()
+        .map(|l| format!("{}=\"{}\"", l.get_name(), l.get_value()))
+        .collect::<Vec<_>>();
+    if labels.is_empty() {
+        family.to_string()
+    } else {
+        format!("{}{{{}}}", family, labels.join(","))
+    }
+}
+
+fn write_line(path: &Path, line: &str, max_size: u64) -> io::Result<()> {
+    if let Ok(meta) = fs::metadata(path) {
+        if meta.len() >= max_size {
+            let mut rotated = path.as_os_str().to_owned();
+            rotated.push(".1");
+            fs::rename(path, rotated)?;
+        }
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+#[test]
+fn dump_line_is_json() {
+    let line = dump_line();
+    assert!(!line.contains('\n'));
+
+    let value: ::serde_json::Value = ::serde_json::from_str(&line).unwrap();
+    assert!(value["timestamp"].is_i64());
+    assert!(value["metrics"].is_object());
+}
+
+#[test]
+fn dump_writes_at_interval() {
+    let path = ::std::env::temp_dir().join(format!("dmbc-metrics-{}.log", ::std::process::id()));
+
+    let _ = spawn(Duration::from_millis(50), Some(path.clone()), 10 * 1024 * 1024);
+    thread::sleep(Duration::from_millis(300));
+
+    let content = fs::read_to_string(&path).unwrap();
+    let lines = content.lines().collect::<Vec<_>>();
+    assert!(lines.len() >= 2);
+    for line in lines {
+        let value: ::serde_json::Value = ::serde_json::from_str(line).unwrap();
+        assert!(value["metrics"].is_object());
+    }
+
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn dump_rotates_file() {
+    let path = ::std::env::temp_dir().join(format!("dmbc-metrics-rotate-{}.log", ::std::process::id()));
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+
+    write_line(&path, "first", 1).unwrap();
+    write_line(&path, "second", 1).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+    assert_eq!(fs::read_to_string(&rotated).unwrap(), "first\n");
+
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(&rotated);
+}
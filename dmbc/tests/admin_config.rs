@@ -0,0 +1,72 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::config;
+use dmbc::currency::api::admin::{AdminConfigResponse, REDACTED};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+
+/// Length of the longest run of hex digits in `s`.
+fn longest_hex_run(s: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in s.chars() {
+        if c.is_digit(16) {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+#[test]
+fn admin_config_effective_values() {
+    let fees = TransactionFees::with_default_key(10, 20, 30, 40, 50, 60);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(fees.clone()))
+        .create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, AdminConfigResponse) =
+        api.get_private_with_status("/v1/admin/config");
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap();
+    assert_eq!(body.service.fees(), fees);
+
+    let node_api = &body.node["api"];
+    assert_eq!(node_api["address"], config::config().api().address());
+    assert_eq!(node_api["private_address"], config::config().api().private_address());
+    assert_eq!(node_api["peer_address"], config::config().api().peer_address());
+    assert_eq!(body.node["db"]["path"], config::config().db().path());
+}
+
+#[test]
+fn admin_config_redacts_secrets() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, serde_json::Value) =
+        api.get_private_with_status("/v1/admin/config");
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response["Ok"]["node"]["api"]["keys_path"], REDACTED);
+
+    let body = response.to_string();
+    assert!(!body.contains(&config::config().api().keys_path()));
+    // Secret keys are 64 bytes, public keys and hashes only 32.
+    assert!(longest_hex_run(&body) < 128);
+}
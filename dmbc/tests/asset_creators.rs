@@ -0,0 +1,160 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum_testkit::TestKitApi;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::asset::{CreatedAsset, CreatedAssetsBody, CreatedAssetsResponse};
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::assets;
+use dmbc::currency::assets::{AssetId, MetaAsset};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+use dmbc::currency::Service;
+
+fn created_assets(api: &TestKitApi, pub_key: &str) -> (StatusCode, CreatedAssetsResponse) {
+    api.get_with_status(&format!("/v1/creators/{}/assets", pub_key))
+}
+
+#[test]
+fn created_assets_outstanding() {
+    let balance = 100_000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (creator_key, creator_secret) = crypto::gen_keypair();
+    let (other_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&creator_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let (status, response) = created_assets(&api, &creator_key.to_hex());
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.unwrap().assets, vec![]);
+
+    let fees = || dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let tx_add_assets = transaction::Builder::new()
+        .keypair(creator_key, creator_secret.clone())
+        .tx_add_assets()
+        .add_asset_value(MetaAsset::new(&creator_key, "first", 10, fees()))
+        .add_asset_value(MetaAsset::new(&creator_key, "second", 20, fees()))
+        .seed(1)
+        .build();
+    api.post_tx(&tx_add_assets);
+    testkit.create_block();
+    assert_eq!(api.get_tx_status(&tx_add_assets).1, Ok(Ok(())));
+
+    let tx_del_assets = transaction::Builder::new()
+        .keypair(creator_key, creator_secret)
+        .tx_del_assets()
+        .add_asset("second", 5)
+        .seed(2)
+        .build();
+    api.post_tx(&tx_del_assets);
+    testkit.create_block();
+    assert_eq!(api.get_tx_status(&tx_del_assets).1, Ok(Ok(())));
+
+    let mut expected = vec![
+        CreatedAsset {
+            id: AssetId::from_data("first", &creator_key),
+            amount: 10,
+        },
+        CreatedAsset {
+            id: AssetId::from_data("second", &creator_key),
+            amount: 15,
+        },
+    ];
+    expected.sort_by_key(|asset| asset.id.to_hex());
+
+    let (status, response) = created_assets(&api, &creator_key.to_hex());
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(CreatedAssetsBody {
+            total: 2,
+            count: 2,
+            assets: expected.clone(),
+        })
+    );
+
+    let (_, response) = created_assets(&api, &format!("{}?offset=1&limit=1", creator_key.to_hex()));
+    assert_eq!(
+        response,
+        Ok(CreatedAssetsBody {
+            total: 2,
+            count: 1,
+            assets: expected[1..].to_vec(),
+        })
+    );
+
+    let (_, response) = created_assets(&api, &other_key.to_hex());
+    assert_eq!(response.unwrap().total, 0);
+}
+
+#[test]
+fn created_assets_listed_for_existing_chain() {
+    let (creator_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        10,
+        dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    // An asset stored before the assets by creator index existed.
+    {
+        let blockchain = testkit.blockchain_mut();
+        let mut fork = blockchain.fork();
+        assets::Schema(&mut fork).index_mut().put(&asset.id(), info);
+        assets::Schema(&mut fork).creators_indexed_mut().remove();
+        assert!(blockchain.merge(fork.into_patch()).is_ok());
+    }
+
+    let (_, response) = created_assets(&api, &creator_key.to_hex());
+    assert_eq!(response.unwrap().total, 0);
+
+    // What the node does at startup.
+    {
+        let blockchain = testkit.blockchain_mut();
+        let mut fork = blockchain.fork();
+        Service::migrate(&mut fork);
+        assert!(blockchain.merge(fork.into_patch()).is_ok());
+    }
+
+    let (status, response) = created_assets(&api, &creator_key.to_hex());
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response.unwrap().assets,
+        vec![CreatedAsset {
+            id: asset.id(),
+            amount: 10,
+        }]
+    );
+}
+
+#[test]
+fn created_assets_invalid_key() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response) = created_assets(&api, "invalid");
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::WalletHexInvalid));
+}
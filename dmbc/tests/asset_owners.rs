@@ -0,0 +1,129 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestKitApi, DmbcTestApiBuilder};
+
+use dmbc::currency::api::asset::{AssetHolder, AssetOwnersResponse};
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::assets::{AssetBundle, AssetId, MetaAsset};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn asset_owners_creator_is_minter() {
+    let meta_data = "asset";
+    let balance = 100_000;
+
+    let (creator_public_key, creator_secret_key) = crypto::gen_keypair();
+    let (receiver_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&creator_public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let fees = dmbc_testkit::asset_fees(10, "0.0".parse().unwrap());
+    let tx_add_assets = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key)
+        .tx_add_assets()
+        .add_asset_value(MetaAsset::new(&receiver_key, meta_data, 3, fees))
+        .seed(85)
+        .build();
+
+    api.post_tx(&tx_add_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_add_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let id = AssetId::from_data(meta_data, &creator_public_key);
+    let (status, response): (StatusCode, AssetOwnersResponse) =
+        api.get_with_status(&format!("/v1/assets/{}/owners", id.to_string()));
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap();
+    assert_eq!(body.creator, creator_public_key);
+    assert_eq!(body.holders, None);
+}
+
+#[test]
+fn asset_owners_lists_holders() {
+    let units = 5;
+    let (creator_key, _) = crypto::gen_keypair();
+    let (first_key, _) = crypto::gen_keypair();
+    let (second_key, _) = crypto::gen_keypair();
+    let (other_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(10, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees.clone(), &creator_key);
+    let (other, other_info) = dmbc_testkit::create_asset("other", units, fees, &creator_key);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .add_asset_info(&asset.id(), info)
+        .add_asset_info(&other.id(), other_info)
+        .add_wallet_value(&first_key, Wallet::new(0, vec![AssetBundle::new(asset.id(), 2)]))
+        .add_wallet_value(&second_key, Wallet::new(0, vec![AssetBundle::new(asset.id(), 3)]))
+        .add_wallet_value(&other_key, Wallet::new(0, vec![other.clone()]))
+        .create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, AssetOwnersResponse) = api.get_with_status(
+        &format!("/v1/assets/{}/owners?holders=true", asset.id().to_string())
+    );
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap();
+    assert_eq!(body.creator, creator_key);
+
+    let holders = body.holders.unwrap();
+    assert_eq!(holders.total, 2);
+    assert_eq!(holders.count, 2);
+    assert!(holders.holders.contains(&AssetHolder { pub_key: first_key, amount: 2 }));
+    assert!(holders.holders.contains(&AssetHolder { pub_key: second_key, amount: 3 }));
+
+    let (status, response): (StatusCode, AssetOwnersResponse) = api.get_with_status(
+        &format!("/v1/assets/{}/owners?holders=true&offset=1&limit=5", asset.id().to_string())
+    );
+
+    assert_eq!(status, StatusCode::Ok);
+    let holders = response.unwrap().holders.unwrap();
+    assert_eq!(holders.total, 2);
+    assert_eq!(holders.count, 1);
+}
+
+#[test]
+fn asset_owners_unknown_asset() {
+    let (public_key, _) = crypto::gen_keypair();
+    let id = AssetId::from_data("asset", &public_key);
+
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, AssetOwnersResponse) =
+        api.get_with_status(&format!("/v1/assets/{}/owners", id.to_string()));
+
+    assert_eq!(status, StatusCode::NotFound);
+    assert_eq!(response, Err(ApiError::AssetIdNotFound));
+}
+
+#[test]
+fn asset_owners_invalid_id() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, AssetOwnersResponse) =
+        api.get_with_status("/v1/assets/badassetid/owners");
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::AssetIdInvalid));
+}
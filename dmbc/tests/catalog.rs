@@ -0,0 +1,38 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum_testkit::TestKit;
+use dmbc_testkit::DmbcTestKitApi;
+
+use dmbc::currency::api::catalog::CatalogResponse;
+
+#[test]
+fn catalog_lists_wallet_and_transaction_endpoints() {
+    let api = TestKit::default().api();
+
+    let (status, response): (StatusCode, CatalogResponse) = api.get_with_status("/v1");
+
+    assert_eq!(status, StatusCode::Ok);
+    let routes = response.unwrap().routes;
+    let has_route = |method: &str, path: &str| {
+        routes.iter().any(|r| r.method == method && r.path == path)
+    };
+
+    assert!(has_route("POST", "/v1/transactions"));
+    assert!(has_route("GET", "/v1/transactions/:hash/status"));
+    assert!(has_route("GET", "/v1/wallets"));
+    assert!(has_route("GET", "/v1/wallets/:pub_key"));
+    assert!(has_route("POST", "/v1/wallets/balances"));
+    assert!(has_route("GET", "/v1/wallets/:pub_key/assets"));
+    assert!(has_route("POST", "/v1/fees/offers"));
+    assert!(has_route("GET", "/v1/fees/revenue"));
+}
@@ -19,6 +19,7 @@ use dmbc::currency::assets::{self, AssetBundle, AssetInfo, Fees, MetaAsset, Asse
 use dmbc::currency::transactions::builders::fee;
 use dmbc::currency::api::transaction::{TxPostResponse, StatusResponse};
 use dmbc::currency::api::fees::FeesResponse;
+use dmbc::currency::api::ApiLimits;
 use dmbc::currency::api::wallet as wallet_api;
 use dmbc::currency::api::wallet::{WalletResponse, WalletAssetsResponse,
                                     WalletInfo, ExtendedAsset};
@@ -27,6 +28,8 @@ use dmbc::currency::configuration::GENESIS_WALLET_PUB_KEY;
 pub trait DmbcTestKit {
     fn default() -> Self;
 
+    fn with_service(service: Service) -> Self;
+
     fn add_assets(&mut self, pub_key: &PublicKey, assets: Vec<(AssetBundle, AssetInfo)>);
 
     fn set_configuration(&mut self, configuration: Configuration);
@@ -42,9 +45,13 @@ pub trait DmbcTestKit {
 
 impl DmbcTestKit for ExonumTestKit {
     fn default() -> Self {
+        Self::with_service(Service::new())
+    }
+
+    fn with_service(service: Service) -> Self {
         TestKitBuilder::validator()
             .with_validators(4)
-            .with_service(Service::new())
+            .with_service(service)
             .create()
     }
 
@@ -58,6 +65,7 @@ impl DmbcTestKit for ExonumTestKit {
             assets::Schema(&mut fork).store(&asset.id(), info);
         }
         wallet::Schema(&mut fork).store(&pub_key, Wallet::new(wallet.balance(), wallet.assets()));
+        wallet::Schema(&mut fork).record_snapshots();
 
         assert!(blockchain.merge(fork.into_patch()).is_ok());
     }
@@ -88,6 +96,7 @@ impl DmbcTestKit for ExonumTestKit {
         existing_wallet.add_assets(wallet.assets());
         let updated_balance = existing_wallet.balance() + wallet.balance();
         wallet::Schema(&mut fork).store(&pub_key, Wallet::new(updated_balance, existing_wallet.assets()));
+        wallet::Schema(&mut fork).record_snapshots();
 
         assert!(blockchain.merge(fork.into_patch()).is_ok());
     }
@@ -116,6 +125,9 @@ pub trait DmbcTestKitApi {
     fn get_with_status<D>(&self, endpoint: &str) -> (StatusCode, D)
     where for<'de> D: Deserialize<'de>;
 
+    fn get_private_with_status<D>(&self, endpoint: &str) -> (StatusCode, D)
+    where for<'de> D: Deserialize<'de>;
+
     fn post_with_status<T, D>(&self, endpoint: &str, transaction: &T) -> (StatusCode, D)
     where T: Serialize, for<'de> D: Deserialize<'de>;
 
@@ -128,6 +140,9 @@ pub trait DmbcTestKitApi {
     fn post_tx<T>(&self, tx: &T) -> (StatusCode, TxPostResponse)
     where T: Message + Serialize; 
 
+    fn post_tx_with_headers<T>(&self, tx: &T) -> (StatusCode, Headers, TxPostResponse)
+    where T: Message + Serialize;
+
     fn get_tx_status<T>(&self, transaction: &T) -> (StatusCode, StatusResponse)
     where T: Message + Serialize;
 
@@ -160,6 +175,15 @@ impl DmbcTestKitApi for ExonumTestKitApi {
         )
     }
 
+    fn get_private_with_status<D>(&self, endpoint: &str) -> (StatusCode, D)
+    where for <'de> D: Deserialize<'de>
+    {
+        ExonumTestKitApi::get_internal_with_status(
+            self.private_handler(),
+            endpoint,
+        )
+    }
+
     fn post_raw_with_status<D>(&self, endpoint: &str, body: &str) -> (StatusCode, D)
     where for <'de> D: Deserialize<'de> 
     {
@@ -211,6 +235,26 @@ impl DmbcTestKitApi for ExonumTestKitApi {
         self.post_with_status("v1/transactions", &tx)
     }
 
+    fn post_tx_with_headers<T>(&self, tx: &T) -> (StatusCode, Headers, TxPostResponse)
+    where T: Message + Serialize
+    {
+        let url = format!("http://localhost:3000/api/services/{}/v1/transactions", SERVICE_NAME);
+        let response = request::post(
+            &url,
+            {
+                let mut headers = Headers::new();
+                headers.set(ContentType::json());
+                headers
+            },
+            &serde_json::to_string(&tx).expect("Cannot serialize data to JSON"),
+            self.public_handler(),
+        ).expect("Cannot send data");
+        let status = response.status.unwrap();
+        let headers = response.headers.clone();
+        let body = response::extract_body_to_string(response);
+        (status, headers, serde_json::from_str(&body).unwrap())
+    }
+
     fn get_tx_status<T>(&self, transaction: &T) -> (StatusCode, StatusResponse)
     where T: Message + Serialize 
     {   
@@ -297,6 +341,7 @@ pub fn default_genesis_key() -> PublicKey {
 
 pub struct DmbcTestApiBuilder {
     configuration: Option<Configuration>,
+    api_limits: Option<ApiLimits>,
     wallets: Vec<(PublicKey, Wallet)>,
     assets: Vec<(PublicKey, (AssetBundle, AssetInfo))>,
     infos: Vec<(AssetId, AssetInfo)>,
@@ -306,6 +351,7 @@ impl DmbcTestApiBuilder {
     pub fn new() -> Self {
         DmbcTestApiBuilder {
             configuration: None,
+            api_limits: None,
             wallets: Vec::new(),
             assets: Vec::new(),
             infos: Vec::new(),
@@ -319,6 +365,13 @@ impl DmbcTestApiBuilder {
         }
     }
 
+    pub fn with_api_limits(self, api_limits: ApiLimits) -> Self {
+        DmbcTestApiBuilder {
+            api_limits: Some(api_limits),
+            ..self
+        }
+    }
+
     pub fn add_asset_to_wallet(mut self, public_key: &PublicKey, asset: (AssetBundle, AssetInfo)) -> Self {
         self.assets.push((*public_key, asset));
         self
@@ -335,7 +388,12 @@ impl DmbcTestApiBuilder {
     }
 
     pub fn create(self) -> ExonumTestKit {
-        let mut testkit = ExonumTestKit::default();
+        let mut testkit = match self.api_limits {
+            Some(api_limits) => {
+                ExonumTestKit::with_service(Service::new().with_api_limits(api_limits))
+            }
+            None => ExonumTestKit::default(),
+        };
         if let Some(configuration) = self.configuration {
             testkit.set_configuration(configuration);
         }
@@ -0,0 +1,128 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+// `dmbc_testkit` boots the service on an in-memory exonum testkit node.
+// Transactions posted through its API go through the same dispatch,
+// verification, execution and status storage as on a real node.
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::messages::Message;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::blocks::BlockResponse;
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::{StatusResponse, TransactionResponse};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn transfer_submission_inclusion_and_status() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 2500;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+    let tx_hash = tx_transfer.hash();
+
+    // Submission: accepted into the pool, not yet executed.
+    let (status, response) = api.post_tx(&tx_transfer);
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    let (status, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(status, StatusCode::NotFound);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+
+    // Inclusion: the next block carries the transaction.
+    testkit.create_block();
+    let height = testkit.height().0;
+
+    let (status, response): (StatusCode, BlockResponse) =
+        api.get_with_status(&format!("/v1/blocks/{}", height));
+    assert_eq!(status, StatusCode::Ok);
+    let info = response.unwrap().unwrap();
+    assert_eq!(info.txs, vec![tx_hash]);
+
+    // Status and resulting state.
+    let (status, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    assert_eq!(api.get_wallet(&public_key).balance, balance - amount - transaction_fee);
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+}
+
+#[test]
+fn transfer_status_polled_by_hash() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(100)
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+    let endpoint = format!("/v1/transactions/{}/status", tx_transfer.hash().to_string());
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    assert_eq!(status, StatusCode::Accepted);
+
+    // Pending transactions are reported as not found until committed.
+    let mut polls = 0;
+    let tx_status = loop {
+        let (status, tx_status): (StatusCode, StatusResponse) = api.get_with_status(&endpoint);
+        if status != StatusCode::NotFound {
+            assert_eq!(status, StatusCode::Ok);
+            break tx_status;
+        }
+        assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+        assert!(polls < 3, "transaction was never committed");
+        polls += 1;
+        testkit.create_block();
+    };
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let (status, tx_status): (StatusCode, StatusResponse) =
+        api.get_with_status("/v1/transactions/not-a-hash/status");
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(tx_status, Err(ApiError::TransactionHashInvalid));
+}
@@ -0,0 +1,138 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::messages::Message;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::events::EventsResponse;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::events::MAX_BLOCKS;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::TRANSFER_ID;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn events_transfer_applied() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (bystander_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    // Subscribe: nothing has been applied for the wallet yet.
+    let (status, response): (StatusCode, EventsResponse) =
+        api.get_with_status(&format!("/v1/events?key={}", public_key.to_hex()));
+    assert_eq!(status, StatusCode::Ok);
+    let subscribed = response.unwrap();
+    assert_eq!(subscribed.events, vec![]);
+
+    let tx_ok = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+    let tx_failed = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(balance)
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_ok);
+    api.post_tx(&tx_failed);
+    testkit.create_block();
+
+    let (status, response): (StatusCode, EventsResponse) = api.get_with_status(&format!(
+        "/v1/events?after={}&key={}&timeout=1000",
+        subscribed.height,
+        public_key.to_hex()
+    ));
+    assert_eq!(status, StatusCode::Ok);
+    let received = response.unwrap();
+    assert!(received.height > subscribed.height);
+    assert!(!received.gap);
+    assert_eq!(received.events.len(), 2);
+
+    let event_ok = received.events.iter().find(|e| e.tx_hash == tx_ok.hash()).unwrap();
+    assert_eq!(event_ok.tx_type, TRANSFER_ID);
+    assert_eq!(event_ok.height, received.height);
+    assert_eq!(event_ok.status, Ok(()));
+
+    let event_failed = received.events.iter().find(|e| e.tx_hash == tx_failed.hash()).unwrap();
+    assert_eq!(event_failed.status, Err(Error::InsufficientFunds));
+
+    // The recipient is involved too, an unrelated wallet is not.
+    let (_, response): (StatusCode, EventsResponse) = api.get_with_status(&format!(
+        "/v1/events?after={}&key={}",
+        subscribed.height,
+        recipient_key.to_hex()
+    ));
+    assert_eq!(response.unwrap().events.len(), 2);
+
+    let (_, response): (StatusCode, EventsResponse) = api.get_with_status(&format!(
+        "/v1/events?after={}&key={}",
+        subscribed.height,
+        bystander_key.to_hex()
+    ));
+    assert_eq!(response.unwrap().events, vec![]);
+
+    // Continuing from the returned height yields no repeats.
+    let (_, response): (StatusCode, EventsResponse) = api.get_with_status(&format!(
+        "/v1/events?after={}&key={}",
+        received.height,
+        public_key.to_hex()
+    ));
+    assert_eq!(response.unwrap().events, vec![]);
+}
+
+#[test]
+fn events_invalid_key() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, _): (StatusCode, EventsResponse) =
+        api.get_with_status("/v1/events?key=invalid");
+    assert_eq!(status, StatusCode::BadRequest);
+}
+
+#[test]
+fn events_gap_reported() {
+    let mut testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    for _ in 0..MAX_BLOCKS + 2 {
+        testkit.create_block();
+    }
+
+    // The oldest blocks are dropped, a subscriber that far behind is told.
+    let (status, response): (StatusCode, EventsResponse) = api.get_with_status("/v1/events?after=0");
+    assert_eq!(status, StatusCode::Ok);
+    let received = response.unwrap();
+    assert!(received.gap);
+
+    let (_, response): (StatusCode, EventsResponse) =
+        api.get_with_status(&format!("/v1/events?after={}", received.height - 1));
+    assert!(!response.unwrap().gap);
+}
@@ -0,0 +1,67 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::FeesResponseBody;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn fees_follow_configuration_change() {
+    let old_fee = 1000;
+    let new_fee = 2500;
+    let balance = 100_000;
+    let amount = 10;
+
+    let (sender_pub_key, sender_sec_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(TransactionFees::with_default_key(0, 0, 0, 0, 0, old_fee)))
+        .add_wallet_value(&sender_pub_key, Wallet::new(balance, vec![]))
+        .create();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_pub_key, sender_sec_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let (status, response) = testkit.api().post_fee(&tx_transfer);
+    let mut expected = HashMap::new();
+    expected.insert(sender_pub_key, old_fee);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+
+    testkit.set_configuration(Configuration::new(TransactionFees::with_default_key(0, 0, 0, 0, 0, new_fee)));
+
+    let (status, response) = testkit.api().post_fee(&tx_transfer);
+    let mut expected = HashMap::new();
+    expected.insert(sender_pub_key, new_fee);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+
+    let (status, _) = testkit.api().post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = testkit.api().get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(testkit.api().get_wallet(&sender_pub_key).balance, balance - new_fee - amount);
+}
@@ -0,0 +1,172 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::assets::{AssetBundle, AssetId};
+use dmbc::currency::configuration::{Configuration, FeeCurrency, TransactionFees,
+                                    TransferFeePolicy};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::TRANSFER_ID;
+use dmbc::currency::wallet::Wallet;
+
+const TRANSACTION_FEE: u64 = 3;
+const BALANCE: u64 = 100_000;
+const AMOUNT: u64 = 500;
+
+fn asset_amount<A: DmbcTestKitApi>(api: &A, key: &PublicKey, id: AssetId) -> u64 {
+    let assets = api.get_wallet_assets(key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assets.iter().find(|a| a.id() == id).map_or(0, |a| a.amount())
+}
+
+#[test]
+fn transfer_fee_paid_in_asset() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (creator_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (fee_asset, info) = dmbc_testkit::create_asset("fee_asset", 10, fees, &creator_key);
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+    let configuration = Configuration::new(config_fees)
+        .with_fee_currency(TRANSFER_ID, FeeCurrency::Asset(fee_asset.id()));
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&sender_key, Wallet::new(BALANCE, vec![]))
+        .add_asset_to_wallet(&sender_key, (fee_asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let genesis_key = dmbc_testkit::default_genesis_key();
+    let genesis_balance = api.get_wallet(&genesis_key).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_key, sender_secret)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .amount(AMOUNT)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // Coins only move by the transferred amount, the fee is paid in units.
+    assert_eq!(api.get_wallet(&sender_key).balance, BALANCE - AMOUNT);
+    assert_eq!(api.get_wallet(&recipient_key).balance, AMOUNT);
+    assert_eq!(api.get_wallet(&genesis_key).balance, genesis_balance);
+
+    assert_eq!(
+        asset_amount(&api, &sender_key, fee_asset.id()),
+        fee_asset.amount() - TRANSACTION_FEE
+    );
+    assert_eq!(asset_amount(&api, &genesis_key, fee_asset.id()), TRANSACTION_FEE);
+}
+
+#[test]
+fn transfer_fee_from_amount_paid_in_asset() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (creator_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (fee_asset, info) = dmbc_testkit::create_asset("fee_asset", 10, fees, &creator_key);
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+    let configuration = Configuration::new(config_fees)
+        .with_fee_currency(TRANSFER_ID, FeeCurrency::Asset(fee_asset.id()))
+        .with_transfer_fee_policy(TransferFeePolicy::FromAmount);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&sender_key, Wallet::new(BALANCE, vec![]))
+        .add_asset_to_wallet(&sender_key, (fee_asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let genesis_key = dmbc_testkit::default_genesis_key();
+    let genesis_balance = api.get_wallet(&genesis_key).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_key, sender_secret)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .amount(AMOUNT)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // The fee in units can't come out of the amount, which arrives whole.
+    assert_eq!(api.get_wallet(&sender_key).balance, BALANCE - AMOUNT);
+    assert_eq!(api.get_wallet(&recipient_key).balance, AMOUNT);
+    assert_eq!(api.get_wallet(&genesis_key).balance, genesis_balance);
+
+    assert_eq!(
+        asset_amount(&api, &sender_key, fee_asset.id()),
+        fee_asset.amount() - TRANSACTION_FEE
+    );
+    assert_eq!(asset_amount(&api, &genesis_key, fee_asset.id()), TRANSACTION_FEE);
+}
+
+#[test]
+fn transfer_fee_asset_missing() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    // The fee asset doesn't have to exist for the payer to lack it.
+    let fee_asset = AssetId::from_data("fee_asset", &recipient_key);
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+    let configuration = Configuration::new(config_fees)
+        .with_fee_currency(TRANSFER_ID, FeeCurrency::Asset(fee_asset));
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&sender_key, Wallet::new(BALANCE, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_key, sender_secret)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .amount(AMOUNT)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
+
+    assert_eq!(api.get_wallet(&sender_key).balance, BALANCE);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+}
+
+#[test]
+fn fee_currency_defaults_to_coin() {
+    let configuration = Configuration::default();
+    assert_eq!(configuration.fee_currency(TRANSFER_ID), FeeCurrency::Coin);
+}
@@ -0,0 +1,194 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::FeesResponseBody;
+use dmbc::currency::assets::{AssetBundle, AssetInfo, TradeAsset};
+use dmbc::currency::configuration::{Configuration, FeeDiscount, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::wallet::Wallet;
+
+const TRANSACTION_FEE: u64 = 1000;
+const MIN_AMOUNT: u64 = 10;
+const DISCOUNT_BPS: u64 = 2500;
+const DISCOUNTED_FEE: u64 = 750;
+
+fn loyalty_asset(creator_key: &PublicKey, amount: u64) -> (AssetBundle, AssetInfo) {
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    dmbc_testkit::create_asset("loyalty", amount, fees, creator_key)
+}
+
+fn configuration(fees: TransactionFees, asset: &AssetBundle) -> Configuration {
+    Configuration::new(fees).with_fee_discount(FeeDiscount {
+        asset_id: asset.id(),
+        min_amount: MIN_AMOUNT,
+        discount_bps: DISCOUNT_BPS,
+    })
+}
+
+/// Balance of the sender after it transfers `amount` with the transfer fee
+/// and the discount configured, holding `held` units of the discount asset.
+fn transfer_with_held(balance: u64, amount: u64, held: u64) -> u64 {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (loyalty, info) = loyalty_asset(&creator_key, held);
+
+    let mut builder = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(config_fees, &loyalty))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]));
+    if held > 0 {
+        builder = builder.add_asset_to_wallet(&public_key, (loyalty, info));
+    }
+    let mut testkit = builder.create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+
+    api.get_wallet(&public_key).balance
+}
+
+#[test]
+fn fee_discount_holder_pays_discounted_fee() {
+    let balance = 100_000;
+    let amount = 500;
+
+    let left = transfer_with_held(balance, amount, MIN_AMOUNT);
+    assert_eq!(left, balance - amount - DISCOUNTED_FEE);
+}
+
+#[test]
+fn fee_discount_non_holder_pays_full_fee() {
+    let balance = 100_000;
+    let amount = 500;
+
+    let left = transfer_with_held(balance, amount, 0);
+    assert_eq!(left, balance - amount - TRANSACTION_FEE);
+}
+
+#[test]
+fn fee_discount_below_min_amount_pays_full_fee() {
+    let balance = 100_000;
+    let amount = 500;
+
+    let left = transfer_with_held(balance, amount, MIN_AMOUNT - 1);
+    assert_eq!(left, balance - amount - TRANSACTION_FEE);
+}
+
+#[test]
+fn fee_discount_estimate() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (holder_key, holder_secret) = crypto::gen_keypair();
+    let (other_key, other_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (loyalty, info) = loyalty_asset(&creator_key, MIN_AMOUNT);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(config_fees, &loyalty))
+        .add_asset_to_wallet(&holder_key, (loyalty, info))
+        .create();
+    let api = testkit.api();
+
+    for &(ref key, ref secret, fee) in &[
+        (holder_key, holder_secret, DISCOUNTED_FEE),
+        (other_key, other_secret, TRANSACTION_FEE),
+    ] {
+        let tx_transfer = transaction::Builder::new()
+            .keypair(*key, secret.clone())
+            .tx_transfer()
+            .amount(1)
+            .recipient(recipient_key)
+            .seed(1)
+            .build();
+
+        let (status, response) = api.post_fee(&tx_transfer);
+
+        let mut expected = HashMap::new();
+        expected.insert(*key, fee);
+
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    }
+}
+
+#[test]
+fn fee_discount_split_between_parties() {
+    let balance = 100_000;
+    let price = 100;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, TRANSACTION_FEE, 0);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (seller_key, seller_secret) = crypto::gen_keypair();
+    let (buyer_key, buyer_secret) = crypto::gen_keypair();
+
+    let (loyalty, loyalty_info) = loyalty_asset(&creator_key, MIN_AMOUNT);
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", 1, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(config_fees, &loyalty))
+        .add_wallet_value(&buyer_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&buyer_key, (loyalty, loyalty_info))
+        .add_asset_to_wallet(&seller_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_key, buyer_secret)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset, price))
+        .seller(seller_key, seller_secret)
+        .fee_strategy(FeeStrategy::RecipientAndSender)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // Only the buyer holds the discount asset, so only its half is discounted.
+    let half = TRANSACTION_FEE / 2;
+    assert_eq!(
+        api.get_wallet(&buyer_key).balance,
+        balance - price - (half - half * DISCOUNT_BPS / 10_000)
+    );
+    assert_eq!(api.get_wallet(&seller_key).balance, balance + price - half);
+}
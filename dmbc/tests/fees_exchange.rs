@@ -271,7 +271,7 @@ let transaction_fee = 1000;
     let (status, response) = api.post_fee(&tx_exchange_assets);
 
     assert_eq!(status, StatusCode::BadRequest);
-    assert_eq!(response, Ok(Err(Error::InvalidTransaction)));
+    assert_eq!(response, Ok(Err(Error::InvalidFeeStrategy)));
 }
 
 #[test]
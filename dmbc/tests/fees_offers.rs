@@ -0,0 +1,151 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::{OfferFeesRequest, OfferFeesResponse, OfferFeesResponseBody};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::assets::{AssetBundle, TradeAsset};
+
+#[test]
+fn fees_for_trade_offer() {
+    let transaction_fee = 1000;
+    let fixed0 = 10;
+    let fixed1 = 20;
+    let units = 2;
+    let price_per_unit = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+
+    let (creator0_pub_key, _) = crypto::gen_keypair();
+    let (creator1_pub_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset0, info0) = dmbc_testkit::create_asset("asset0", units, dmbc_testkit::asset_fees(fixed0, "0.0".parse().unwrap()), &creator0_pub_key);
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(fixed1, "0.0".parse().unwrap()), &creator1_pub_key);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_asset_to_wallet(&seller_public_key, (asset0.clone(), info0))
+        .add_asset_to_wallet(&seller_public_key, (asset1.clone(), info1))
+        .create();
+    let api = testkit.api();
+
+    let offer = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset0, price_per_unit))
+        .add_asset_value(TradeAsset::from_bundle(asset1, price_per_unit))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(12)
+        .build()
+        .offer();
+
+    let (status, response): (StatusCode, OfferFeesResponse) =
+        api.post_with_status("/v1/fees/offers", &OfferFeesRequest::Trade(offer));
+
+    let mut assets_fees = HashMap::new();
+    assets_fees.insert(creator0_pub_key, fixed0 * units);
+    assets_fees.insert(creator1_pub_key, fixed1 * units);
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(Ok(OfferFeesResponseBody {
+            transaction_fee,
+            assets_fees,
+        }))
+    );
+}
+
+#[test]
+fn fees_for_exchange_offer() {
+    let transaction_fee = 1000;
+    let fixed = 10;
+    let units = 2;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (sender_public_key, sender_secret_key) = crypto::gen_keypair();
+    let (recipient_public_key, recipient_secret_key) = crypto::gen_keypair();
+
+    let (asset0, info0) = dmbc_testkit::create_asset("asset0", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pub_key);
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pub_key);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_asset_to_wallet(&sender_public_key, (asset0.clone(), info0))
+        .add_asset_to_wallet(&recipient_public_key, (asset1.clone(), info1))
+        .create();
+    let api = testkit.api();
+
+    let offer = transaction::Builder::new()
+        .keypair(recipient_public_key, recipient_secret_key)
+        .tx_exchange()
+        .sender(sender_public_key)
+        .sender_secret(sender_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(asset0)
+        .recipient_add_asset_value(asset1)
+        .build()
+        .offer();
+
+    let (status, response): (StatusCode, OfferFeesResponse) =
+        api.post_with_status("/v1/fees/offers", &OfferFeesRequest::Exchange(offer));
+
+    let mut assets_fees = HashMap::new();
+    assets_fees.insert(creator_pub_key, fixed * units * 2);
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(Ok(OfferFeesResponseBody {
+            transaction_fee,
+            assets_fees,
+        }))
+    );
+}
+
+#[test]
+fn fees_for_offer_asset_not_found() {
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let asset = AssetBundle::from_data("asset", 2, &creator_pub_key);
+
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let offer = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset, 1000))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(12)
+        .build()
+        .offer();
+
+    let (status, response): (StatusCode, OfferFeesResponse) =
+        api.post_with_status("/v1/fees/offers", &OfferFeesRequest::Trade(offer));
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::AssetNotFound)));
+}
@@ -0,0 +1,178 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::assets::{AssetBundle, TradeAsset};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn platform_wallet_receives_exchange_intermediary_fee() {
+    let transaction_fee = 1000;
+    let balance = 100_000;
+    let units = 2;
+
+    let (platform_key, _) = crypto::gen_keypair();
+    let config_fees = TransactionFees::new(&platform_key, 0, 0, 0, transaction_fee, 0, 0);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (sender_public_key, sender_secret_key) = crypto::gen_keypair();
+    let (recipient_public_key, recipient_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset0, info0) = dmbc_testkit::create_asset("asset0", units, fees.clone(), &creator_key);
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&recipient_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&sender_public_key, (asset0.clone(), info0))
+        .add_asset_to_wallet(&recipient_public_key, (asset1.clone(), info1))
+        .create();
+    let api = testkit.api();
+
+    let default_genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange = transaction::Builder::new()
+        .keypair(recipient_public_key, recipient_secret_key)
+        .tx_exchange_with_intermediary()
+        .sender_key_pair(sender_public_key, sender_secret_key)
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(0)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(asset0)
+        .recipient_add_asset_value(asset1)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_exchange);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    assert_eq!(api.get_wallet(&platform_key).balance, transaction_fee);
+    assert_eq!(api.get_wallet(&recipient_public_key).balance, balance - transaction_fee);
+    assert_eq!(
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance,
+        default_genesis_balance
+    );
+}
+
+#[test]
+fn platform_wallet_receives_trade_intermediary_fee() {
+    let transaction_fee = 1000;
+    let balance = 100_000;
+    let units = 2;
+    let price = 500;
+
+    let (platform_key, _) = crypto::gen_keypair();
+    let config_fees = TransactionFees::new(&platform_key, 0, 0, 0, 0, transaction_fee, 0);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let default_genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(0)
+        .add_asset_value(TradeAsset::from_bundle(asset, price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    assert_eq!(api.get_wallet(&platform_key).balance, transaction_fee);
+    assert_eq!(
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance,
+        default_genesis_balance
+    );
+}
+
+#[test]
+fn platform_wallet_created_by_first_exchange_fee() {
+    let transaction_fee = 1000;
+    let balance = 100_000;
+    let units = 2;
+
+    // No wallet exists for the platform key until a fee is paid to it.
+    let (platform_key, _) = crypto::gen_keypair();
+    let config_fees = TransactionFees::new(&platform_key, 0, 0, 0, transaction_fee, 0, 0);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (sender_public_key, sender_secret_key) = crypto::gen_keypair();
+    let (recipient_public_key, recipient_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset0, info0) = dmbc_testkit::create_asset("asset0", units, fees.clone(), &creator_key);
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&recipient_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&sender_public_key, (asset0.clone(), info0))
+        .add_asset_to_wallet(&recipient_public_key, (asset1.clone(), info1))
+        .create();
+    let api = testkit.api();
+
+    assert_eq!(api.get_wallet(&platform_key).balance, 0);
+
+    for seed in 1..3 {
+        let tx_exchange = transaction::Builder::new()
+            .keypair(recipient_public_key, recipient_secret_key.clone())
+            .tx_exchange()
+            .sender(sender_public_key)
+            .sender_secret(sender_secret_key.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .sender_add_asset_value(AssetBundle::new(asset0.id(), 1))
+            .recipient_add_asset_value(AssetBundle::new(asset1.id(), 1))
+            .seed(seed)
+            .build();
+
+        api.post_tx(&tx_exchange);
+        testkit.create_block();
+
+        let (_, tx_status) = api.get_tx_status(&tx_exchange);
+        assert_eq!(tx_status, Ok(Ok(())));
+
+        // The fee of every block adds up in the stored platform wallet.
+        assert_eq!(api.get_wallet(&platform_key).balance, seed * transaction_fee);
+    }
+
+    assert_eq!(api.get_wallet(&recipient_public_key).balance, balance - 2 * transaction_fee);
+}
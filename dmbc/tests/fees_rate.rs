@@ -0,0 +1,142 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::FeesResponseBody;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::components::{percentage_fee, FeeStrategy};
+use dmbc::currency::assets::TradeAsset;
+use dmbc::currency::wallet::Wallet;
+
+// All tests in this file use a fee rate of `FEE_RATE_BPS`.
+const FEE_RATE_BPS: u64 = 33;
+
+#[test]
+fn percentage_fee_rounding() {
+    assert_eq!(percentage_fee(1000, 9999, 1), Ok(1001));
+    assert_eq!(percentage_fee(1000, 4999, 1), Ok(1000));
+    assert_eq!(percentage_fee(1000, 5000, 1), Ok(1001));
+    assert_eq!(percentage_fee(1000, 14_999, 1), Ok(1001));
+    assert_eq!(percentage_fee(1000, 15_000, 1), Ok(1002));
+    assert_eq!(percentage_fee(0, 10_000, 10_000), Ok(10_000));
+}
+
+#[test]
+fn percentage_fee_zero_rate_is_flat() {
+    assert_eq!(percentage_fee(1000, 0, 0), Ok(1000));
+    assert_eq!(percentage_fee(1000, 9999, 0), Ok(1000));
+    assert_eq!(percentage_fee(1000, u64::max_value(), 0), Ok(1000));
+}
+
+#[test]
+fn percentage_fee_overflow() {
+    assert_eq!(percentage_fee(0, u64::max_value(), 2), Err(Error::Overflow));
+    assert_eq!(percentage_fee(u64::max_value(), 10_000, 1), Err(Error::Overflow));
+}
+
+#[test]
+fn fees_for_trade_with_rate() {
+    let transaction_fee = 1000;
+    let fixed = 10;
+    let units = 2;
+    let price_per_unit = 1000;
+    let meta_data = "asset";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let configuration = Configuration::new(config_fees).with_fee_rate_bps(FEE_RATE_BPS);
+
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pub_key);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset, price_per_unit))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(12)
+        .build();
+
+    let (status, response) = api.post_fee(&tx_trade);
+    // 33 bps of 2000 is 6.6, rounded to 7.
+    let mut expected = HashMap::new();
+    let expected_fee = transaction_fee + 7 + fixed * units;
+    expected.insert(buyer_public_key, expected_fee);
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+}
+
+#[test]
+fn trade_with_rate() {
+    let transaction_fee = 1000;
+    let units = 2;
+    let price_per_unit = 1000;
+    let balance = 100_000;
+    let meta_data = "asset";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let configuration = Configuration::new(config_fees).with_fee_rate_bps(FEE_RATE_BPS);
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset, price_per_unit))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let expected_fee = transaction_fee + 7;
+    let total = units * price_per_unit;
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + expected_fee);
+
+    let buyer_wallet = api.get_wallet(&buyer_public_key);
+    assert_eq!(buyer_wallet.balance, balance - total - expected_fee);
+
+    let seller_wallet = api.get_wallet(&seller_public_key);
+    assert_eq!(seller_wallet.balance, total);
+}
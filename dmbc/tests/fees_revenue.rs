@@ -0,0 +1,84 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::{RevenueResponse, RevenueResponseBody};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::wallet::Wallet;
+use dmbc::currency::transactions::components::FeeStrategy;
+
+#[test]
+fn revenue_counts_exchange_fees() {
+    let transaction_fee = 1001;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 10;
+    let balance = 100_000;
+    let units = 10;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let (status, response): (StatusCode, RevenueResponse) = api.get_with_status("/v1/fees/revenue");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(RevenueResponseBody { total: 0 }));
+
+    let strategies = vec![
+        FeeStrategy::Recipient,
+        FeeStrategy::Sender,
+        FeeStrategy::RecipientAndSender,
+    ];
+    let exchanges = strategies.len() as u64;
+    for (seed, fee_strategy) in strategies.into_iter().enumerate() {
+        let tx_exchange = transaction::Builder::new()
+            .keypair(recipient_pk, recipient_sk.clone())
+            .tx_exchange()
+            .sender(sender_pk)
+            .sender_secret(sender_sk.clone())
+            .fee_strategy(fee_strategy)
+            .sender_add_asset_value(AssetBundle::new(asset1.id(), 1))
+            .recipient_add_asset_value(AssetBundle::new(asset2.id(), 1))
+            .seed(seed as u64)
+            .build();
+
+        api.post_tx(&tx_exchange);
+        testkit.create_block();
+
+        let (_, tx_status) = api.get_tx_status(&tx_exchange);
+        assert_eq!(tx_status, Ok(Ok(())));
+    }
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    let collected = genesis_wallet.balance - genesis_balance;
+    assert_eq!(collected, exchanges * transaction_fee);
+
+    let (status, response): (StatusCode, RevenueResponse) = api.get_with_status("/v1/fees/revenue");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(RevenueResponseBody { total: collected }));
+}
@@ -0,0 +1,155 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::FeesResponseBody;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::assets::TradeAsset;
+use dmbc::currency::wallet::Wallet;
+
+// All tests in this file use a royalty of `ROYALTY_BPS` unless stated otherwise.
+const ROYALTY_BPS: u64 = 250;
+
+/// Balances of the creator, seller and buyer after a `trade_intermediary`
+/// of `units` units of an asset at `price` per unit, sold by someone else
+/// than its creator if `secondary`. The buyer pays all fees.
+fn trade_intermediary(royalty_bps: u64, secondary: bool, units: u64, price: u64) -> (u64, u64, u64) {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let configuration = Configuration::new(config_fees).with_royalty_bps(royalty_bps);
+    let fixed = 10;
+    let balance = 100_000;
+
+    let (creator_public_key, creator_secret_key) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = if secondary {
+        crypto::gen_keypair()
+    } else {
+        (creator_public_key, creator_secret_key)
+    };
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(0)
+        .add_asset_value(TradeAsset::from_bundle(asset, price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    (
+        api.get_wallet(&creator_public_key).balance,
+        api.get_wallet(&seller_public_key).balance,
+        api.get_wallet(&buyer_public_key).balance,
+    )
+}
+
+#[test]
+fn trade_intermediary_pays_royalty_to_creator() {
+    let units = 2;
+    let price = 1000;
+    let (creator, seller, buyer) = trade_intermediary(ROYALTY_BPS, true, units, price);
+
+    // 250 bps of 2000 is 50, on top of the asset's trade fee of 10 per unit.
+    let total = units * price;
+    let royalty = 50;
+    assert_eq!(creator, royalty + units * 10);
+    assert_eq!(seller, total);
+    assert_eq!(buyer, 100_000 - total - royalty - units * 10 - 1000);
+}
+
+#[test]
+fn trade_intermediary_zero_royalty() {
+    let units = 2;
+    let price = 1000;
+    let (creator, seller, buyer) = trade_intermediary(0, true, units, price);
+
+    let total = units * price;
+    assert_eq!(creator, units * 10);
+    assert_eq!(seller, total);
+    assert_eq!(buyer, 100_000 - total - units * 10 - 1000);
+}
+
+#[test]
+fn trade_intermediary_no_royalty_on_primary_sale() {
+    let units = 2;
+    let price = 1000;
+    let (creator, seller, buyer) = trade_intermediary(ROYALTY_BPS, false, units, price);
+
+    let total = units * price;
+    assert_eq!(creator, seller);
+    assert_eq!(creator, total + units * 10);
+    assert_eq!(buyer, 100_000 - total - units * 10 - 1000);
+}
+
+#[test]
+fn fees_for_trade_with_royalty() {
+    let transaction_fee = 1000;
+    let fixed = 10;
+    let units = 2;
+    let price_per_unit = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let configuration = Configuration::new(config_fees).with_royalty_bps(ROYALTY_BPS);
+
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pub_key);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset, price_per_unit))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    let (status, response) = api.post_fee(&tx_trade);
+    // 250 bps of 2000 is 50.
+    let mut expected = HashMap::new();
+    expected.insert(buyer_public_key, transaction_fee + 50 + fixed * units);
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+}
@@ -0,0 +1,105 @@
+extern crate dmbc;
+
+use dmbc::currency::transactions::components::{split_coins, split_fee};
+
+/// SplitMix64, so that the sampled values are the same on every run.
+fn next_value(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn assert_split(coins: u64) {
+    let (first, second) = split_coins(coins);
+    assert_eq!(first.checked_add(second), Some(coins), "coins: {}", coins);
+    assert!(first >= second, "coins: {}", coins);
+    assert!(first - second <= 1, "coins: {}", coins);
+}
+
+#[test]
+fn split_coins_edge_values() {
+    let f64_safe = 1u64 << 53;
+    let values = [
+        0,
+        1,
+        2,
+        3,
+        f64_safe - 1,
+        f64_safe,
+        f64_safe + 1,
+        f64_safe + 3,
+        u64::max_value() - 1,
+        u64::max_value(),
+    ];
+
+    for &coins in values.iter() {
+        assert_split(coins);
+    }
+
+    assert_eq!(split_coins(f64_safe + 1), (f64_safe / 2 + 1, f64_safe / 2));
+    assert_eq!(
+        split_coins(u64::max_value()),
+        (u64::max_value() / 2 + 1, u64::max_value() / 2)
+    );
+}
+
+#[test]
+fn split_coins_random_values() {
+    let mut state = 0x5EED;
+    for _ in 0..100_000 {
+        let coins = next_value(&mut state);
+        assert_split(coins);
+        // Values below the f64 safe range are covered as well.
+        assert_split(coins >> 11);
+    }
+}
+
+fn assert_split_fee(total: u64, parties: usize) {
+    let parts = split_fee(total, parties);
+    assert_eq!(parts.len(), parties, "total: {}, parties: {}", total, parties);
+    let sum = parts.iter().fold(Some(0u64), |sum, &part| sum.and_then(|s| s.checked_add(part)));
+    assert_eq!(sum, Some(total), "total: {}, parties: {}", total, parties);
+    // Everyone pays the same share, the first party also pays the remainder.
+    let share = total / parties as u64;
+    assert_eq!(parts[0], share + total % parties as u64);
+    assert!(parts[1..].iter().all(|&part| part == share));
+}
+
+#[test]
+fn split_fee_edge_values() {
+    let values = [0, 1, 2, 3, 7, 1 << 53, u64::max_value() - 1, u64::max_value()];
+    let parties = [1, 2, 3, 4, 7, 64, 1000];
+
+    for &total in values.iter() {
+        for &count in parties.iter() {
+            assert_split_fee(total, count);
+        }
+    }
+
+    assert_eq!(split_fee(10, 3), vec![4, 3, 3]);
+    assert_eq!(split_fee(2, 3), vec![2, 0, 0]);
+    assert!(split_fee(10, 0).is_empty());
+}
+
+#[test]
+fn split_fee_random_values() {
+    let mut state = 0xFEE5;
+    for _ in 0..10_000 {
+        let total = next_value(&mut state);
+        let parties = (next_value(&mut state) % 16 + 1) as usize;
+        assert_split_fee(total, parties);
+        assert_split_fee(total >> 11, parties);
+    }
+}
+
+#[test]
+fn split_coins_is_split_fee_for_two() {
+    let mut state = 0x2;
+    for _ in 0..10_000 {
+        let coins = next_value(&mut state);
+        let parts = split_fee(coins, 2);
+        assert_eq!(split_coins(coins), (parts[0], parts[1]));
+    }
+}
@@ -0,0 +1,56 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use dmbc_testkit::DmbcTestApiBuilder;
+
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::transactions::components::ThirdPartyFees;
+
+#[test]
+fn third_party_fees_ordered_by_creator_key() {
+    let fixed = 10;
+    let units = 2;
+
+    let creators = (0..8).map(|_| crypto::gen_keypair().0).collect::<Vec<_>>();
+    let (owner, _) = crypto::gen_keypair();
+
+    let mut builder = DmbcTestApiBuilder::new();
+    let mut assets = Vec::new();
+    for (i, creator) in creators.iter().enumerate() {
+        let (asset, info) = dmbc_testkit::create_asset(
+            &format!("asset{}", i),
+            units,
+            dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()),
+            creator,
+        );
+        builder = builder.add_asset_to_wallet(&owner, (asset.clone(), info));
+        assets.push(asset);
+    }
+    let mut testkit = builder.create();
+    let snapshot = testkit.blockchain_mut().snapshot();
+
+    let mut expected = creators.clone();
+    expected.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+
+    let creators_of = |assets: Vec<AssetBundle>| -> Vec<PublicKey> {
+        let fees = ThirdPartyFees::new_exchange(&snapshot, assets).unwrap();
+        fees.0.keys().cloned().collect()
+    };
+
+    let mut reversed = assets.clone();
+    reversed.reverse();
+    for _ in 0..10 {
+        assert_eq!(creators_of(assets.clone()), expected);
+        assert_eq!(creators_of(reversed.clone()), expected);
+    }
+}
@@ -15,12 +15,14 @@ use hyper::status::StatusCode;
 use exonum::crypto;
 use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 
+use dmbc::currency::api::error::ApiError;
 use dmbc::currency::api::fees::FeesResponseBody;
 use dmbc::currency::configuration::{Configuration, TransactionFees};
 use dmbc::currency::transactions::builders::transaction;
 use dmbc::currency::error::Error;
 use dmbc::currency::transactions::components::FeeStrategy;
-use dmbc::currency::assets::TradeAsset;
+use dmbc::currency::assets::{AssetBundle, TradeAsset};
+use dmbc::currency::wallet::Wallet;
 
 #[test]
 fn fees_for_trade_recipient() {
@@ -213,7 +215,7 @@ fn fees_for_trade_invalid_transaction() {
     let (status, response) = api.post_fee(&tx_trade);
 
     assert_eq!(status, StatusCode::BadRequest);
-    assert_eq!(response, Ok(Err(Error::InvalidTransaction)));
+    assert_eq!(response, Ok(Err(Error::InvalidFeeStrategy)));
 }
 
 #[test]
@@ -250,3 +252,51 @@ fn fees_for_trade_asset_not_found() {
     assert_eq!(status, StatusCode::BadRequest);
     assert_eq!(response, Ok(Err(Error::AssetNotFound)));
 }
+
+#[test]
+fn fees_for_trade_does_not_submit() {
+    let transaction_fee = 1000;
+    let fixed = 10;
+    let units = 2;
+    let price_per_unit = 1000;
+    let balance = 100_000;
+    let meta_data = "asset";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pub_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price_per_unit))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(12)
+        .build();
+
+    let (status, _) = api.post_fee(&tx_trade);
+    assert_eq!(status, StatusCode::Ok);
+
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+
+    let buyer_wallet = api.get_wallet(&buyer_public_key);
+    assert_eq!(buyer_wallet.balance, balance);
+    assert_eq!(buyer_wallet.assets_count, 0);
+
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(seller_assets, vec![asset]);
+}
@@ -0,0 +1,142 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::{RevenueResponse, RevenueResponseBody};
+use dmbc::currency::configuration::{Configuration, TransactionFees, ValidatorReward};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn transfer_fee_split_with_validators() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let (validators_key, _) = crypto::gen_keypair();
+    let configuration = Configuration::new(config_fees).with_validator_reward(ValidatorReward {
+        wallet: validators_key,
+        platform_bps: 7_000,
+    });
+    let balance = 100_000;
+    let amount = 10_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance - genesis_balance, 700);
+    assert_eq!(api.get_wallet(&validators_key).balance, 300);
+    assert_eq!(api.get_wallet(&public_key).balance, balance - amount - transaction_fee);
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+
+    let (status, response): (StatusCode, RevenueResponse) = api.get_with_status("/v1/fees/revenue");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(RevenueResponseBody { total: 700 }));
+}
+
+#[test]
+fn asset_fees_not_split_with_validators() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let (validators_key, _) = crypto::gen_keypair();
+    let configuration = Configuration::new(config_fees).with_validator_reward(ValidatorReward {
+        wallet: validators_key,
+        platform_bps: 2_500,
+    });
+    let fixed = 10;
+    let balance = 100_000;
+    let units = 10;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&sender_pk, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange()
+        .sender(sender_pk)
+        .sender_secret(sender_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), 1))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), 1))
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_exchange);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // 25% of the blockchain fee stays with the platform, the asset fees all
+    // go to the creator.
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance - genesis_balance, 250);
+    assert_eq!(api.get_wallet(&validators_key).balance, 750);
+    assert_eq!(api.get_wallet(&creator_pk).balance, 2 * fixed);
+    assert_eq!(api.get_wallet(&recipient_pk).balance, balance - transaction_fee - 2 * fixed);
+}
+
+#[test]
+fn validator_reward_split() {
+    let (wallet, _) = crypto::gen_keypair();
+    let reward = ValidatorReward {
+        wallet,
+        platform_bps: 3_333,
+    };
+    assert_eq!(reward.split(1000), (333, 667));
+    assert_eq!(reward.split(0), (0, 0));
+    assert_eq!(reward.split(u64::max_value()).0 + reward.split(u64::max_value()).1, u64::max_value());
+
+    let everything = ValidatorReward {
+        wallet,
+        platform_bps: 10_000,
+    };
+    assert_eq!(everything.split(1000), (1000, 0));
+}
@@ -0,0 +1,138 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::crypto::{Hash, PublicKey};
+use exonum::encoding::serialize::FromHex;
+use exonum_testkit::TestKitBuilder;
+use dmbc_testkit::{DmbcTestKit, DmbcTestKitApi};
+
+use dmbc::currency::Service;
+use dmbc::currency::assets::{AssetBundle, AssetId};
+use dmbc::currency::configuration::GENESIS_WALLET_PUB_KEY;
+use dmbc::currency::genesis::{Allocation, GenesisAllocation, GenesisAsset};
+
+#[test]
+fn genesis_allocation_funds_wallets() {
+    let (funded_key, _) = crypto::gen_keypair();
+    let (creator_key, _) = crypto::gen_keypair();
+    let (unlisted_key, _) = crypto::gen_keypair();
+    let fees = dmbc_testkit::asset_fees(10, "0.1".parse().unwrap());
+
+    let allocation = GenesisAllocation::new(vec![
+        Allocation {
+            pub_key: funded_key,
+            balance: 1_000_000,
+            assets: vec![],
+        },
+        Allocation {
+            pub_key: creator_key,
+            balance: 500,
+            assets: vec![GenesisAsset {
+                data: "genesis_asset".to_string(),
+                amount: 42,
+                fees: fees.clone(),
+            }],
+        },
+    ]).unwrap();
+
+    let mut testkit = TestKitBuilder::validator()
+        .with_service(Service::with_allocation(allocation))
+        .create();
+    let api = testkit.api();
+
+    assert_eq!(api.get_wallet(&funded_key).balance, 1_000_000);
+    assert_eq!(api.get_wallet(&funded_key).assets_count, 0);
+    assert_eq!(api.get_wallet(&creator_key).balance, 500);
+    assert_eq!(api.get_wallet(&unlisted_key).balance, 0);
+
+    let id = AssetId::from_data("genesis_asset", &creator_key);
+    let assets = api.get_wallet_assets(&creator_key);
+    assert_eq!(
+        assets.iter().map(|a| a.into()).collect::<Vec<AssetBundle>>(),
+        vec![AssetBundle::new(id, 42)]
+    );
+
+    let info = testkit.fetch_asset_info(&id).unwrap();
+    assert_eq!(info.creator(), &creator_key);
+    assert_eq!(info.origin(), &Hash::zero());
+    assert_eq!(info.amount(), 42);
+    assert_eq!(info.fees(), fees);
+}
+
+#[test]
+fn genesis_allocation_from_json() {
+    let (first_key, _) = crypto::gen_keypair();
+    let (second_key, _) = crypto::gen_keypair();
+    let fees = serde_json::to_string(&dmbc_testkit::asset_fees(0, "0.0".parse().unwrap())).unwrap();
+
+    let json = format!(
+        r#"[
+            {{ "pub_key": "{}", "balance": 100 }},
+            {{ "pub_key": "{}", "balance": 0, "assets": [
+                {{ "data": "asset", "amount": 3, "fees": {} }}
+            ] }}
+        ]"#,
+        first_key.to_hex(),
+        second_key.to_hex(),
+        fees
+    );
+
+    let allocation = GenesisAllocation::from_json(&json).unwrap();
+    let allocations = allocation.allocations();
+    assert_eq!(allocations.len(), 2);
+    assert_eq!(allocations[0].pub_key, first_key);
+    assert_eq!(allocations[0].balance, 100);
+    assert!(allocations[0].assets.is_empty());
+    assert_eq!(allocations[1].assets[0].amount, 3);
+}
+
+#[test]
+fn genesis_allocation_rejects_duplicate_keys() {
+    let (public_key, _) = crypto::gen_keypair();
+
+    let json = format!(
+        r#"[
+            {{ "pub_key": "{key}", "balance": 100 }},
+            {{ "pub_key": "{key}", "balance": 200 }}
+        ]"#,
+        key = public_key.to_hex()
+    );
+
+    assert!(GenesisAllocation::from_json(&json).is_err());
+}
+
+#[test]
+fn genesis_allocation_rejects_genesis_wallet() {
+    let genesis_key = PublicKey::from_hex(GENESIS_WALLET_PUB_KEY).unwrap();
+
+    let json = format!(
+        r#"[{{ "pub_key": "{}", "balance": 100 }}]"#,
+        genesis_key.to_hex()
+    );
+
+    assert!(GenesisAllocation::from_json(&json).is_err());
+}
+
+#[test]
+fn genesis_allocation_rejects_malformed_file() {
+    let (public_key, _) = crypto::gen_keypair();
+
+    // Missing balance.
+    let json = format!(r#"[{{ "pub_key": "{}" }}]"#, public_key.to_hex());
+    assert!(GenesisAllocation::from_json(&json).is_err());
+
+    // Not a key.
+    assert!(GenesisAllocation::from_json(r#"[{ "pub_key": "zz", "balance": 1 }]"#).is_err());
+
+    // Not a list.
+    assert!(GenesisAllocation::from_json(r#"{ "balance": 1 }"#).is_err());
+}
@@ -66,7 +66,7 @@ fn hex_offer_tx() {
         "/v1/hex/transactions/offer",
         &trade
     );
-    let hex = HexApi::hex_string(trade.offer_raw().to_vec());
+    let hex = HexApi::hex_string(trade.offer_signing_bytes());
 
     assert_eq!(status, StatusCode::Ok);
     assert_eq!(response, Ok(Some(HexResponse { hex })));
@@ -0,0 +1,177 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate serde_json;
+
+use exonum::crypto;
+use serde_json::Value;
+
+use dmbc::currency::assets::{AssetBundle, AssetId};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::transactions::{Exchange, Transfer, MAX_TAG_LENGTH};
+
+const U64_MAX: &str = "18446744073709551615";
+const U64_MAX_PLUS_ONE: &str = "18446744073709551616";
+
+fn transfer() -> Transfer {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let asset_id = AssetId::from_data("asset", &public_key);
+
+    transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(u64::max_value())
+        .add_asset_value(AssetBundle::new(asset_id, u64::max_value()))
+        .recipient(recipient_key)
+        .seed(u64::max_value())
+        .build()
+}
+
+fn exchange() -> Exchange {
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+
+    transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange()
+        .sender(sender_pk)
+        .sender_secret(sender_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_value(u64::max_value())
+        .seed(u64::max_value())
+        .build()
+}
+
+fn with_field<F>(value: &Value, set: F) -> Value
+where
+    F: FnOnce(&mut Value),
+{
+    let mut value = value.clone();
+    set(&mut value);
+    value
+}
+
+#[test]
+fn transfer_u64_max_round_trip() {
+    let tx = transfer();
+    let json = serde_json::to_value(&tx).unwrap();
+
+    assert_eq!(json["body"]["amount"], Value::String(U64_MAX.to_string()));
+    assert_eq!(json["body"]["seed"], Value::String(U64_MAX.to_string()));
+    assert_eq!(json["body"]["assets"][0]["amount"], Value::String(U64_MAX.to_string()));
+
+    let parsed: Transfer = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, tx);
+    assert_eq!(parsed.amount(), u64::max_value());
+    assert_eq!(parsed.seed(), u64::max_value());
+    assert_eq!(parsed.assets()[0].amount(), u64::max_value());
+}
+
+#[test]
+fn transfer_u64_overflow_is_rejected() {
+    let json = serde_json::to_value(&transfer()).unwrap();
+    let too_big = Value::String(U64_MAX_PLUS_ONE.to_string());
+
+    let amount = with_field(&json, |v| v["body"]["amount"] = too_big.clone());
+    assert!(serde_json::from_value::<Transfer>(amount).is_err());
+
+    let seed = with_field(&json, |v| v["body"]["seed"] = too_big.clone());
+    assert!(serde_json::from_value::<Transfer>(seed).is_err());
+
+    let asset_amount = with_field(&json, |v| v["body"]["assets"][0]["amount"] = too_big.clone());
+    assert!(serde_json::from_value::<Transfer>(asset_amount).is_err());
+}
+
+#[test]
+fn transfer_u64_must_be_string() {
+    let json = serde_json::to_value(&transfer()).unwrap();
+
+    let amount = with_field(&json, |v| v["body"]["amount"] = Value::from(3u64));
+    assert!(serde_json::from_value::<Transfer>(amount).is_err());
+
+    let negative = with_field(&json, |v| v["body"]["amount"] = Value::String("-1".to_string()));
+    assert!(serde_json::from_value::<Transfer>(negative).is_err());
+}
+
+#[test]
+fn exchange_sender_value_boundary() {
+    let tx = exchange();
+    let json = serde_json::to_value(&tx).unwrap();
+
+    assert_eq!(json["body"]["offer"]["sender_value"], Value::String(U64_MAX.to_string()));
+
+    let parsed: Exchange = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(parsed, tx);
+    assert_eq!(parsed.offer().sender_value(), u64::max_value());
+
+    let too_big = Value::String(U64_MAX_PLUS_ONE.to_string());
+    let sender_value = with_field(&json, |v| v["body"]["offer"]["sender_value"] = too_big.clone());
+    assert!(serde_json::from_value::<Exchange>(sender_value).is_err());
+
+    let seed = with_field(&json, |v| v["body"]["offer"]["seed"] = too_big.clone());
+    assert!(serde_json::from_value::<Exchange>(seed).is_err());
+}
+
+#[test]
+fn exchange_fee_strategy_out_of_range_is_rejected() {
+    let json = serde_json::to_value(&exchange()).unwrap();
+
+    // 257 used to wrap around to 1 (`Recipient`).
+    let fee_strategy = with_field(&json, |v| v["body"]["offer"]["fee_strategy"] = Value::from(257));
+    assert!(serde_json::from_value::<Exchange>(fee_strategy).is_err());
+
+    let negative = with_field(&json, |v| v["body"]["offer"]["fee_strategy"] = Value::from(-1));
+    assert!(serde_json::from_value::<Exchange>(negative).is_err());
+}
+
+#[test]
+fn transfer_pathological_strings_round_trip() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut data_info = "\"\\\u{0}\u{1f}\n\t</script>ü€𝄞\u{feff}".to_string();
+    while data_info.len() < 16 * 1024 {
+        data_info.push_str("\u{202e}x\"\\");
+    }
+    let tag = "\u{0}".repeat(MAX_TAG_LENGTH);
+
+    let tx = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .data_info(&data_info)
+        .tag(&tag)
+        .build();
+
+    let json = serde_json::to_value(&tx).unwrap();
+    assert_eq!(json["body"]["data_info"], Value::String(data_info.clone()));
+    assert_eq!(json["body"]["tag"], Value::String(tag.clone()));
+
+    let text = serde_json::to_string(&tx).unwrap();
+    let parsed: Transfer = serde_json::from_str(&text).unwrap();
+    assert_eq!(parsed, tx);
+    assert_eq!(parsed.data_info(), data_info.as_str());
+
+    // Debug output is what gets logged while processing.
+    assert!(!format!("{:?}", tx).is_empty());
+}
+
+#[test]
+fn transfer_empty_strings_round_trip() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let tx = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .build();
+
+    let json = serde_json::to_value(&tx).unwrap();
+    assert_eq!(json["body"]["data_info"], Value::String(String::new()));
+    assert_eq!(json["body"]["assets"], Value::Array(vec![]));
+
+    let parsed: Transfer = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, tx);
+}
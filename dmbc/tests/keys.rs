@@ -0,0 +1,66 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use exonum::encoding::serialize::FromHex;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::wallet::WalletResponse;
+use dmbc::currency::wallet::Wallet;
+use dmbc::keys;
+
+#[test]
+fn keys_hex_round_trip() {
+    let (public_key, _) = crypto::gen_keypair();
+    let rendered = keys::hex(&public_key).to_string();
+
+    assert_eq!(rendered.len(), 64);
+    assert_eq!(rendered, rendered.to_lowercase());
+    assert_eq!(PublicKey::from_hex(&rendered).unwrap(), public_key);
+    assert_eq!(rendered, public_key.to_hex());
+}
+
+#[test]
+fn keys_hex_matches_json() {
+    let (public_key, _) = crypto::gen_keypair();
+    let json = serde_json::to_string(&public_key).unwrap();
+
+    assert_eq!(json, format!("\"{}\"", keys::hex(&public_key)));
+}
+
+#[test]
+fn keys_fingerprint_is_prefix() {
+    let (public_key, _) = crypto::gen_keypair();
+    let full = keys::hex(&public_key).to_string();
+    let fingerprint = keys::fingerprint(&public_key).to_string();
+
+    assert_eq!(fingerprint.len(), keys::FINGERPRINT_BYTES * 2);
+    assert!(full.starts_with(&fingerprint));
+}
+
+#[test]
+fn keys_hex_accepted_by_api() {
+    let balance = 1000;
+    let (public_key, _) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, WalletResponse) =
+        api.get_with_status(&format!("/v1/wallets/{}", keys::hex(&public_key)));
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.unwrap().balance, balance);
+}
@@ -56,8 +56,8 @@ fn add_assets_mine_new_asset_to_receiver_empty_wallet() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -123,8 +123,8 @@ fn add_assets_mine_existing_asset_to_receivers_non_empty_wallet() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -184,8 +184,8 @@ fn add_assets_mine_existing_asset_to_creators_empty_wallet() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -254,8 +254,8 @@ fn add_assets_mine_existing_asset_to_creator_and_receiver() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -319,8 +319,8 @@ fn add_assets_mine_existing_asset_to_receivers_wallet_with_different_asset() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -385,8 +385,8 @@ fn add_assets_mine_existing_asset_with_different_fees() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Ok(Err(Error::InvalidAssetInfo)));
@@ -431,8 +431,8 @@ fn add_assets_insufficient_funds() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
@@ -482,3 +482,54 @@ fn add_assets_transaction_too_large() {
     let (_, tx_status) = api.get_tx_status(&tx_add_assets);
     assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
 }
+
+#[test]
+fn add_assets_same_data_different_creators() {
+    let meta_data = "asset";
+    let units = 3;
+    let balance = 100_000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (creator1_public_key, creator1_secret_key) = crypto::gen_keypair();
+    let (creator2_public_key, creator2_secret_key) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&creator1_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&creator2_public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let tx_add_assets1 = transaction::Builder::new()
+        .keypair(creator1_public_key, creator1_secret_key)
+        .tx_add_assets()
+        .add_asset(meta_data, units, fees.clone())
+        .seed(1)
+        .build();
+    let tx_add_assets2 = transaction::Builder::new()
+        .keypair(creator2_public_key, creator2_secret_key)
+        .tx_add_assets()
+        .add_asset(meta_data, units, fees)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_add_assets1);
+    api.post_tx(&tx_add_assets2);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_add_assets1);
+    assert_eq!(tx_status, Ok(Ok(())));
+    let (_, tx_status) = api.get_tx_status(&tx_add_assets2);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let assets1: Vec<AssetBundle> = api.get_wallet_assets(&creator1_public_key).iter().map(|a| a.into()).collect();
+    let assets2: Vec<AssetBundle> = api.get_wallet_assets(&creator2_public_key).iter().map(|a| a.into()).collect();
+
+    assert_eq!(assets1.len(), 1);
+    assert_eq!(assets2.len(), 1);
+    assert_ne!(assets1[0].id(), assets2[0].id());
+
+    let infos1: Vec<AssetInfo> = api.get_wallet_assets(&creator1_public_key).iter().map(|a| a.clone().meta_data.unwrap()).collect();
+    assert_eq!(infos1[0].creator(), &creator1_public_key);
+}
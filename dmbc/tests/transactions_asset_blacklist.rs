@@ -0,0 +1,171 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::messages::Message;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::error::Error;
+use dmbc::currency::api::transaction::TransactionResponse;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn asset_blacklist_blocks_and_restores_movement() {
+    let fixed = 10;
+    let meta_data = "asset";
+    let units = 5;
+    let balance = 100_000;
+
+    let (platform_pk, platform_sk) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::new(&platform_pk, 0, 0, 0, 0, 0, 0);
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_blacklist = transaction::Builder::new()
+        .keypair(platform_pk, platform_sk.clone())
+        .tx_set_asset_blacklist()
+        .asset_id(asset.id())
+        .seed(1)
+        .build();
+
+    let tx_hash = tx_blacklist.hash();
+
+    let (status, response) = api.post_tx(&tx_blacklist);
+    testkit.create_block();
+
+    // check post response
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    let (_, tx_status) = api.get_tx_status(&tx_blacklist);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_transfer()
+        .add_asset_value(asset.clone())
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::AssetBlacklisted)));
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.assets_count, 0);
+
+    let sender_assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_assets, vec![asset.clone()]);
+
+    let tx_unblacklist = transaction::Builder::new()
+        .keypair(platform_pk, platform_sk)
+        .tx_set_asset_blacklist()
+        .asset_id(asset.id())
+        .blacklisted(false)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_unblacklist);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_unblacklist);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .add_asset_value(asset.clone())
+        .recipient(recipient_key)
+        .seed(43)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_assets = api.get_wallet_assets(&recipient_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(recipient_assets, vec![asset]);
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.assets_count, 0);
+}
+
+#[test]
+fn asset_blacklist_not_platform_wallet() {
+    let fixed = 10;
+    let meta_data = "asset";
+    let units = 5;
+    let balance = 100_000;
+
+    let (platform_pk, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::new(&platform_pk, 0, 0, 0, 0, 0, 0);
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_blacklist = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_set_asset_blacklist()
+        .asset_id(asset.id())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_blacklist);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_blacklist);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidTransaction)));
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .add_asset_value(asset.clone())
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_assets = api.get_wallet_assets(&recipient_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(recipient_assets, vec![asset]);
+}
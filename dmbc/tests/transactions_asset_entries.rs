@@ -0,0 +1,95 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+
+use dmbc::currency::assets::{AssetBundle, TradeAsset};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+
+#[test]
+fn transfer_rejects_zero_amount_and_duplicate_assets() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let a = AssetBundle::from_data("a", 3, &public_key);
+    let b = AssetBundle::from_data("b", 3, &public_key);
+
+    let transfer = |assets: Vec<AssetBundle>| {
+        let mut builder = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .recipient(recipient_key)
+            .seed(1);
+        for bundle in assets {
+            builder = builder.add_asset_value(bundle);
+        }
+        builder.build()
+    };
+
+    assert!(transfer(vec![a.clone(), b.clone()]).verify());
+    assert!(!transfer(vec![a.clone(), AssetBundle::new(b.id(), 0)]).verify());
+    assert!(!transfer(vec![a.clone(), b, a]).verify());
+}
+
+#[test]
+fn trade_rejects_zero_amount_and_duplicate_assets() {
+    let (seller_key, seller_secret) = crypto::gen_keypair();
+    let (buyer_key, buyer_secret) = crypto::gen_keypair();
+    let a = AssetBundle::from_data("a", 3, &seller_key);
+    let b = AssetBundle::from_data("b", 3, &seller_key);
+
+    let trade = |assets: Vec<TradeAsset>| {
+        let mut builder = transaction::Builder::new()
+            .keypair(buyer_key, buyer_secret.clone())
+            .tx_trade_assets()
+            .seller(seller_key, seller_secret.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .seed(1);
+        for asset in assets {
+            builder = builder.add_asset_value(asset);
+        }
+        builder.build()
+    };
+
+    assert!(trade(vec![TradeAsset::from_bundle(a.clone(), 10), TradeAsset::from_bundle(b.clone(), 10)]).verify());
+    assert!(!trade(vec![TradeAsset::new(a.id(), 0, 10)]).verify());
+    assert!(!trade(vec![TradeAsset::from_bundle(a.clone(), 10), TradeAsset::from_bundle(a, 20)]).verify());
+}
+
+#[test]
+fn exchange_rejects_zero_amount_and_duplicate_assets() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, recipient_secret) = crypto::gen_keypair();
+    let a = AssetBundle::from_data("a", 3, &sender_key);
+    let b = AssetBundle::from_data("b", 3, &recipient_key);
+
+    let exchange = |sender_assets: Vec<AssetBundle>, recipient_assets: Vec<AssetBundle>| {
+        let mut builder = transaction::Builder::new()
+            .keypair(recipient_key, recipient_secret.clone())
+            .tx_exchange()
+            .sender(sender_key)
+            .sender_secret(sender_secret.clone())
+            .fee_strategy(FeeStrategy::Recipient);
+        for bundle in sender_assets {
+            builder = builder.sender_add_asset_value(bundle);
+        }
+        for bundle in recipient_assets {
+            builder = builder.recipient_add_asset_value(bundle);
+        }
+        builder.build()
+    };
+
+    assert!(exchange(vec![a.clone()], vec![b.clone()]).verify());
+    assert!(!exchange(vec![AssetBundle::new(a.id(), 0)], vec![b.clone()]).verify());
+    assert!(!exchange(vec![a.clone()], vec![b.clone(), b.clone()]).verify());
+    assert!(!exchange(vec![a.clone(), a], vec![b]).verify());
+}
@@ -0,0 +1,263 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::transactions::FREEZE_ASSET_ID;
+use dmbc::currency::assets::{AssetBundle, TradeAsset};
+use dmbc::currency::error::Error;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn asset_freeze_blocks_and_restores_transfer() {
+    let fixed = 10;
+    let meta_data = "asset";
+    let units = 5;
+    let balance = 100_000;
+
+    let (creator_pk, creator_sk) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_freeze = transaction::Builder::new()
+        .keypair(creator_pk, creator_sk.clone())
+        .tx_freeze_asset()
+        .asset_id(asset.id())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_freeze);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_freeze);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_transfer()
+        .add_asset_value(asset.clone())
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::AssetFrozen)));
+
+    let sender_assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_assets, vec![asset.clone()]);
+
+    let tx_unfreeze = transaction::Builder::new()
+        .keypair(creator_pk, creator_sk)
+        .tx_freeze_asset()
+        .asset_id(asset.id())
+        .frozen(false)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_unfreeze);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_unfreeze);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .add_asset_value(asset.clone())
+        .recipient(recipient_key)
+        .seed(43)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_assets = api.get_wallet_assets(&recipient_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(recipient_assets, vec![asset]);
+}
+
+#[test]
+fn asset_freeze_blocks_and_restores_trade() {
+    let fixed = 10;
+    let meta_data = "asset";
+    let units = 3;
+    let price = 500;
+    let balance = 100_000;
+
+    let (creator_pk, creator_sk) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_freeze = transaction::Builder::new()
+        .keypair(creator_pk, creator_sk.clone())
+        .tx_freeze_asset()
+        .asset_id(asset.id())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_freeze);
+    testkit.create_block();
+
+    let trade = |seed| {
+        transaction::Builder::new()
+            .keypair(buyer_public_key, buyer_secret_key.clone())
+            .tx_trade_assets_with_intermediary()
+            .intermediary_key_pair(intermediary_public_key, intermediary_secret_key.clone())
+            .commission(0)
+            .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+            .seller(seller_public_key, seller_secret_key.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .seed(seed)
+            .build()
+    };
+
+    let tx_trade = trade(2);
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::AssetFrozen)));
+
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(seller_assets, vec![asset.clone()]);
+    assert_eq!(api.get_wallet(&buyer_public_key).assets_count, 0);
+
+    let tx_unfreeze = transaction::Builder::new()
+        .keypair(creator_pk, creator_sk)
+        .tx_freeze_asset()
+        .asset_id(asset.id())
+        .frozen(false)
+        .seed(3)
+        .build();
+
+    api.post_tx(&tx_unfreeze);
+    testkit.create_block();
+
+    let tx_trade = trade(4);
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let buyer_assets = api.get_wallet_assets(&buyer_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(buyer_assets, vec![asset]);
+}
+
+#[test]
+fn asset_freeze_not_creator() {
+    let fixed = 10;
+    let meta_data = "asset";
+    let units = 5;
+    let balance = 100_000;
+
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    // The holder of the asset is not allowed to freeze it.
+    let tx_freeze = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_freeze_asset()
+        .asset_id(asset.id())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_freeze);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_freeze);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidTransaction)));
+}
+
+#[test]
+fn asset_freeze_charges_operation_fee() {
+    let fixed = 10;
+    let meta_data = "asset";
+    let units = 5;
+    let balance = 100_000;
+    let fee = 300;
+
+    let (creator_pk, creator_sk) = crypto::gen_keypair();
+    let (public_key, _) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_operation_fee(FREEZE_ASSET_ID, fee))
+        .add_wallet_value(&creator_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_freeze = transaction::Builder::new()
+        .keypair(creator_pk, creator_sk)
+        .tx_freeze_asset()
+        .asset_id(asset.id())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_freeze);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_freeze);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&creator_pk).balance, balance - fee);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + fee);
+}
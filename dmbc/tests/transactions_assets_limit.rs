@@ -0,0 +1,136 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::assets::{AssetBundle, AssetInfo};
+use dmbc::currency::configuration::{Configuration, TransactionFees, DEFAULT_MAX_ASSETS};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::wallet::Wallet;
+
+// All tests in this file allow transactions to list `MAX_ASSETS` assets.
+const MAX_ASSETS: u64 = 2;
+
+fn configuration() -> Configuration {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    Configuration::new(config_fees).with_max_assets(MAX_ASSETS)
+}
+
+fn assets(count: usize, name: &str, creator: &PublicKey) -> Vec<(AssetBundle, AssetInfo)> {
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    (0..count)
+        .map(|i| dmbc_testkit::create_asset(&format!("{}{}", name, i), 1, fees.clone(), creator))
+        .collect()
+}
+
+#[test]
+fn transfer_assets_limit() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let assets = assets(MAX_ASSETS as usize + 1, "asset", &public_key);
+
+    let mut builder = DmbcTestApiBuilder::new()
+        .with_configuration(configuration())
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]));
+    for asset in assets.iter().cloned() {
+        builder = builder.add_asset_to_wallet(&public_key, asset);
+    }
+    let mut testkit = builder.create();
+    let api = testkit.api();
+
+    let transfer = |count: usize, seed| {
+        let mut builder = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .recipient(recipient_key)
+            .seed(seed);
+        for &(ref bundle, _) in assets.iter().take(count) {
+            builder = builder.add_asset_value(bundle.clone());
+        }
+        builder.build()
+    };
+
+    let tx_over = transfer(MAX_ASSETS as usize + 1, 1);
+    api.post_tx(&tx_over);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_over);
+    assert_eq!(tx_status, Ok(Err(Error::TooManyAssets)));
+
+    let tx_within = transfer(MAX_ASSETS as usize, 2);
+    api.post_tx(&tx_within);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_within);
+    assert_eq!(tx_status, Ok(Ok(())));
+}
+
+#[test]
+fn exchange_assets_limit() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, recipient_secret) = crypto::gen_keypair();
+    let sender_assets = assets(MAX_ASSETS as usize, "sender_asset", &sender_key);
+    let recipient_assets = assets(1, "recipient_asset", &recipient_key);
+
+    let mut builder = DmbcTestApiBuilder::new()
+        .with_configuration(configuration())
+        .add_wallet_value(&sender_key, Wallet::new(100_000, vec![]))
+        .add_wallet_value(&recipient_key, Wallet::new(100_000, vec![]));
+    for asset in sender_assets.iter().cloned() {
+        builder = builder.add_asset_to_wallet(&sender_key, asset);
+    }
+    for asset in recipient_assets.iter().cloned() {
+        builder = builder.add_asset_to_wallet(&recipient_key, asset);
+    }
+    let mut testkit = builder.create();
+    let api = testkit.api();
+
+    // The limit applies to the assets of both parties together.
+    let exchange = |sender_count: usize, seed| {
+        let mut builder = transaction::Builder::new()
+            .keypair(recipient_key, recipient_secret.clone())
+            .tx_exchange()
+            .sender(sender_key)
+            .sender_secret(sender_secret.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .seed(seed);
+        for &(ref bundle, _) in sender_assets.iter().take(sender_count) {
+            builder = builder.sender_add_asset_value(bundle.clone());
+        }
+        for &(ref bundle, _) in &recipient_assets {
+            builder = builder.recipient_add_asset_value(bundle.clone());
+        }
+        builder.build()
+    };
+
+    let tx_over = exchange(MAX_ASSETS as usize, 1);
+    api.post_tx(&tx_over);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_over);
+    assert_eq!(tx_status, Ok(Err(Error::TooManyAssets)));
+
+    let tx_within = exchange(MAX_ASSETS as usize - 1, 2);
+    api.post_tx(&tx_within);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_within);
+    assert_eq!(tx_status, Ok(Ok(())));
+}
+
+#[test]
+fn max_assets_default() {
+    assert_eq!(Configuration::default().max_assets(), DEFAULT_MAX_ASSETS);
+}
@@ -54,8 +54,8 @@ fn delete_assets_one_from_bundle() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -103,8 +103,8 @@ fn delete_assets_all_from_bundle() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -144,8 +144,8 @@ fn delete_assets_that_doent_exist() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Err(Error::AssetNotFound)));
@@ -188,8 +188,8 @@ fn delete_assets_that_doent_exist2() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Err(Error::AssetNotFound)));
@@ -238,8 +238,8 @@ fn delete_assets_amount_more_than_wallet_have() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
@@ -287,8 +287,8 @@ fn delete_assets_insufficient_funds() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
@@ -336,8 +336,8 @@ fn delete_assets_with_different_creator() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -389,8 +389,8 @@ fn delete_assets_two_assets_where_one_asset_doesnt_have_enough_items() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
@@ -443,8 +443,8 @@ fn delete_assets_two_assets_where_one_have_another_creator() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
     assert_eq!(tx_status, Ok(Ok(())));
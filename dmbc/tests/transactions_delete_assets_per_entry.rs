@@ -0,0 +1,136 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::FeesResponseBody;
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+// All tests in this file charge `PER_ENTRY_FEE` for every deleted unit.
+const PER_ENTRY_FEE: u64 = 7;
+
+fn configuration(transaction_fee: u64) -> Configuration {
+    let config_fees = TransactionFees::with_default_key(0, 0, transaction_fee, 0, 0, 0);
+    Configuration::new(config_fees).with_delete_assets_per_entry(PER_ENTRY_FEE)
+}
+
+#[test]
+fn fees_for_delete_assets_per_entry() {
+    let transaction_fee = 1000;
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(transaction_fee))
+        .create();
+    let api = testkit.api();
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let tx_delete_assets = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_del_assets()
+        .add_asset("asset1", 5)
+        .add_asset("asset2", 3)
+        .seed(85)
+        .build();
+
+    let (status, response) = api.post_fee(&tx_delete_assets);
+
+    let mut expected = HashMap::new();
+    expected.insert(public_key, transaction_fee + PER_ENTRY_FEE * 8);
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+}
+
+#[test]
+fn delete_assets_partial_charges_per_entry() {
+    let meta_data = "asset";
+    let units = 5;
+    let units_to_remove = 2;
+    let transaction_fee = 100;
+    let balance = 100_000;
+    let fixed = 10;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(transaction_fee))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_delete_assets = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_del_assets()
+        .add_asset(meta_data, units_to_remove)
+        .seed(5)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_delete_assets);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let wallet = api.get_wallet(&public_key);
+    let assets: Vec<AssetBundle> = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect();
+    assert_eq!(wallet.balance, balance - transaction_fee - PER_ENTRY_FEE * units_to_remove);
+    assert_eq!(assets, vec![AssetBundle::new(asset.id(), units - units_to_remove)]);
+}
+
+#[test]
+fn delete_assets_more_than_owned_keeps_assets() {
+    let meta_data = "asset";
+    let units = 5;
+    let transaction_fee = 100;
+    let balance = 100_000;
+    let fixed = 10;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(transaction_fee))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_delete_assets = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_del_assets()
+        .add_asset(meta_data, units + 1)
+        .seed(5)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_delete_assets);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_delete_assets);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
+
+    // The blockchain fee is still charged, but the assets are untouched.
+    let wallet = api.get_wallet(&public_key);
+    let assets: Vec<AssetBundle> = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect();
+    assert_eq!(wallet.balance, balance - transaction_fee - PER_ENTRY_FEE * (units + 1));
+    assert_eq!(assets, vec![AssetBundle::new(asset.id(), units)]);
+}
@@ -0,0 +1,187 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+extern crate prometheus;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::messages::Message;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::DryRunResponse;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+/// Sum of the counters and histogram samples of the transaction `name`.
+fn transaction_metrics(name: &str) -> u64 {
+    let prefix = format!("dmbc_transaction_{}_", name);
+    prometheus::gather()
+        .iter()
+        .filter(|family| family.get_name().starts_with(&prefix))
+        .flat_map(|family| family.get_metric())
+        .map(|metric| {
+            metric.get_counter().get_value() as u64 + metric.get_histogram().get_sample_count()
+        })
+        .sum()
+}
+
+#[test]
+fn dry_run_transfer_predicts_balances() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 2500;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_key = dmbc_testkit::default_genesis_key();
+    let genesis_balance = api.get_wallet(&genesis_key).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, response): (StatusCode, DryRunResponse) =
+        api.post_with_status("/v1/transactions/dry_run", &tx_transfer);
+    assert_eq!(status, StatusCode::Ok);
+
+    let prediction = response.unwrap().unwrap();
+    assert_eq!(prediction.tx_hash, tx_transfer.hash());
+    assert_eq!(prediction.status, Ok(()));
+    assert_eq!(
+        prediction.wallets[&public_key].balance(),
+        balance - amount - transaction_fee
+    );
+    assert_eq!(prediction.wallets[&recipient_key].balance(), amount);
+    assert_eq!(
+        prediction.wallets[&genesis_key].balance(),
+        genesis_balance + transaction_fee
+    );
+
+    // Nothing is submitted or changed.
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+    assert_eq!(api.get_wallet(&genesis_key).balance, genesis_balance);
+}
+
+#[test]
+fn dry_run_insufficient_funds_reports_failure() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_key = dmbc_testkit::default_genesis_key();
+    let genesis_balance = api.get_wallet(&genesis_key).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(balance)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, response): (StatusCode, DryRunResponse) =
+        api.post_with_status("/v1/transactions/dry_run", &tx_transfer);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.unwrap().unwrap().status, Err(Error::InsufficientFunds));
+
+    // The real state is untouched, even after the next block.
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+    assert_eq!(api.get_wallet(&genesis_key).balance, genesis_balance);
+}
+
+#[test]
+fn dry_run_unverified_transaction() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    // Transfers to oneself never pass verification.
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(public_key)
+        .seed(1)
+        .build();
+
+    let (status, response): (StatusCode, DryRunResponse) =
+        api.post_with_status("/v1/transactions/dry_run", &tx_transfer);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+}
+
+#[test]
+fn dry_run_leaves_metrics_alone() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let metrics = transaction_metrics("transfer");
+
+    let (status, response): (StatusCode, DryRunResponse) =
+        api.post_with_status("/v1/transactions/dry_run", &tx_transfer);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.unwrap().unwrap().status, Ok(()));
+    assert_eq!(transaction_metrics("transfer"), metrics);
+
+    // Only the real execution is counted.
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    assert_eq!(api.get_tx_status(&tx_transfer).1, Ok(Ok(())));
+    assert!(transaction_metrics("transfer") > metrics);
+}
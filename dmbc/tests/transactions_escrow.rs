@@ -0,0 +1,459 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+use exonum::messages::Message;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::{ESCROW_REFUND_ID, ESCROW_RELEASE_ID};
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn escrow_release() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 30_000;
+    let units = 5;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_create = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_escrow_create()
+        .recipient(recipient_key)
+        .amount(amount)
+        .add_asset_value(asset.clone())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_create);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_create);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // The funds are locked, neither party holds them.
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - transaction_fee - amount);
+    assert!(api.get_wallet_assets(&public_key).is_empty());
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+    assert!(api.get_wallet_assets(&recipient_key).is_empty());
+
+    let tx_release = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_escrow_release()
+        .escrow(tx_create.hash())
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_release);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_release);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    let recipient_assets = api.get_wallet_assets(&recipient_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(recipient_wallet.balance, amount);
+    assert_eq!(recipient_assets, vec![asset]);
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - transaction_fee - amount);
+}
+
+#[test]
+fn escrow_refund() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 30_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, recipient_secret) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_create = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_escrow_create()
+        .recipient(recipient_key)
+        .amount(amount)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_create);
+    testkit.create_block();
+
+    // Only the recipient may refund.
+    let (other_key, other_secret) = crypto::gen_keypair();
+    let tx_refund_other = transaction::Builder::new()
+        .keypair(other_key, other_secret)
+        .tx_escrow_refund()
+        .escrow(tx_create.hash())
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_refund_other);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_refund_other);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidTransaction)));
+
+    let tx_refund = transaction::Builder::new()
+        .keypair(recipient_key, recipient_secret)
+        .tx_escrow_refund()
+        .escrow(tx_create.hash())
+        .seed(3)
+        .build();
+
+    api.post_tx(&tx_refund);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_refund);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - transaction_fee);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+}
+
+#[test]
+fn escrow_double_release() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 30_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_create = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_escrow_create()
+        .recipient(recipient_key)
+        .amount(amount)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_create);
+    testkit.create_block();
+
+    let tx_release = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_escrow_release()
+        .escrow(tx_create.hash())
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_release);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_release);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let tx_release_again = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_escrow_release()
+        .escrow(tx_create.hash())
+        .seed(3)
+        .build();
+
+    api.post_tx(&tx_release_again);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_release_again);
+    assert_eq!(tx_status, Ok(Err(Error::EscrowNotFound)));
+
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+    assert_eq!(api.get_wallet(&public_key).balance, balance - transaction_fee - amount);
+}
+
+#[test]
+fn escrow_locked_funds_not_spendable() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 60_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_create = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_escrow_create()
+        .recipient(recipient_key)
+        .amount(amount)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_create);
+    testkit.create_block();
+
+    // The remaining balance no longer covers the same amount again.
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .amount(amount)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+    assert_eq!(
+        api.get_wallet(&public_key).balance,
+        balance - amount - 2 * transaction_fee
+    );
+}
+
+#[test]
+fn escrow_release_and_refund_charge_operation_fee() {
+    let release_fee = 100;
+    let refund_fee = 50;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let configuration = Configuration::new(config_fees)
+        .with_operation_fee(ESCROW_RELEASE_ID, release_fee)
+        .with_operation_fee(ESCROW_REFUND_ID, refund_fee);
+    let balance = 100_000;
+    let amount = 30_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, recipient_secret) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let escrow = |seed| {
+        transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_escrow_create()
+            .recipient(recipient_key)
+            .amount(amount)
+            .seed(seed)
+            .build()
+    };
+    let tx_released = escrow(1);
+    let tx_refunded = escrow(2);
+
+    api.post_tx(&tx_released);
+    api.post_tx(&tx_refunded);
+    testkit.create_block();
+
+    let tx_release = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_escrow_release()
+        .escrow(tx_released.hash())
+        .seed(3)
+        .build();
+    let tx_refund = transaction::Builder::new()
+        .keypair(recipient_key, recipient_secret)
+        .tx_escrow_refund()
+        .escrow(tx_refunded.hash())
+        .seed(4)
+        .build();
+
+    api.post_tx(&tx_release);
+    api.post_tx(&tx_refund);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_release);
+    assert_eq!(tx_status, Ok(Ok(())));
+    let (_, tx_status) = api.get_tx_status(&tx_refund);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // The sender got the refunded escrow back and paid for the release.
+    assert_eq!(api.get_wallet(&public_key).balance, balance - amount - release_fee);
+    assert_eq!(api.get_wallet(&recipient_key).balance, balance + amount - refund_fee);
+}
+
+#[test]
+fn escrow_create_rejects_empty_and_invalid_assets() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let a = AssetBundle::from_data("a", 3, &public_key);
+    let b = AssetBundle::from_data("b", 3, &public_key);
+
+    let escrow = |amount: u64, assets: Vec<AssetBundle>| {
+        let mut builder = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_escrow_create()
+            .recipient(recipient_key)
+            .amount(amount)
+            .seed(1);
+        for bundle in assets {
+            builder = builder.add_asset_value(bundle);
+        }
+        builder.build()
+    };
+
+    assert!(escrow(10, vec![]).verify());
+    assert!(escrow(0, vec![a.clone(), b.clone()]).verify());
+    assert!(!escrow(0, vec![]).verify());
+    assert!(!escrow(10, vec![a.clone(), AssetBundle::new(b.id(), 0)]).verify());
+    assert!(!escrow(10, vec![a.clone(), b, a]).verify());
+}
+
+#[test]
+fn escrow_release_frozen_asset() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let units = 5;
+
+    let (creator_pk, creator_sk) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, recipient_secret) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_create = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_escrow_create()
+        .recipient(recipient_key)
+        .add_asset_value(asset.clone())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_create);
+    testkit.create_block();
+
+    let tx_freeze = transaction::Builder::new()
+        .keypair(creator_pk, creator_sk)
+        .tx_freeze_asset()
+        .asset_id(asset.id())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_freeze);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_freeze);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // Neither release nor refund may deliver the frozen asset.
+    let tx_release = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_escrow_release()
+        .escrow(tx_create.hash())
+        .seed(2)
+        .build();
+    let tx_refund = transaction::Builder::new()
+        .keypair(recipient_key, recipient_secret)
+        .tx_escrow_refund()
+        .escrow(tx_create.hash())
+        .seed(3)
+        .build();
+
+    api.post_tx(&tx_release);
+    api.post_tx(&tx_refund);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_release);
+    assert_eq!(tx_status, Ok(Err(Error::AssetFrozen)));
+    let (_, tx_status) = api.get_tx_status(&tx_refund);
+    assert_eq!(tx_status, Ok(Err(Error::AssetFrozen)));
+
+    assert!(api.get_wallet_assets(&public_key).is_empty());
+    assert!(api.get_wallet_assets(&recipient_key).is_empty());
+}
+
+#[test]
+fn escrow_release_asset_overflow() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let units = 5;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &public_key);
+    let held = AssetBundle::new(asset.id(), u64::max_value());
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]))
+        .add_wallet_value(&recipient_key, Wallet::new(0, vec![held.clone()]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_create = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_escrow_create()
+        .recipient(recipient_key)
+        .add_asset_value(asset.clone())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_create);
+    testkit.create_block();
+
+    let tx_release = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_escrow_release()
+        .escrow(tx_create.hash())
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_release);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_release);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    let recipient_assets = api.get_wallet_assets(&recipient_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(recipient_assets, vec![held]);
+}
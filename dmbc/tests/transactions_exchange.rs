@@ -87,8 +87,8 @@ fn exchange_assets_fee_from_recipient() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -202,8 +202,8 @@ fn exchange_assets_fee_from_sender() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -317,8 +317,8 @@ fn exchange_assets_fee_from_recipient_and_sender() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -513,8 +513,8 @@ fn exchange_assets_insufficient_funds() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
@@ -597,8 +597,8 @@ fn exchange_assets_assets_not_found() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Err(Error::AssetNotFound)));
@@ -683,8 +683,8 @@ fn exchange_assets_insufficient_assets() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
@@ -703,4 +703,175 @@ fn exchange_assets_insufficient_assets() {
     assert_eq!(recipient_wallet.balance, expected_balance);
     assert_eq!(genesis_wallet.balance, expected_genesis_balance);
     assert_eq!(creator_wallet.balance, expected_creator_balance);
-}
\ No newline at end of file
+}
+#[test]
+fn exchange_assets_listed_on_both_sides() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 10;
+    let others_balance = 100_000;
+    let units = 10;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(others_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1.clone()))
+        .add_asset_to_wallet(&recipient_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+
+    // Both parties list asset1, it would only be swapped for itself.
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk.clone())
+        .tx_exchange()
+        .sender(sender_pk)
+        .sender_secret(sender_sk.clone())
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), 1))
+        .recipient_add_asset_value(AssetBundle::new(asset1.id(), 1))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), 1))
+        .build();
+
+    let (status, response) = api.post_tx(&tx_exchange_assets);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange()
+        .sender(sender_pk)
+        .sender_secret(sender_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), 1))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), 1))
+        .build();
+
+    let tx_hash = tx_exchange_assets.hash();
+
+    let (status, response) = api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+}
+
+#[test]
+fn exchange_assets_offer_expired() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let units = 10;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+
+    testkit.create_block();
+    testkit.create_block();
+
+    let offer = |valid_until_height| {
+        transaction::Builder::new()
+            .keypair(recipient_pk, recipient_sk.clone())
+            .tx_exchange()
+            .sender(sender_pk)
+            .sender_secret(sender_sk.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .sender_add_asset_value(AssetBundle::new(asset1.id(), 1))
+            .recipient_add_asset_value(AssetBundle::new(asset2.id(), 1))
+            .valid_until_height(valid_until_height)
+            .build()
+    };
+
+    let tx_expired = offer(2);
+    let (status, _) = api.post_tx(&tx_expired);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_expired);
+    assert_eq!(tx_status, Ok(Err(Error::OfferExpired)));
+
+    let tx_valid = offer(10);
+    let (status, _) = api.post_tx(&tx_valid);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_valid);
+    assert_eq!(tx_status, Ok(Ok(())));
+}
+
+#[test]
+fn exchange_third_party_fees_overflow() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let fixed = u64::max_value() / 2;
+
+    let (sender_public_key, sender_secret_key) = crypto::gen_keypair();
+    let (recipient_public_key, recipient_secret_key) = crypto::gen_keypair();
+    let (creator_public_key, _) = crypto::gen_keypair();
+
+    let (recipient_asset, recipient_info) = dmbc_testkit::create_asset("recipient_asset", 1, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &recipient_public_key);
+
+    let mut builder = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&recipient_public_key, (recipient_asset.clone(), recipient_info));
+    let mut assets = Vec::new();
+    for meta_data in &["asset1", "asset2", "asset3"] {
+        // Each asset alone has a payable fee, the creator's total overflows.
+        let (asset, info) = dmbc_testkit::create_asset(meta_data, 1, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_public_key);
+        builder = builder.add_asset_to_wallet(&sender_public_key, (asset.clone(), info));
+        assets.push(asset);
+    }
+    let mut testkit = builder.create();
+    let api = testkit.api();
+
+    let tx_exchange = assets.iter()
+        .fold(
+            transaction::Builder::new()
+                .keypair(recipient_public_key, recipient_secret_key)
+                .tx_exchange(),
+            |tx, asset| tx.sender_add_asset_value(asset.clone())
+        )
+        .sender(sender_public_key)
+        .sender_secret(sender_secret_key)
+        .recipient_add_asset_value(recipient_asset)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_exchange);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    assert_eq!(api.get_wallet(&sender_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&recipient_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&sender_public_key).assets_count, assets.len() as u64);
+    assert_eq!(api.get_wallet(&recipient_public_key).assets_count, 1);
+}
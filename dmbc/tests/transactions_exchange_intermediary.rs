@@ -10,6 +10,7 @@ extern crate mount;
 pub mod dmbc_testkit;
 
 use hyper::status::StatusCode;
+use exonum::blockchain::Transaction;
 use exonum::messages::Message;
 use exonum::crypto;
 use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
@@ -20,7 +21,9 @@ use dmbc::currency::assets::AssetBundle;
 use dmbc::currency::error::Error;
 use dmbc::currency::api::transaction::TransactionResponse;
 use dmbc::currency::wallet::Wallet;
-use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::transactions::components::{FeeStrategy, Intermediary};
+use dmbc::currency::transactions::{ExchangeIntermediary, ExchangeOfferIntermediary};
+use dmbc::currency::status;
 
 #[test]
 fn exchange_intermediary_assets() {
@@ -90,8 +93,8 @@ fn exchange_intermediary_assets() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -193,8 +196,8 @@ fn exchange_intermediary_assets_creator_is_sender() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -285,8 +288,8 @@ fn exchange_intermediary_assets_creator_is_intermediary() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -377,8 +380,8 @@ fn exchange_intermediary_assets_payer_fee_intermediary() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -464,8 +467,8 @@ fn exchange_intermediary_assets_asset_not_found() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Err(Error::AssetNotFound)));
@@ -536,8 +539,8 @@ fn exchange_intermediary_assets_insufficient_assets() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
@@ -562,7 +565,7 @@ fn exchange_intermediary_assets_insufficient_funds() {
     let transaction_fee = 1000;
     let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
     let fixed = 10;
-    let intermediary_balance = 0;
+    let intermediary_balance = 1;
     let others_balance = 100_000;
     let receiver_units = 10;
     let senders_units = 8;
@@ -608,8 +611,8 @@ fn exchange_intermediary_assets_insufficient_funds() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
@@ -628,3 +631,527 @@ fn exchange_intermediary_assets_insufficient_funds() {
     assert_eq!(intermediary_wallet.balance, expected_intermediary_balance);
     assert_eq!(genesis_wallet.balance, expected_genesis_balance);
 }
+
+#[test]
+fn exchange_intermediary_sender_affords_fee_but_not_total() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 10;
+    let units = 5;
+    let sender_value = 500;
+    let intermediary_commision = 100;
+    let sender_balance = transaction_fee + sender_value;
+    let others_balance = 100_000;
+    let meta_data1 = "asset1";
+    let meta_data2 = "asset2";
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (intermediary_pk, intermediary_sk) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset(meta_data1, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset(meta_data2, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(sender_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .intermediary_key_pair(intermediary_pk, intermediary_sk)
+        .commission(intermediary_commision)
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::Sender)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), units))
+        .sender_value(sender_value)
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), units))
+        .build();
+
+    let tx_hash = tx_exchange_assets.hash();
+
+    let (status, response) = api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+
+    // check post response
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let sender_wallet = api.get_wallet(&sender_pk);
+    let recipient_wallet = api.get_wallet(&recipient_pk);
+    let intermediary_wallet = api.get_wallet(&intermediary_pk);
+    let creator_wallet = api.get_wallet(&creator_pk);
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+
+    assert_eq!(sender_wallet.balance, sender_balance);
+    assert_eq!(recipient_wallet.balance, others_balance);
+    assert_eq!(intermediary_wallet.balance, 0);
+    assert_eq!(creator_wallet.balance, 0);
+    assert_eq!(genesis_wallet.balance, genesis_balance);
+
+    let sender_assets = api.get_wallet_assets(&sender_pk).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    let recipient_assets = api.get_wallet_assets(&recipient_pk).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_assets, vec![AssetBundle::new(asset1.id(), units)]);
+    assert_eq!(recipient_assets, vec![AssetBundle::new(asset2.id(), units)]);
+}
+
+#[test]
+fn exchange_intermediary_odd_commission_conserves_coins() {
+    let transaction_fee = 101;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 0;
+    let units = 5;
+    let others_balance = 100_000;
+    let intermediary_commision = 51;
+    let meta_data1 = "asset1";
+    let meta_data2 = "asset2";
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (intermediary_pk, intermediary_sk) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset(meta_data1, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset(meta_data2, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(others_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+    let total_before = genesis_balance + others_balance * 2;
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .intermediary_key_pair(intermediary_pk, intermediary_sk)
+        .commission(intermediary_commision)
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::RecipientAndSender)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), units))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), units))
+        .build();
+
+    api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let sender_wallet = api.get_wallet(&sender_pk);
+    let recipient_wallet = api.get_wallet(&recipient_pk);
+    let intermediary_wallet = api.get_wallet(&intermediary_pk);
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+
+    assert_eq!(intermediary_wallet.balance, intermediary_commision);
+    assert_eq!(genesis_wallet.balance, genesis_balance + transaction_fee);
+    assert_eq!(sender_wallet.balance, others_balance - 51 - 26);
+    assert_eq!(recipient_wallet.balance, others_balance - 50 - 25);
+
+    let total_after = sender_wallet.balance
+        + recipient_wallet.balance
+        + intermediary_wallet.balance
+        + genesis_wallet.balance;
+    assert_eq!(total_after, total_before);
+}
+
+#[test]
+fn exchange_intermediary_each_payer_checked_against_own_balance() {
+    let transaction_fee = 101;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 0;
+    let units = 5;
+    let sender_half = 51;
+    let recipient_half = 50;
+    let meta_data1 = "asset1";
+    let meta_data2 = "asset2";
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (intermediary_pk, intermediary_sk) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset(meta_data1, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset(meta_data2, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(sender_half, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(recipient_half, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .intermediary_key_pair(intermediary_pk, intermediary_sk)
+        .commission(0)
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::RecipientAndSender)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), units))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), units))
+        .build();
+
+    api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let sender_wallet = api.get_wallet(&sender_pk);
+    let recipient_wallet = api.get_wallet(&recipient_pk);
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+
+    assert_eq!(sender_wallet.balance, 0);
+    assert_eq!(recipient_wallet.balance, 0);
+    assert_eq!(genesis_wallet.balance, genesis_balance + transaction_fee);
+}
+
+#[test]
+fn exchange_intermediary_one_payer_has_its_half_other_has_nothing() {
+    let transaction_fee = 101;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 0;
+    let units = 5;
+    let recipient_half = 50;
+    let meta_data1 = "asset1";
+    let meta_data2 = "asset2";
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (intermediary_pk, intermediary_sk) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset(meta_data1, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset(meta_data2, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&recipient_pk, Wallet::new(recipient_half, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .intermediary_key_pair(intermediary_pk, intermediary_sk)
+        .commission(0)
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::RecipientAndSender)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), units))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), units))
+        .build();
+
+    api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let sender_wallet = api.get_wallet(&sender_pk);
+    let recipient_wallet = api.get_wallet(&recipient_pk);
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+
+    assert_eq!(sender_wallet.balance, 0);
+    assert_eq!(recipient_wallet.balance, recipient_half);
+    assert_eq!(genesis_wallet.balance, genesis_balance);
+}
+
+#[test]
+fn exchange_intermediary_missing_intermediary_wallet() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 10;
+    let others_balance = 100_000;
+    let units = 5;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (intermediary_pk, intermediary_sk) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &sender_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &recipient_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(others_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .intermediary_key_pair(intermediary_pk, intermediary_sk)
+        .commission(100)
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::Intermediary)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), units))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), units))
+        .build();
+
+    api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Err(Error::WalletNotFound)));
+
+    assert_eq!(api.get_wallet(&sender_pk).balance, others_balance);
+    assert_eq!(api.get_wallet(&recipient_pk).balance, others_balance);
+    assert_eq!(api.get_wallet(&intermediary_pk).balance, 0);
+    assert_eq!(api.get_wallet(&dmbc_testkit::default_genesis_key()).balance, genesis_balance);
+
+    let sender_assets = api.get_wallet_assets(&sender_pk).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_assets, vec![asset1]);
+}
+
+#[test]
+fn exchange_intermediary_without_intermediary() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 10;
+    let others_balance = 100_000;
+    let units = 5;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &sender_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &recipient_pk);
+
+    // The null intermediary needs no registration.
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_intermediary_registry(true))
+        .add_wallet_value(&sender_pk, Wallet::new(others_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .without_intermediary()
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(AssetBundle::new(asset1.id(), units))
+        .recipient_add_asset_value(AssetBundle::new(asset2.id(), units))
+        .build();
+    assert!(tx_exchange_assets.offer().intermediary().is_none());
+
+    let (status, _) = api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // No commission, the recipient pays the fee on the asset of the sender
+    // to its creator.
+    let asset_fee = units * fixed;
+    assert_eq!(api.get_wallet(&sender_pk).balance, others_balance + asset_fee);
+    assert_eq!(
+        api.get_wallet(&recipient_pk).balance,
+        others_balance - transaction_fee - asset_fee
+    );
+    assert_eq!(api.get_wallet(&Intermediary::null_wallet()).balance, 0);
+    assert_eq!(
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance,
+        genesis_balance + transaction_fee
+    );
+
+    let sender_assets = api.get_wallet_assets(&sender_pk).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    let recipient_assets = api.get_wallet_assets(&recipient_pk).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_assets, vec![asset2]);
+    assert_eq!(recipient_assets, vec![asset1]);
+}
+
+#[test]
+fn exchange_intermediary_without_intermediary_fee_from_intermediary() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let others_balance = 100_000;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(others_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    // The null intermediary can't be the one paying the fees.
+    let tx_exchange = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .without_intermediary()
+        .sender_key_pair(sender_pk, sender_sk)
+        .sender_value(10)
+        .fee_strategy(FeeStrategy::Intermediary)
+        .build();
+
+    let (status, response) = api.post_tx(&tx_exchange);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+}
+
+#[test]
+fn exchange_intermediary_unknown_fee_strategy() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let others_balance = 100_000;
+
+    let (sender_pk, _) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(others_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .create();
+
+    // Such an offer never passes verification, execute it directly.
+    let offer = ExchangeOfferIntermediary::new(
+        Intermediary::none(),
+        &sender_pk,
+        vec![],
+        10,
+        &recipient_pk,
+        vec![],
+        99,
+        1,
+        "",
+        0,
+    );
+    let tx_exchange = ExchangeIntermediary::new(
+        offer,
+        &Intermediary::null_signature(),
+        &Intermediary::null_signature(),
+        &recipient_sk,
+    );
+    assert!(!tx_exchange.verify());
+
+    let mut fork = testkit.blockchain_mut().fork();
+    tx_exchange.execute(&mut fork);
+
+    assert_eq!(
+        status::Schema(&fork).fetch(&tx_exchange.hash()),
+        Some(Err(Error::InvalidFeeStrategy))
+    );
+}
+
+#[test]
+fn exchange_intermediary_assets_listed_on_both_sides() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let fixed = 10;
+    let others_balance = 100_000;
+    let units = 10;
+
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+    let (creator_pk, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_pk);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(others_balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(others_balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset.clone(), info.clone()))
+        .add_asset_to_wallet(&recipient_pk, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_exchange = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .without_intermediary()
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(AssetBundle::new(asset.id(), 1))
+        .recipient_add_asset_value(AssetBundle::new(asset.id(), 2))
+        .build();
+
+    let (status, response) = api.post_tx(&tx_exchange);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+}
+
+#[test]
+fn exchange_intermediary_is_fee_recipient() {
+    let transaction_fee = 1000;
+    let balance = 100_000;
+    let units = 5;
+    let intermediary_commission = 100;
+
+    let (platform_pk, platform_sk) = crypto::gen_keypair();
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::new(&platform_pk, 0, 0, 0, transaction_fee, 0, 0);
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &sender_pk);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &recipient_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_pk, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&sender_pk, (asset1.clone(), info1))
+        .add_asset_to_wallet(&recipient_pk, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+
+    let tx_exchange_assets = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange_with_intermediary()
+        .intermediary_key_pair(platform_pk, platform_sk)
+        .commission(intermediary_commission)
+        .sender_key_pair(sender_pk, sender_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(asset1.clone())
+        .recipient_add_asset_value(asset2.clone())
+        .build();
+
+    let (status, _) = api.post_tx(&tx_exchange_assets);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange_assets);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidTransaction)));
+
+    let sender_assets = api.get_wallet_assets(&sender_pk).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    let recipient_assets = api.get_wallet_assets(&recipient_pk).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_assets, vec![asset1]);
+    assert_eq!(recipient_assets, vec![asset2]);
+    assert_eq!(api.get_wallet(&sender_pk).balance, balance);
+    assert_eq!(api.get_wallet(&recipient_pk).balance, balance);
+    assert_eq!(api.get_wallet(&platform_pk).balance, 0);
+}
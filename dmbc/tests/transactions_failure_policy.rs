@@ -0,0 +1,273 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, FailurePolicy, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::wallet::Wallet;
+
+const TRANSACTION_FEE: u64 = 1000;
+
+/// Balances of the sender and the genesis wallet after a transfer of more
+/// than the sender holds, which fails after the fee is collected.
+fn failed_transfer(policy: FailurePolicy) -> (u64, u64, u64) {
+    let balance = 5000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_failure_policy(policy))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(balance)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+
+    (
+        balance,
+        api.get_wallet(&public_key).balance,
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance - genesis_balance,
+    )
+}
+
+#[test]
+fn failure_policy_default_keeps_fee() {
+    assert_eq!(Configuration::default().failure_policy(), FailurePolicy::KeepFee);
+}
+
+#[test]
+fn failure_policy_transfer_keep_fee() {
+    let (balance, left, collected) = failed_transfer(FailurePolicy::KeepFee);
+
+    assert_eq!(left, balance - TRANSACTION_FEE);
+    assert_eq!(collected, TRANSACTION_FEE);
+}
+
+#[test]
+fn failure_policy_transfer_full_rollback() {
+    let (balance, left, collected) = failed_transfer(FailurePolicy::FullRollback);
+
+    assert_eq!(left, balance);
+    assert_eq!(collected, 0);
+}
+
+/// Balances of the recipient and the genesis wallet after an exchange in
+/// which the sender lacks the offered asset, which fails after the fee is
+/// collected.
+fn failed_exchange(policy: FailurePolicy) -> (u64, u64, u64) {
+    let balance = 100_000;
+    let units = 2;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, TRANSACTION_FEE, 0, 0);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (sender_public_key, sender_secret_key) = crypto::gen_keypair();
+    let (recipient_public_key, recipient_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset0, _) = dmbc_testkit::create_asset("asset0", units, fees.clone(), &creator_key);
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_failure_policy(policy))
+        .add_wallet_value(&recipient_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&recipient_public_key, (asset1.clone(), info1))
+        .create();
+    let api = testkit.api();
+
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange = transaction::Builder::new()
+        .keypair(recipient_public_key, recipient_secret_key)
+        .tx_exchange()
+        .sender(sender_public_key)
+        .sender_secret(sender_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_add_asset_value(asset0)
+        .recipient_add_asset_value(asset1)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_exchange);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
+    assert_eq!(api.get_wallet(&recipient_public_key).assets_count, 1);
+
+    (
+        balance,
+        api.get_wallet(&recipient_public_key).balance,
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance - genesis_balance,
+    )
+}
+
+#[test]
+fn failure_policy_exchange_keep_fee() {
+    let (balance, left, collected) = failed_exchange(FailurePolicy::KeepFee);
+
+    assert_eq!(left, balance - TRANSACTION_FEE);
+    assert_eq!(collected, TRANSACTION_FEE);
+}
+
+#[test]
+fn failure_policy_exchange_full_rollback() {
+    let (balance, left, collected) = failed_exchange(FailurePolicy::FullRollback);
+
+    assert_eq!(left, balance);
+    assert_eq!(collected, 0);
+}
+
+/// Balances of the sender, the recipient and the intermediary, and the fee
+/// collected by the genesis wallet, after an exchange through an
+/// intermediary in which the sender lacks the offered asset.
+fn failed_exchange_intermediary(fee_strategy: FeeStrategy) -> (u64, [u64; 3], u64) {
+    let balance = 100_000;
+    let units = 2;
+    let commission = 500;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, TRANSACTION_FEE, 0, 0);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (sender_public_key, sender_secret_key) = crypto::gen_keypair();
+    let (recipient_public_key, recipient_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset0, _) = dmbc_testkit::create_asset("asset0", units, fees.clone(), &creator_key);
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&intermediary_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&recipient_public_key, (asset1.clone(), info1))
+        .create();
+    let api = testkit.api();
+
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_exchange = transaction::Builder::new()
+        .keypair(recipient_public_key, recipient_secret_key)
+        .tx_exchange_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(commission)
+        .sender_key_pair(sender_public_key, sender_secret_key)
+        .fee_strategy(fee_strategy)
+        .sender_add_asset_value(asset0)
+        .recipient_add_asset_value(asset1)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_exchange);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_exchange);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
+    assert_eq!(api.get_wallet(&recipient_public_key).assets_count, 1);
+
+    (
+        balance,
+        [
+            api.get_wallet(&sender_public_key).balance,
+            api.get_wallet(&recipient_public_key).balance,
+            api.get_wallet(&intermediary_public_key).balance,
+        ],
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance - genesis_balance,
+    )
+}
+
+#[test]
+fn failure_policy_exchange_intermediary_refunds_fee() {
+    let outcomes = vec![
+        failed_exchange_intermediary(FeeStrategy::Recipient),
+        failed_exchange_intermediary(FeeStrategy::Sender),
+        failed_exchange_intermediary(FeeStrategy::RecipientAndSender),
+        failed_exchange_intermediary(FeeStrategy::Intermediary),
+    ];
+
+    for (balance, left, collected) in outcomes {
+        assert_eq!(left, [balance, balance, balance]);
+        assert_eq!(collected, 0);
+    }
+}
+
+#[test]
+fn failure_policy_full_rollback_keeps_seed_used() {
+    let balance = 5000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+    let configuration = Configuration::new(config_fees)
+        .with_failure_policy(FailurePolicy::FullRollback)
+        .with_seed_nonces(true);
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let transfer = |amount, seed| {
+        transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .amount(amount)
+            .recipient(recipient_key)
+            .seed(seed)
+            .build()
+    };
+
+    let tx_failed = transfer(balance, 1);
+    api.post_tx(&tx_failed);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_failed);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+
+    // The fee was rolled back, the seed was not.
+    let tx_same_seed = transfer(100, 1);
+    api.post_tx(&tx_same_seed);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_same_seed);
+    assert_eq!(tx_status, Ok(Err(Error::StaleSeed)));
+
+    let tx_next_seed = transfer(100, 2);
+    api.post_tx(&tx_next_seed);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_next_seed);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&recipient_key).balance, 100);
+}
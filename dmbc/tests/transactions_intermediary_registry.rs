@@ -0,0 +1,110 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::assets::TradeAsset;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::wallet::Wallet;
+
+/// Status of a trade through an intermediary with the registry enabled,
+/// after the platform registered the intermediary or not.
+fn trade_through_intermediary(registered: bool) -> Result<(), Error> {
+    let balance = 100_000;
+    let price = 500;
+
+    let (platform_pk, platform_sk) = crypto::gen_keypair();
+    let (seller_pk, seller_sk) = crypto::gen_keypair();
+    let (buyer_pk, buyer_sk) = crypto::gen_keypair();
+    let (intermediary_pk, intermediary_sk) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::new(&platform_pk, 0, 0, 0, 0, 0, 0);
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", 1, fees, &seller_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_intermediary_registry(true))
+        .add_wallet_value(&buyer_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_pk, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    if registered {
+        let tx_register = transaction::Builder::new()
+            .keypair(platform_pk, platform_sk)
+            .tx_set_intermediary()
+            .intermediary(intermediary_pk)
+            .seed(1)
+            .build();
+
+        api.post_tx(&tx_register);
+        testkit.create_block();
+
+        let (_, tx_status) = api.get_tx_status(&tx_register);
+        assert_eq!(tx_status, Ok(Ok(())));
+    }
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_pk, buyer_sk)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_pk, intermediary_sk)
+        .commission(0)
+        .add_asset_value(TradeAsset::from_bundle(asset, price))
+        .seller(seller_pk, seller_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let expected_assets = if registered { 1 } else { 0 };
+    assert_eq!(api.get_wallet(&buyer_pk).assets_count, expected_assets);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    tx_status.unwrap()
+}
+
+#[test]
+fn intermediary_registry_rejects_unregistered() {
+    assert_eq!(trade_through_intermediary(false), Err(Error::IntermediaryNotRegistered));
+}
+
+#[test]
+fn intermediary_registry_accepts_registered() {
+    assert_eq!(trade_through_intermediary(true), Ok(()));
+}
+
+#[test]
+fn intermediary_registry_requires_platform_wallet() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (intermediary_pk, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let tx_register = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_set_intermediary()
+        .intermediary(intermediary_pk)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_register);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_register);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidTransaction)));
+}
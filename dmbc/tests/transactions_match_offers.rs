@@ -0,0 +1,209 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::configuration::{Configuration, FailurePolicy, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::{offer_signing_bytes, FeeStrategy};
+use dmbc::currency::transactions::{MatchOffer, MatchOffers, MATCH_OFFERS_ID};
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn match_offers() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let balance = 100_000;
+    let price = 5000;
+    let units = 3;
+
+    let (maker_pk, maker_sk) = crypto::gen_keypair();
+    let (taker_pk, taker_sk) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &maker_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&maker_pk, Wallet::new(balance, vec![]))
+        .add_wallet_value(&taker_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&maker_pk, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_match = transaction::Builder::new()
+        .keypair(taker_pk, taker_sk)
+        .tx_match_offers()
+        .maker_key_pair(maker_pk, maker_sk)
+        .maker_add_asset_value(asset)
+        .taker_value(price)
+        .fee_strategy(FeeStrategy::RecipientAndSender)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_match);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_match);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let maker = api.get_wallet(&maker_pk);
+    assert_eq!(maker.balance, balance + price - transaction_fee / 2);
+    assert_eq!(maker.assets_count, 0);
+
+    let taker = api.get_wallet(&taker_pk);
+    assert_eq!(taker.balance, balance - price - transaction_fee / 2);
+    assert_eq!(taker.assets_count, 1);
+
+    let genesis = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis.balance, genesis_balance + transaction_fee);
+}
+
+#[test]
+fn match_offers_mismatch() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let balance = 100_000;
+    let units = 3;
+
+    let (maker_pk, maker_sk) = crypto::gen_keypair();
+    let (taker_pk, taker_sk) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (maker_asset, maker_info) =
+        dmbc_testkit::create_asset("foo", units, fees.clone(), &maker_pk);
+    let (taker_asset, taker_info) =
+        dmbc_testkit::create_asset("bar", units, fees.clone(), &taker_pk);
+    let (other_asset, _) = dmbc_testkit::create_asset("baz", units, fees, &taker_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&maker_pk, Wallet::new(balance, vec![]))
+        .add_wallet_value(&taker_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&maker_pk, (maker_asset.clone(), maker_info))
+        .add_asset_to_wallet(&taker_pk, (taker_asset.clone(), taker_info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    // The maker wants an asset the taker doesn't give.
+    let fee_strategy = FeeStrategy::Recipient as u8;
+    let maker = MatchOffer::new(
+        &maker_pk,
+        vec![maker_asset.clone()],
+        0,
+        vec![other_asset],
+        0,
+        fee_strategy,
+        1,
+    );
+    let taker = MatchOffer::new(
+        &taker_pk,
+        vec![taker_asset],
+        0,
+        vec![maker_asset],
+        0,
+        fee_strategy,
+        1,
+    );
+    let signing_bytes = offer_signing_bytes(MATCH_OFFERS_ID, &maker.clone().into_bytes());
+    let maker_signature = crypto::sign(&signing_bytes, &maker_sk);
+    let tx_match = MatchOffers::new(maker, &maker_signature, taker, &taker_sk);
+
+    api.post_tx(&tx_match);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_match);
+    assert_eq!(tx_status, Ok(Err(Error::OffersMismatch)));
+
+    let maker = api.get_wallet(&maker_pk);
+    assert_eq!(maker.balance, balance);
+    assert_eq!(maker.assets_count, 1);
+
+    let taker = api.get_wallet(&taker_pk);
+    assert_eq!(taker.balance, balance);
+    assert_eq!(taker.assets_count, 1);
+
+    let genesis = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis.balance, genesis_balance);
+}
+
+/// Balance of the taker and the fee collected by the genesis wallet after a
+/// match in which the taker can pay the fee but not the price.
+fn match_offers_insufficient_funds(policy: FailurePolicy) -> (u64, u64) {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, transaction_fee, 0, 0);
+    let balance = 100_000;
+    let units = 3;
+
+    let (maker_pk, maker_sk) = crypto::gen_keypair();
+    let (taker_pk, taker_sk) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &maker_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_failure_policy(policy))
+        .add_wallet_value(&maker_pk, Wallet::new(balance, vec![]))
+        .add_wallet_value(&taker_pk, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&maker_pk, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_match = transaction::Builder::new()
+        .keypair(taker_pk, taker_sk)
+        .tx_match_offers()
+        .maker_key_pair(maker_pk, maker_sk)
+        .maker_add_asset_value(AssetBundle::new(asset.id(), units))
+        .taker_value(balance)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_match);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_match);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let maker = api.get_wallet(&maker_pk);
+    assert_eq!(maker.balance, balance);
+    assert_eq!(maker.assets_count, 1);
+
+    let taker = api.get_wallet(&taker_pk);
+    assert_eq!(taker.assets_count, 0);
+
+    (
+        taker.balance,
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance - genesis_balance,
+    )
+}
+
+#[test]
+fn match_offers_insufficient_funds_keep_fee() {
+    let (taker_balance, collected) = match_offers_insufficient_funds(FailurePolicy::KeepFee);
+
+    assert_eq!(taker_balance, 100_000 - 1000);
+    assert_eq!(collected, 1000);
+}
+
+#[test]
+fn match_offers_insufficient_funds_full_rollback() {
+    let (taker_balance, collected) = match_offers_insufficient_funds(FailurePolicy::FullRollback);
+
+    assert_eq!(taker_balance, 100_000);
+    assert_eq!(collected, 0);
+}
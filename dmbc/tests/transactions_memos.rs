@@ -0,0 +1,103 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+use exonum::crypto::{PublicKey, SecretKey};
+use exonum::messages::Message;
+use hyper::status::StatusCode;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::wallet::WalletHistoryResponse;
+use dmbc::currency::assets::{AssetBundle, TradeAsset};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::{FeeStrategy, MAX_DATA_INFO_LENGTH};
+use dmbc::currency::transactions::TradeIntermediary;
+use dmbc::currency::wallet::Wallet;
+
+fn trade(
+    buyer: (PublicKey, SecretKey),
+    seller: (PublicKey, SecretKey),
+    asset: &AssetBundle,
+    data_info: &str,
+) -> TradeIntermediary {
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+    transaction::Builder::new()
+        .keypair(buyer.0, buyer.1)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(10)
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), 100))
+        .seller(seller.0, seller.1)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .data_info(data_info)
+        .build()
+}
+
+#[test]
+fn trade_intermediary_memo_in_transaction_and_history() {
+    let memo = "invoice #42";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", 3, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(100_000, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(100_000, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = trade(
+        (buyer_public_key, buyer_secret_key),
+        (seller_public_key, seller_secret_key),
+        &asset,
+        memo,
+    );
+
+    let json = serde_json::to_string(&tx_trade).unwrap();
+    assert!(json.contains(&format!("\"data_info\":\"{}\"", memo)));
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    for key in &[buyer_public_key, seller_public_key] {
+        let endpoint = format!("/v1/wallets/{}/history", key.to_string());
+        let (status, response): (StatusCode, WalletHistoryResponse) = api.get_with_status(&endpoint);
+
+        assert_eq!(status, StatusCode::Ok);
+        let body = response.unwrap();
+        assert_eq!(body.transactions[0].tx_hash, tx_trade.hash());
+        assert_eq!(body.transactions[0].data_info, Some(memo.to_string()));
+    }
+}
+
+#[test]
+fn trade_intermediary_memo_length_cap() {
+    let seller = crypto::gen_keypair();
+    let buyer = crypto::gen_keypair();
+    let asset = AssetBundle::from_data("asset", 3, &seller.0);
+
+    let memo = "m".repeat(MAX_DATA_INFO_LENGTH);
+    assert!(trade(buyer.clone(), seller.clone(), &asset, &memo).verify());
+
+    let memo = "m".repeat(MAX_DATA_INFO_LENGTH + 1);
+    assert!(!trade(buyer, seller, &asset, &memo).verify());
+}
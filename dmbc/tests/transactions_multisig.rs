@@ -0,0 +1,211 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::crypto::{PublicKey, SecretKey};
+use exonum_testkit::TestKit;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::SET_MULTISIG_ID;
+use dmbc::currency::wallet::Wallet;
+
+const TRANSACTION_FEE: u64 = 1000;
+
+/// Testkit with a 2-of-3 multisig wallet holding `balance`, and the keys
+/// of its signers.
+fn multisig_wallet(
+    wallet_key: PublicKey,
+    wallet_secret: SecretKey,
+    balance: u64,
+) -> (TestKit, Vec<(PublicKey, SecretKey)>) {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+    let signers = vec![
+        crypto::gen_keypair(),
+        crypto::gen_keypair(),
+        crypto::gen_keypair(),
+    ];
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&wallet_key, Wallet::new(balance, vec![]))
+        .create();
+
+    let tx_set_multisig = transaction::Builder::new()
+        .keypair(wallet_key, wallet_secret)
+        .tx_set_multisig()
+        .add_key(signers[0].0)
+        .add_key(signers[1].0)
+        .add_key(signers[2].0)
+        .threshold(2)
+        .seed(1)
+        .build();
+
+    let api = testkit.api();
+    api.post_tx(&tx_set_multisig);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_set_multisig);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    (testkit, signers)
+}
+
+#[test]
+fn multisig_transfer_one_of_two_insufficient() {
+    let balance = 100_000;
+    let amount = 500;
+
+    let (wallet_key, wallet_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (mut testkit, signers) = multisig_wallet(wallet_key, wallet_secret, balance);
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(signers[0].0, signers[0].1.clone())
+        .tx_multisig_transfer()
+        .wallet(wallet_key)
+        .recipient(recipient_key)
+        .amount(amount)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientSignatures)));
+    assert_eq!(api.get_wallet(&wallet_key).balance, balance);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+}
+
+#[test]
+fn multisig_transfer_two_of_three() {
+    let balance = 100_000;
+    let amount = 500;
+
+    let (wallet_key, wallet_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (mut testkit, signers) = multisig_wallet(wallet_key, wallet_secret, balance);
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(signers[0].0, signers[0].1.clone())
+        .tx_multisig_transfer()
+        .wallet(wallet_key)
+        .recipient(recipient_key)
+        .amount(amount)
+        .cosigner(signers[2].0, signers[2].1.clone())
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&wallet_key).balance, balance - amount - TRANSACTION_FEE);
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+}
+
+#[test]
+fn multisig_transfer_unauthorized_cosigner() {
+    let balance = 100_000;
+    let amount = 500;
+
+    let (wallet_key, wallet_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (other_key, other_secret) = crypto::gen_keypair();
+    let (mut testkit, signers) = multisig_wallet(wallet_key, wallet_secret, balance);
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(signers[1].0, signers[1].1.clone())
+        .tx_multisig_transfer()
+        .wallet(wallet_key)
+        .recipient(recipient_key)
+        .amount(amount)
+        .cosigner(other_key, other_secret)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientSignatures)));
+    assert_eq!(api.get_wallet(&wallet_key).balance, balance);
+}
+
+#[test]
+fn multisig_wallet_rejects_single_signature_transfer() {
+    let balance = 100_000;
+    let amount = 500;
+
+    let (wallet_key, wallet_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (mut testkit, _) = multisig_wallet(wallet_key, wallet_secret.clone(), balance);
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(wallet_key, wallet_secret)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientSignatures)));
+    assert_eq!(api.get_wallet(&wallet_key).balance, balance);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+}
+
+#[test]
+fn set_multisig_charges_operation_fee() {
+    let balance = 100_000;
+    let fee = 300;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (wallet_key, wallet_secret) = crypto::gen_keypair();
+    let (signer_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_operation_fee(SET_MULTISIG_ID, fee))
+        .add_wallet_value(&wallet_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_set_multisig = transaction::Builder::new()
+        .keypair(wallet_key, wallet_secret)
+        .tx_set_multisig()
+        .add_key(wallet_key)
+        .add_key(signer_key)
+        .threshold(2)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_set_multisig);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_set_multisig);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&wallet_key).balance, balance - fee);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + fee);
+}
@@ -0,0 +1,184 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+use exonum::messages::Message;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::assets::{AssetBundle, AssetInfo, MetaAsset};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn non_fungible_amount_must_be_one() {
+    let (creator_public_key, creator_secret_key) = crypto::gen_keypair();
+
+    let meta_asset = MetaAsset::new(&creator_public_key, "certificate", 2, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()));
+    let tx_add_assets = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key)
+        .tx_add_assets()
+        .add_asset_value(meta_asset)
+        .non_fungible(true)
+        .seed(1)
+        .build();
+
+    assert!(!tx_add_assets.verify());
+}
+
+#[test]
+fn non_fungible_transfer_keeps_info() {
+    let meta_data = "certificate";
+    let balance = 100_000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (creator_public_key, creator_secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&creator_public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let meta_asset = MetaAsset::new(&creator_public_key, meta_data, 1, fees.clone());
+    let tx_add_assets = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key.clone())
+        .tx_add_assets()
+        .add_asset_value(meta_asset)
+        .non_fungible(true)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_add_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_add_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let (asset, info) = dmbc_testkit::create_asset2(meta_data, 1, fees, &creator_public_key, &tx_add_assets.hash());
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key)
+        .tx_transfer()
+        .add_asset_value(asset.clone())
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_assets = api.get_wallet_assets(&recipient_key);
+    let assets: Vec<AssetBundle> = recipient_assets.iter().map(|a| a.into()).collect();
+    assert_eq!(assets, vec![asset]);
+
+    let asset_info: Vec<AssetInfo> = recipient_assets.iter().map(|a| a.clone().meta_data.unwrap()).collect();
+    assert_eq!(asset_info, vec![info]);
+}
+
+#[test]
+fn non_fungible_can_not_be_issued_again() {
+    let meta_data = "certificate";
+    let balance = 100_000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (creator_public_key, creator_secret_key) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&creator_public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let meta_asset = MetaAsset::new(&creator_public_key, meta_data, 1, fees.clone());
+    let tx_add_non_fungible = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key.clone())
+        .tx_add_assets()
+        .add_asset_value(meta_asset.clone())
+        .non_fungible(true)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_add_non_fungible);
+    testkit.create_block();
+
+    let tx_add_fungible = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key.clone())
+        .tx_add_assets()
+        .add_asset_value(meta_asset.clone())
+        .seed(2)
+        .build();
+
+    let tx_add_non_fungible_again = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key)
+        .tx_add_assets()
+        .add_asset_value(meta_asset)
+        .non_fungible(true)
+        .seed(3)
+        .build();
+
+    api.post_tx(&tx_add_fungible);
+    api.post_tx(&tx_add_non_fungible_again);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_add_fungible);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidAssetInfo)));
+
+    let (_, tx_status) = api.get_tx_status(&tx_add_non_fungible_again);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidAssetInfo)));
+
+    let (asset, _) = dmbc_testkit::create_asset2(meta_data, 1, fees, &creator_public_key, &tx_add_non_fungible.hash());
+    let creator_assets = api.get_wallet_assets(&creator_public_key);
+    let assets: Vec<AssetBundle> = creator_assets.iter().map(|a| a.into()).collect();
+    assert_eq!(assets, vec![asset]);
+}
+
+#[test]
+fn non_fungible_add_to_existing_fungible() {
+    let meta_data = "asset";
+    let balance = 100_000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let (creator_public_key, creator_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, 5, fees.clone(), &creator_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&creator_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&creator_public_key, (asset, info))
+        .create();
+    let api = testkit.api();
+
+    let meta_asset = MetaAsset::new(&creator_public_key, meta_data, 1, fees);
+    let tx_add_assets = transaction::Builder::new()
+        .keypair(creator_public_key, creator_secret_key)
+        .tx_add_assets()
+        .add_asset_value(meta_asset)
+        .non_fungible(true)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_add_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_add_assets);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidAssetInfo)));
+}
@@ -0,0 +1,189 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::messages::Message;
+use exonum_testkit::TestKitApi;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::PriceWarningsResponse;
+use dmbc::currency::assets::{AssetBundle, PriceDeviation, TradeAsset};
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::transactions::SET_REFERENCE_PRICE_ID;
+use dmbc::currency::wallet::Wallet;
+
+fn price_warnings<T: Message>(api: &TestKitApi, tx: &T) -> (StatusCode, PriceWarningsResponse) {
+    api.get_with_status(&format!("/v1/transactions/{}/warnings", tx.hash().to_hex()))
+}
+
+#[test]
+fn trade_off_reference_price_flagged() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    // Prices more than 10% off the reference price are flagged.
+    let configuration = Configuration::new(config_fees).with_price_deviation_bps(1000);
+    let balance = 100_000;
+    let reference_price = 100;
+
+    let (seller_key, seller_secret) = crypto::gen_keypair();
+    let (buyer_key, buyer_secret) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        10,
+        dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()),
+        &seller_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&buyer_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_reference_price = transaction::Builder::new()
+        .keypair(seller_key, seller_secret.clone())
+        .tx_set_reference_price()
+        .asset_id(asset.id())
+        .price(reference_price)
+        .seed(1)
+        .build();
+    api.post_tx(&tx_reference_price);
+    testkit.create_block();
+    assert_eq!(api.get_tx_status(&tx_reference_price).1, Ok(Ok(())));
+
+    let trade = |price, seed| {
+        transaction::Builder::new()
+            .keypair(buyer_key, buyer_secret.clone())
+            .tx_trade_assets()
+            .add_asset_value(TradeAsset::from_bundle(AssetBundle::new(asset.id(), 1), price))
+            .seller(seller_key, seller_secret.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .seed(seed)
+            .build()
+    };
+    let tx_on_market = trade(reference_price + 10, 2);
+    let tx_off_market = trade(reference_price * 2, 3);
+
+    api.post_tx(&tx_on_market);
+    api.post_tx(&tx_off_market);
+    testkit.create_block();
+
+    // Flagged trades still go through.
+    assert_eq!(api.get_tx_status(&tx_on_market).1, Ok(Ok(())));
+    assert_eq!(api.get_tx_status(&tx_off_market).1, Ok(Ok(())));
+
+    let (status, response) = price_warnings(&api, &tx_on_market);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(vec![]));
+
+    let (status, response) = price_warnings(&api, &tx_off_market);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(vec![PriceDeviation::new(asset.id(), reference_price * 2, reference_price)])
+    );
+}
+
+#[test]
+fn set_reference_price_not_creator() {
+    let (creator_key, _) = crypto::gen_keypair();
+    let (other_key, other_secret) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        10,
+        dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&other_key, Wallet::new(100, vec![]))
+        .add_asset_to_wallet(&creator_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_reference_price = transaction::Builder::new()
+        .keypair(other_key, other_secret)
+        .tx_set_reference_price()
+        .asset_id(asset.id())
+        .price(100)
+        .seed(1)
+        .build();
+    api.post_tx(&tx_reference_price);
+    testkit.create_block();
+    assert_eq!(
+        api.get_tx_status(&tx_reference_price).1,
+        Ok(Err(Error::InvalidTransaction))
+    );
+
+    // Not a trade, but executed: nothing flagged.
+    let (_, response) = price_warnings(&api, &tx_reference_price);
+    assert_eq!(response, Ok(vec![]));
+}
+
+#[test]
+fn price_warnings_unknown_transaction() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, PriceWarningsResponse) = api.get_with_status(
+        "/v1/transactions/0000000000000000000000000000000000000000000000000000000000000000/warnings",
+    );
+    assert_eq!(status, StatusCode::NotFound);
+    assert_eq!(response, Err(ApiError::TransactionNotFound));
+}
+
+#[test]
+fn set_reference_price_charges_operation_fee() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let configuration = Configuration::new(config_fees).with_operation_fee(SET_REFERENCE_PRICE_ID, 300);
+    let balance = 100_000;
+
+    let (creator_key, creator_secret) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        10,
+        dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&creator_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&creator_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_reference_price = transaction::Builder::new()
+        .keypair(creator_key, creator_secret)
+        .tx_set_reference_price()
+        .asset_id(asset.id())
+        .price(100)
+        .seed(1)
+        .build();
+    api.post_tx(&tx_reference_price);
+    testkit.create_block();
+
+    assert_eq!(api.get_tx_status(&tx_reference_price).1, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&creator_key).balance, balance - 300);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + 300);
+}
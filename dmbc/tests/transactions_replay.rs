@@ -0,0 +1,156 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::messages::Message;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::transaction::TransactionResponse;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn transfer_replay_does_not_double_debit() {
+    let fixed = 1000;
+    let balance = 100_000;
+    let amount = 500;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, fixed);
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // Resubmit the very same signed transaction.
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&public_key).balance, balance - amount - fixed);
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+}
+
+#[test]
+fn resubmission_returns_known_status() {
+    let fixed = 1000;
+    let balance = 100_000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, fixed);
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_ok = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_transfer()
+        .amount(1)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+    let tx_failed = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(balance)
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_ok);
+    testkit.create_block();
+    api.post_tx(&tx_failed);
+    testkit.create_block();
+
+    let (status, response) = api.post_tx(&tx_ok);
+    let tx_hash = tx_ok.hash();
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: Some(Ok(())) })));
+
+    let (status, response) = api.post_tx(&tx_failed);
+    let tx_hash = tx_failed.hash();
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(Ok(TransactionResponse { tx_hash, status: Some(Err(Error::InsufficientFunds)) }))
+    );
+}
+
+#[test]
+fn resubmission_while_pending_is_not_resent() {
+    let fixed = 1000;
+    let balance = 100_000;
+    let amount = 500;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, fixed);
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+    let tx_hash = tx_transfer.hash();
+
+    let (status, response) = api.post_tx(&tx_transfer);
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    // Submitted again before any block, it is still pending.
+    let (status, response) = api.post_tx(&tx_transfer);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&public_key).balance, balance - amount - fixed);
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+
+    let (status, response) = api.post_tx(&tx_transfer);
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: Some(Ok(())) })));
+}
@@ -0,0 +1,185 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::SET_MULTISIG_ID;
+use dmbc::currency::error::Error;
+use dmbc::currency::wallet::{self, Wallet};
+
+// All tests in this file require wallets to keep `RESERVE` coins.
+const RESERVE: u64 = 5000;
+
+fn configuration(transaction_fee: u64) -> Configuration {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    Configuration::new(config_fees).with_reserve(RESERVE)
+}
+
+#[test]
+fn transfer_up_to_reserve() {
+    let transaction_fee = 1000;
+    let balance = 10_000;
+    let amount = balance - transaction_fee - RESERVE;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(transaction_fee))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // The recipient is below the reserve, yet it can receive.
+    assert_eq!(api.get_wallet(&public_key).balance, RESERVE);
+    assert_eq!(api.get_wallet(&recipient_key).balance, amount);
+}
+
+#[test]
+fn transfer_into_reserve() {
+    let transaction_fee = 1000;
+    let balance = 10_000;
+    let amount = balance - transaction_fee - RESERVE + 1;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(transaction_fee))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    assert_eq!(api.get_wallet(&public_key).balance, balance - transaction_fee);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+}
+
+#[test]
+fn transfer_send_all_keeps_reserve() {
+    let transaction_fee = 1000;
+    let balance = 10_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(transaction_fee))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .send_all(true)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    assert_eq!(api.get_wallet(&public_key).balance, RESERVE);
+    assert_eq!(api.get_wallet(&recipient_key).balance, balance - transaction_fee - RESERVE);
+}
+
+#[test]
+fn spendable_balance_excludes_reserve() {
+    let balance = 10_000;
+
+    let (public_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(0))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    testkit.create_block();
+    let api = testkit.api();
+
+    let wallet = Wallet::new(api.get_wallet(&public_key).balance, vec![]);
+    assert_eq!(wallet.balance(), balance);
+    assert_eq!(wallet.spendable_balance(RESERVE), balance - RESERVE);
+    assert!(wallet::can_spend(&wallet, balance - RESERVE, RESERVE));
+    assert!(!wallet::can_spend(&wallet, balance - RESERVE + 1, RESERVE));
+
+    let wallet = Wallet::new(RESERVE - 1, vec![]);
+    assert_eq!(wallet.balance(), RESERVE - 1);
+    assert_eq!(wallet.spendable_balance(RESERVE), 0);
+}
+
+#[test]
+fn fee_paid_from_reserve() {
+    let fee = 300;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (signer_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(0).with_operation_fee(SET_MULTISIG_ID, fee))
+        .add_wallet_value(&public_key, Wallet::new(RESERVE, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_set_multisig = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_set_multisig()
+        .add_key(public_key)
+        .add_key(signer_key)
+        .threshold(2)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_set_multisig);
+    testkit.create_block();
+
+    // Only payments keep the reserve, fees can be paid out of it.
+    let (_, tx_status) = api.get_tx_status(&tx_set_multisig);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&public_key).balance, RESERVE - fee);
+}
@@ -0,0 +1,121 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::crypto::{PublicKey, SecretKey};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::Transfer;
+use dmbc::currency::wallet::Wallet;
+
+const BALANCE: u64 = 100_000;
+const AMOUNT: u64 = 100;
+
+fn transfer(key: PublicKey, secret: SecretKey, to: PublicKey, seed: u64) -> Transfer {
+    transaction::Builder::new()
+        .keypair(key, secret)
+        .tx_transfer()
+        .recipient(to)
+        .amount(AMOUNT)
+        .seed(seed)
+        .build()
+}
+
+fn configuration(seed_nonces: bool) -> Configuration {
+    let fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    Configuration::new(fees).with_seed_nonces(seed_nonces)
+}
+
+#[test]
+fn seed_nonces_increasing() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(true))
+        .add_wallet_value(&sender_key, Wallet::new(BALANCE, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let first = transfer(sender_key, sender_secret.clone(), recipient_key, 1);
+    let second = transfer(sender_key, sender_secret, recipient_key, 2);
+
+    api.post_tx(&first);
+    testkit.create_block();
+    api.post_tx(&second);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&first);
+    assert_eq!(tx_status, Ok(Ok(())));
+    let (_, tx_status) = api.get_tx_status(&second);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    assert_eq!(api.get_wallet(&sender_key).balance, BALANCE - 2 * AMOUNT);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 2 * AMOUNT);
+}
+
+#[test]
+fn seed_nonces_stale_seed() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(true))
+        .add_wallet_value(&sender_key, Wallet::new(BALANCE, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let first = transfer(sender_key, sender_secret.clone(), recipient_key, 5);
+    let lower = transfer(sender_key, sender_secret, recipient_key, 3);
+
+    api.post_tx(&first);
+    testkit.create_block();
+    api.post_tx(&lower);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&first);
+    assert_eq!(tx_status, Ok(Ok(())));
+    let (_, tx_status) = api.get_tx_status(&lower);
+    assert_eq!(tx_status, Ok(Err(Error::StaleSeed)));
+
+    assert_eq!(api.get_wallet(&sender_key).balance, BALANCE - AMOUNT);
+    assert_eq!(api.get_wallet(&recipient_key).balance, AMOUNT);
+}
+
+#[test]
+fn seed_nonces_disabled() {
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration(false))
+        .add_wallet_value(&sender_key, Wallet::new(BALANCE, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let first = transfer(sender_key, sender_secret.clone(), recipient_key, 5);
+    let lower = transfer(sender_key, sender_secret, recipient_key, 3);
+
+    api.post_tx(&first);
+    testkit.create_block();
+    api.post_tx(&lower);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&first);
+    assert_eq!(tx_status, Ok(Ok(())));
+    let (_, tx_status) = api.get_tx_status(&lower);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    assert_eq!(api.get_wallet(&sender_key).balance, BALANCE - 2 * AMOUNT);
+}
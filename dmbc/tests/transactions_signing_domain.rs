@@ -0,0 +1,89 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+use exonum::messages::{Message, RawMessage};
+use exonum::storage::StorageValue;
+
+use dmbc::currency::assets::TradeAsset;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::{offer_signing_bytes, FeeStrategy};
+use dmbc::currency::transactions::{Trade, EXCHANGE_ID, FREEZE_ASSET_ID, TRADE_ID};
+
+#[test]
+fn transfer_signature_bound_to_message_type() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .amount(10)
+        .seed(1)
+        .build();
+
+    assert!(tx_transfer.verify_signature(&public_key));
+
+    // Same body and signature, claimed to be a different transaction type.
+    let mut bytes = tx_transfer.raw().as_ref().to_vec();
+    bytes[2] = FREEZE_ASSET_ID as u8;
+    bytes[3] = (FREEZE_ASSET_ID >> 8) as u8;
+    let raw = RawMessage::from_vec(bytes);
+
+    assert_eq!(raw.message_type(), FREEZE_ASSET_ID);
+    assert!(!raw.verify_signature(&public_key));
+}
+
+#[test]
+fn offer_signature_bound_to_transaction_type() {
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+
+    let (asset, _) = dmbc_testkit::create_asset(
+        "asset",
+        5,
+        dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()),
+        &seller_public_key,
+    );
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key.clone())
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset, 100))
+        .seller(seller_public_key, seller_secret_key.clone())
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    assert!(tx_trade.verify());
+
+    let offer = tx_trade.offer();
+    let offer_bytes = offer.clone().into_bytes();
+
+    // The offer bytes signed as is, without any domain.
+    let signature = crypto::sign(&offer_bytes, &seller_secret_key);
+    let tx_undomained = Trade::new(offer.clone(), &signature, 0, 1, &buyer_secret_key);
+    assert!(!tx_undomained.verify());
+
+    // The very same offer bytes signed for an exchange.
+    let signing_bytes = offer_signing_bytes(EXCHANGE_ID, &offer_bytes);
+    let signature = crypto::sign(&signing_bytes, &seller_secret_key);
+    let tx_exchange_domain = Trade::new(offer.clone(), &signature, 0, 1, &buyer_secret_key);
+    assert!(!tx_exchange_domain.verify());
+
+    let signing_bytes = offer_signing_bytes(TRADE_ID, &offer_bytes);
+    let signature = crypto::sign(&signing_bytes, &seller_secret_key);
+    let tx_trade_domain = Trade::new(offer, &signature, 0, 1, &buyer_secret_key);
+    assert!(tx_trade_domain.verify());
+    assert_eq!(tx_trade_domain, tx_trade);
+}
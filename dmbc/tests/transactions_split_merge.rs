@@ -0,0 +1,307 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use exonum::blockchain::Transaction;
+use exonum::crypto;
+use exonum::messages::Message;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
+
+use dmbc::currency::api::fees::FeesResponseBody;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::{MERGE_ASSET_ID, SPLIT_ASSET_ID};
+use dmbc::currency::assets::{AssetBundle, MetaAsset, Tranche};
+use dmbc::currency::error::Error;
+use dmbc::currency::wallet::Wallet;
+
+fn total(assets: &[AssetBundle]) -> u64 {
+    assets.iter().map(|a| a.amount()).sum()
+}
+
+#[test]
+fn split_and_merge_preserve_total_amount() {
+    let units = 10;
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(100, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_split = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_split_asset()
+        .asset(AssetBundle::new(asset.id(), 7))
+        .add_tranche("lot-a", 3)
+        .add_tranche("lot-b", 4)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_split);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_split);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let lot_a = Tranche::new("lot-a", 3).id(&asset.id(), &creator_pk);
+    let lot_b = Tranche::new("lot-b", 4).id(&asset.id(), &creator_pk);
+
+    let assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(total(&assets), units);
+    assert!(assets.contains(&AssetBundle::new(asset.id(), 3)));
+    assert!(assets.contains(&AssetBundle::new(lot_a, 3)));
+    assert!(assets.contains(&AssetBundle::new(lot_b, 4)));
+
+    let lot_info = testkit.fetch_asset_info(&lot_a).unwrap();
+    assert_eq!(lot_info.amount(), 3);
+    assert_eq!(lot_info.creator(), &creator_pk);
+    assert_eq!(testkit.fetch_asset_info(&asset.id()).unwrap().amount(), 3);
+
+    let tx_merge = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_merge_asset()
+        .add_lot(AssetBundle::new(lot_a, 3))
+        .add_lot(AssetBundle::new(lot_b, 4))
+        .into_asset(asset.id())
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_merge);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_merge);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(assets, vec![AssetBundle::new(asset.id(), units)]);
+
+    assert_eq!(testkit.fetch_asset_info(&asset.id()).unwrap().amount(), units);
+    assert!(testkit.fetch_asset_info(&lot_a).is_none());
+    assert!(testkit.fetch_asset_info(&lot_b).is_none());
+}
+
+#[test]
+fn split_more_than_held_fails() {
+    let units = 5;
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(100, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_split = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_split_asset()
+        .asset(AssetBundle::new(asset.id(), units + 1))
+        .add_tranche("lot-a", units)
+        .add_tranche("lot-b", 1)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_split);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_split);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
+
+    let assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(assets, vec![asset.clone()]);
+    assert_eq!(testkit.fetch_asset_info(&asset.id()).unwrap().amount(), units);
+}
+
+#[test]
+fn split_must_conserve_amount() {
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let asset = AssetBundle::from_data("asset", 5, &creator_pk);
+
+    let unbalanced = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_split_asset()
+        .asset(asset.clone())
+        .add_tranche("lot-a", 2)
+        .add_tranche("lot-b", 2)
+        .build();
+    let duplicate = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_split_asset()
+        .asset(asset)
+        .add_tranche("lot-a", 2)
+        .add_tranche("lot-a", 3)
+        .build();
+
+    assert!(!unbalanced.verify());
+    assert!(!duplicate.verify());
+}
+
+#[test]
+fn split_non_fungible_fails() {
+    let meta_data = "certificate";
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(100, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let meta_asset = MetaAsset::new(&public_key, meta_data, 1, fees.clone());
+    let tx_add_assets = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_add_assets()
+        .add_asset_value(meta_asset)
+        .non_fungible(true)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_add_assets);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_add_assets);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let (asset, _) = dmbc_testkit::create_asset2(meta_data, 1, fees, &public_key, &tx_add_assets.hash());
+
+    let tx_split = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_split_asset()
+        .asset(asset.clone())
+        .add_tranche("lot-a", 1)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_split);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_split);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidTransaction)));
+
+    let assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(assets, vec![asset]);
+}
+
+#[test]
+fn split_and_merge_charge_operation_fee() {
+    let units = 10;
+    let balance = 100;
+    let split_fee = 10;
+    let merge_fee = 20;
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let configuration = Configuration::new(config_fees)
+        .with_operation_fee(SPLIT_ASSET_ID, split_fee)
+        .with_operation_fee(MERGE_ASSET_ID, merge_fee);
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_split = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_split_asset()
+        .asset(AssetBundle::new(asset.id(), units))
+        .add_tranche("lot-a", units)
+        .seed(1)
+        .build();
+
+    let (_, response) = api.post_fee(&tx_split);
+    let mut expected = HashMap::new();
+    expected.insert(public_key, split_fee);
+    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+
+    api.post_tx(&tx_split);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_split);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&public_key).balance, balance - split_fee);
+
+    let lot_a = Tranche::new("lot-a", units).id(&asset.id(), &creator_pk);
+    let tx_merge = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_merge_asset()
+        .add_lot(AssetBundle::new(lot_a, units))
+        .into_asset(asset.id())
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_merge);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_merge);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(api.get_wallet(&public_key).balance, balance - split_fee - merge_fee);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + split_fee + merge_fee);
+}
+
+#[test]
+fn split_without_operation_fee_fails() {
+    let units = 10;
+    let split_fee = 10;
+    let (creator_pk, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let configuration = Configuration::new(config_fees).with_operation_fee(SPLIT_ASSET_ID, split_fee);
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &creator_pk);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(split_fee - 1, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_split = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_split_asset()
+        .asset(AssetBundle::new(asset.id(), units))
+        .add_tranche("lot-a", units)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_split);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_split);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(assets, vec![asset]);
+    assert_eq!(api.get_wallet(&public_key).balance, split_fee - 1);
+}
@@ -0,0 +1,153 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+const TRANSACTION_FEE: u64 = 1000;
+
+#[test]
+fn sponsored_transfer_sponsor_pays_fee() {
+    let balance = 100_000;
+    let amount = 500;
+    let units = 2;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (sponsor_key, _) = crypto::gen_keypair();
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_sponsor(&sponsor_key))
+        .add_wallet_value(&sponsor_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&sender_key, Wallet::new(amount, vec![]))
+        .add_asset_to_wallet(&sender_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_key, sender_secret)
+        .tx_sponsored_transfer()
+        .recipient(recipient_key)
+        .amount(amount)
+        .add_asset_value(asset)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let sender = api.get_wallet(&sender_key);
+    let recipient = api.get_wallet(&recipient_key);
+    assert_eq!(sender.balance, 0);
+    assert_eq!(sender.assets_count, 0);
+    assert_eq!(recipient.balance, amount);
+    assert_eq!(recipient.assets_count, 1);
+
+    assert_eq!(api.get_wallet(&sponsor_key).balance, balance - TRANSACTION_FEE);
+    assert_eq!(
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance,
+        genesis_balance + TRANSACTION_FEE
+    );
+}
+
+#[test]
+fn sponsored_transfer_sponsor_insufficient_funds() {
+    let amount = 500;
+    let units = 2;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (sponsor_key, _) = crypto::gen_keypair();
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(0, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_sponsor(&sponsor_key))
+        .add_wallet_value(&sponsor_key, Wallet::new(TRANSACTION_FEE - 1, vec![]))
+        .add_wallet_value(&sender_key, Wallet::new(amount, vec![]))
+        .add_asset_to_wallet(&sender_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_key, sender_secret)
+        .tx_sponsored_transfer()
+        .recipient(recipient_key)
+        .amount(amount)
+        .add_asset_value(asset)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let sender = api.get_wallet(&sender_key);
+    let recipient = api.get_wallet(&recipient_key);
+    assert_eq!(sender.balance, amount);
+    assert_eq!(sender.assets_count, 1);
+    assert_eq!(recipient.balance, 0);
+    assert_eq!(recipient.assets_count, 0);
+    assert_eq!(api.get_wallet(&sponsor_key).balance, TRANSACTION_FEE - 1);
+}
+
+#[test]
+fn sponsored_transfer_without_sponsor() {
+    let amount = 500;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, TRANSACTION_FEE);
+
+    let (sender_key, sender_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&sender_key, Wallet::new(amount + TRANSACTION_FEE, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_key, sender_secret)
+        .tx_sponsored_transfer()
+        .recipient(recipient_key)
+        .amount(amount)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::WalletNotFound)));
+    assert_eq!(api.get_wallet(&sender_key).balance, amount + TRANSACTION_FEE);
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+}
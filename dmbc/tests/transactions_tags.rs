@@ -0,0 +1,141 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::messages::Message;
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::TaggedTransactionsResponse;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::MAX_TAG_LENGTH;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn tagged_transfers_by_tag() {
+    let transaction_fee = 10;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let transfer = |amount, seed, tag| {
+        transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .amount(amount)
+            .recipient(recipient_key)
+            .seed(seed)
+            .tag(tag)
+            .build()
+    };
+
+    let payroll1 = transfer(10, 1, "payroll");
+    let refund = transfer(20, 2, "refund");
+    let payroll2 = transfer(30, 3, "payroll");
+    let untagged = transfer(40, 4, "");
+    // Failed transfers are indexed as well.
+    let payroll_failed = transfer(balance, 5, "payroll");
+
+    for tx in &[&payroll1, &refund, &payroll2, &untagged, &payroll_failed] {
+        let (status, _) = api.post_tx(*tx);
+        assert_eq!(status, StatusCode::Accepted);
+        testkit.create_block();
+    }
+
+    let (_, tx_status) = api.get_tx_status(&payroll_failed);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let (status, response): (StatusCode, TaggedTransactionsResponse) =
+        api.get_with_status("/v1/transactions?tag=payroll");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(vec![payroll1.hash(), payroll2.hash(), payroll_failed.hash()]));
+
+    let (_, response): (StatusCode, TaggedTransactionsResponse) =
+        api.get_with_status("/v1/transactions?tag=payroll&offset=1&limit=1");
+    assert_eq!(response, Ok(vec![payroll2.hash()]));
+
+    let (_, response): (StatusCode, TaggedTransactionsResponse) =
+        api.get_with_status("/v1/transactions?tag=refund");
+    assert_eq!(response, Ok(vec![refund.hash()]));
+
+    let (status, response): (StatusCode, TaggedTransactionsResponse) =
+        api.get_with_status("/v1/transactions?tag=unknown");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(vec![]));
+
+    let (status, response): (StatusCode, TaggedTransactionsResponse) =
+        api.get_with_status("/v1/transactions");
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::IncorrectRequest));
+}
+
+#[test]
+fn transfer_tag_in_transaction_json() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(1)
+        .recipient(recipient_key)
+        .seed(1)
+        .tag("payroll")
+        .build();
+
+    let json = serde_json::to_value(&tx_transfer).unwrap();
+    assert_eq!(json["body"]["tag"], "payroll");
+}
+
+#[test]
+fn transfer_tag_too_long() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 10);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tag = "t".repeat(MAX_TAG_LENGTH + 1);
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(1)
+        .recipient(recipient_key)
+        .seed(1)
+        .tag(&tag)
+        .build();
+
+    let (status, response) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+}
@@ -63,8 +63,8 @@ fn trade_fee_from_recipient() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -128,8 +128,8 @@ fn trade_fee_from_sender() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -192,8 +192,8 @@ fn trade_fee_from_recipient_and_sender() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -274,6 +274,46 @@ fn trade_fee_bad_request() {
     assert!(buyer_wallet.assets_count == 0);
 }
 
+#[test]
+fn trade_buyer_is_seller() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let meta_data = "asset";
+    let balance = 100_000;
+    let units = 3;
+    let price = 500;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(public_key, secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    let (status, response) = api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+}
+
 #[test]
 fn trade_asset_not_found() {
     let transaction_fee = 1000;
@@ -313,8 +353,8 @@ fn trade_asset_not_found() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Err(Error::AssetNotFound)));
@@ -373,8 +413,8 @@ fn trade_insufficient_assets() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
@@ -433,8 +473,8 @@ fn trade_insufficient_funds() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
@@ -450,4 +490,491 @@ fn trade_insufficient_funds() {
     assert_eq!(seller_wallet.balance, expected_sellers_balance);
     assert_eq!(buyer_wallet.balance, expected_buyer_balace);
     assert_eq!(genesis_wallet.balance, expected_genesis_balance);
-}
\ No newline at end of file
+}
+#[test]
+fn trade_buyer_one_coin_short() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let meta_data = "asset";
+    let balance = 100_000;
+    let units = 3;
+    let price = 500;
+    let buyer_balance = units * price - 1;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(buyer_balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_trade);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, buyer_balance);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance);
+    assert_eq!(seller_assets, vec![asset]);
+    assert_eq!(api.get_wallet(&buyer_public_key).assets_count, 0);
+}
+
+#[test]
+fn trade_total_price_overflow() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let balance = 100_000;
+    let price = u64::max_value() - 10;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(10, "0.0".parse().unwrap());
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", 1, fees.clone(), &seller_public_key);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", 1, fees, &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset1.clone(), info1))
+        .add_asset_to_wallet(&seller_public_key, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+
+    // Each price fits into u64, their sum would wrap to a few coins.
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset1, price))
+        .add_asset_value(TradeAsset::from_bundle(asset2, price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&buyer_public_key).assets_count, 0);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&seller_public_key).assets_count, 2);
+}
+
+#[test]
+fn trade_offer_expired() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let units = 3;
+    let price = 500;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    // The offer is only valid up to the second block.
+    testkit.create_block();
+    testkit.create_block();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .valid_until_height(2)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_trade);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::OfferExpired)));
+
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance);
+    assert_eq!(seller_assets, vec![asset]);
+}
+
+#[test]
+fn trade_offer_before_expiry() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let units = 3;
+    let price = 500;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    testkit.create_block();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .valid_until_height(2)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_trade);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let buyer_assets = api.get_wallet_assets(&buyer_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance - units * price);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance + units * price);
+    assert_eq!(buyer_assets, vec![asset]);
+}
+
+#[test]
+fn trade_third_party_fees_overflow() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let price = 1;
+    let fixed = u64::max_value() / 2;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (creator_public_key, _) = crypto::gen_keypair();
+
+    let mut builder = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]));
+    let mut assets = Vec::new();
+    for meta_data in &["asset1", "asset2", "asset3"] {
+        // Each asset alone has a payable fee, the creator's total overflows.
+        let (asset, info) = dmbc_testkit::create_asset(meta_data, 1, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_public_key);
+        builder = builder.add_asset_to_wallet(&seller_public_key, (asset.clone(), info));
+        assets.push(asset);
+    }
+    let mut testkit = builder.create();
+    let api = testkit.api();
+
+    let tx_trade = assets.iter()
+        .fold(
+            transaction::Builder::new()
+                .keypair(buyer_public_key, buyer_secret_key)
+                .tx_trade_assets(),
+            |tx, asset| tx.add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        )
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_trade);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance);
+    assert_eq!(seller_assets.len(), assets.len());
+    assert_eq!(api.get_wallet(&buyer_public_key).assets_count, 0);
+}
+
+#[test]
+fn trade_partial_fill() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let units = 100;
+    let price = 10;
+    let fixed = 2;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (creator_public_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let trade = |quantity| {
+        transaction::Builder::new()
+            .keypair(buyer_public_key, buyer_secret_key.clone())
+            .tx_trade_assets()
+            .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+            .seller(seller_public_key, seller_secret_key.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .seed(1)
+            .valid_until_height(100)
+            .quantity(quantity)
+            .build()
+    };
+
+    let tx_first = trade(40);
+    api.post_tx(&tx_first);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_first);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // Price and fees only cover the 40 units bought, 60 are left.
+    let buyer_assets = api.get_wallet_assets(&buyer_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(buyer_assets, vec![AssetBundle::new(asset.id(), 40)]);
+    assert_eq!(seller_assets, vec![AssetBundle::new(asset.id(), 60)]);
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance - 40 * price - 40 * fixed);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance + 40 * price);
+    assert_eq!(api.get_wallet(&creator_public_key).balance, 40 * fixed);
+
+    let tx_second = trade(60);
+    api.post_tx(&tx_second);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_second);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let buyer_assets = api.get_wallet_assets(&buyer_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(buyer_assets, vec![asset.clone()]);
+    assert_eq!(api.get_wallet(&seller_public_key).assets_count, 0);
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance - units * price - units * fixed);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance + units * price);
+    assert_eq!(api.get_wallet(&creator_public_key).balance, units * fixed);
+
+    // The sold out offer stays filled until it expires.
+    let tx_sold_out = trade(1);
+    api.post_tx(&tx_sold_out);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_sold_out);
+    assert_eq!(tx_status, Ok(Err(Error::OfferOverfilled)));
+}
+
+#[test]
+fn trade_partial_overfill() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let units = 100;
+    let price = 10;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    // The seller keeps more units than offered, so only the offer limits fills.
+    let (asset, info) = dmbc_testkit::create_asset("asset", 2 * units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+    let offered = AssetBundle::new(asset.id(), units);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let trade = |quantity| {
+        transaction::Builder::new()
+            .keypair(buyer_public_key, buyer_secret_key.clone())
+            .tx_trade_assets()
+            .add_asset_value(TradeAsset::from_bundle(offered.clone(), price))
+            .seller(seller_public_key, seller_secret_key.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .seed(1)
+            .valid_until_height(100)
+            .quantity(quantity)
+            .build()
+    };
+
+    // More than offered doesn't verify.
+    let tx_too_many = trade(units + 1);
+    let (status, response) = api.post_tx(&tx_too_many);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+
+    let tx_first = trade(40);
+    api.post_tx(&tx_first);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_first);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // Only 60 units are left, neither 70 nor the whole offer fit.
+    let tx_overfill = trade(70);
+    api.post_tx(&tx_overfill);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_overfill);
+    assert_eq!(tx_status, Ok(Err(Error::OfferOverfilled)));
+
+    let tx_whole = trade(0);
+    api.post_tx(&tx_whole);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_whole);
+    assert_eq!(tx_status, Ok(Err(Error::OfferOverfilled)));
+
+    let buyer_assets = api.get_wallet_assets(&buyer_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(buyer_assets, vec![AssetBundle::new(asset.id(), 40)]);
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance - 40 * price);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance + 40 * price);
+}
+
+#[test]
+fn trade_partial_fill_seed_nonces() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let units = 100;
+    let price = 10;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees).with_seed_nonces(true))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    // Both fills share the offer and its seed, the buyer seeds each trade.
+    let trade = |trade_seed| {
+        transaction::Builder::new()
+            .keypair(buyer_public_key, buyer_secret_key.clone())
+            .tx_trade_assets()
+            .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+            .seller(seller_public_key, seller_secret_key.clone())
+            .fee_strategy(FeeStrategy::Recipient)
+            .seed(1)
+            .valid_until_height(100)
+            .quantity(units / 2)
+            .trade_seed(trade_seed)
+            .build()
+    };
+
+    let tx_first = trade(1);
+    let tx_second = trade(2);
+    assert_eq!(tx_first.offer(), tx_second.offer());
+
+    api.post_tx(&tx_first);
+    testkit.create_block();
+    api.post_tx(&tx_second);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_first);
+    assert_eq!(tx_status, Ok(Ok(())));
+    let (_, tx_status) = api.get_tx_status(&tx_second);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let buyer_assets = api.get_wallet_assets(&buyer_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(buyer_assets, vec![asset.clone()]);
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance - units * price);
+
+    // A trade seed already used is stale, even for a fresh fill.
+    let tx_replayed = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key.clone())
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key.clone())
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(2)
+        .valid_until_height(100)
+        .quantity(1)
+        .trade_seed(1)
+        .build();
+    api.post_tx(&tx_replayed);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_replayed);
+    assert_eq!(tx_status, Ok(Err(Error::StaleSeed)));
+}
+
+#[test]
+fn trade_partial_fill_unverified() {
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let asset = AssetBundle::from_data("asset", 100, &seller_public_key);
+
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    // Offers which never expire can't be filled partially.
+    let tx_never_expires = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key.clone())
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), 10))
+        .seller(seller_public_key, seller_secret_key.clone())
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .quantity(40)
+        .build();
+    let (status, response) = api.post_tx(&tx_never_expires);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+
+    // Whole offers are traded with the seed of the offer.
+    let tx_reseeded = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key.clone())
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), 10))
+        .seller(seller_public_key, seller_secret_key.clone())
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .trade_seed(2)
+        .build();
+    let (status, response) = api.post_tx(&tx_reseeded);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+}
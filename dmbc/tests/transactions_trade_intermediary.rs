@@ -12,6 +12,7 @@ pub mod dmbc_testkit;
 use hyper::status::StatusCode;
 use exonum::messages::Message;
 use exonum::crypto;
+use exonum_testkit::TestKitApi;
 use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 
 use dmbc::currency::configuration::{Configuration, TransactionFees};
@@ -20,7 +21,7 @@ use dmbc::currency::assets::{TradeAsset, AssetBundle};
 use dmbc::currency::error::Error;
 use dmbc::currency::api::transaction::TransactionResponse;
 use dmbc::currency::wallet::Wallet;
-use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::transactions::components::{FeeStrategy, Intermediary};
 
 #[test]
 fn trade_intermediary_fee_from_recipient() {
@@ -67,8 +68,8 @@ fn trade_intermediary_fee_from_recipient() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -139,8 +140,8 @@ fn trade_intermediary_fee_from_sender() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -210,8 +211,8 @@ fn trade_intermediary_fee_from_recipient_and_sender() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -281,8 +282,8 @@ fn trade_intermediary_fee_from_intermediary() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -354,8 +355,8 @@ fn trade_intermediary_asset_not_found() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Err(Error::AssetNotFound)));
@@ -423,8 +424,8 @@ fn trade_intermediary_insufficient_assets() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
@@ -492,8 +493,8 @@ fn trade_intermediary_insufficient_funds() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_trade);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
@@ -513,3 +514,361 @@ fn trade_intermediary_insufficient_funds() {
     assert_eq!(genesis_wallet.balance, expected_genesis_balance);
     assert_eq!(intermediary_wallet.balance, expected_intermediary_balance);
 }
+
+#[test]
+fn trade_intermediary_fees_fail_after_price_is_affordable() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let meta_data = "asset";
+    let fixed = 10;
+    let balance = 100_000;
+    let units = 3;
+    let intermediary_commission = 50;
+    let price = 500;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (creator_public_key, _) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&intermediary_public_key, Wallet::new(transaction_fee, vec![]))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(intermediary_commission)
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Intermediary)
+        .seed(1)
+        .data_info("trade_test")
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let seller_wallet = api.get_wallet(&seller_public_key);
+    let buyer_wallet = api.get_wallet(&buyer_public_key);
+    let creator_wallet = api.get_wallet(&creator_public_key);
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    let intermediary_wallet = api.get_wallet(&intermediary_public_key);
+
+    // The intermediary can't cover all of its fees, so nothing is collected.
+    assert_eq!(seller_wallet.balance, balance);
+    assert_eq!(buyer_wallet.balance, balance);
+    assert_eq!(creator_wallet.balance, 0);
+    assert_eq!(genesis_wallet.balance, genesis_balance);
+    assert_eq!(intermediary_wallet.balance, transaction_fee);
+
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(seller_assets, vec![asset]);
+    assert_eq!(buyer_wallet.assets_count, 0);
+}
+
+fn trade_intermediary_conserves_coins(fee_strategy: FeeStrategy) {
+    let transaction_fee = 1001;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let balance = 100_000;
+    let intermediary_commission = 51;
+
+    let (creator1_public_key, _) = crypto::gen_keypair();
+    let (creator2_public_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", 3, dmbc_testkit::asset_fees(11, "0.13".parse().unwrap()), &creator1_public_key);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", 5, dmbc_testkit::asset_fees(7, "0.07".parse().unwrap()), &creator2_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&intermediary_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset1.clone(), info1))
+        .add_asset_to_wallet(&seller_public_key, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+
+    let participants = vec![
+        dmbc_testkit::default_genesis_key(),
+        creator1_public_key,
+        creator2_public_key,
+        seller_public_key,
+        buyer_public_key,
+        intermediary_public_key,
+    ];
+    let total_supply = |api: &TestKitApi| -> u64 {
+        participants.iter().map(|key| api.get_wallet(key).balance).sum()
+    };
+
+    let supply_before = total_supply(&api);
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(intermediary_commission)
+        .add_asset_value(TradeAsset::from_bundle(asset1, 333))
+        .add_asset_value(TradeAsset::from_bundle(asset2, 77))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(fee_strategy)
+        .seed(1)
+        .data_info("trade_test")
+        .build();
+
+    let (status, _) = api.post_tx(&tx_trade);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    assert_eq!(total_supply(&api), supply_before);
+    assert_eq!(api.get_wallet(&seller_public_key).assets_count, 0);
+}
+
+#[test]
+fn trade_intermediary_conserves_coins_fee_from_recipient() {
+    trade_intermediary_conserves_coins(FeeStrategy::Recipient);
+}
+
+#[test]
+fn trade_intermediary_conserves_coins_fee_from_sender() {
+    trade_intermediary_conserves_coins(FeeStrategy::Sender);
+}
+
+#[test]
+fn trade_intermediary_conserves_coins_fee_from_recipient_and_sender() {
+    trade_intermediary_conserves_coins(FeeStrategy::RecipientAndSender);
+}
+
+#[test]
+fn trade_intermediary_conserves_coins_fee_from_intermediary() {
+    trade_intermediary_conserves_coins(FeeStrategy::Intermediary);
+}
+
+#[test]
+fn trade_intermediary_missing_intermediary_wallet() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let meta_data = "asset";
+    let fixed = 10;
+    let balance = 100_000;
+    let units = 3;
+    let price = 500;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(50)
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Intermediary)
+        .seed(1)
+        .data_info("trade_test")
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::WalletNotFound)));
+
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&dmbc_testkit::default_genesis_key()).balance, genesis_balance);
+    assert_eq!(api.get_wallet(&intermediary_public_key).balance, 0);
+
+    let seller_assets = api.get_wallet_assets(&seller_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(seller_assets, vec![asset]);
+}
+
+#[test]
+fn trade_intermediary_without_intermediary() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let meta_data = "asset";
+    let fixed = 10;
+    let balance = 100_000;
+    let units = 3;
+    let price = 500;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .without_intermediary()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_trade);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // Same as `trade_intermediary_fee_from_recipient`, less the commission.
+    let assets_price = units * price;
+    let trade_fee = units * fixed;
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance + assets_price + trade_fee);
+    assert_eq!(
+        api.get_wallet(&buyer_public_key).balance,
+        balance - assets_price - transaction_fee - trade_fee
+    );
+    assert_eq!(
+        api.get_wallet(&dmbc_testkit::default_genesis_key()).balance,
+        genesis_balance + transaction_fee
+    );
+    assert_eq!(api.get_wallet(&Intermediary::null_wallet()).balance, 0);
+
+    let buyer_assets = api.get_wallet_assets(&buyer_public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(buyer_assets, vec![asset]);
+}
+
+#[test]
+fn trade_intermediary_total_price_overflow() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let balance = 100_000;
+    let price = u64::max_value() - 10;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(10, "0.0".parse().unwrap());
+    let (asset1, info1) = dmbc_testkit::create_asset("asset1", 1, fees.clone(), &seller_public_key);
+    let (asset2, info2) = dmbc_testkit::create_asset("asset2", 1, fees, &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset1.clone(), info1))
+        .add_asset_to_wallet(&seller_public_key, (asset2.clone(), info2))
+        .create();
+    let api = testkit.api();
+
+    // Each price fits into u64, their sum would wrap to a few coins.
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(50)
+        .add_asset_value(TradeAsset::from_bundle(asset1, price))
+        .add_asset_value(TradeAsset::from_bundle(asset2, price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_trade);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&buyer_public_key).assets_count, 0);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&seller_public_key).assets_count, 2);
+    assert_eq!(api.get_wallet(&intermediary_public_key).balance, 0);
+}
+
+#[test]
+fn trade_intermediary_is_fee_recipient() {
+    let transaction_fee = 1000;
+    let balance = 100_000;
+    let units = 3;
+    let price = 500;
+    let intermediary_commission = 50;
+
+    let (platform_public_key, platform_secret_key) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let config_fees = TransactionFees::new(&platform_public_key, 0, 0, 0, 0, transaction_fee, 0);
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &seller_public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&buyer_public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .intermediary_key_pair(platform_public_key, platform_secret_key)
+        .commission(intermediary_commission)
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_trade);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_trade);
+    assert_eq!(tx_status, Ok(Err(Error::InvalidTransaction)));
+
+    assert_eq!(api.get_wallet(&buyer_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&seller_public_key).balance, balance);
+    assert_eq!(api.get_wallet(&platform_public_key).balance, 0);
+    assert_eq!(api.get_wallet(&buyer_public_key).assets_count, 0);
+}
@@ -9,6 +9,7 @@ extern crate mount;
 
 pub mod dmbc_testkit;
 
+use hyper::header::Location;
 use hyper::status::StatusCode;
 use exonum::messages::Message;
 use exonum::crypto;
@@ -18,7 +19,9 @@ use dmbc::currency::configuration::{Configuration, TransactionFees};
 use dmbc::currency::transactions::builders::transaction;
 use dmbc::currency::assets::AssetBundle;
 use dmbc::currency::error::Error;
-use dmbc::currency::api::transaction::TransactionResponse;
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::{StatusResponse, TransactionResponse};
+use dmbc::currency::SERVICE_NAME;
 use dmbc::currency::wallet::Wallet;
 
 #[test]
@@ -56,8 +59,8 @@ fn transfer() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_transfer);
     assert_eq!(tx_status, Ok(Ok(())));
@@ -108,8 +111,8 @@ fn transfer_asset_not_found() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_transfer);
     assert_eq!(tx_status, Ok(Err(Error::AssetNotFound)));
@@ -159,8 +162,8 @@ fn transfer_insufficient_funds() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_transfer);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
@@ -210,8 +213,8 @@ fn transfer_insufficient_assets() {
     testkit.create_block();
 
     // check post response
-    assert_eq!(status, StatusCode::Created);
-    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash })));
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
 
     let (_, tx_status) = api.get_tx_status(&tx_transfer);
     assert_eq!(tx_status, Ok(Err(Error::InsufficientAssets)));
@@ -224,3 +227,697 @@ fn transfer_insufficient_assets() {
     let expected_balance = balance - transaction_fee;
     assert_eq!(sender_wallet.balance, expected_balance);
 }
+
+#[test]
+fn transfer_amount_overflow() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(u64::max_value())
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let tx_hash = tx_transfer.hash();
+
+    let (status, response) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    // check post response
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, 0);
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance);
+}
+
+#[test]
+fn transfer_submission_location() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let tx_hash = tx_transfer.hash();
+
+    let (status, headers, response) = api.post_tx_with_headers(&tx_transfer);
+    testkit.create_block();
+
+    assert_eq!(status, StatusCode::Accepted);
+    assert_eq!(response, Ok(Ok(TransactionResponse { tx_hash, status: None })));
+
+    let location = headers.get::<Location>().map(|l| l.to_string());
+    let expected_location = format!(
+        "/api/services/{}/v1/transactions/{}/status",
+        SERVICE_NAME,
+        tx_hash.to_hex()
+    );
+    assert_eq!(location, Some(expected_location));
+
+    let (status, tx_status): (StatusCode, StatusResponse) = api.get_with_status(&format!(
+        "/v1/transactions/{}/status",
+        tx_hash.to_hex()
+    ));
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(tx_status, Ok(Ok(())));
+}
+
+#[test]
+fn transfer_send_all() {
+    let fixed = 10;
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let meta_data = "asset";
+    let units = 5;
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (creator_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .send_all(true)
+        .add_asset_value(asset.clone())
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let asset_fee = fixed * units;
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, 0);
+    assert_eq!(sender_wallet.assets_count, 0);
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, balance - transaction_fee - asset_fee);
+
+    let creator_wallet = api.get_wallet(&creator_key);
+    assert_eq!(creator_wallet.balance, asset_fee);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + transaction_fee);
+}
+
+#[test]
+fn transfer_failure_reason_in_status() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(balance)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (status, tx_status): (StatusCode, serde_json::Value) = api.get_with_status(&format!(
+        "/v1/transactions/{}/status",
+        tx_transfer.hash().to_hex()
+    ));
+    let expected: serde_json::Value =
+        serde_json::from_str(r#"{ "Ok": { "Err": "InsufficientFunds" } }"#).unwrap();
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(tx_status, expected);
+}
+
+#[test]
+fn transfer_seed_distinguishes_transactions() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 10;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let transfer = |seed| {
+        transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .amount(amount)
+            .recipient(recipient_key)
+            .seed(seed)
+            .build()
+    };
+
+    let tx_first = transfer(transaction::generate_seed());
+    let tx_second = transfer(transaction::generate_seed());
+    assert_ne!(tx_first.hash(), tx_second.hash());
+
+    api.post_tx(&tx_first);
+    api.post_tx(&tx_second);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_first);
+    assert_eq!(tx_status, Ok(Ok(())));
+    let (_, tx_status) = api.get_tx_status(&tx_second);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, amount * 2);
+}
+
+#[test]
+fn transfer_same_seed_is_replay() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 10;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let transfer = || {
+        transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .amount(amount)
+            .recipient(recipient_key)
+            .seed(42)
+            .build()
+    };
+
+    let tx_first = transfer();
+    let tx_replay = transfer();
+    assert_eq!(tx_first.hash(), tx_replay.hash());
+
+    api.post_tx(&tx_first);
+    testkit.create_block();
+    api.post_tx(&tx_replay);
+    testkit.create_block();
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, amount);
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - amount - transaction_fee);
+}
+
+#[test]
+fn transfer_recipient_below_condition_met() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let recipient_balance = 500;
+    let amount = 1000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_key, Wallet::new(recipient_balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .only_if_recipient_below(recipient_balance + 1)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, recipient_balance + amount);
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - amount - transaction_fee);
+}
+
+#[test]
+fn transfer_recipient_below_condition_not_met() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let recipient_balance = 500;
+    let amount = 1000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_key, Wallet::new(recipient_balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .only_if_recipient_below(recipient_balance)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::ConditionNotMet)));
+
+    // Only the blockchain fee is collected.
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, recipient_balance);
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - transaction_fee);
+}
+
+#[test]
+fn transfer_to_full_wallet_overflows() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let amount = 1;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_key, Wallet::new(u64::max_value(), vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    assert_eq!(api.get_wallet(&public_key).balance, balance - transaction_fee);
+    assert_eq!(api.get_wallet(&recipient_key).balance, u64::max_value());
+}
+
+#[test]
+fn transfer_strict_exact_assets() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let units = 5;
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .add_asset_value(asset.clone())
+        .strict(true)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let recipient_assets = api.get_wallet_assets(&recipient_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(recipient_assets, vec![asset]);
+
+    let sender_assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert!(sender_assets.is_empty());
+}
+
+#[test]
+fn transfer_strict_holds_more_assets() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let units = 5;
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .add_asset_value(AssetBundle::new(asset.id(), units - 2))
+        .strict(true)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::ConditionNotMet)));
+
+    // Only the blockchain fee is collected, the assets stay with the sender.
+    let recipient_assets = api.get_wallet_assets(&recipient_key);
+    assert!(recipient_assets.is_empty());
+
+    let sender_wallet = api.get_wallet(&public_key);
+    let sender_assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_wallet.balance, balance - transaction_fee);
+    assert_eq!(sender_assets, vec![asset]);
+}
+
+#[test]
+fn transfer_pays_creator_royalty() {
+    let royalty = 25;
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let units = 4;
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (creator_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(royalty, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .add_asset_value(asset)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - transaction_fee - royalty * units);
+
+    let creator_wallet = api.get_wallet(&creator_key);
+    assert_eq!(creator_wallet.balance, royalty * units);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + transaction_fee);
+}
+
+#[test]
+fn transfer_by_creator_pays_no_royalty() {
+    let royalty = 25;
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let units = 4;
+    let balance = 100_000;
+
+    let (creator_key, creator_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(royalty, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&creator_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&creator_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(creator_key, creator_secret)
+        .tx_transfer()
+        .add_asset_value(asset)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let creator_wallet = api.get_wallet(&creator_key);
+    assert_eq!(creator_wallet.balance, balance - transaction_fee);
+    assert_eq!(creator_wallet.assets_count, 0);
+}
+
+#[test]
+fn transfer_empty_rejected() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(0)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let (status, response) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::UnableToVerifyTransaction)));
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance);
+}
+
+#[test]
+fn transfer_twice_to_same_recipient_accumulates() {
+    let fixed = 10;
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let units = 5;
+    let balance = 100_000;
+    let recipient_balance = 500;
+    let amount = 2_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &public_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_wallet_value(&recipient_key, Wallet::new(recipient_balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    for seed in 0..2 {
+        let tx_transfer = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .amount(amount)
+            .add_asset_value(AssetBundle::new(asset.id(), 2))
+            .recipient(recipient_key)
+            .seed(seed)
+            .build();
+
+        api.post_tx(&tx_transfer);
+        testkit.create_block();
+
+        let (_, tx_status) = api.get_tx_status(&tx_transfer);
+        assert_eq!(tx_status, Ok(Ok(())));
+    }
+
+    // The recipient already existed, both transfers add to what it holds.
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    let recipient_assets = api.get_wallet_assets(&recipient_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(recipient_wallet.balance, recipient_balance + 2 * amount);
+    assert_eq!(recipient_assets, vec![AssetBundle::new(asset.id(), 4)]);
+
+    let sender_wallet = api.get_wallet(&public_key);
+    let sender_assets = api.get_wallet_assets(&public_key).iter().map(|a| a.into()).collect::<Vec<AssetBundle>>();
+    assert_eq!(sender_wallet.balance, balance - 2 * (amount + transaction_fee));
+    assert_eq!(sender_assets, vec![AssetBundle::new(asset.id(), 1)]);
+}
+
+#[test]
+fn transfer_third_party_fees_overflow() {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let balance = 100_000;
+    let fixed = u64::max_value() / 2;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (creator_key, _) = crypto::gen_keypair();
+
+    let mut builder = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]));
+    let mut assets = Vec::new();
+    for meta_data in &["asset1", "asset2", "asset3"] {
+        // Each asset alone has a payable fee, the creator's total overflows.
+        let (asset, info) = dmbc_testkit::create_asset(meta_data, 1, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &creator_key);
+        builder = builder.add_asset_to_wallet(&public_key, (asset.clone(), info));
+        assets.push(asset);
+    }
+    let mut testkit = builder.create();
+    let api = testkit.api();
+
+    let tx_transfer = assets.iter()
+        .fold(
+            transaction::Builder::new()
+                .keypair(public_key, secret_key)
+                .tx_transfer(),
+            |tx, asset| tx.add_asset_value(asset.clone())
+        )
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let (status, _) = api.post_tx(&tx_transfer);
+    testkit.create_block();
+    assert_eq!(status, StatusCode::Accepted);
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::Overflow)));
+
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+    assert_eq!(api.get_wallet(&public_key).assets_count, assets.len() as u64);
+    assert_eq!(api.get_wallet(&recipient_key).assets_count, 0);
+}
@@ -0,0 +1,199 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees, TransferFeePolicy};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::error::Error;
+use dmbc::currency::wallet::Wallet;
+
+// All tests in this file use `TransferFeePolicy::FromAmount`.
+
+#[test]
+fn transfer_fee_from_amount() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let configuration = Configuration::new(config_fees)
+        .with_transfer_fee_policy(TransferFeePolicy::FromAmount);
+    let balance = 100_000;
+    let amount = 5000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - amount);
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, amount - transaction_fee);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + transaction_fee);
+}
+
+#[test]
+fn transfer_fee_from_amount_below_fee() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let configuration = Configuration::new(config_fees)
+        .with_transfer_fee_policy(TransferFeePolicy::FromAmount);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(transaction_fee - 1)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance);
+}
+
+#[test]
+fn transfer_send_all_fee_from_amount() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let configuration = Configuration::new(config_fees)
+        .with_transfer_fee_policy(TransferFeePolicy::FromAmount);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .send_all(true)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, 0);
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, balance - transaction_fee);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + transaction_fee);
+}
+
+#[test]
+fn transfer_fee_from_amount_with_royalty() {
+    let royalty = 25;
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let configuration = Configuration::new(config_fees)
+        .with_transfer_fee_policy(TransferFeePolicy::FromAmount);
+    let balance = 100_000;
+    let amount = 5000;
+    let units = 4;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (creator_key, _) = crypto::gen_keypair();
+
+    let fees = dmbc_testkit::asset_fees(royalty, "0.0".parse().unwrap());
+    let (asset, info) = dmbc_testkit::create_asset("asset", units, fees, &creator_key);
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+    let genesis_balance = api.get_wallet(&dmbc_testkit::default_genesis_key()).balance;
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .add_asset_value(asset)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+
+    // The royalty is paid on top of the amount, the blockchain fee out of it.
+    let sender_wallet = api.get_wallet(&public_key);
+    assert_eq!(sender_wallet.balance, balance - amount - royalty * units);
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, amount - transaction_fee);
+
+    let creator_wallet = api.get_wallet(&creator_key);
+    assert_eq!(creator_wallet.balance, royalty * units);
+
+    let genesis_wallet = api.get_wallet(&dmbc_testkit::default_genesis_key());
+    assert_eq!(genesis_wallet.balance, genesis_balance + transaction_fee);
+}
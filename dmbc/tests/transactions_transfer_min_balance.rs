@@ -0,0 +1,65 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::error::Error;
+use dmbc::currency::wallet::Wallet;
+
+const MIN_BALANCE: u64 = 1000;
+
+fn transfer_leaving(left: u64) -> (Result<(), Error>, u64) {
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, 0);
+    let configuration = Configuration::new(config_fees).with_min_balance(MIN_BALANCE);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(balance - left)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    (tx_status.unwrap(), api.get_wallet(&public_key).balance)
+}
+
+#[test]
+fn transfer_leaving_min_balance() {
+    assert_eq!(transfer_leaving(MIN_BALANCE), (Ok(()), MIN_BALANCE));
+}
+
+#[test]
+fn transfer_leaving_dust() {
+    assert_eq!(transfer_leaving(MIN_BALANCE - 1), (Err(Error::DustBalance), 100_000));
+    assert_eq!(transfer_leaving(1), (Err(Error::DustBalance), 100_000));
+}
+
+#[test]
+fn transfer_emptying_wallet() {
+    assert_eq!(transfer_leaving(0), (Ok(()), 0));
+}
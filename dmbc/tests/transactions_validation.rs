@@ -0,0 +1,190 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum_testkit::TestKit;
+use serde_json::Value;
+use dmbc_testkit::{DmbcTestKit, DmbcTestKitApi};
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::{DryRunResponse, TxPostResponse};
+use dmbc::currency::api::validation::FieldError;
+use dmbc::currency::assets::{AssetBundle, AssetId};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::transactions::TRANSFER_ID;
+
+fn transfer() -> Value {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let asset_id = AssetId::from_data("asset", &public_key);
+
+    let tx = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .add_asset_value(AssetBundle::new(asset_id, 1))
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+    serde_json::to_value(&tx).unwrap()
+}
+
+fn exchange() -> Value {
+    let (sender_pk, sender_sk) = crypto::gen_keypair();
+    let (recipient_pk, recipient_sk) = crypto::gen_keypair();
+
+    let tx = transaction::Builder::new()
+        .keypair(recipient_pk, recipient_sk)
+        .tx_exchange()
+        .sender(sender_pk)
+        .sender_secret(sender_sk)
+        .fee_strategy(FeeStrategy::Recipient)
+        .sender_value(10)
+        .seed(1)
+        .build();
+    serde_json::to_value(&tx).unwrap()
+}
+
+fn with_field<F>(value: &Value, set: F) -> Value
+where
+    F: FnOnce(&mut Value),
+{
+    let mut value = value.clone();
+    set(&mut value);
+    value
+}
+
+fn invalid_field(field: &str, message: &str) -> ApiError {
+    ApiError::InvalidField(FieldError {
+        field: field.to_string(),
+        message: message.to_string(),
+    })
+}
+
+fn post(body: &Value) -> (StatusCode, TxPostResponse) {
+    let api = TestKit::default().api();
+    api.post_raw_with_status("v1/transactions", &body.to_string())
+}
+
+#[test]
+fn short_signature() {
+    let tx = with_field(&transfer(), |v| v["signature"] = Value::String("abcd".to_string()));
+
+    let (status, response) = post(&tx);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(invalid_field("signature", "signature must be 128 hex chars")));
+}
+
+#[test]
+fn non_hex_public_key() {
+    let key = "z".repeat(64);
+    let tx = with_field(&transfer(), |v| v["body"]["to"] = Value::String(key));
+
+    let (status, response) = post(&tx);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(invalid_field("body.to", "body.to must be 64 hex chars")));
+}
+
+#[test]
+fn short_asset_id() {
+    let tx = with_field(&transfer(), |v| {
+        v["body"]["assets"][0]["id"] = Value::String("00ff".to_string())
+    });
+
+    let (status, response) = post(&tx);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(
+        response,
+        Err(invalid_field("body.assets[0].id", "body.assets[0].id must be 32 hex chars"))
+    );
+}
+
+#[test]
+fn nested_offer_key() {
+    let tx = with_field(&exchange(), |v| {
+        v["body"]["offer"]["sender"] = Value::String("00".to_string())
+    });
+
+    let (status, response) = post(&tx);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(
+        response,
+        Err(invalid_field("body.offer.sender", "body.offer.sender must be 64 hex chars"))
+    );
+}
+
+#[test]
+fn unknown_message_id() {
+    let tx = with_field(&transfer(), |v| v["message_id"] = Value::from(999));
+
+    let (status, response) = post(&tx);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(
+        response,
+        Err(invalid_field("message_id", "message_id must be a known transaction type"))
+    );
+}
+
+#[test]
+fn retired_message_id() {
+    let tx = with_field(&transfer(), |v| v["message_id"] = Value::from(200));
+
+    let (status, response) = post(&tx);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(
+        response,
+        Err(invalid_field("message_id", &format!("message_id is retired, use {}", TRANSFER_ID)))
+    );
+}
+
+#[test]
+fn missing_body() {
+    let tx = with_field(&transfer(), |v| {
+        v.as_object_mut().unwrap().remove("body");
+    });
+
+    let (status, response) = post(&tx);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(invalid_field("body", "body must be a JSON object")));
+}
+
+#[test]
+fn not_an_object() {
+    let (status, response) = post(&Value::from(vec![1, 2, 3]));
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(
+        response,
+        Err(invalid_field("transaction", "transaction must be a JSON object"))
+    );
+}
+
+#[test]
+fn malformed_json_is_incorrect_request() {
+    let api = TestKit::default().api();
+
+    let (status, response): (StatusCode, TxPostResponse) =
+        api.post_raw_with_status("v1/transactions", "{\"body\": ");
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::IncorrectRequest));
+}
+
+#[test]
+fn dry_run_reports_field() {
+    let api = TestKit::default().api();
+    let tx = with_field(&transfer(), |v| v["signature"] = Value::String("abcd".to_string()));
+
+    let (status, response): (StatusCode, DryRunResponse) =
+        api.post_raw_with_status("v1/transactions/dry_run", &tx.to_string());
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(invalid_field("signature", "signature must be 128 hex chars")));
+}
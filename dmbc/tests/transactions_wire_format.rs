@@ -0,0 +1,42 @@
+extern crate dmbc;
+extern crate exonum;
+
+use exonum::blockchain::Service as ServiceTrait;
+use exonum::crypto;
+use exonum::messages::{Message, RawMessage};
+
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::{RETIRED_IDS, TRANSFER_ID};
+use dmbc::currency::Service;
+
+fn with_message_type(raw: &RawMessage, message_type: u16) -> RawMessage {
+    let mut bytes = raw.as_ref().to_vec();
+    bytes[2] = message_type as u8;
+    bytes[3] = (message_type >> 8) as u8;
+    RawMessage::from_vec(bytes)
+}
+
+#[test]
+fn retired_ids_rejected() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .recipient(recipient_key)
+        .amount(10)
+        .only_if_recipient_below(100)
+        .seed(1)
+        .build();
+
+    let service = Service::new();
+    assert_eq!(tx_transfer.raw().message_type(), TRANSFER_ID);
+    assert!(service.tx_from_raw(tx_transfer.raw().clone()).is_ok());
+
+    // Payloads for a retired ID are never read in the layout of its successor.
+    for &(retired, _) in RETIRED_IDS {
+        let raw = with_message_type(tx_transfer.raw(), retired);
+        assert!(service.tx_from_raw(raw).is_err());
+    }
+}
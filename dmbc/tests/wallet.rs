@@ -17,7 +17,7 @@ use dmbc_testkit::{DmbcTestKit, DmbcTestApiBuilder, DmbcTestKitApi};
 
 use dmbc::currency::api::wallet::{self, ExtendedAsset, WalletAssetsResponse, WalletAssetsResponseBody,
                                   WalletInfo, WalletResponse, WalletsResponse, WalletsResponseBody, 
-                                  WalletAssetResponse};
+                                  WalletAssetResponse, WalletStateResponse, WalletStateResponseBody};
 use dmbc::currency::wallet::Wallet;
 use dmbc::currency::assets::AssetId;
 use dmbc::currency::api::error::ApiError;
@@ -365,4 +365,54 @@ fn wallet_asset_whit_info() {
 
     assert_eq!(status, StatusCode::Ok);
     assert_eq!(response, Ok(extended_asset));    
-}
\ No newline at end of file
+}
+#[test]
+fn wallet_state() {
+    let fixed = 10;
+    let units = 2;
+    let balance = 1000;
+    let meta_data = "asset";
+
+    let (pub_key, _) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(meta_data, units, dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()), &pub_key);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&pub_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&pub_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, WalletStateResponse) = api.get_with_status(
+        &format!("/v1/wallets/{}/state", pub_key.to_string())
+    );
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(WalletStateResponseBody { balance, assets: vec![asset], has_more: false }));
+}
+
+#[test]
+fn wallet_state_unknown_wallet() {
+    let (pub_key, _) = crypto::gen_keypair();
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, WalletStateResponse) = api.get_with_status(
+        &format!("/v1/wallets/{}/state", pub_key.to_string())
+    );
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response, Ok(WalletStateResponseBody { balance: 0, assets: vec![], has_more: false }));
+}
+
+#[test]
+fn wallet_state_invalid_public_key() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, WalletStateResponse) = api.get_with_status(
+        "/v1/wallets/invalidpublickey/state"
+    );
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::WalletHexInvalid));
+}
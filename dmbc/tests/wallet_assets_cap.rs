@@ -0,0 +1,83 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use hyper::status::StatusCode;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::ApiLimits;
+use dmbc::currency::api::wallet::WalletStateResponse;
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::wallet::Wallet;
+
+const MAX_WALLET_ASSETS: usize = 2;
+
+fn api_limits() -> ApiLimits {
+    ApiLimits {
+        max_wallet_assets: MAX_WALLET_ASSETS,
+        ..ApiLimits::from_config()
+    }
+}
+
+fn wallet_with_assets(pub_key: &PublicKey, count: usize) -> Wallet {
+    let assets = (0..count)
+        .map(|i| AssetBundle::from_data(&format!("asset{}", i), 1, pub_key))
+        .collect();
+    Wallet::new(1000, assets)
+}
+
+#[test]
+fn wallet_state_truncates_assets() {
+    let (pub_key, _) = crypto::gen_keypair();
+    let wallet = wallet_with_assets(&pub_key, MAX_WALLET_ASSETS + 3);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_api_limits(api_limits())
+        .add_wallet_value(&pub_key, wallet.clone())
+        .create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, WalletStateResponse) = api.get_with_status(
+        &format!("/v1/wallets/{}/state", pub_key.to_string())
+    );
+
+    assert_eq!(status, StatusCode::Ok);
+    let state = response.unwrap();
+    assert_eq!(state.balance, wallet.balance());
+    assert_eq!(state.assets, wallet.assets()[..MAX_WALLET_ASSETS].to_vec());
+    assert!(state.has_more);
+
+    // The assets endpoint still lists all of them.
+    let assets = api.get_wallet_assets(&pub_key);
+    assert_eq!(assets.len(), MAX_WALLET_ASSETS + 3);
+}
+
+#[test]
+fn wallet_state_at_cap_has_no_more() {
+    let (pub_key, _) = crypto::gen_keypair();
+    let wallet = wallet_with_assets(&pub_key, MAX_WALLET_ASSETS);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_api_limits(api_limits())
+        .add_wallet_value(&pub_key, wallet.clone())
+        .create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, WalletStateResponse) = api.get_with_status(
+        &format!("/v1/wallets/{}/state", pub_key.to_string())
+    );
+
+    assert_eq!(status, StatusCode::Ok);
+    let state = response.unwrap();
+    assert_eq!(state.assets, wallet.assets());
+    assert!(!state.has_more);
+}
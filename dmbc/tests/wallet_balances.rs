@@ -0,0 +1,93 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use hyper::status::StatusCode;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::ApiLimits;
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::wallet::{WalletBalanceEntry, WalletBalancesResponse};
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::wallet::Wallet;
+
+const MAX_BATCH_WALLETS: usize = 3;
+
+fn api_limits() -> ApiLimits {
+    ApiLimits {
+        max_batch_wallets: MAX_BATCH_WALLETS,
+        ..ApiLimits::from_config()
+    }
+}
+
+#[test]
+fn wallet_balances_known_and_unknown() {
+    let (known_key, _) = crypto::gen_keypair();
+    let (unknown_key, _) = crypto::gen_keypair();
+    let asset = AssetBundle::from_data("asset", 5, &known_key);
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_api_limits(api_limits())
+        .add_wallet_value(&known_key, Wallet::new(1000, vec![asset.clone()]))
+        .create();
+    let api = testkit.api();
+
+    let keys = vec![unknown_key.to_string(), known_key.to_string()];
+    let (status, response): (StatusCode, WalletBalancesResponse) =
+        api.post_with_status("/v1/wallets/balances", &keys);
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(vec![
+            WalletBalanceEntry {
+                pub_key: unknown_key,
+                balance: 0,
+                assets: vec![],
+            },
+            WalletBalanceEntry {
+                pub_key: known_key,
+                balance: 1000,
+                assets: vec![asset],
+            },
+        ])
+    );
+}
+
+#[test]
+fn wallet_balances_too_many() {
+    let testkit = DmbcTestApiBuilder::new()
+        .with_api_limits(api_limits())
+        .create();
+    let api = testkit.api();
+
+    let keys = (0..MAX_BATCH_WALLETS + 1)
+        .map(|_| crypto::gen_keypair().0.to_string())
+        .collect::<Vec<_>>();
+    let (status, response): (StatusCode, WalletBalancesResponse) =
+        api.post_with_status("/v1/wallets/balances", &keys);
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::TooManyWallets));
+}
+
+#[test]
+fn wallet_balances_invalid_key() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let keys = vec!["invalidpublickey".to_string()];
+    let (status, response): (StatusCode, WalletBalancesResponse) =
+        api.post_with_status("/v1/wallets/balances", &keys);
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::WalletHexInvalid));
+}
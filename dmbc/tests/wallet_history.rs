@@ -0,0 +1,120 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use exonum::crypto;
+use exonum::messages::Message;
+use hyper::status::StatusCode;
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::wallet::WalletHistoryResponse;
+use dmbc::currency::configuration::{Configuration, TransactionFees};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn wallet_history_order_and_pagination() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 10_000;
+    let amount = 2_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (other_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    // The fourth transfer runs out of funds, failed transactions are kept
+    // in the history as well.
+    let mut hashes = Vec::new();
+    for seed in 0..4 {
+        let recipient = if seed % 2 == 0 { recipient_key } else { other_key };
+        let tx_transfer = transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .recipient(recipient)
+            .amount(amount)
+            .seed(seed)
+            .build();
+
+        api.post_tx(&tx_transfer);
+        testkit.create_block();
+        hashes.push(tx_transfer.hash());
+    }
+
+    let endpoint = format!("/v1/wallets/{}/history", public_key.to_string());
+    let (status, response): (StatusCode, WalletHistoryResponse) = api.get_with_status(&endpoint);
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap();
+    assert_eq!(body.total, 4);
+    assert_eq!(body.count, 4);
+    assert_eq!(
+        body.transactions.iter().map(|t| t.tx_hash).collect::<Vec<_>>(),
+        hashes
+    );
+    assert_eq!(body.transactions[0].status, Ok(()));
+    assert_eq!(body.transactions[3].status, Err(Error::InsufficientFunds));
+
+    let endpoint = format!("/v1/wallets/{}/history?offset=1&limit=2", public_key.to_string());
+    let (_, response): (StatusCode, WalletHistoryResponse) = api.get_with_status(&endpoint);
+
+    let body = response.unwrap();
+    assert_eq!(body.total, 4);
+    assert_eq!(body.count, 2);
+    assert_eq!(
+        body.transactions.iter().map(|t| t.tx_hash).collect::<Vec<_>>(),
+        hashes[1..3].to_vec()
+    );
+
+    let endpoint = format!("/v1/wallets/{}/history?offset=3&limit=10", public_key.to_string());
+    let (_, response): (StatusCode, WalletHistoryResponse) = api.get_with_status(&endpoint);
+
+    let body = response.unwrap();
+    assert_eq!(body.total, 4);
+    assert_eq!(body.count, 1);
+    assert_eq!(body.transactions[0].tx_hash, hashes[3]);
+
+    let endpoint = format!("/v1/wallets/{}/history?offset=4&limit=10", public_key.to_string());
+    let (_, response): (StatusCode, WalletHistoryResponse) = api.get_with_status(&endpoint);
+
+    let body = response.unwrap();
+    assert_eq!(body.total, 4);
+    assert_eq!(body.count, 0);
+    assert!(body.transactions.is_empty());
+
+    // Recipients only see the transfers addressed to them.
+    let endpoint = format!("/v1/wallets/{}/history", recipient_key.to_string());
+    let (_, response): (StatusCode, WalletHistoryResponse) = api.get_with_status(&endpoint);
+
+    let body = response.unwrap();
+    assert_eq!(body.total, 2);
+    assert_eq!(
+        body.transactions.iter().map(|t| t.tx_hash).collect::<Vec<_>>(),
+        vec![hashes[0], hashes[2]]
+    );
+}
+
+#[test]
+fn wallet_history_invalid_key() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, _): (StatusCode, WalletHistoryResponse) =
+        api.get_with_status("/v1/wallets/invalid/history");
+
+    assert_eq!(status, StatusCode::BadRequest);
+}
@@ -0,0 +1,202 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate serde_json;
+extern crate mount;
+
+pub mod dmbc_testkit;
+
+use hyper::status::StatusCode;
+use exonum::crypto;
+use exonum::crypto::PublicKey;
+use exonum_testkit::{TestKit, TestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::wallet::WalletSnapshotResponse;
+use dmbc::currency::assets::AssetBundle;
+use dmbc::currency::error::Error;
+use dmbc::currency::configuration::{Configuration, FailurePolicy, TransactionFees};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::{self, Wallet};
+
+fn snapshot_at(
+    api: &TestKitApi,
+    pub_key: &PublicKey,
+    height: u64,
+) -> (StatusCode, WalletSnapshotResponse) {
+    api.get_with_status(&format!(
+        "/v1/wallets/{}/snapshot?height={}",
+        pub_key.to_hex(),
+        height
+    ))
+}
+
+fn snapshots_len(testkit: &mut TestKit, pub_key: &PublicKey) -> u64 {
+    let fork = testkit.blockchain_mut().fork();
+    wallet::Schema(&fork).snapshots(pub_key).len()
+}
+
+#[test]
+fn wallet_snapshots_reconstruct_balance() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let balance = 100_000;
+    let units = 10;
+    let meta_data = "asset";
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        meta_data,
+        units,
+        dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()),
+        &public_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let initial_height = testkit.height().0;
+
+    let tx_coins = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_transfer()
+        .amount(1000)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+    let tx_assets = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_transfer()
+        .add_asset_value(AssetBundle::new(asset.id(), 3))
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+    let tx_more_coins = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(2000)
+        .recipient(recipient_key)
+        .seed(3)
+        .build();
+
+    let mut heights = Vec::new();
+    for tx in &[tx_coins, tx_assets, tx_more_coins] {
+        api.post_tx(tx);
+        testkit.create_block();
+        heights.push(testkit.height().0);
+    }
+
+    // Before the transfers. The testkit stores the initial wallets outside of
+    // any block, so they are recorded at the height of the next one.
+    let (status, response) = snapshot_at(&api, &recipient_key, initial_height);
+    assert_eq!(status, StatusCode::NotFound);
+    assert_eq!(response, Err(ApiError::SnapshotNotFound));
+
+    // After the first transfer.
+    let snapshot = snapshot_at(&api, &public_key, heights[0]).1.unwrap();
+    assert_eq!(snapshot.height(), heights[0]);
+    assert_eq!(snapshot.balance(), balance - 1000 - transaction_fee);
+    assert_eq!(snapshot.assets_removed(), vec![]);
+
+    let snapshot = snapshot_at(&api, &recipient_key, heights[0]).1.unwrap();
+    assert_eq!(snapshot.balance(), 1000);
+
+    // After the second transfer.
+    let snapshot = snapshot_at(&api, &public_key, heights[1]).1.unwrap();
+    assert_eq!(snapshot.height(), heights[1]);
+    assert_eq!(snapshot.balance(), balance - 1000 - 2 * transaction_fee);
+    assert_eq!(snapshot.assets_removed(), vec![AssetBundle::new(asset.id(), 3)]);
+
+    let snapshot = snapshot_at(&api, &recipient_key, heights[1]).1.unwrap();
+    assert_eq!(snapshot.balance(), 1000);
+    assert_eq!(snapshot.assets_added(), vec![AssetBundle::new(asset.id(), 3)]);
+
+    // After the third transfer, also the latest snapshot.
+    let expected = balance - 3000 - 3 * transaction_fee;
+    let snapshot = snapshot_at(&api, &public_key, heights[2]).1.unwrap();
+    assert_eq!(snapshot.height(), heights[2]);
+    assert_eq!(snapshot.balance(), expected);
+    assert_eq!(api.get_wallet(&public_key).balance, expected);
+
+    let (_, response): (StatusCode, WalletSnapshotResponse) =
+        api.get_with_status(&format!("/v1/wallets/{}/snapshot", public_key.to_hex()));
+    assert_eq!(response.unwrap().balance(), expected);
+
+    let snapshot = snapshot_at(&api, &recipient_key, heights[2]).1.unwrap();
+    assert_eq!(snapshot.balance(), 3000);
+}
+
+#[test]
+fn wallet_snapshots_one_per_transaction() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let configuration =
+        Configuration::new(config_fees).with_failure_policy(FailurePolicy::FullRollback);
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(configuration)
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    let initial = snapshots_len(&mut testkit, &public_key);
+
+    // The fee and the amount are moved apart, yet the transfer is one change.
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key.clone())
+        .tx_transfer()
+        .amount(1000)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+    assert_eq!(snapshots_len(&mut testkit, &public_key), initial + 1);
+
+    let snapshot = snapshot_at(&api, &public_key, testkit.height().0).1.unwrap();
+    assert_eq!(snapshot.balance(), balance - 1000 - transaction_fee);
+
+    // A failed transfer is rolled back and leaves no snapshot.
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(balance)
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Err(Error::InsufficientFunds)));
+    assert_eq!(snapshots_len(&mut testkit, &public_key), initial + 1);
+}
+
+#[test]
+fn wallet_snapshots_invalid_key() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, WalletSnapshotResponse) =
+        api.get_with_status("/v1/wallets/invalid/snapshot");
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::WalletHexInvalid));
+}